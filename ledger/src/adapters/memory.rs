@@ -1,7 +1,7 @@
 // ledger/src/adapters/memory.rs
 use crate::{
-    Asset, Balance, ExecutionPlan, Holding, LedgerAdapter, MoneyError, Operation, Transaction,
-    ValueObject, ValueObjectState,
+    Asset, Balance, ExecutionPlan, Holding, LedgerAdapter, LedgerIntegrityReport, MoneyError,
+    Operation, Transaction, TransactionKind, TransactionPage, ValueObject, ValueObjectState,
 };
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -40,256 +40,295 @@ impl MemoryAdapter {
     }
 }
 
-#[async_trait]
-impl LedgerAdapter for MemoryAdapter {
-    async fn execute_plan(
-        &self,
-        plan: &ExecutionPlan,
-        locks: &[(Uuid, Uuid, u64)],
-    ) -> Result<(), MoneyError> {
-        // Hold the mutex for the ENTIRE operation — this is the MemoryAdapter's
-        // equivalent of BEGIN/SELECT FOR UPDATE/COMMIT. No other task can enter
-        // execute_plan while we hold it.
-        let mut value_objects = self.store.value_objects.lock().unwrap();
-        let assets = self.store.assets.lock().unwrap();
-        let mut transactions = self.store.transactions.lock().unwrap();
+/// Lock, verify and execute every operation of `plan` against the given maps.
+fn apply_plan(
+    value_objects: &mut HashMap<Uuid, ValueObject>,
+    assets: &HashMap<String, Asset>,
+    transactions: &mut HashMap<Uuid, Transaction>,
+    keys: &mut HashMap<String, Uuid>,
+    plan: &ExecutionPlan,
+    locks: &[(Uuid, Uuid, u64)],
+) -> Result<(), MoneyError> {
+    // ── Phase 1: Select & verify under lock ───────────────────────────────
+    // HashMap<(asset_id, owner) -> (selected_vo_ids, total_locked)>
+    let mut locked: HashMap<(Uuid, Uuid), (Vec<Uuid>, u64)> = HashMap::new();
+
+    for (asset_id, owner, required) in locks {
+        let mut candidates: Vec<(Uuid, u64)> = value_objects
+            .values()
+            .filter(|vo| vo.asset == *asset_id && vo.owner == *owner && vo.state.is_alive())
+            .map(|vo| (vo.id, vo.amount))
+            .collect();
 
-        // ── Phase 1: Select & verify under lock ───────────────────────────────
-        // HashMap<(asset_id, owner) -> (selected_vo_ids, total_locked)>
-        let mut locked: HashMap<(Uuid, Uuid), (Vec<Uuid>, u64)> = HashMap::new();
+        // Smallest-first selection (matches Postgres ORDER BY amount ASC)
+        candidates.sort_by_key(|(_, amt)| *amt);
 
-        for (asset_id, owner, required) in locks {
-            let mut candidates: Vec<(Uuid, u64)> = value_objects
-                .values()
-                .filter(|vo| vo.asset == *asset_id && vo.owner == *owner && vo.state.is_alive())
-                .map(|vo| (vo.id, vo.amount))
-                .collect();
+        let mut ids = Vec::new();
+        let mut total = 0u64;
 
-            // Smallest-first selection (matches Postgres ORDER BY amount ASC)
-            candidates.sort_by_key(|(_, amt)| *amt);
+        for (id, amt) in candidates {
+            ids.push(id);
+            total += amt;
+            if total >= *required {
+                break;
+            }
+        }
 
-            let mut ids = Vec::new();
-            let mut total = 0u64;
+        // Checked while holding the mutex — this is the real double-spend guard
+        if total < *required {
+            return Err(MoneyError::InsufficientFunds);
+        }
+
+        locked.insert((*asset_id, *owner), (ids, total));
+    }
+
+    // ── Phase 2: Execute operations ───────────────────────────────────────
+    // Track how much of each locked pool is actually consumed
+    let mut used: HashMap<(Uuid, Uuid), u64> = HashMap::new();
+
+    for op in plan.operations() {
+        match op {
+            Operation::Mint {
+                asset_id,
+                owner,
+                amount,
+                ..
+            } => {
+                let asset = assets
+                    .values()
+                    .find(|a| a.id == *asset_id)
+                    .ok_or_else(|| MoneyError::AssetNotFound(asset_id.to_string()))?;
 
-            for (id, amt) in candidates {
-                ids.push(id);
-                total += amt;
-                if total >= *required {
-                    break;
+                let mut remaining = *amount;
+                while remaining > 0 {
+                    let chunk = remaining.min(asset.unit);
+                    let vo = ValueObject::new_alive(*asset_id, *owner, chunk);
+                    value_objects.insert(vo.id, vo);
+                    remaining -= chunk;
                 }
             }
 
-            // Checked while holding the mutex — this is the real double-spend guard
-            if total < *required {
-                return Err(MoneyError::InsufficientFunds);
+            Operation::Burn {
+                asset_id,
+                owner,
+                amount,
+                ..
+            } => {
+                *used.entry((*asset_id, *owner)).or_insert(0) += amount;
             }
 
-            locked.insert((*asset_id, *owner), (ids, total));
-        }
+            Operation::Transfer {
+                asset_id,
+                from,
+                to,
+                amount,
+                ..
+            } => {
+                *used.entry((*asset_id, *from)).or_insert(0) += amount;
 
-        // ── Phase 2: Execute operations ───────────────────────────────────────
-        // Track how much of each locked pool is actually consumed
-        let mut used: HashMap<(Uuid, Uuid), u64> = HashMap::new();
+                let asset = assets
+                    .values()
+                    .find(|a| a.id == *asset_id)
+                    .ok_or_else(|| MoneyError::AssetNotFound(asset_id.to_string()))?;
 
-        for op in plan.operations() {
-            match op {
-                Operation::Mint {
-                    asset_id,
-                    owner,
-                    amount,
-                    ..
-                } => {
-                    let asset = assets
-                        .values()
-                        .find(|a| a.id == *asset_id)
-                        .ok_or_else(|| MoneyError::AssetNotFound(asset_id.to_string()))?;
-
-                    let mut remaining = *amount;
-                    while remaining > 0 {
-                        let chunk = remaining.min(asset.unit);
-                        let vo = ValueObject::new_alive(*asset_id, *owner, chunk);
-                        value_objects.insert(vo.id, vo);
-                        remaining -= chunk;
-                    }
+                let mut remaining = *amount;
+                while remaining > 0 {
+                    let chunk = remaining.min(asset.unit);
+                    let vo = ValueObject::new_alive(*asset_id, *to, chunk);
+                    value_objects.insert(vo.id, vo);
+                    remaining -= chunk;
                 }
+            }
 
-                Operation::Burn {
-                    asset_id,
-                    owner,
-                    amount,
-                    ..
-                } => {
-                    *used.entry((*asset_id, *owner)).or_insert(0) += amount;
-                }
+            Operation::Reserve {
+                asset_id,
+                from,
+                for_authority,
+                amount,
+                ..
+            } => {
+                *used.entry((*asset_id, *from)).or_insert(0) += amount;
 
-                Operation::Transfer {
-                    asset_id,
-                    from,
-                    to,
-                    amount,
-                    ..
-                } => {
-                    *used.entry((*asset_id, *from)).or_insert(0) += amount;
-
-                    let asset = assets
-                        .values()
-                        .find(|a| a.id == *asset_id)
-                        .ok_or_else(|| MoneyError::AssetNotFound(asset_id.to_string()))?;
-
-                    let mut remaining = *amount;
-                    while remaining > 0 {
-                        let chunk = remaining.min(asset.unit);
-                        let vo = ValueObject::new_alive(*asset_id, *to, chunk);
-                        value_objects.insert(vo.id, vo);
-                        remaining -= chunk;
-                    }
-                }
+                let asset = assets
+                    .values()
+                    .find(|a| a.id == *asset_id)
+                    .ok_or_else(|| MoneyError::AssetNotFound(asset_id.to_string()))?;
 
-                Operation::Reserve {
-                    asset_id,
-                    from,
-                    for_authority,
-                    amount,
-                    ..
-                } => {
-                    *used.entry((*asset_id, *from)).or_insert(0) += amount;
-
-                    let asset = assets
-                        .values()
-                        .find(|a| a.id == *asset_id)
-                        .ok_or_else(|| MoneyError::AssetNotFound(asset_id.to_string()))?;
-
-                    let mut remaining = *amount;
-                    while remaining > 0 {
-                        let chunk = remaining.min(asset.unit);
-                        let vo = ValueObject::new_reserved(
-                            *asset_id,
-                            *for_authority,
-                            chunk,
-                            *for_authority,
-                        );
-                        value_objects.insert(vo.id, vo);
-                        remaining -= chunk;
-                    }
+                let mut remaining = *amount;
+                while remaining > 0 {
+                    let chunk = remaining.min(asset.unit);
+                    let vo = ValueObject::new_reserved(
+                        *asset_id,
+                        *for_authority,
+                        chunk,
+                        *for_authority,
+                    );
+                    value_objects.insert(vo.id, vo);
+                    remaining -= chunk;
                 }
+            }
 
-                Operation::Settle {
-                    asset_id,
-                    authority,
-                    receiver,
-                    amount,
-                    ..
-                } => {
-                    // Select reserved VOs owned by authority, smallest-first
-                    let mut candidates: Vec<(Uuid, u64)> = value_objects
-                        .values()
-                        .filter(|vo| {
-                            vo.asset == *asset_id
-                                && vo.owner == *authority
-                                && vo.state.is_reserved()
-                        })
-                        .map(|vo| (vo.id, vo.amount))
-                        .collect();
-                    candidates.sort_by_key(|(_, amt)| *amt);
-
-                    let mut ids_to_burn = Vec::new();
-                    let mut total_reserved = 0u64;
-                    for (id, amt) in candidates {
-                        ids_to_burn.push(id);
-                        total_reserved += amt;
-                        if total_reserved >= *amount {
-                            break;
-                        }
+            Operation::Settle {
+                asset_id,
+                authority,
+                receiver,
+                amount,
+                ..
+            } => {
+                // Select reserved VOs owned by authority, smallest-first
+                let mut candidates: Vec<(Uuid, u64)> = value_objects
+                    .values()
+                    .filter(|vo| {
+                        vo.asset == *asset_id
+                            && vo.owner == *authority
+                            && vo.state.is_reserved()
+                    })
+                    .map(|vo| (vo.id, vo.amount))
+                    .collect();
+                candidates.sort_by_key(|(_, amt)| *amt);
+
+                let mut ids_to_burn = Vec::new();
+                let mut total_reserved = 0u64;
+                for (id, amt) in candidates {
+                    ids_to_burn.push(id);
+                    total_reserved += amt;
+                    if total_reserved >= *amount {
+                        break;
                     }
+                }
 
-                    if total_reserved < *amount {
-                        return Err(MoneyError::InsufficientFunds);
-                    }
+                if total_reserved < *amount {
+                    return Err(MoneyError::InsufficientFunds);
+                }
 
-                    // Burn the selected reserved VOs
-                    for id in &ids_to_burn {
-                        if let Some(vo) = value_objects.get_mut(id) {
-                            vo.state = ValueObjectState::Burned;
-                        }
+                // Burn the selected reserved VOs
+                for id in &ids_to_burn {
+                    if let Some(vo) = value_objects.get_mut(id) {
+                        vo.state = ValueObjectState::Burned;
                     }
+                }
 
-                    let asset = assets
-                        .values()
-                        .find(|a| a.id == *asset_id)
-                        .ok_or_else(|| MoneyError::AssetNotFound(asset_id.to_string()))?;
-
-                    // Return change as reserved VOs for authority
-                    let change = total_reserved - *amount;
-                    if change > 0 {
-                        let mut remaining = change;
-                        while remaining > 0 {
-                            let chunk = remaining.min(asset.unit);
-                            let vo =
-                                ValueObject::new_reserved(*asset_id, *authority, chunk, *authority);
-                            value_objects.insert(vo.id, vo);
-                            remaining -= chunk;
-                        }
-                    }
+                let asset = assets
+                    .values()
+                    .find(|a| a.id == *asset_id)
+                    .ok_or_else(|| MoneyError::AssetNotFound(asset_id.to_string()))?;
 
-                    // Mint alive VOs for receiver
-                    let mut remaining = *amount;
+                // Return change as reserved VOs for authority
+                let change = total_reserved - *amount;
+                if change > 0 {
+                    let mut remaining = change;
                     while remaining > 0 {
                         let chunk = remaining.min(asset.unit);
-                        let vo = ValueObject::new_alive(*asset_id, *receiver, chunk);
+                        let vo =
+                            ValueObject::new_reserved(*asset_id, *authority, chunk, *authority);
                         value_objects.insert(vo.id, vo);
                         remaining -= chunk;
                     }
                 }
 
-                Operation::RecordTransaction { transaction } => {
-                    if let Some(ref raw_key) = transaction.idempotency_key {
-                        let hash = crate::hash_idempotency_key(raw_key);
-
-                        let mut keys = self.store.idempotency_keys.lock().unwrap();
+                // Mint alive VOs for receiver
+                let mut remaining = *amount;
+                while remaining > 0 {
+                    let chunk = remaining.min(asset.unit);
+                    let vo = ValueObject::new_alive(*asset_id, *receiver, chunk);
+                    value_objects.insert(vo.id, vo);
+                    remaining -= chunk;
+                }
+            }
 
-                        // Check for duplicate while holding the mutex — atomic with the insert
-                        if keys.contains_key(&hash) {
-                            return Err(MoneyError::DuplicateIdempotencyKey(transaction.id));
-                        }
+            Operation::RecordTransaction { transaction } => {
+                if let Some(ref raw_key) = transaction.idempotency_key {
+                    let hash = crate::hash_idempotency_key(raw_key);
 
-                        keys.insert(hash, transaction.id);
+                    // Check for duplicate while holding the mutex — atomic with the insert
+                    if keys.contains_key(&hash) {
+                        return Err(MoneyError::DuplicateIdempotencyKey(transaction.id));
                     }
 
-                    transactions.insert(transaction.id, transaction.clone());
+                    keys.insert(hash, transaction.id);
                 }
+
+                transactions.insert(transaction.id, transaction.clone());
             }
         }
+    }
 
-        // ── Phase 3: Burn locked VOs, mint change ─────────────────────────────
-        for ((asset_id, owner), (ids, total_locked)) in &locked {
-            let total_used = used.get(&(*asset_id, *owner)).copied().unwrap_or(0);
+    // ── Phase 3: Burn locked VOs, mint change ─────────────────────────────
+    for ((asset_id, owner), (ids, total_locked)) in &locked {
+        let total_used = used.get(&(*asset_id, *owner)).copied().unwrap_or(0);
 
-            // Burn every selected VO
-            for id in ids {
-                if let Some(vo) = value_objects.get_mut(id) {
-                    vo.state = ValueObjectState::Burned;
-                }
+        // Burn every selected VO
+        for id in ids {
+            if let Some(vo) = value_objects.get_mut(id) {
+                vo.state = ValueObjectState::Burned;
             }
+        }
 
-            // Mint change if we locked more than we spent
-            let change = total_locked - total_used;
-            if change > 0 {
-                let asset = assets
-                    .values()
-                    .find(|a| a.id == *asset_id)
-                    .ok_or_else(|| MoneyError::AssetNotFound(asset_id.to_string()))?;
-
-                let mut remaining = change;
-                while remaining > 0 {
-                    let chunk = remaining.min(asset.unit);
-                    let vo = ValueObject::new_alive(*asset_id, *owner, chunk);
-                    value_objects.insert(vo.id, vo);
-                    remaining -= chunk;
-                }
+        // Mint change if we locked more than we spent
+        let change = total_locked - total_used;
+        if change > 0 {
+            let asset = assets
+                .values()
+                .find(|a| a.id == *asset_id)
+                .ok_or_else(|| MoneyError::AssetNotFound(asset_id.to_string()))?;
+
+            let mut remaining = change;
+            while remaining > 0 {
+                let chunk = remaining.min(asset.unit);
+                let vo = ValueObject::new_alive(*asset_id, *owner, chunk);
+                value_objects.insert(vo.id, vo);
+                remaining -= chunk;
             }
         }
+    }
 
-        Ok(())
+    Ok(())
+}
+
+#[async_trait]
+impl LedgerAdapter for MemoryAdapter {
+    async fn execute_plan(
+        &self,
+        plan: &ExecutionPlan,
+        locks: &[(Uuid, Uuid, u64)],
+    ) -> Result<(), MoneyError> {
+        // Hold the mutex for the ENTIRE operation — this is the MemoryAdapter's
+        // equivalent of BEGIN/SELECT FOR UPDATE/COMMIT. No other task can enter
+        // execute_plan while we hold it.
+        let mut value_objects = self.store.value_objects.lock().unwrap();
+        let assets = self.store.assets.lock().unwrap();
+        let mut transactions = self.store.transactions.lock().unwrap();
+        let mut keys = self.store.idempotency_keys.lock().unwrap();
+
+        apply_plan(
+            &mut value_objects,
+            &assets,
+            &mut transactions,
+            &mut keys,
+            plan,
+            locks,
+        )
+    }
+
+    async fn simulate_plan(
+        &self,
+        plan: &ExecutionPlan,
+        locks: &[(Uuid, Uuid, u64)],
+    ) -> Result<(), MoneyError> {
+        // Run against snapshots of the store; dropping them is the ROLLBACK.
+        let mut value_objects = self.store.value_objects.lock().unwrap().clone();
+        let assets = self.store.assets.lock().unwrap();
+        let mut transactions = self.store.transactions.lock().unwrap().clone();
+        let mut keys = self.store.idempotency_keys.lock().unwrap().clone();
+
+        apply_plan(
+            &mut value_objects,
+            &assets,
+            &mut transactions,
+            &mut keys,
+            plan,
+            locks,
+        )
     }
 
     async fn get_balance(&self, asset_id: Uuid, owner: Uuid) -> Result<Balance, MoneyError> {
@@ -368,6 +407,46 @@ impl LedgerAdapter for MemoryAdapter {
             .collect::<Vec<_>>())
     }
 
+    async fn get_transactions_page(
+        &self,
+        owner: Uuid,
+        asset_code: &str,
+        cursor: Option<Uuid>,
+        limit: u32,
+    ) -> Result<TransactionPage, MoneyError> {
+        let txs = self.store.transactions.lock().unwrap();
+        let mut items: Vec<Transaction> = txs
+            .values()
+            .filter(|tx| {
+                (tx.sender == Some(owner) || tx.receiver == Some(owner))
+                    && tx.code == asset_code
+                    && cursor.is_none_or(|cursor| tx.id < cursor)
+            })
+            .cloned()
+            .collect();
+        items.sort_by_key(|tx| std::cmp::Reverse(tx.id));
+        items.truncate(limit as usize + 1);
+        Ok(TransactionPage::from_rows(items, limit))
+    }
+
+    async fn get_transactions_count(
+        &self,
+        owner: Uuid,
+        asset_code: &str,
+        timespan: &[DateTime<Utc>; 2],
+    ) -> Result<u64, MoneyError> {
+        let txs = self.store.transactions.lock().unwrap();
+        Ok(txs
+            .values()
+            .filter(|tx| {
+                (tx.sender == Some(owner) || tx.receiver == Some(owner))
+                    && tx.code == asset_code
+                    && tx.created_at >= timespan[0]
+                    && tx.created_at <= timespan[1]
+            })
+            .count() as u64)
+    }
+
     async fn get_asset(&self, code: &str) -> Result<Asset, MoneyError> {
         let assets = self.store.assets.lock().unwrap();
         assets
@@ -382,6 +461,38 @@ impl LedgerAdapter for MemoryAdapter {
         Ok(())
     }
 
+    async fn list_assets(&self) -> Result<Vec<Asset>, MoneyError> {
+        let assets = self.store.assets.lock().unwrap();
+        let mut list: Vec<Asset> = assets.values().cloned().collect();
+        list.sort_by(|a, b| a.code.cmp(&b.code));
+        Ok(list)
+    }
+
+    async fn delete_asset(&self, code: &str) -> Result<(), MoneyError> {
+        let mut vos = self.store.value_objects.lock().unwrap();
+        let mut assets = self.store.assets.lock().unwrap();
+        let mut transactions = self.store.transactions.lock().unwrap();
+        let mut keys = self.store.idempotency_keys.lock().unwrap();
+
+        let asset_id = assets
+            .get(code)
+            .ok_or_else(|| MoneyError::AssetNotFound(code.to_string()))?
+            .id;
+        if vos
+            .values()
+            .any(|vo| vo.asset == asset_id && (vo.state.is_alive() || vo.state.is_reserved()))
+        {
+            return Err(MoneyError::AssetInUse(code.to_string()));
+        }
+
+        // Only burned fragments and history remain; they go with the asset
+        vos.retain(|_, vo| vo.asset != asset_id);
+        transactions.retain(|_, tx| tx.asset != asset_id);
+        keys.retain(|_, tx_id| transactions.contains_key(tx_id));
+        assets.remove(code);
+        Ok(())
+    }
+
     async fn get_holdings(&self, owner: Uuid) -> Result<Vec<Holding>, MoneyError> {
         let vos = self.store.value_objects.lock().unwrap();
         let assets = self.store.assets.lock().unwrap();
@@ -434,6 +545,50 @@ impl LedgerAdapter for MemoryAdapter {
             .cloned()
             .collect())
     }
+
+    async fn verify_ledger_integrity(
+        &self,
+        asset_code: &str,
+    ) -> Result<LedgerIntegrityReport, MoneyError> {
+        // Same lock order as `delete_asset`; holding all three reads one snapshot
+        let vos = self.store.value_objects.lock().unwrap();
+        let assets = self.store.assets.lock().unwrap();
+        let txs = self.store.transactions.lock().unwrap();
+
+        let asset_id = assets
+            .get(asset_code)
+            .ok_or_else(|| MoneyError::AssetNotFound(asset_code.to_string()))?
+            .id;
+
+        let alive_supply: u64 = vos
+            .values()
+            .filter(|vo| vo.asset == asset_id && !vo.state.is_burned())
+            .map(|vo| vo.amount)
+            .sum();
+        let mut total_minted = 0u64;
+        let mut total_burned = 0u64;
+        for tx in txs.values().filter(|tx| tx.asset == asset_id) {
+            match tx.kind {
+                TransactionKind::Mint => total_minted += tx.minted_amount,
+                TransactionKind::Burn => total_burned += tx.burned_amount,
+                _ => {}
+            }
+        }
+
+        Ok(LedgerIntegrityReport::new(
+            asset_code.to_string(),
+            alive_supply as i64,
+            total_minted as i64,
+            total_burned as i64,
+        ))
+    }
+
+    async fn expire_value_objects(&self) -> Result<u64, MoneyError> {
+        let mut vos = self.store.value_objects.lock().unwrap();
+        let before = vos.len();
+        vos.retain(|_, vo| !vo.state.is_burned());
+        Ok((before - vos.len()) as u64)
+    }
 }
 
 impl Default for MemoryAdapter {
@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
 use crate::{
-    Asset, Balance, ExecutionPlan, Holding, LedgerAdapter, MoneyError, Operation, Transaction,
-    ValueObject,
+    Asset, Balance, ExecutionPlan, Holding, IsolationLevel, LedgerAdapter, LedgerIntegrityReport,
+    MoneyError, Operation, Transaction, TransactionPage, ValueObject,
 };
 use chrono::{DateTime, Utc};
 use sqlx::Row;
@@ -40,6 +40,7 @@ where
                 code TEXT NOT NULL UNIQUE,
                 unit BIGINT NOT NULL,
                 decimals SMALLINT NOT NULL,
+                active BOOLEAN NOT NULL DEFAULT TRUE,
                 created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
             )
             "#,
@@ -48,6 +49,17 @@ where
         .await
         .map_err(|e| MoneyError::Storage(e.to_string()))?;
 
+        // Schemas created before assets could be deactivated
+        sqlx::query(
+            r#"
+            ALTER TABLE ledger_assets
+            ADD COLUMN IF NOT EXISTS active BOOLEAN NOT NULL DEFAULT TRUE
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
         // ValueObjects table
         sqlx::query(
             r#"
@@ -121,6 +133,7 @@ where
                 asset UUID NOT NULL REFERENCES ledger_assets(id),
                 sender UUID,
                 receiver UUID,
+                kind TEXT NOT NULL DEFAULT 'transfer',
                 burned_amount BIGINT NOT NULL,
                 minted_amount BIGINT NOT NULL,
                 metadata TEXT NOT NULL,
@@ -132,6 +145,17 @@ where
         .await
         .map_err(|e| MoneyError::Storage(e.to_string()))?;
 
+        // Schemas created before transaction kinds existed
+        sqlx::query(
+            r#"
+            ALTER TABLE ledger_transactions
+            ADD COLUMN IF NOT EXISTS kind TEXT NOT NULL DEFAULT 'transfer'
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
         sqlx::query(
             r#"
             CREATE INDEX IF NOT EXISTS idx_transactions_asset
@@ -207,6 +231,65 @@ where
 
 const DEFAULT_MAX_FRAGMENTS: u64 = 1_000;
 
+fn asset_from_row(row: &sqlx::postgres::PgRow) -> Result<Asset, MoneyError> {
+    Ok(Asset {
+        id: row
+            .try_get("id")
+            .map_err(|e| MoneyError::Storage(e.to_string()))?,
+        code: row
+            .try_get("code")
+            .map_err(|e| MoneyError::Storage(e.to_string()))?,
+        unit: row
+            .try_get::<i64, _>("unit")
+            .map_err(|e| MoneyError::Storage(e.to_string()))? as u64,
+        decimals: row
+            .try_get::<i16, _>("decimals")
+            .map_err(|e| MoneyError::Storage(e.to_string()))? as u8,
+        active: row
+            .try_get("active")
+            .map_err(|e| MoneyError::Storage(e.to_string()))?,
+    })
+}
+
+fn transaction_from_row(row: &sqlx::postgres::PgRow) -> Result<Transaction, MoneyError> {
+    Ok(Transaction {
+        id: row
+            .try_get("id")
+            .map_err(|e| MoneyError::Storage(e.to_string()))?,
+        idempotency_key: row
+            .try_get("idempotency_key")
+            .map_err(|e| MoneyError::Storage(e.to_string()))?,
+        asset: row
+            .try_get("asset")
+            .map_err(|e| MoneyError::Storage(e.to_string()))?,
+        code: row
+            .try_get("code")
+            .map_err(|e| MoneyError::Storage(e.to_string()))?,
+        kind: row
+            .try_get::<String, _>("kind")
+            .map_err(|e| MoneyError::Storage(e.to_string()))?
+            .parse()?,
+        sender: row
+            .try_get("sender")
+            .map_err(|e| MoneyError::Storage(e.to_string()))?,
+        receiver: row
+            .try_get("receiver")
+            .map_err(|e| MoneyError::Storage(e.to_string()))?,
+        burned_amount: row
+            .try_get::<i64, _>("burned_amount")
+            .map_err(|e| MoneyError::Storage(e.to_string()))? as u64,
+        minted_amount: row
+            .try_get::<i64, _>("minted_amount")
+            .map_err(|e| MoneyError::Storage(e.to_string()))? as u64,
+        metadata: row
+            .try_get("metadata")
+            .map_err(|e| MoneyError::Storage(e.to_string()))?,
+        created_at: row
+            .try_get("created_at")
+            .map_err(|e| MoneyError::Storage(e.to_string()))?,
+    })
+}
+
 fn fragment_amount_smart(
     amount: u64,
     unit: u64,
@@ -281,6 +364,15 @@ trait PostgresInternalLedgerAdapter {
 
     async fn get_asset_by_id(&self, asset_id: Uuid) -> Result<Asset, MoneyError>;
 
+    /// Lock, verify and execute every operation of `plan` inside `tx`.
+    /// The caller decides whether to COMMIT or ROLLBACK.
+    async fn apply_plan_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        plan: &ExecutionPlan,
+        locks: &[(Uuid, Uuid, u64)],
+    ) -> Result<(), MoneyError>;
+
     /// Hard cap on fragment count per mint. Defaults to 1,000.
     /// Override per-adapter if needed.
     fn max_fragments(&self) -> u64 {
@@ -288,6 +380,20 @@ trait PostgresInternalLedgerAdapter {
     }
 }
 
+// Must run before any other statement in `tx`
+async fn set_isolation(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    level: Option<IsolationLevel>,
+) -> Result<(), MoneyError> {
+    if let Some(level) = level {
+        sqlx::query(&level.set_transaction_sql())
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| MoneyError::Storage(e.to_string()))?;
+    }
+    Ok(())
+}
+
 #[async_trait::async_trait]
 impl<T> PostgresInternalLedgerAdapter for T
 where
@@ -404,14 +510,15 @@ where
         sqlx::query(
             r#"
             INSERT INTO ledger_transactions
-                (id, asset, sender, receiver, burned_amount, minted_amount, metadata, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                (id, asset, sender, receiver, kind, burned_amount, minted_amount, metadata, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
             "#,
         )
         .bind(transaction.id)
         .bind(transaction.asset)
         .bind(transaction.sender)
         .bind(transaction.receiver)
+        .bind(transaction.kind.as_str())
         .bind(transaction.burned_amount as i64)
         .bind(transaction.minted_amount as i64)
         .bind(&transaction.metadata)
@@ -423,55 +530,12 @@ where
         Ok(())
     }
 
-    async fn get_asset_by_id(&self, asset_id: Uuid) -> Result<Asset, MoneyError> {
-        let row = sqlx::query(
-            r#"
-            SELECT id, code, unit, decimals
-            FROM ledger_assets
-            WHERE id = $1
-            "#,
-        )
-        .bind(asset_id)
-        .fetch_optional(&self.get_pool())
-        .await
-        .map_err(|e| MoneyError::Storage(e.to_string()))?
-        .ok_or_else(|| MoneyError::AssetNotFound(asset_id.to_string()))?;
-
-        Ok(Asset {
-            id: row
-                .try_get("id")
-                .map_err(|e| MoneyError::Storage(e.to_string()))?,
-            code: row
-                .try_get("code")
-                .map_err(|e| MoneyError::Storage(e.to_string()))?,
-            unit: row
-                .try_get::<i64, _>("unit")
-                .map_err(|e| MoneyError::Storage(e.to_string()))? as u64,
-            decimals: row
-                .try_get::<i16, _>("decimals")
-                .map_err(|e| MoneyError::Storage(e.to_string()))? as u8,
-        })
-    }
-
-    // max_fragments has a default impl above; override per-adapter if needed.
-}
-
-#[async_trait::async_trait]
-impl<T> LedgerAdapter for T
-where
-    T: PostgresLedgerAdapter + PostgresInternalLedgerAdapter + Send + Sync,
-{
-    async fn execute_plan(
+    async fn apply_plan_tx(
         &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
         plan: &ExecutionPlan,
         locks: &[(Uuid, Uuid, u64)],
     ) -> Result<(), MoneyError> {
-        let mut tx = self
-            .get_pool()
-            .begin()
-            .await
-            .map_err(|e| MoneyError::Storage(e.to_string()))?;
-
         // ── Phase 1: Lock & verify ─────────────────────────────────────────────
         // Select oldest VOs first (FIFO) so burned rows age out predictably and
         // can be archived by a background job once cold.
@@ -490,7 +554,7 @@ where
             )
             .bind(asset_id)
             .bind(owner)
-            .fetch_all(&mut *tx)
+            .fetch_all(&mut **tx)
             .await
             .map_err(|e| MoneyError::Storage(e.to_string()))?;
 
@@ -515,7 +579,6 @@ where
 
             // Checked INSIDE the lock — this is the real double-spend guard
             if total < *required {
-                tx.rollback().await.ok();
                 return Err(MoneyError::InsufficientFunds);
             }
 
@@ -533,7 +596,7 @@ where
                     amount,
                     ..
                 } => {
-                    self.mint_internal_tx(&mut tx, *asset_id, *owner, *amount)
+                    self.mint_internal_tx(tx, *asset_id, *owner, *amount)
                         .await?;
                 }
                 Operation::Burn {
@@ -552,7 +615,7 @@ where
                     ..
                 } => {
                     *used.entry((*asset_id, *from)).or_insert(0) += amount;
-                    self.mint_internal_tx(&mut tx, *asset_id, *to, *amount)
+                    self.mint_internal_tx(tx, *asset_id, *to, *amount)
                         .await?;
                 }
                 Operation::Reserve {
@@ -564,7 +627,7 @@ where
                 } => {
                     *used.entry((*asset_id, *from)).or_insert(0) += amount;
                     self.mint_reserved_internal_tx(
-                        &mut tx,
+                        tx,
                         *asset_id,
                         *for_authority,
                         *amount,
@@ -591,7 +654,7 @@ where
                     )
                     .bind(asset_id)
                     .bind(authority)
-                    .fetch_all(&mut *tx)
+                    .fetch_all(&mut **tx)
                     .await
                     .map_err(|e| MoneyError::Storage(e.to_string()))?;
 
@@ -613,7 +676,6 @@ where
                     }
 
                     if total_reserved < *amount {
-                        tx.rollback().await.ok();
                         return Err(MoneyError::InsufficientFunds);
                     }
 
@@ -625,7 +687,7 @@ where
                             "UPDATE ledger_value_objects SET state = 'burned' WHERE id = $1",
                         )
                         .bind(id)
-                        .execute(&mut *tx)
+                        .execute(&mut **tx)
                         .await
                         .map_err(|e| MoneyError::Storage(e.to_string()))?;
                     }
@@ -633,13 +695,13 @@ where
                     // Return change as reserved VOs for authority
                     let change = total_reserved - *amount;
                     if change > 0 {
-                        self.mint_reserved_internal_tx(&mut tx, *asset_id, *authority, change, *authority)
+                        self.mint_reserved_internal_tx(tx, *asset_id, *authority, change, *authority)
                             .await?;
                     }
 
                     // Mint alive VOs for receiver, consolidated into at most burned_count fragments
                     self.mint_internal_tx_with_max_fragments(
-                        &mut tx,
+                        tx,
                         *asset_id,
                         *receiver,
                         *amount,
@@ -649,7 +711,7 @@ where
                 }
 
                 Operation::RecordTransaction { transaction } => {
-                    self.record_transaction_internal_tx(&mut tx, transaction.clone())
+                    self.record_transaction_internal_tx(tx, transaction.clone())
                         .await?;
                 }
             }
@@ -666,7 +728,7 @@ where
             for id in ids {
                 sqlx::query("UPDATE ledger_value_objects SET state = 'burned' WHERE id = $1")
                     .bind(id)
-                    .execute(&mut *tx)
+                    .execute(&mut **tx)
                     .await
                     .map_err(|e| MoneyError::Storage(e.to_string()))?;
             }
@@ -675,7 +737,7 @@ where
             let change = total_locked - total_used;
             if change > 0 {
                 self.mint_internal_tx_with_max_fragments(
-                    &mut tx,
+                    tx,
                     *asset_id,
                     *owner,
                     change,
@@ -685,12 +747,78 @@ where
             }
         }
 
+        Ok(())
+    }
+
+    async fn get_asset_by_id(&self, asset_id: Uuid) -> Result<Asset, MoneyError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, code, unit, decimals, active
+            FROM ledger_assets
+            WHERE id = $1
+            "#,
+        )
+        .bind(asset_id)
+        .fetch_optional(&self.get_pool())
+        .await
+        .map_err(|e| MoneyError::Storage(e.to_string()))?
+        .ok_or_else(|| MoneyError::AssetNotFound(asset_id.to_string()))?;
+
+        asset_from_row(&row)
+    }
+
+    // max_fragments has a default impl above; override per-adapter if needed.
+}
+
+#[async_trait::async_trait]
+impl<T> LedgerAdapter for T
+where
+    T: PostgresLedgerAdapter + PostgresInternalLedgerAdapter + Send + Sync,
+{
+    async fn execute_plan(
+        &self,
+        plan: &ExecutionPlan,
+        locks: &[(Uuid, Uuid, u64)],
+    ) -> Result<(), MoneyError> {
+        let mut tx = self
+            .get_pool()
+            .begin()
+            .await
+            .map_err(|e| MoneyError::Storage(e.to_string()))?;
+        set_isolation(&mut tx, plan.isolation()).await?;
+
+        if let Err(e) = self.apply_plan_tx(&mut tx, plan, locks).await {
+            tx.rollback().await.ok();
+            return Err(e);
+        }
+
         tx.commit()
             .await
             .map_err(|e| MoneyError::Storage(e.to_string()))?;
         Ok(())
     }
 
+    async fn simulate_plan(
+        &self,
+        plan: &ExecutionPlan,
+        locks: &[(Uuid, Uuid, u64)],
+    ) -> Result<(), MoneyError> {
+        let mut tx = self
+            .get_pool()
+            .begin()
+            .await
+            .map_err(|e| MoneyError::Storage(e.to_string()))?;
+        set_isolation(&mut tx, plan.isolation()).await?;
+
+        let result = self.apply_plan_tx(&mut tx, plan, locks).await;
+
+        // Always roll back — a simulation must never leave a trace
+        tx.rollback()
+            .await
+            .map_err(|e| MoneyError::Storage(e.to_string()))?;
+        result
+    }
+
     async fn get_balance(&self, asset_id: Uuid, owner: Uuid) -> Result<Balance, MoneyError> {
         // PostgreSQL SUM returns NUMERIC, we need to cast to BIGINT
         let alive_sum: i64 = sqlx::query_scalar(
@@ -762,7 +890,7 @@ where
             r#"
             SELECT
                 lt.id, ik.key as idempotency_key, lt.asset, la.code,
-                lt.sender, lt.receiver,
+                lt.sender, lt.receiver, lt.kind,
                 lt.burned_amount, lt.minted_amount,
                 lt.metadata, lt.created_at
             FROM ledger_transaction_idempotency_keys ik
@@ -790,6 +918,10 @@ where
             code: row
                 .try_get("code")
                 .map_err(|e| MoneyError::Storage(e.to_string()))?,
+            kind: row
+                .try_get::<String, _>("kind")
+                .map_err(|e| MoneyError::Storage(e.to_string()))?
+                .parse()?,
             sender: row
                 .try_get("sender")
                 .map_err(|e| MoneyError::Storage(e.to_string()))?,
@@ -814,7 +946,7 @@ where
     async fn get_transaction(&self, tx_id: Uuid) -> Result<Transaction, MoneyError> {
         let row = sqlx::query(
             r#"
-            SELECT lt.id, ik.key as idempotency_key, lt.asset, a.code, lt.sender, lt.receiver, lt.burned_amount, lt.minted_amount, lt.metadata, lt.created_at
+            SELECT lt.id, ik.key as idempotency_key, lt.asset, a.code, lt.sender, lt.receiver, lt.kind, lt.burned_amount, lt.minted_amount, lt.metadata, lt.created_at
             FROM ledger_transactions lt
             LEFT JOIN assets a ON lt.asset = a.id
             LEFT JOIN ledger_transaction_idempotency_keys ik ON ik.transaction_id = lt.id
@@ -837,6 +969,10 @@ where
             code: row
                 .try_get("code")
                 .map_err(|e| MoneyError::Storage(e.to_string()))?,
+            kind: row
+                .try_get::<String, _>("kind")
+                .map_err(|e| MoneyError::Storage(e.to_string()))?
+                .parse()?,
             sender: row
                 .try_get("sender")
                 .map_err(|e| MoneyError::Storage(e.to_string()))?,
@@ -868,7 +1004,7 @@ where
     ) -> Result<Vec<Transaction>, MoneyError> {
         let rows = sqlx::query(
             r#"
-            SELECT lt.id, ik.key as idempotency_key, lt.asset, a.code, lt.sender, lt.receiver, lt.burned_amount, lt.minted_amount, lt.metadata, lt.created_at
+            SELECT lt.id, ik.key as idempotency_key, lt.asset, a.code, lt.sender, lt.receiver, lt.kind, lt.burned_amount, lt.minted_amount, lt.metadata, lt.created_at
             FROM ledger_transactions lt
             LEFT JOIN ledger_assets a ON lt.asset = a.id
             LEFT JOIN ledger_transaction_idempotency_keys ik ON ik.transaction_id = lt.id
@@ -893,6 +1029,10 @@ where
             let code = row
                 .try_get("code")
                 .map_err(|e| MoneyError::Storage(e.to_string()))?;
+            let kind = row
+                .try_get::<String, _>("kind")
+                .map_err(|e| MoneyError::Storage(e.to_string()))?
+                .parse()?;
             let sender = row
                 .try_get("sender")
                 .map_err(|e| MoneyError::Storage(e.to_string()))?;
@@ -922,6 +1062,7 @@ where
                 idempotency_key,
                 asset,
                 code,
+                kind,
                 sender,
                 receiver,
                 burned_amount,
@@ -934,10 +1075,73 @@ where
         Ok(transactions)
     }
 
+    async fn get_transactions_page(
+        &self,
+        owner: Uuid,
+        asset_code: &str,
+        cursor: Option<Uuid>,
+        limit: u32,
+    ) -> Result<TransactionPage, MoneyError> {
+        // v7 ids sort by creation time; one extra row tells us whether another page exists
+        let rows = sqlx::query(
+            r#"
+            SELECT lt.id, ik.key as idempotency_key, lt.asset, a.code, lt.sender, lt.receiver, lt.kind, lt.burned_amount, lt.minted_amount, lt.metadata, lt.created_at
+            FROM ledger_transactions lt
+            JOIN ledger_assets a ON lt.asset = a.id
+            LEFT JOIN ledger_transaction_idempotency_keys ik ON ik.transaction_id = lt.id
+            WHERE (lt.sender = $1 OR lt.receiver = $1)
+              AND a.code = $2
+              AND ($3::uuid IS NULL OR lt.id < $3)
+            ORDER BY lt.id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(owner)
+        .bind(asset_code)
+        .bind(cursor)
+        .bind(limit as i64 + 1)
+        .fetch_all(&self.get_pool())
+        .await
+        .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
+        let items = rows
+            .iter()
+            .map(transaction_from_row)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(TransactionPage::from_rows(items, limit))
+    }
+
+    async fn get_transactions_count(
+        &self,
+        owner: Uuid,
+        asset_code: &str,
+        timespan: &[DateTime<Utc>; 2],
+    ) -> Result<u64, MoneyError> {
+        let count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM ledger_transactions lt
+            JOIN ledger_assets a ON lt.asset = a.id
+            WHERE (lt.sender = $1 OR lt.receiver = $1)
+              AND a.code = $2
+              AND lt.created_at BETWEEN $3 AND $4
+            "#,
+        )
+        .bind(owner)
+        .bind(asset_code)
+        .bind(timespan[0])
+        .bind(timespan[1])
+        .fetch_one(&self.get_pool())
+        .await
+        .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
+        Ok(count as u64)
+    }
+
     async fn get_asset(&self, code: &str) -> Result<Asset, MoneyError> {
         let row = sqlx::query(
             r#"
-            SELECT id, code, unit, decimals
+            SELECT id, code, unit, decimals, active
             FROM ledger_assets
             WHERE code = $1
             "#,
@@ -948,33 +1152,22 @@ where
         .map_err(|e| MoneyError::Storage(e.to_string()))?
         .ok_or_else(|| MoneyError::AssetNotFound(code.to_string()))?;
 
-        Ok(Asset {
-            id: row
-                .try_get("id")
-                .map_err(|e| MoneyError::Storage(e.to_string()))?,
-            code: row
-                .try_get("code")
-                .map_err(|e| MoneyError::Storage(e.to_string()))?,
-            unit: row
-                .try_get::<i64, _>("unit")
-                .map_err(|e| MoneyError::Storage(e.to_string()))? as u64,
-            decimals: row
-                .try_get::<i16, _>("decimals")
-                .map_err(|e| MoneyError::Storage(e.to_string()))? as u8,
-        })
+        asset_from_row(&row)
     }
 
     async fn create_asset(&self, asset: Asset) -> Result<(), MoneyError> {
         sqlx::query(
             r#"
-            INSERT INTO ledger_assets (id, code, unit, decimals, created_at)
-            VALUES ($1, $2, $3, $4, NOW()) ON CONFLICT (code) DO UPDATE SET unit = $3, decimals = $4
+            INSERT INTO ledger_assets (id, code, unit, decimals, active, created_at)
+            VALUES ($1, $2, $3, $4, $5, NOW())
+            ON CONFLICT (code) DO UPDATE SET unit = $3, decimals = $4, active = $5
             "#,
         )
         .bind(asset.id)
         .bind(asset.code)
         .bind(asset.unit as i64)
         .bind(asset.decimals as i16)
+        .bind(asset.active)
         .execute(&self.get_pool())
         .await
         .map_err(|e| MoneyError::Storage(e.to_string()))?;
@@ -982,17 +1175,89 @@ where
         Ok(())
     }
 
+    async fn list_assets(&self) -> Result<Vec<Asset>, MoneyError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, code, unit, decimals, active
+            FROM ledger_assets
+            ORDER BY code
+            "#,
+        )
+        .fetch_all(&self.get_pool())
+        .await
+        .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
+        rows.iter().map(asset_from_row).collect()
+    }
+
+    async fn delete_asset(&self, code: &str) -> Result<(), MoneyError> {
+        let mut tx = self
+            .get_pool()
+            .begin()
+            .await
+            .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
+        let asset_id: Uuid =
+            sqlx::query_scalar("SELECT id FROM ledger_assets WHERE code = $1 FOR UPDATE")
+                .bind(code)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| MoneyError::Storage(e.to_string()))?
+                .ok_or_else(|| MoneyError::AssetNotFound(code.to_string()))?;
+
+        let outstanding: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM ledger_value_objects
+            WHERE asset = $1 AND state IN ('alive', 'reserved')
+            "#,
+        )
+        .bind(asset_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
+        if outstanding > 0 {
+            tx.rollback()
+                .await
+                .map_err(|e| MoneyError::Storage(e.to_string()))?;
+            return Err(MoneyError::AssetInUse(code.to_string()));
+        }
+
+        // Burned fragments and history reference the asset; they go with it
+        for sql in [
+            r#"
+            DELETE FROM ledger_transaction_idempotency_keys
+            WHERE transaction_id IN (SELECT id FROM ledger_transactions WHERE asset = $1)
+            "#,
+            "DELETE FROM ledger_transactions WHERE asset = $1",
+            "DELETE FROM ledger_value_objects WHERE asset = $1",
+            "DELETE FROM ledger_assets WHERE id = $1",
+        ] {
+            sqlx::query(sql)
+                .bind(asset_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| MoneyError::Storage(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
+        Ok(())
+    }
+
     async fn get_holdings(&self, owner: Uuid) -> Result<Vec<Holding>, MoneyError> {
         let rows = sqlx::query(
             r#"
             SELECT
-                la.id, la.code, la.unit, la.decimals,
+                la.id, la.code, la.unit, la.decimals, la.active,
                 COALESCE(SUM(vo.amount) FILTER (WHERE vo.state = 'alive'), 0)::BIGINT  AS alive_sum,
                 COALESCE(SUM(vo.amount) FILTER (WHERE vo.state = 'reserved'), 0)::BIGINT AS reserved_sum
             FROM ledger_value_objects vo
             JOIN ledger_assets la ON vo.asset = la.id
             WHERE vo.owner = $1
-            GROUP BY la.id, la.code, la.unit, la.decimals
+            GROUP BY la.id, la.code, la.unit, la.decimals, la.active
             HAVING COALESCE(SUM(vo.amount), 0) > 0
             "#,
         )
@@ -1006,18 +1271,7 @@ where
             let asset_id: Uuid = row
                 .try_get("id")
                 .map_err(|e| MoneyError::Storage(e.to_string()))?;
-            let asset = Asset {
-                id: asset_id,
-                code: row
-                    .try_get("code")
-                    .map_err(|e| MoneyError::Storage(e.to_string()))?,
-                unit: row
-                    .try_get::<i64, _>("unit")
-                    .map_err(|e| MoneyError::Storage(e.to_string()))? as u64,
-                decimals: row
-                    .try_get::<i16, _>("decimals")
-                    .map_err(|e| MoneyError::Storage(e.to_string()))? as u8,
-            };
+            let asset = asset_from_row(&row)?;
             let alive = row
                 .try_get::<i64, _>("alive_sum")
                 .map_err(|e| MoneyError::Storage(e.to_string()))? as u64;
@@ -1039,7 +1293,7 @@ where
         let rows = sqlx::query(
             r#"
             SELECT lt.id, ik.key as idempotency_key, lt.asset, la.code,
-                   lt.sender, lt.receiver, lt.burned_amount, lt.minted_amount,
+                   lt.sender, lt.receiver, lt.kind, lt.burned_amount, lt.minted_amount,
                    lt.metadata, lt.created_at
             FROM ledger_transactions lt
             LEFT JOIN ledger_assets la ON lt.asset = la.id
@@ -1069,6 +1323,10 @@ where
                 code: row
                     .try_get("code")
                     .map_err(|e| MoneyError::Storage(e.to_string()))?,
+                kind: row
+                    .try_get::<String, _>("kind")
+                    .map_err(|e| MoneyError::Storage(e.to_string()))?
+                    .parse()?,
                 sender: row
                     .try_get("sender")
                     .map_err(|e| MoneyError::Storage(e.to_string()))?,
@@ -1092,4 +1350,81 @@ where
 
         Ok(transactions)
     }
+
+    async fn expire_value_objects(&self) -> Result<u64, MoneyError> {
+        let result = sqlx::query("DELETE FROM ledger_value_objects WHERE state = 'burned'")
+            .execute(&self.get_pool())
+            .await
+            .map_err(|e| MoneyError::Storage(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn verify_ledger_integrity(
+        &self,
+        asset_code: &str,
+    ) -> Result<LedgerIntegrityReport, MoneyError> {
+        let mut tx = self
+            .get_pool()
+            .begin()
+            .await
+            .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
+        // All three sums must come from one snapshot, or a concurrent mint
+        // landing between them shows up as a false discrepancy
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
+        let asset_id: Uuid = sqlx::query_scalar("SELECT id FROM ledger_assets WHERE code = $1")
+            .bind(asset_code)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| MoneyError::Storage(e.to_string()))?
+            .ok_or_else(|| MoneyError::AssetNotFound(asset_code.to_string()))?;
+
+        let alive_supply: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(SUM(amount), 0)::BIGINT FROM ledger_value_objects
+            WHERE asset = $1 AND state IN ('alive', 'reserved')
+            "#,
+        )
+        .bind(asset_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
+        let total_minted: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(SUM(minted_amount), 0)::BIGINT FROM ledger_transactions
+            WHERE asset = $1 AND kind = 'mint'
+            "#,
+        )
+        .bind(asset_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
+        let total_burned: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(SUM(burned_amount), 0)::BIGINT FROM ledger_transactions
+            WHERE asset = $1 AND kind = 'burn'
+            "#,
+        )
+        .bind(asset_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| MoneyError::Storage(e.to_string()))?;
+
+        Ok(LedgerIntegrityReport::new(
+            asset_code.to_string(),
+            alive_supply,
+            total_minted,
+            total_burned,
+        ))
+    }
 }
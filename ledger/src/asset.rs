@@ -2,24 +2,70 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::MoneyError;
+
+/// Active ISO 4217 currency codes, sorted for binary search
+const ISO_4217_CODES: &[&str] = &[
+    "AED", "AFN", "ALL", "AMD", "ANG", "AOA", "ARS", "AUD", "AWG", "AZN", "BAM", "BBD",
+    "BDT", "BGN", "BHD", "BIF", "BMD", "BND", "BOB", "BOV", "BRL", "BSD", "BTN", "BWP",
+    "BYN", "BZD", "CAD", "CDF", "CHE", "CHF", "CHW", "CLF", "CLP", "CNY", "COP", "COU",
+    "CRC", "CUC", "CUP", "CVE", "CZK", "DJF", "DKK", "DOP", "DZD", "EGP", "ERN", "ETB",
+    "EUR", "FJD", "FKP", "GBP", "GEL", "GHS", "GIP", "GMD", "GNF", "GTQ", "GYD", "HKD",
+    "HNL", "HTG", "HUF", "IDR", "ILS", "INR", "IQD", "IRR", "ISK", "JMD", "JOD", "JPY",
+    "KES", "KGS", "KHR", "KMF", "KPW", "KRW", "KWD", "KYD", "KZT", "LAK", "LBP", "LKR",
+    "LRD", "LSL", "LYD", "MAD", "MDL", "MGA", "MKD", "MMK", "MNT", "MOP", "MRU", "MUR",
+    "MVR", "MWK", "MXN", "MXV", "MYR", "MZN", "NAD", "NGN", "NIO", "NOK", "NPR", "NZD",
+    "OMR", "PAB", "PEN", "PGK", "PHP", "PKR", "PLN", "PYG", "QAR", "RON", "RSD", "RUB",
+    "RWF", "SAR", "SBD", "SCR", "SDG", "SEK", "SGD", "SHP", "SLE", "SLL", "SOS", "SRD",
+    "SSP", "STN", "SVC", "SYP", "SZL", "THB", "TJS", "TMT", "TND", "TOP", "TRY", "TTD",
+    "TWD", "TZS", "UAH", "UGX", "USD", "USN", "UYI", "UYU", "UYW", "UZS", "VED", "VES",
+    "VND", "VUV", "WST", "XAF", "XAG", "XAU", "XBA", "XBB", "XBC", "XBD", "XCD", "XCG",
+    "XDR", "XOF", "XPD", "XPF", "XPT", "XSU", "XTS", "XUA", "XXX", "YER", "ZAR", "ZMW",
+    "ZWG", "ZWL",
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Asset {
     pub id: Uuid,
     pub code: String,
     pub unit: u64,
     pub decimals: u8,
+    /// Inactive assets stay readable but are soft-disabled
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+fn default_active() -> bool {
+    true
 }
 
 impl Asset {
-    pub fn new(code: &str, unit: u64, decimals: u8) -> Self {
+    /// Asset for an ISO 4217 currency. Fails with `InvalidCurrencyCode` unless
+    /// `code` is a known three-letter uppercase code.
+    pub fn new(code: &str, unit: u64, decimals: u8) -> Result<Self, MoneyError> {
+        let well_formed = code.len() == 3 && code.bytes().all(|b| b.is_ascii_uppercase());
+        if !well_formed || ISO_4217_CODES.binary_search(&code).is_err() {
+            return Err(MoneyError::InvalidCurrencyCode(code.to_string()));
+        }
+        Ok(Self::new_custom(code, unit, decimals))
+    }
+
+    /// Asset with any code, for non-currency units such as points, credits
+    /// or tokens
+    pub fn new_custom(code: &str, unit: u64, decimals: u8) -> Self {
         Self {
             id: uuid::Uuid::now_v7(),
             code: code.to_string(),
             unit,
             decimals,
+            active: true,
         }
     }
 
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
     pub fn to_internal(&self, display_amount: f64) -> u64 {
         (display_amount * 10_f64.powi(self.decimals as i32)) as u64
     }
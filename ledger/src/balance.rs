@@ -35,4 +35,9 @@ impl Balance {
             timestamp: Utc::now(),
         }
     }
+
+    /// Whether `amount` can be spent from the available (unreserved) balance
+    pub fn is_sufficient(&self, amount: u64) -> bool {
+        self.available >= amount
+    }
 }
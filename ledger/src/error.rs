@@ -5,6 +5,8 @@ use std::fmt;
 pub enum MoneyError {
     InsufficientFunds,
     AssetNotFound(String),
+    AssetInUse(String),
+    InvalidCurrencyCode(String),
     InvalidAmount,
     UnconsumedSlice,
     ReservationNotFound,
@@ -12,6 +14,7 @@ pub enum MoneyError {
     TransactionNotFound,
     DuplicateIdempotencyKey(uuid::Uuid),
     Storage(String),
+    NotSupported,
 }
 
 impl fmt::Display for MoneyError {
@@ -19,6 +22,8 @@ impl fmt::Display for MoneyError {
         match self {
             Self::InsufficientFunds => write!(f, "Insufficient funds"),
             Self::AssetNotFound(code) => write!(f, "Asset not found: {}", code),
+            Self::AssetInUse(code) => write!(f, "Asset still has outstanding value: {}", code),
+            Self::InvalidCurrencyCode(code) => write!(f, "Invalid currency code: {}", code),
             Self::InvalidAmount => write!(f, "Invalid amount"),
             Self::UnconsumedSlice => write!(f, "Not all slices were consumed"),
             Self::ReservationNotFound => write!(f, "Reservation not found"),
@@ -26,6 +31,7 @@ impl fmt::Display for MoneyError {
             Self::TransactionNotFound => write!(f, "Transaction not found"),
             Self::DuplicateIdempotencyKey(id) => write!(f, "Duplicate idempotency key: {}", id),
             Self::Storage(msg) => write!(f, "Storage error: {}", msg),
+            Self::NotSupported => write!(f, "Operation not supported by this adapter"),
         }
     }
 }
@@ -0,0 +1,32 @@
+// ledger/src/integrity.rs
+use serde::{Deserialize, Serialize};
+
+/// Result of `LedgerAdapter::verify_ledger_integrity` for one asset.
+///
+/// Every unit in circulation must come from a mint and leave through a burn,
+/// so `total_minted - total_burned` should equal the value still held.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerIntegrityReport {
+    pub asset: String,
+    /// Sum of value objects not yet burned (alive or reserved)
+    pub alive_supply: i64,
+    pub total_minted: i64,
+    pub total_burned: i64,
+    pub is_consistent: bool,
+    /// `alive_supply - (total_minted - total_burned)`; zero when consistent
+    pub discrepancy: i64,
+}
+
+impl LedgerIntegrityReport {
+    pub fn new(asset: String, alive_supply: i64, total_minted: i64, total_burned: i64) -> Self {
+        let discrepancy = alive_supply - (total_minted - total_burned);
+        Self {
+            asset,
+            alive_supply,
+            total_minted,
+            total_burned,
+            is_consistent: discrepancy == 0,
+            discrepancy,
+        }
+    }
+}
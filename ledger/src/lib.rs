@@ -4,6 +4,7 @@ pub mod asset;
 pub mod balance;
 pub mod error;
 pub mod holding;
+pub mod integrity;
 pub mod money;
 pub mod transaction;
 pub mod value_object;
@@ -13,8 +14,12 @@ pub use balance::Balance;
 use chrono::{DateTime, Utc};
 pub use error::MoneyError;
 pub use holding::{Holding, Portfolio};
-pub use money::{ExecutionPlan, LedgerContext, Money, MoneySlice, Operation, TransactionContext};
-pub use transaction::Transaction;
+pub use integrity::LedgerIntegrityReport;
+pub use money::{
+    ExecutionPlan, IsolationLevel, LedgerContext, Money, MoneySlice, Operation, SimulationResult,
+    TransactionContext, TransactionReceipt,
+};
+pub use transaction::{Transaction, TransactionKind, TransactionPage};
 pub use value_object::{ValueObject, ValueObjectState};
 
 use async_trait::async_trait;
@@ -41,6 +46,39 @@ pub trait LedgerAdapter: Send + Sync {
         locks: &[(Uuid, Uuid, u64)],
     ) -> Result<(), MoneyError>;
 
+    /// Run the plan exactly like `execute_plan`, then ROLLBACK unconditionally.
+    /// Returns the error `execute_plan` would have produced, if any.
+    ///
+    /// The default checks the plan against the read methods instead: every
+    /// lock must be covered by the owner's available balance and no
+    /// idempotency key may have been used. Adapters that can roll back a real
+    /// run should override it.
+    async fn simulate_plan(
+        &self,
+        plan: &ExecutionPlan,
+        locks: &[(Uuid, Uuid, u64)],
+    ) -> Result<(), MoneyError> {
+        for (asset_id, owner, required) in locks {
+            if self.get_balance(*asset_id, *owner).await?.available < *required {
+                return Err(MoneyError::InsufficientFunds);
+            }
+        }
+        for op in plan.operations() {
+            if let Operation::Mint {
+                idempotency_key: Some(key),
+                ..
+            }
+            | Operation::Burn {
+                idempotency_key: Some(key),
+                ..
+            } = op
+            {
+                self.check_idempotency_key(key).await?;
+            }
+        }
+        Ok(())
+    }
+
     // READ OPERATIONS
     async fn get_balance(&self, asset_id: Uuid, owner: Uuid) -> Result<Balance, MoneyError>;
     async fn get_transaction(&self, tx_id: Uuid) -> Result<Transaction, MoneyError>;
@@ -49,6 +87,31 @@ pub trait LedgerAdapter: Send + Sync {
         owner: Uuid,
         timespan: &[DateTime<Utc>; 2],
     ) -> Result<Vec<Transaction>, MoneyError>;
+
+    /// Up to `limit` of `owner`'s transactions in `asset_code`, newest first.
+    /// `cursor` is the last transaction id of the previous page.
+    /// The default returns `NotSupported`.
+    async fn get_transactions_page(
+        &self,
+        _owner: Uuid,
+        _asset_code: &str,
+        _cursor: Option<Uuid>,
+        _limit: u32,
+    ) -> Result<TransactionPage, MoneyError> {
+        Err(MoneyError::NotSupported)
+    }
+
+    /// Number of `owner`'s transactions in `asset_code` within `timespan`.
+    /// The default returns `NotSupported`.
+    async fn get_transactions_count(
+        &self,
+        _owner: Uuid,
+        _asset_code: &str,
+        _timespan: &[DateTime<Utc>; 2],
+    ) -> Result<u64, MoneyError> {
+        Err(MoneyError::NotSupported)
+    }
+
     async fn check_idempotency_key(&self, key: &str) -> Result<(), MoneyError>;
     async fn get_transaction_by_idempotency_key(
         &self,
@@ -57,6 +120,17 @@ pub trait LedgerAdapter: Send + Sync {
     async fn get_asset(&self, code: &str) -> Result<Asset, MoneyError>;
     async fn create_asset(&self, asset: Asset) -> Result<(), MoneyError>;
 
+    /// Every registered asset. The default returns `NotSupported`.
+    async fn list_assets(&self) -> Result<Vec<Asset>, MoneyError> {
+        Err(MoneyError::NotSupported)
+    }
+
+    /// Remove an asset. Fails with `AssetInUse` while any alive or reserved
+    /// value objects of it remain. The default returns `NotSupported`.
+    async fn delete_asset(&self, _code: &str) -> Result<(), MoneyError> {
+        Err(MoneyError::NotSupported)
+    }
+
     /// All assets held by `owner` with a non-zero balance.
     async fn get_holdings(&self, owner: Uuid) -> Result<Vec<Holding>, MoneyError>;
 
@@ -66,6 +140,24 @@ pub trait LedgerAdapter: Send + Sync {
         asset_id: Uuid,
         timespan: &[DateTime<Utc>; 2],
     ) -> Result<Vec<Transaction>, MoneyError>;
+
+    /// Check that `asset_code`'s unburned supply equals everything minted
+    /// minus everything burned, read from a single snapshot.
+    /// The default returns `NotSupported`.
+    async fn verify_ledger_integrity(
+        &self,
+        _asset_code: &str,
+    ) -> Result<LedgerIntegrityReport, MoneyError> {
+        Err(MoneyError::NotSupported)
+    }
+
+    /// Delete every burned value object. They no longer count toward any
+    /// balance, and the integrity check reads transactions instead, so this
+    /// only reclaims space. Returns how many were removed.
+    /// The default returns `NotSupported`.
+    async fn expire_value_objects(&self) -> Result<u64, MoneyError> {
+        Err(MoneyError::NotSupported)
+    }
 }
 
 /// Initialize the ledger system with an adapter
@@ -97,15 +189,28 @@ mod tests {
 
     #[test]
     fn test_asset_conversion() {
-        let usd = Asset::new("USD", 10_000, 2);
+        let usd = Asset::new("USD", 10_000, 2).unwrap();
         assert_eq!(usd.to_internal(100.50), 10050);
         assert_eq!(usd.to_display(10050), 100.50);
 
-        let eth = Asset::new("ETH", 1_000_000_000_000_000_000u64, 18);
+        let eth = Asset::new_custom("ETH", 1_000_000_000_000_000_000u64, 18);
         let one_eth = 1_000_000_000_000_000_000u64;
         assert_eq!(eth.to_display(one_eth), 1.0);
     }
 
+    #[test]
+    fn test_asset_code_validation() {
+        assert!(Asset::new("NGN", 100, 2).is_ok());
+        assert!(matches!(
+            Asset::new("usd", 100, 2),
+            Err(MoneyError::InvalidCurrencyCode(_))
+        ));
+        assert!(Asset::new("USDT", 100, 2).is_err());
+        // Not an ISO 4217 code, but allowed as a custom asset
+        assert!(Asset::new("XBT", 100, 8).is_err());
+        assert_eq!(Asset::new_custom("XBT", 100, 8).code, "XBT");
+    }
+
     #[test]
     fn test_value_object_states() {
         assert!(matches!(ValueObjectState::Alive, ValueObjectState::Alive));
@@ -115,4 +220,23 @@ mod tests {
         ));
         assert!(matches!(ValueObjectState::Burned, ValueObjectState::Burned));
     }
+
+    #[test]
+    fn test_isolation_override() {
+        let ctx = LedgerContext::new(Arc::new(adapters::memory::MemoryAdapter::new()));
+        let serializable = ctx.with_isolation(IsolationLevel::Serializable);
+
+        assert_eq!(ctx.isolation(), None);
+        assert_eq!(serializable.isolation(), Some(IsolationLevel::Serializable));
+        assert_eq!(IsolationLevel::Serializable.as_sql(), "SERIALIZABLE");
+        assert_eq!(IsolationLevel::RepeatableRead.as_sql(), "REPEATABLE READ");
+        assert_eq!(
+            IsolationLevel::ReadCommitted.set_transaction_sql(),
+            "SET TRANSACTION ISOLATION LEVEL READ COMMITTED"
+        );
+        assert_eq!(
+            IsolationLevel::Serializable.set_transaction_sql(),
+            "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE"
+        );
+    }
 }
@@ -1,7 +1,11 @@
 // ledger/src/money.rs
-use super::{Balance, Holding, LedgerAdapter, MoneyError, Transaction};
+use super::{
+    Asset, Balance, Holding, LedgerAdapter, LedgerIntegrityReport, MoneyError, Transaction,
+    TransactionKind, TransactionPage,
+};
 use chrono::{DateTime, Utc};
 use metrics::{counter, histogram};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
@@ -50,15 +54,23 @@ pub enum Operation {
 #[derive(Clone)]
 pub struct ExecutionPlan {
     operations: Vec<Operation>,
+    isolation: Option<IsolationLevel>,
 }
 
 impl ExecutionPlan {
     fn new() -> Self {
         Self {
             operations: Vec::new(),
+            isolation: None,
         }
     }
 
+    /// Isolation level the plan's transaction must run under; `None` keeps
+    /// the database default.
+    pub fn isolation(&self) -> Option<IsolationLevel> {
+        self.isolation
+    }
+
     fn add(&mut self, op: Operation) {
         self.operations.push(op);
     }
@@ -68,7 +80,6 @@ impl ExecutionPlan {
     }
 
     pub fn calculate_locks(&self) -> Vec<(Uuid, Uuid, u64)> {
-        use std::collections::HashMap;
         let mut locks: HashMap<(Uuid, Uuid), u64> = HashMap::new();
 
         for op in &self.operations {
@@ -108,20 +119,142 @@ impl ExecutionPlan {
     }
 }
 
+/// Outcome of `Money::simulate` — nothing in here was persisted.
+#[derive(Debug, Default)]
+pub struct SimulationResult {
+    /// Net balance delta per owner, per asset code, had the plan committed.
+    pub balance_changes: HashMap<Uuid, HashMap<String, i64>>,
+    /// The error `Money::atomic` would have returned, if any.
+    pub would_fail: Option<MoneyError>,
+}
+
+impl SimulationResult {
+    pub fn would_succeed(&self) -> bool {
+        self.would_fail.is_none()
+    }
+
+    /// Projected delta for `owner` in `asset_code` (0 when untouched).
+    pub fn change_for(&self, owner: Uuid, asset_code: &str) -> i64 {
+        self.balance_changes
+            .get(&owner)
+            .and_then(|assets| assets.get(asset_code))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn failed(error: MoneyError) -> Self {
+        Self {
+            balance_changes: HashMap::new(),
+            would_fail: Some(error),
+        }
+    }
+}
+
+/// What a committed `Money::atomic` block wrote to the ledger.
+#[derive(Debug, Clone)]
+pub struct TransactionReceipt {
+    /// Ids of every transaction recorded by the block, in plan order.
+    pub transaction_ids: Vec<Uuid>,
+    pub total_minted: i64,
+    pub total_burned: i64,
+    /// Sum of transfer, reserve, settle and swap legs.
+    pub total_transferred: i64,
+    /// Asset code(s) touched, comma-separated when more than one.
+    pub asset_code: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl TransactionReceipt {
+    fn from_plan(plan: &ExecutionPlan) -> Self {
+        let mut receipt = Self {
+            transaction_ids: Vec::new(),
+            total_minted: 0,
+            total_burned: 0,
+            total_transferred: 0,
+            asset_code: String::new(),
+            timestamp: Utc::now(),
+        };
+        let mut codes: Vec<&str> = Vec::new();
+
+        for operation in &plan.operations {
+            if let Operation::RecordTransaction { transaction } = operation {
+                receipt.transaction_ids.push(transaction.id);
+                match transaction.kind {
+                    TransactionKind::Mint => {
+                        receipt.total_minted += transaction.minted_amount as i64
+                    }
+                    TransactionKind::Burn => {
+                        receipt.total_burned += transaction.burned_amount as i64
+                    }
+                    _ => receipt.total_transferred += transaction.minted_amount as i64,
+                }
+                if !codes.contains(&transaction.code.as_str()) {
+                    codes.push(&transaction.code);
+                }
+            }
+        }
+
+        receipt.asset_code = codes.join(",");
+        receipt
+    }
+}
+
+/// Transaction isolation for the plans a [`LedgerContext`] executes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IsolationLevel {
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+
+    /// Statement that applies the level; must be the first in its transaction
+    pub fn set_transaction_sql(&self) -> String {
+        format!("SET TRANSACTION ISOLATION LEVEL {}", self.as_sql())
+    }
+}
+
 #[derive(Clone)]
 pub struct LedgerContext {
     adapter: Arc<dyn LedgerAdapter>,
+    isolation: Option<IsolationLevel>,
 }
 
 impl LedgerContext {
     pub fn new(adapter: Arc<dyn LedgerAdapter>) -> Self {
-        Self { adapter }
+        Self {
+            adapter,
+            isolation: None,
+        }
     }
 
     pub fn adapter(&self) -> &dyn LedgerAdapter {
         self.adapter.as_ref()
     }
 
+    /// Same adapter, but `Money::atomic` and `Money::simulate` run their plans
+    /// under `level`. Postgres applies it with `SET TRANSACTION ISOLATION LEVEL`;
+    /// the memory adapter already applies plans one at a time and ignores it.
+    pub fn with_isolation(&self, level: IsolationLevel) -> LedgerContext {
+        Self {
+            adapter: Arc::clone(&self.adapter),
+            isolation: Some(level),
+        }
+    }
+
+    /// Isolation override set by `with_isolation`, if any
+    pub fn isolation(&self) -> Option<IsolationLevel> {
+        self.isolation
+    }
+
     /// Balance for a single asset by code — no round-trip needed from the caller.
     pub async fn balance(&self, asset_code: &str, owner: Uuid) -> Result<Balance, MoneyError> {
         let asset = self.adapter.get_asset(asset_code).await?;
@@ -142,6 +275,32 @@ impl LedgerContext {
         self.adapter.get_transactions_for_owner(owner, timespan).await
     }
 
+    /// One page of `owner`'s transactions in `asset_code`, newest first.
+    /// Pass the previous page's `next_cursor` to continue.
+    pub async fn transactions_page(
+        &self,
+        owner: Uuid,
+        asset_code: &str,
+        cursor: Option<Uuid>,
+        limit: u32,
+    ) -> Result<TransactionPage, MoneyError> {
+        self.adapter
+            .get_transactions_page(owner, asset_code, cursor, limit)
+            .await
+    }
+
+    /// Number of `owner`'s transactions in `asset_code` within `timespan`.
+    pub async fn transactions_count(
+        &self,
+        owner: Uuid,
+        asset_code: &str,
+        timespan: &[DateTime<Utc>; 2],
+    ) -> Result<u64, MoneyError> {
+        self.adapter
+            .get_transactions_count(owner, asset_code, timespan)
+            .await
+    }
+
     /// Transactions for a specific asset (by code) within `timespan`.
     pub async fn transactions_for_asset(
         &self,
@@ -151,6 +310,14 @@ impl LedgerContext {
         let asset = self.adapter.get_asset(asset_code).await?;
         self.adapter.get_transactions_for_asset(asset.id, timespan).await
     }
+
+    /// Minted minus burned against current supply for `asset_code`.
+    pub async fn verify_integrity(
+        &self,
+        asset_code: &str,
+    ) -> Result<LedgerIntegrityReport, MoneyError> {
+        self.adapter.verify_ledger_integrity(asset_code).await
+    }
 }
 
 struct MoneyState {
@@ -427,7 +594,8 @@ impl TransactionContext {
                 amount,
                 metadata,
                 None,
-            ),
+            )
+            .with_kind(TransactionKind::Reserve),
         });
 
         Ok(())
@@ -473,7 +641,48 @@ impl TransactionContext {
                 amount,
                 metadata,
                 None,
-            ),
+            )
+            .with_kind(TransactionKind::Settle),
+        });
+
+        Ok(())
+    }
+
+    async fn swap_leg(
+        &self,
+        asset: &Asset,
+        from: Uuid,
+        to: Uuid,
+        amount: u64,
+        metadata: String,
+    ) -> Result<(), MoneyError> {
+        // Advisory pre-flight — the real guard is the adapter's inline lock during execute_plan
+        let balance = self.ctx.adapter().get_balance(asset.id, from).await?;
+        if balance.available < amount {
+            return Err(MoneyError::InsufficientFunds);
+        }
+
+        let mut plan = self.plan.lock().unwrap();
+        plan.add(Operation::Transfer {
+            asset_id: asset.id,
+            from,
+            to,
+            amount,
+            metadata: metadata.clone(),
+        });
+
+        plan.add(Operation::RecordTransaction {
+            transaction: Transaction::new(
+                asset.id,
+                asset.code.clone(),
+                Some(from),
+                Some(to),
+                amount,
+                amount,
+                metadata,
+                None,
+            )
+            .with_kind(TransactionKind::Swap),
         });
 
         Ok(())
@@ -518,22 +727,26 @@ pub struct Money {
 }
 
 impl Money {
-    pub async fn atomic<F, Fut>(ledger_ctx: &LedgerContext, f: F) -> Result<(), MoneyError>
+    /// Plan with `f`, then execute the plan in one database transaction.
+    /// Returns the closure's value together with a receipt of what was recorded.
+    pub async fn atomic<F, Fut, R>(
+        ledger_ctx: &LedgerContext,
+        f: F,
+    ) -> Result<(R, TransactionReceipt), MoneyError>
     where
         F: FnOnce(TransactionContext) -> Fut,
-        Fut: std::future::Future<Output = Result<(), MoneyError>>,
+        Fut: std::future::Future<Output = Result<R, MoneyError>>,
     {
         let tx_ctx = TransactionContext::new(Arc::clone(&ledger_ctx.adapter));
 
         // Run the planning closure (pure memory, no DB writes)
-        if let Err(e) = f(tx_ctx.clone()).await {
-            return Err(e);
-        }
+        let value = f(tx_ctx.clone()).await?;
 
         // Validate slice accounting
         tx_ctx.validate()?;
 
-        let plan = tx_ctx.plan.lock().unwrap().clone();
+        let mut plan = tx_ctx.plan.lock().unwrap().clone();
+        plan.isolation = ledger_ctx.isolation;
         let locks = plan.calculate_locks();
 
         // Metrics (unchanged)
@@ -556,7 +769,138 @@ impl Money {
         )
         .increment(1);
 
-        result
+        result?;
+        Ok((value, TransactionReceipt::from_plan(&plan)))
+    }
+
+    /// `atomic` without the receipt.
+    pub async fn atomic_silent<F, Fut, R>(ledger_ctx: &LedgerContext, f: F) -> Result<R, MoneyError>
+    where
+        F: FnOnce(TransactionContext) -> Fut,
+        Fut: std::future::Future<Output = Result<R, MoneyError>>,
+    {
+        Money::atomic(ledger_ctx, f).await.map(|(value, _)| value)
+    }
+
+    /// Exchange `amount_a` of `asset_a` (held by `owner_a`) for `amount_b` of
+    /// `asset_b` (held by `owner_b`). Both legs commit together or not at all.
+    ///
+    /// A [`Transaction`] holds a single asset, so the swap is recorded as two
+    /// `TransactionKind::Swap` transactions written in the same commit, one
+    /// per leg: `owner_a -> owner_b` in `asset_a` and `owner_b -> owner_a` in
+    /// `asset_b`. Both carry `memo`, and each party sees both legs in its
+    /// history.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn atomic_swap(
+        ledger_ctx: &LedgerContext,
+        asset_a: &str,
+        owner_a: Uuid,
+        amount_a: u64,
+        asset_b: &str,
+        owner_b: Uuid,
+        amount_b: u64,
+        memo: impl Into<String>,
+    ) -> Result<(), MoneyError> {
+        if amount_a == 0 || amount_b == 0 {
+            return Err(MoneyError::InvalidAmount);
+        }
+
+        let adapter = ledger_ctx.adapter();
+        let asset_a = adapter.get_asset(asset_a).await?;
+        let asset_b = adapter.get_asset(asset_b).await?;
+        let memo = memo.into();
+
+        Money::atomic_silent(ledger_ctx, |tx| async move {
+            tx.swap_leg(&asset_a, owner_a, owner_b, amount_a, memo.clone())
+                .await?;
+            tx.swap_leg(&asset_b, owner_b, owner_a, amount_b, memo)
+                .await
+        })
+        .await
+    }
+
+    /// Dry-run `f`: the plan is executed inside a transaction that is always
+    /// rolled back, so no value objects or transactions are persisted.
+    pub async fn simulate<F, Fut>(
+        ledger_ctx: &LedgerContext,
+        f: F,
+    ) -> Result<SimulationResult, MoneyError>
+    where
+        F: FnOnce(TransactionContext) -> Fut,
+        Fut: std::future::Future<Output = Result<(), MoneyError>>,
+    {
+        let tx_ctx = TransactionContext::new(Arc::clone(&ledger_ctx.adapter));
+
+        if let Err(e) = f(tx_ctx.clone()).await {
+            return Ok(SimulationResult::failed(e));
+        }
+
+        if let Err(e) = tx_ctx.validate() {
+            return Ok(SimulationResult::failed(e));
+        }
+
+        let mut plan = tx_ctx.plan.lock().unwrap().clone();
+        plan.isolation = ledger_ctx.isolation;
+        let locks = plan.calculate_locks();
+
+        if let Err(e) = ledger_ctx.adapter().simulate_plan(&plan, &locks).await {
+            return Ok(SimulationResult::failed(e));
+        }
+
+        let mut balance_changes: HashMap<Uuid, HashMap<String, i64>> = HashMap::new();
+        for operation in &plan.operations {
+            if let Operation::RecordTransaction { transaction } = operation {
+                if let Some(sender) = transaction.sender {
+                    *balance_changes
+                        .entry(sender)
+                        .or_default()
+                        .entry(transaction.code.clone())
+                        .or_insert(0) -= transaction.burned_amount as i64;
+                }
+                if let Some(receiver) = transaction.receiver {
+                    *balance_changes
+                        .entry(receiver)
+                        .or_default()
+                        .entry(transaction.code.clone())
+                        .or_insert(0) += transaction.minted_amount as i64;
+                }
+            }
+        }
+
+        Ok(SimulationResult {
+            balance_changes,
+            would_fail: None,
+        })
+    }
+
+    /// Pre-flight check: does `owner` hold at least `amount` of `asset`?
+    ///
+    /// Reads the balance without taking any lock, so it is only a hint — the
+    /// balance can change before a later transaction runs. Spending still goes
+    /// through `Money::atomic`, which re-checks under lock.
+    pub async fn can_afford(
+        asset: &str,
+        owner: Uuid,
+        amount: u64,
+        ctx: &LedgerContext,
+    ) -> Result<bool, MoneyError> {
+        let balance = ctx.balance(asset, owner).await?;
+        Ok(balance.is_sufficient(amount))
+    }
+
+    /// Like [`Money::can_afford`], but returns the balance on success and
+    /// `MoneyError::InsufficientFunds` otherwise. Same no-lock caveat applies.
+    pub async fn ensure_sufficient(
+        asset: &str,
+        owner: Uuid,
+        amount: u64,
+        ctx: &LedgerContext,
+    ) -> Result<Balance, MoneyError> {
+        let balance = ctx.balance(asset, owner).await?;
+        if !balance.is_sufficient(amount) {
+            return Err(MoneyError::InsufficientFunds);
+        }
+        Ok(balance)
     }
 
     pub fn slice(&self, amount: u64) -> Result<MoneySlice, MoneyError> {
@@ -1,14 +1,60 @@
 // ledger/src/transaction.rs
+use crate::MoneyError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use uuid::Uuid;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionKind {
+    Mint,
+    Burn,
+    Transfer,
+    Reserve,
+    Settle,
+    Swap,
+}
+
+impl TransactionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransactionKind::Mint => "mint",
+            TransactionKind::Burn => "burn",
+            TransactionKind::Transfer => "transfer",
+            TransactionKind::Reserve => "reserve",
+            TransactionKind::Settle => "settle",
+            TransactionKind::Swap => "swap",
+        }
+    }
+}
+
+impl FromStr for TransactionKind {
+    type Err = MoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mint" => Ok(TransactionKind::Mint),
+            "burn" => Ok(TransactionKind::Burn),
+            "transfer" => Ok(TransactionKind::Transfer),
+            "reserve" => Ok(TransactionKind::Reserve),
+            "settle" => Ok(TransactionKind::Settle),
+            "swap" => Ok(TransactionKind::Swap),
+            other => Err(MoneyError::Storage(format!(
+                "Unknown transaction kind: {}",
+                other
+            ))),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
     pub id: Uuid,
     pub idempotency_key: Option<String>,
     pub asset: Uuid,
     pub code: String,
+    pub kind: TransactionKind,
     pub sender: Option<Uuid>,
     pub receiver: Option<Uuid>,
     pub burned_amount: u64,
@@ -33,6 +79,11 @@ impl Transaction {
             idempotency_key,
             asset: asset_id,
             code: asset_name,
+            kind: match (sender, receiver) {
+                (None, _) => TransactionKind::Mint,
+                (_, None) => TransactionKind::Burn,
+                _ => TransactionKind::Transfer,
+            },
             sender,
             receiver,
             burned_amount,
@@ -41,4 +92,38 @@ impl Transaction {
             created_at: Utc::now(),
         }
     }
+
+    /// Override the kind inferred from sender/receiver.
+    pub fn with_kind(mut self, kind: TransactionKind) -> Self {
+        self.kind = kind;
+        self
+    }
+}
+
+/// One page of an owner's transactions, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionPage {
+    pub items: Vec<Transaction>,
+    /// Pass back as `cursor` to fetch the next page
+    pub next_cursor: Option<Uuid>,
+    pub has_more: bool,
+}
+
+impl TransactionPage {
+    /// Build a page from up to `limit + 1` rows sorted by id descending;
+    /// the extra row only signals that another page exists.
+    pub(crate) fn from_rows(mut items: Vec<Transaction>, limit: u32) -> Self {
+        let has_more = items.len() > limit as usize;
+        items.truncate(limit as usize);
+        let next_cursor = if has_more {
+            items.last().map(|tx| tx.id)
+        } else {
+            None
+        };
+        Self {
+            items,
+            next_cursor,
+            has_more,
+        }
+    }
 }
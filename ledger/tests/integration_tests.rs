@@ -1,7 +1,8 @@
 use chrono::{Days, Utc};
 // ledger/tests/integration_tests.rs
 use ousia_ledger::{
-    Asset, Balance, LedgerContext, LedgerSystem, Money, MoneyError, adapters::MemoryAdapter,
+    Asset, Balance, LedgerContext, LedgerSystem, Money, MoneyError, TransactionKind,
+    adapters::MemoryAdapter,
 };
 use std::sync::Arc;
 use uuid::Uuid;
@@ -16,7 +17,7 @@ fn setup() -> (Arc<LedgerSystem>, LedgerContext, Uuid) {
 }
 
 async fn create_usd_asset(system: &LedgerSystem) -> Asset {
-    let usd = Asset::new("USD", 10_00, 2);
+    let usd = Asset::new("USD", 10_00, 2).unwrap();
     system.adapter().create_asset(usd.clone()).await.unwrap();
     usd
 }
@@ -513,7 +514,7 @@ async fn test_concurrent_transfers_double_spend_protection() {
 
 #[tokio::test]
 async fn test_asset_decimals_conversion() {
-    let usd = Asset::new("USD", 10_00, 2);
+    let usd = Asset::new("USD", 10_00, 2).unwrap();
     assert_eq!(usd.decimals, 2);
     assert_eq!(usd.to_internal(100.50), 10050);
     assert_eq!(usd.to_display(10050), 100.50);
@@ -612,7 +613,7 @@ async fn test_rollback_on_error() {
             .await?;
 
         // Intentionally fail
-        return Err(MoneyError::Storage("simulated error".to_string()));
+        return Err::<(), _>(MoneyError::Storage("simulated error".to_string()));
     })
     .await;
 
@@ -632,7 +633,7 @@ async fn test_multiple_assets() {
     let (system, ctx, user) = setup();
     create_usd_asset(&system).await;
 
-    let ngn = Asset::new("NGN", 500_000, 2); // ₦5,000 unit
+    let ngn = Asset::new("NGN", 500_000, 2).unwrap(); // ₦5,000 unit
     system.adapter().create_asset(ngn).await.unwrap();
 
     Money::atomic(&ctx, |tx| async move {
@@ -694,6 +695,75 @@ async fn test_fetch_transactions() {
     assert_eq!(transactions.len(), 2);
 }
 
+#[tokio::test]
+async fn test_transactions_page() {
+    let (system, ctx, user) = setup();
+    create_usd_asset(&system).await;
+    let ngn = Asset::new("NGN", 100_00, 2).unwrap();
+    system.adapter().create_asset(ngn).await.unwrap();
+
+    for _ in 0..25 {
+        Money::atomic(&ctx, |tx| async move {
+            tx.mint("USD", user, 1_00, "deposit".to_string()).await?;
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+    // Other assets are left out
+    Money::atomic(&ctx, |tx| async move {
+        tx.mint("NGN", user, 1_00, "deposit".to_string()).await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    let mut pages = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = ctx
+            .transactions_page(user, "USD", cursor, 10)
+            .await
+            .unwrap();
+        pages.push(page.items.len());
+        if !page.has_more {
+            assert!(page.next_cursor.is_none());
+            break;
+        }
+        assert_eq!(page.next_cursor, page.items.last().map(|tx| tx.id));
+        cursor = page.next_cursor;
+    }
+    assert_eq!(pages, vec![10, 10, 5]);
+
+    // Newest first, no overlap between pages
+    let first = ctx.transactions_page(user, "USD", None, 10).await.unwrap();
+    let second = ctx
+        .transactions_page(user, "USD", first.next_cursor, 10)
+        .await
+        .unwrap();
+    assert!(first.items.windows(2).all(|w| w[0].id > w[1].id));
+    assert!(first.items.last().unwrap().id > second.items[0].id);
+
+    let timespan = [
+        Utc::now().checked_sub_days(Days::new(1)).unwrap(),
+        Utc::now().checked_add_days(Days::new(1)).unwrap(),
+    ];
+    assert_eq!(
+        ctx.transactions_count(user, "USD", &timespan).await.unwrap(),
+        25
+    );
+    assert_eq!(
+        ctx.transactions_count(user, "NGN", &timespan).await.unwrap(),
+        1
+    );
+    assert_eq!(
+        ctx.transactions_count(Uuid::now_v7(), "USD", &timespan)
+            .await
+            .unwrap(),
+        0
+    );
+}
+
 // ── Fragmentation & Consolidation Tests ──────────────────────────────────────
 //
 // These tests verify the smart fragmentation behaviour introduced in:
@@ -1013,3 +1083,382 @@ async fn test_interleaved_mints_and_spends_balance_integrity() {
     assert_eq!(user_balance.available, expected_user as u64);
     assert_eq!(merchant_balance.available, expected_merchant as u64);
 }
+
+#[tokio::test]
+async fn test_simulate_does_not_persist() {
+    let (system, ctx, user) = setup();
+    let merchant = Uuid::now_v7();
+    create_usd_asset(&system).await;
+
+    Money::atomic(&ctx, |tx| async move {
+        tx.mint("USD", user, 100_00, "deposit".to_string()).await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    let result = Money::simulate(&ctx, |tx| async move {
+        let money = tx.money("USD", user, 30_00).await?;
+        let slice = money.slice(30_00)?;
+        slice.transfer_to(merchant, "payment".to_string()).await?;
+        tx.mint("USD", merchant, 5_00, "bonus".to_string()).await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    assert!(result.would_succeed());
+    assert_eq!(result.change_for(user, "USD"), -30_00);
+    assert_eq!(result.change_for(merchant, "USD"), 35_00);
+
+    // Nothing was committed
+    let user_balance = Balance::get("USD", user, &ctx).await.unwrap();
+    let merchant_balance = Balance::get("USD", merchant, &ctx).await.unwrap();
+    assert_eq!(user_balance.available, 100_00);
+    assert_eq!(merchant_balance.total, 0);
+
+    let timespan = [
+        Utc::now().checked_sub_days(Days::new(1)).unwrap(),
+        Utc::now().checked_add_days(Days::new(1)).unwrap(),
+    ];
+    let merchant_txs = ctx.transactions(merchant, &timespan).await.unwrap();
+    assert!(merchant_txs.is_empty());
+}
+
+#[tokio::test]
+async fn test_simulate_reports_would_fail() {
+    let (system, ctx, user) = setup();
+    let merchant = Uuid::now_v7();
+    create_usd_asset(&system).await;
+
+    Money::atomic(&ctx, |tx| async move {
+        tx.mint("USD", user, 50_00, "deposit".to_string()).await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    // Each money() passes its pre-flight check, but together they overspend,
+    // so the failure only surfaces when the plan is executed.
+    let result = Money::simulate(&ctx, |tx| async move {
+        let first = tx.money("USD", user, 40_00).await?;
+        first
+            .slice(40_00)?
+            .transfer_to(merchant, "first".to_string())
+            .await?;
+        let second = tx.money("USD", user, 40_00).await?;
+        second
+            .slice(40_00)?
+            .transfer_to(merchant, "second".to_string())
+            .await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    assert!(matches!(
+        result.would_fail,
+        Some(MoneyError::InsufficientFunds)
+    ));
+    assert!(result.balance_changes.is_empty());
+
+    let user_balance = Balance::get("USD", user, &ctx).await.unwrap();
+    assert_eq!(user_balance.available, 50_00);
+}
+
+async fn setup_swap(
+    ctx: &LedgerContext,
+    system: &LedgerSystem,
+    alice_usd: u64,
+    bob_ngn: u64,
+) -> (Uuid, Uuid) {
+    let alice = Uuid::now_v7();
+    let bob = Uuid::now_v7();
+    create_usd_asset(system).await;
+    system
+        .adapter()
+        .create_asset(Asset::new("NGN", 1000_00, 2).unwrap())
+        .await
+        .unwrap();
+
+    Money::atomic(ctx, |tx| async move {
+        tx.mint("USD", alice, alice_usd, "deposit".to_string())
+            .await?;
+        tx.mint("NGN", bob, bob_ngn, "deposit".to_string()).await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    (alice, bob)
+}
+
+#[tokio::test]
+async fn test_atomic_swap_success() {
+    let (system, ctx, _) = setup();
+    let (alice, bob) = setup_swap(&ctx, &system, 100_00, 500_00).await;
+
+    Money::atomic_swap(&ctx, "USD", alice, 50_00, "NGN", bob, 100_00, "fx")
+        .await
+        .unwrap();
+
+    assert_eq!(ctx.balance("USD", alice).await.unwrap().available, 50_00);
+    assert_eq!(ctx.balance("USD", bob).await.unwrap().available, 50_00);
+    assert_eq!(ctx.balance("NGN", alice).await.unwrap().available, 100_00);
+    assert_eq!(ctx.balance("NGN", bob).await.unwrap().available, 400_00);
+
+    let timespan = [
+        Utc::now().checked_sub_days(Days::new(1)).unwrap(),
+        Utc::now().checked_add_days(Days::new(1)).unwrap(),
+    ];
+    // One Swap transaction per leg, each in its own asset
+    let swaps: Vec<_> = ctx
+        .transactions(alice, &timespan)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|t| t.kind == TransactionKind::Swap)
+        .collect();
+    assert_eq!(swaps.len(), 2);
+    assert!(swaps.iter().all(|t| t.metadata == "fx"));
+    let usd_leg = swaps.iter().find(|t| t.code == "USD").unwrap();
+    assert_eq!(
+        (usd_leg.sender, usd_leg.receiver, usd_leg.burned_amount),
+        (Some(alice), Some(bob), 50_00)
+    );
+    let ngn_leg = swaps.iter().find(|t| t.code == "NGN").unwrap();
+    assert_eq!(
+        (ngn_leg.sender, ngn_leg.receiver, ngn_leg.burned_amount),
+        (Some(bob), Some(alice), 100_00)
+    );
+
+    let bob_swaps: Vec<_> = ctx
+        .transactions(bob, &timespan)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|t| t.kind == TransactionKind::Swap)
+        .map(|t| t.id)
+        .collect();
+    assert_eq!(bob_swaps.len(), 2);
+    assert!(swaps.iter().all(|t| bob_swaps.contains(&t.id)));
+}
+
+#[tokio::test]
+async fn test_atomic_swap_insufficient_funds_side_a() {
+    let (system, ctx, _) = setup();
+    let (alice, bob) = setup_swap(&ctx, &system, 10_00, 500_00).await;
+
+    let result = Money::atomic_swap(&ctx, "USD", alice, 50_00, "NGN", bob, 100_00, "fx").await;
+    assert!(matches!(result, Err(MoneyError::InsufficientFunds)));
+
+    assert_eq!(ctx.balance("NGN", bob).await.unwrap().available, 500_00);
+    assert_eq!(ctx.balance("NGN", alice).await.unwrap().total, 0);
+    assert_eq!(ctx.balance("USD", alice).await.unwrap().available, 10_00);
+}
+
+#[tokio::test]
+async fn test_atomic_swap_insufficient_funds_side_b() {
+    let (system, ctx, _) = setup();
+    let (alice, bob) = setup_swap(&ctx, &system, 100_00, 50_00).await;
+
+    let result = Money::atomic_swap(&ctx, "USD", alice, 50_00, "NGN", bob, 100_00, "fx").await;
+    assert!(matches!(result, Err(MoneyError::InsufficientFunds)));
+
+    assert_eq!(ctx.balance("USD", alice).await.unwrap().available, 100_00);
+    assert_eq!(ctx.balance("USD", bob).await.unwrap().total, 0);
+    assert_eq!(ctx.balance("NGN", bob).await.unwrap().available, 50_00);
+}
+
+#[tokio::test]
+async fn test_atomic_swap_unknown_asset() {
+    let (system, ctx, _) = setup();
+    let (alice, bob) = setup_swap(&ctx, &system, 100_00, 500_00).await;
+
+    let result = Money::atomic_swap(&ctx, "USD", alice, 50_00, "EUR", bob, 100_00, "fx").await;
+    assert!(matches!(result, Err(MoneyError::AssetNotFound(_))));
+    assert_eq!(ctx.balance("USD", alice).await.unwrap().available, 100_00);
+}
+
+#[tokio::test]
+async fn test_list_and_delete_asset() {
+    let (system, ctx, user) = setup();
+    let usd = create_usd_asset(&system).await;
+    assert!(usd.is_active());
+
+    let assets = system.adapter().list_assets().await.unwrap();
+    assert!(assets.iter().any(|a| a.code == "USD"));
+
+    Money::atomic(&ctx, |tx| async move {
+        tx.mint("USD", user, 50_00, "deposit".to_string()).await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    let result = system.adapter().delete_asset("USD").await;
+    assert!(matches!(result, Err(MoneyError::AssetInUse(_))));
+
+    Money::atomic(&ctx, |tx| async move {
+        tx.burn("USD", user, 50_00, "withdraw".to_string()).await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    system.adapter().delete_asset("USD").await.unwrap();
+
+    let assets = system.adapter().list_assets().await.unwrap();
+    assert!(assets.iter().all(|a| a.code != "USD"));
+    assert!(matches!(
+        system.adapter().get_asset("USD").await,
+        Err(MoneyError::AssetNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_atomic_receipt_lists_every_transfer() {
+    let (system, ctx, user) = setup();
+    let r1 = Uuid::now_v7();
+    let r2 = Uuid::now_v7();
+    let r3 = Uuid::now_v7();
+    create_usd_asset(&system).await;
+
+    let ((), mint_receipt) = Money::atomic(&ctx, |tx| async move {
+        tx.mint("USD", user, 1_000_00, "deposit".to_string())
+            .await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+    assert_eq!(mint_receipt.transaction_ids.len(), 1);
+    assert_eq!(mint_receipt.total_minted, 1_000_00);
+
+    let (label, receipt) = Money::atomic(&ctx, |tx| async move {
+        let money = tx.money("USD", user, 900_00).await?;
+        let mut slice = money.slice(900_00)?;
+
+        let p1 = slice.slice(500_00)?;
+        let p2 = slice.slice(250_00)?;
+        let p3 = slice.slice(150_00)?;
+
+        p1.transfer_to(r1, "payment1".to_string()).await?;
+        p2.transfer_to(r2, "payment2".to_string()).await?;
+        p3.transfer_to(r3, "payment3".to_string()).await?;
+        Ok("split")
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(label, "split");
+    assert_eq!(receipt.transaction_ids.len(), 3);
+    assert_eq!(receipt.total_transferred, 900_00);
+    assert_eq!(receipt.total_minted, 0);
+    assert_eq!(receipt.total_burned, 0);
+    assert_eq!(receipt.asset_code, "USD");
+
+    let timespan = [
+        Utc::now().checked_sub_days(Days::new(1)).unwrap(),
+        Utc::now().checked_add_days(Days::new(1)).unwrap(),
+    ];
+    for recipient in [r1, r2, r3] {
+        let txs = ctx.transactions(recipient, &timespan).await.unwrap();
+        assert_eq!(txs.len(), 1);
+        assert!(receipt.transaction_ids.contains(&txs[0].id));
+    }
+}
+
+#[tokio::test]
+async fn test_atomic_silent_returns_closure_value() {
+    let (system, ctx, user) = setup();
+    create_usd_asset(&system).await;
+
+    let value = Money::atomic_silent(&ctx, |tx| async move {
+        tx.mint("USD", user, 10_00, "deposit".to_string()).await?;
+        Ok(42)
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(value, 42);
+    assert_eq!(ctx.balance("USD", user).await.unwrap().available, 10_00);
+}
+
+#[tokio::test]
+async fn test_can_afford_and_ensure_sufficient() {
+    let (system, ctx, user) = setup();
+    create_usd_asset(&system).await;
+
+    Money::atomic_silent(&ctx, |tx| async move {
+        tx.mint("USD", user, 50_00, "deposit".to_string()).await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    assert!(Money::can_afford("USD", user, 50_00, &ctx).await.unwrap());
+    assert!(!Money::can_afford("USD", user, 50_01, &ctx).await.unwrap());
+
+    let balance = Money::ensure_sufficient("USD", user, 20_00, &ctx)
+        .await
+        .unwrap();
+    assert_eq!(balance.available, 50_00);
+    assert!(matches!(
+        Money::ensure_sufficient("USD", user, 60_00, &ctx).await,
+        Err(MoneyError::InsufficientFunds)
+    ));
+
+    // Checks never move funds
+    assert_eq!(ctx.balance("USD", user).await.unwrap().available, 50_00);
+}
+
+#[tokio::test]
+async fn test_verify_integrity() {
+    let (system, ctx, user) = setup();
+    let merchant = Uuid::now_v7();
+    let authority = Uuid::now_v7();
+    create_usd_asset(&system).await;
+
+    Money::atomic(&ctx, |tx| async move {
+        tx.mint("USD", user, 100_00, "deposit".to_string()).await?;
+        tx.mint("USD", merchant, 20_00, "deposit".to_string())
+            .await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    Money::atomic(&ctx, |tx| async move {
+        let money = tx.money("USD", user, 60_00).await?;
+        let slice = money.slice(60_00)?;
+        slice.transfer_to(merchant, "payment".to_string()).await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    Money::atomic(&ctx, |tx| async move {
+        let money = tx.money("USD", merchant, 15_00).await?;
+        let slice = money.slice(15_00)?;
+        slice.burn("fee".to_string()).await?;
+        tx.reserve("USD", user, authority, 10_00, "escrow".to_string())
+            .await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    let report = ctx.verify_integrity("USD").await.unwrap();
+    assert_eq!(report.asset, "USD");
+    assert_eq!(report.total_minted, 120_00);
+    assert_eq!(report.total_burned, 15_00);
+    // Reserved funds are still in circulation
+    assert_eq!(report.alive_supply, 105_00);
+    assert!(report.is_consistent);
+    assert_eq!(report.discrepancy, 0);
+
+    assert!(matches!(
+        ctx.verify_integrity("EUR").await,
+        Err(MoneyError::AssetNotFound(_))
+    ));
+}
@@ -1,6 +1,8 @@
-use chrono::Utc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
 use sqlx::{
-    PgPool, Postgres, Row,
+    PgPool, Postgres, Row, Transaction,
     postgres::{PgArguments, PgRow},
     query::{Query as PgQuery, QueryScalar},
 };
@@ -8,8 +10,11 @@ use uuid::Uuid;
 
 use crate::{
     adapters::{
-        Adapter, EdgeQuery, EdgeRecord, EdgeTraversal, Error, ObjectRecord, Query,
-        TraversalDirection, UniqueAdapter,
+        Adapter, Aggregation, BatchEdgeResult, DatabaseTransaction, EdgeQuery, EdgeRecord,
+        EdgeTraversal, Error, Granularity, GroupCount, LockGuard, ObjectRecord, OnConflict, Query,
+        QueryCost, SchemaColumn, SchemaExport, SchemaVerification, TableStats, TransactionBackend,
+        TraversalDirection, TypeStats, UniqueAdapter, UpsertOutcome, schema::table_ddl,
+        transaction::finished,
     },
     query::{Cursor, IndexValue, IndexValueInner, QueryFilter},
 };
@@ -48,11 +53,60 @@ use crate::{
 
 pub struct CockroachAdapter {
     pub(crate) pool: PgPool,
+    /// Staleness accepted by every object read; see [`CockroachAdapter::with_follower_read`]
+    pub(crate) follower_read: Option<Duration>,
 }
 
+/// Tables and indexes `init_schema` creates; checked by `verify_schema`
+const SCHEMA_TABLES: &[&str] = &[
+    "objects",
+    "edges",
+    "unique_constraints",
+    "sequences",
+    "retention_policies",
+    "object_history",
+    "object_locks",
+];
+const SCHEMA_INDEXES: &[&str] = &[
+    "idx_objects_type_owner",
+    "idx_objects_type_owner_created",
+    "idx_objects_type_owner_updated",
+    "idx_objects_index_meta",
+    "idx_objects_labels",
+    "idx_edges_from_type",
+    "idx_edges_to_type",
+    "idx_edges_index_meta",
+    "idx_unique_id",
+    "idx_unique_type_key",
+    "idx_object_history_id_version",
+];
+
 impl CockroachAdapter {
     pub fn from_pool(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            follower_read: None,
+        }
+    }
+
+    /// Serve object reads (`fetch_object*`, `query_objects`, `count_objects`)
+    /// `AS OF SYSTEM TIME '-staleness'`, so any replica close to the gateway can
+    /// answer without going through the range leader. Writes are unaffected.
+    /// A query's own [`Query::allow_stale`] takes precedence.
+    pub fn with_follower_read(mut self, staleness: Duration) -> Self {
+        self.follower_read = Some(staleness);
+        self
+    }
+
+    /// `AS OF SYSTEM TIME` clause for a read tolerating `stale`, falling back to
+    /// the adapter-wide setting. Empty for fresh reads.
+    fn as_of_system_time(&self, stale: Option<Duration>) -> String {
+        match stale.or(self.follower_read) {
+            Some(stale) if !stale.is_zero() => {
+                format!("AS OF SYSTEM TIME '-{}ms'", stale.as_millis().max(1))
+            }
+            _ => String::new(),
+        }
     }
 
     /// Initialize the database schema
@@ -123,6 +177,15 @@ impl CockroachAdapter {
         .await
         .map_err(|e| Error::Storage(e.to_string()))?;
 
+        sqlx::query(
+            r#"
+            CREATE INVERTED INDEX IF NOT EXISTS idx_objects_labels ON public.objects ((data->'__labels'));
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
         sqlx::query(
             r#"
             CREATE TABLE IF NOT EXISTS public.edges (
@@ -213,6 +276,62 @@ impl CockroachAdapter {
         .await
         .map_err(|e| Error::Storage(e.to_string()))?;
 
+        // TTLs applied by Engine::run_retention_policies
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS retention_policies (
+                type TEXT PRIMARY KEY,
+                ttl_seconds BIGINT NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        // Pre-update snapshots of versioned objects; see Engine::object_timeline
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS public.object_history (
+                id UUID NOT NULL,
+                version BIGINT NOT NULL,
+                type TEXT NOT NULL,
+                owner UUID NOT NULL,
+                created_at TIMESTAMPTZ NOT NULL,
+                updated_at TIMESTAMPTZ NOT NULL,
+                data JSONB NOT NULL,
+                index_meta JSONB NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            CREATE UNIQUE INDEX IF NOT EXISTS idx_object_history_id_version
+                ON public.object_history(id, version)
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        // Rows only exist inside open lock transactions (see lock_object)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS object_locks (
+                type TEXT NOT NULL,
+                id UUID NOT NULL,
+                PRIMARY KEY (type, id)
+            )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
         tx.commit()
             .await
             .map_err(|e| Error::Storage(e.to_string()))?;
@@ -294,7 +413,7 @@ impl CockroachAdapter {
         direction: TraversalDirection,
     ) -> Result<Vec<(EdgeRecord, ObjectRecord)>, Error> {
         let where_clause = Self::build_object_traversal_query_conditions(
-            direction.clone(),
+            direction,
             obj_filters,
             &plan.filters,
             plan.cursor,
@@ -374,6 +493,7 @@ impl CockroachAdapter {
             IndexValue::Bool(_) => "boolean",
             IndexValue::Timestamp(_) => "timestamptz",
             IndexValue::Uuid(_) => "uuid",
+            IndexValue::Null => "text",
             IndexValue::Array(arr) => match arr.first() {
                 Some(IndexValueInner::String(_)) => "text[]",
                 Some(IndexValueInner::Int(_)) => "bigint[]",
@@ -427,6 +547,51 @@ impl CockroachAdapter {
 
         use crate::query::Comparison::*;
 
+        // Labels live in data, covered by the GIN index on (data->'__labels')
+        if let Some((_, value)) = filter.as_label() {
+            let op = if value.is_some() { "@>" } else { "?" };
+            let cond = format!("{}.data->'__labels' {} ${}", alias, op, param_idx);
+            *param_idx += 1;
+            return Some((cond, operator));
+        }
+
+        if filter.as_namespace().is_some() {
+            let cond = format!("{}.data->>'__namespace' = ${}", alias, param_idx);
+            *param_idx += 1;
+            return Some((cond, operator));
+        }
+
+        // JSON null and a missing key both read as SQL NULL through ->>
+        if let Some(is_null) = filter.as_null_check() {
+            let check = if is_null { "IS NULL" } else { "IS NOT NULL" };
+            let cond = format!("{}.index_meta->>'{}' {}", alias, filter.field.name, check);
+            return Some((cond, operator));
+        }
+
+        // Meta timestamps are native columns covered by the (type, owner, *_at) B-tree indexes
+        if alias == "o"
+            && matches!(filter.field.name, "created_at" | "updated_at")
+            && matches!(filter.value, IndexValue::Timestamp(_))
+        {
+            let comparison = match qs.comparison {
+                Equal => Some("="),
+                NotEqual => Some("<>"),
+                GreaterThan => Some(">"),
+                LessThan => Some("<"),
+                GreaterThanOrEqual => Some(">="),
+                LessThanOrEqual => Some("<="),
+                BeginsWith | Contains | ContainsAll | IsNull | IsNotNull | Regex => None,
+            };
+            if let Some(comparison) = comparison {
+                let cond = format!(
+                    "{}.{} {} ${}",
+                    alias, filter.field.name, comparison, param_idx
+                );
+                *param_idx += 1;
+                return Some((cond, operator));
+            }
+        }
+
         // INVERTED INDEX @> path
         match (&qs.comparison, &filter.value) {
             (
@@ -475,6 +640,9 @@ impl CockroachAdapter {
             BeginsWith => "ILIKE",
             Contains => "ILIKE",
             ContainsAll => "ILIKE",
+            Regex if qs.case_sensitive => "~",
+            Regex => "~*",
+            IsNull | IsNotNull => unreachable!("null checks handled above"),
         };
 
         let condition = format!(
@@ -553,6 +721,22 @@ impl CockroachAdapter {
         Self::build_order_clause_aliased(filters, "e", true)
     }
 
+    /// Edge order for plain edge listings: explicit sorts win, otherwise the
+    /// cursor column descending so `limit` + `cursor` pages are stable
+    fn build_edge_listing_order_clause(
+        filters: &[QueryFilter],
+        direction: TraversalDirection,
+    ) -> String {
+        let order_clause = Self::build_edge_order_clause(filters);
+        if !order_clause.is_empty() {
+            return order_clause;
+        }
+        match direction {
+            TraversalDirection::Forward => r#"ORDER BY e."to" DESC"#.to_string(),
+            TraversalDirection::Reverse => r#"ORDER BY e."from" DESC"#.to_string(),
+        }
+    }
+
     fn build_order_clause_aliased(filters: &[QueryFilter], alias: &str, is_edge: bool) -> String {
         let prefix = if alias.is_empty() {
             String::new()
@@ -584,6 +768,10 @@ impl CockroachAdapter {
                 if matches!(s.field.name, "created_at" | "updated_at") {
                     return format!("{}{} {}", prefix, s.field.name, dir);
                 }
+                // Sort values are placeholders; the sequence is always numeric
+                if s.field.name == "sequence" {
+                    return format!("({}index_meta->>'sequence')::bigint {}", prefix, dir);
+                }
                 let t = match &s.value {
                     IndexValue::String(_) => "text",
                     IndexValue::Int(_) => "bigint",
@@ -653,6 +841,21 @@ impl CockroachAdapter {
     ) -> PgQuery<'a, Postgres, PgArguments> {
         use crate::query::Comparison::*;
         for filter in filters.iter().filter(|f| f.mode.as_search().is_some()) {
+            if let Some((key, value)) = filter.as_label() {
+                query = match value {
+                    Some(value) => query.bind(serde_json::json!({ key: value })),
+                    None => query.bind(key.to_string()),
+                };
+                continue;
+            }
+            if let Some(namespace) = filter.as_namespace() {
+                query = query.bind(namespace.to_string());
+                continue;
+            }
+            // IS NULL / IS NOT NULL take no parameter
+            if filter.as_null_check().is_some() {
+                continue;
+            }
             let search = filter.mode.as_search().unwrap();
             match (&search.comparison, &filter.value) {
                 (
@@ -707,6 +910,7 @@ impl CockroachAdapter {
                     query = query.bind(uid);
                 }
                 (_, IndexValue::Array(_)) => {}
+                (_, IndexValue::Null) => {}
             }
         }
         query
@@ -718,6 +922,21 @@ impl CockroachAdapter {
     ) -> QueryScalar<'a, Postgres, O, PgArguments> {
         use crate::query::Comparison::*;
         for filter in filters.iter().filter(|f| f.mode.as_search().is_some()) {
+            if let Some((key, value)) = filter.as_label() {
+                query = match value {
+                    Some(value) => query.bind(serde_json::json!({ key: value })),
+                    None => query.bind(key.to_string()),
+                };
+                continue;
+            }
+            if let Some(namespace) = filter.as_namespace() {
+                query = query.bind(namespace.to_string());
+                continue;
+            }
+            // IS NULL / IS NOT NULL take no parameter
+            if filter.as_null_check().is_some() {
+                continue;
+            }
             let search = filter.mode.as_search().unwrap();
             match (&search.comparison, &filter.value) {
                 (
@@ -772,6 +991,7 @@ impl CockroachAdapter {
                     query = query.bind(uid);
                 }
                 (_, IndexValue::Array(_)) => {}
+                (_, IndexValue::Null) => {}
             }
         }
         query
@@ -789,7 +1009,7 @@ impl CockroachAdapter {
         direction: TraversalDirection,
     ) -> Result<Vec<ObjectRecord>, Error> {
         let where_clause = Self::build_object_traversal_query_conditions(
-            direction.clone(),
+            direction,
             filters,
             &plan.filters,
             plan.cursor,
@@ -967,8 +1187,82 @@ impl CockroachAdapter {
         plan: EdgeQuery,
         direction: TraversalDirection,
     ) -> Result<Vec<EdgeRecord>, Error> {
+        let sql = Self::edge_query_sql(&plan, direction);
+        let mut query = sqlx::query(&sql).bind(type_name).bind(owner);
+        if let Some(cursor) = plan.cursor {
+            query = query.bind(cursor.last_id);
+        }
+        query = Self::query_bind_filters(query, &plan.filters);
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| Self::map_row_to_edge_record(row).ok())
+            .collect())
+    }
+
+    fn object_query_sql(&self, plan: &Query) -> String {
+        let mut where_clause = Self::build_object_query_conditions(&plan.filters, plan.cursor);
+        let mut order_clause = if plan.sample_size.is_some() {
+            "ORDER BY RANDOM()".to_string()
+        } else {
+            Self::build_order_clause(&plan.filters)
+        };
+
+        if plan.is_global() {
+            where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+        }
+        if plan.ascending {
+            where_clause = where_clause.replace("o.id < ", "o.id > ");
+            if order_clause == "ORDER BY id DESC" {
+                order_clause = "ORDER BY id ASC".to_string();
+            }
+        }
+
+        // DISTINCT ON needs `id` as its leading ORDER BY column, so dedupe in a
+        // subquery and apply the plan's own order outside it
+        let mut sql = if plan.distinct {
+            format!(
+                r#"
+                SELECT id, type, owner, created_at, updated_at, data
+                FROM (
+                    SELECT DISTINCT ON (o.id) o.*
+                    FROM objects o {}
+                    {}
+                    ORDER BY o.id
+                ) o
+                {}
+                "#,
+                self.as_of_system_time(plan.stale),
+                where_clause,
+                order_clause
+            )
+        } else {
+            format!(
+                r#"
+                SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+                FROM objects o {}
+                {}
+                {}
+                "#,
+                self.as_of_system_time(plan.stale),
+                where_clause,
+                order_clause
+            )
+        };
+
+        if let Some(limit) = plan.effective_limit() {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        sql
+    }
+
+    fn edge_query_sql(plan: &EdgeQuery, direction: TraversalDirection) -> String {
         let where_clause = Self::build_edge_query_conditions(&plan.filters, plan.cursor, direction);
-        let order_clause = Self::build_edge_order_clause(&plan.filters);
+        let order_clause = Self::build_edge_listing_order_clause(&plan.filters, direction);
         let mut sql = format!(
             r#"
             SELECT e."from" AS "from", e."to" AS "to", e.type AS "type", e.data, e.index_meta
@@ -981,19 +1275,74 @@ impl CockroachAdapter {
         if let Some(limit) = plan.limit {
             sql.push_str(&format!(" LIMIT {}", limit));
         }
-        let mut query = sqlx::query(&sql).bind(type_name).bind(owner);
-        if let Some(cursor) = plan.cursor {
-            query = query.bind(cursor.last_id);
+        sql
+    }
+
+    // Rolled back when dropped
+    async fn begin_read_only(&self) -> Result<Transaction<'static, Postgres>, Error> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        sqlx::query("SET TRANSACTION READ ONLY")
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(tx)
+    }
+
+    // Plain `EXPLAIN` prints a tree of operators, one attribute per line:
+    //
+    //   • scan
+    //     estimated row count: 10 (0.10% of the table; stats collected 2 minutes ago)
+    //     table: objects@idx_objects_type_owner
+    //     spans: [/'Post'/... - /'Post'/...]
+    //
+    // A full table scan shows `spans: FULL SCAN`. Row counts are only printed
+    // once table statistics exist, and costs only with `EXPLAIN (OPT)`.
+    fn parse_plan_cost(lines: &[String]) -> QueryCost {
+        let mut cost = QueryCost::default();
+        for line in lines.iter().map(|l| l.trim()) {
+            if let Some(rows) = line.strip_prefix("estimated row count: ") {
+                // The first count belongs to the root operator
+                if cost.estimated_rows == 0 {
+                    let digits: String = rows
+                        .chars()
+                        .take_while(|c| c.is_ascii_digit() || *c == ',')
+                        .filter(|c| *c != ',')
+                        .collect();
+                    cost.estimated_rows = digits.parse().unwrap_or(0);
+                }
+            } else if let Some(spans) = line.strip_prefix("spans: ") {
+                if spans.starts_with("FULL SCAN") {
+                    cost.sequential_scan = true;
+                } else {
+                    cost.uses_index = true;
+                }
+            } else if let Some((_, index)) =
+                line.strip_prefix("table: ").and_then(|t| t.split_once('@'))
+            {
+                if index != "primary" && !index.ends_with("_pkey") {
+                    cost.uses_index = true;
+                }
+            }
         }
-        query = Self::query_bind_filters(query, &plan.filters);
+        cost
+    }
+
+    async fn explain_lines(
+        query: PgQuery<'_, Postgres, PgArguments>,
+        tx: &mut Transaction<'static, Postgres>,
+    ) -> Result<Vec<String>, Error> {
         let rows = query
-            .fetch_all(&self.pool)
+            .fetch_all(&mut **tx)
             .await
             .map_err(|e| Error::Storage(e.to_string()))?;
-        Ok(rows
-            .into_iter()
-            .filter_map(|row| Self::map_row_to_edge_record(row).ok())
-            .collect())
+        rows.iter()
+            .map(|row| row.try_get::<String, _>(0))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Error::Deserialize(e.to_string()))
     }
 }
 
@@ -1026,7 +1375,7 @@ impl Adapter for CockroachAdapter {
         .await
         .map_err(|err| {
             if err.to_string().contains("unique") {
-                Error::UniqueConstraintViolation("id".to_string())
+                Error::unique_violation("id")
             } else {
                 Error::Storage(err.to_string())
             }
@@ -1034,23 +1383,56 @@ impl Adapter for CockroachAdapter {
         Ok(())
     }
 
+    async fn insert_object_or_ignore(&self, record: ObjectRecord) -> Result<bool, Error> {
+        let ObjectRecord {
+            id,
+            type_name,
+            owner,
+            created_at,
+            updated_at,
+            data,
+            index_meta,
+        } = record;
+        let inserted: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            INSERT INTO public.objects (id, type, owner, created_at, updated_at, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (id) DO NOTHING
+            RETURNING id
+            "#,
+        )
+        .bind(id)
+        .bind(type_name.as_ref())
+        .bind(owner)
+        .bind(created_at)
+        .bind(updated_at)
+        .bind(data)
+        .bind(index_meta)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(inserted.is_some())
+    }
+
     async fn fetch_object(
         &self,
         type_name: &'static str,
         id: Uuid,
     ) -> Result<Option<ObjectRecord>, Error> {
-        let row = sqlx::query(
+        let sql = format!(
             r#"
             SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-            FROM objects o
+            FROM objects o {}
             WHERE id = $1 AND type = $2
             "#,
-        )
-        .bind(id)
-        .bind(type_name)
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|err| Error::Storage(err.to_string()))?;
+            self.as_of_system_time(None)
+        );
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .bind(type_name)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
 
         match row {
             Some(r) => Self::map_row_to_object_record_slim(r).map(|o| Some(o)),
@@ -1058,23 +1440,59 @@ impl Adapter for CockroachAdapter {
         }
     }
 
+    async fn fetch_object_any_type(&self, id: Uuid) -> Result<Option<ObjectRecord>, Error> {
+        let sql = format!(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o {}
+            WHERE id = $1
+            "#,
+            self.as_of_system_time(None)
+        );
+        let row = sqlx::query(&sql)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        match row {
+            Some(r) => Self::map_row_to_object_record_slim(r).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn fetch_object_owner(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Option<Uuid>, Error> {
+        sqlx::query_scalar::<_, Uuid>("SELECT owner FROM objects WHERE id = $1 AND type = $2")
+            .bind(id)
+            .bind(type_name)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+
     async fn fetch_bulk_objects(
         &self,
         type_name: &'static str,
         ids: Vec<Uuid>,
     ) -> Result<Vec<ObjectRecord>, Error> {
-        let rows = sqlx::query(
+        let sql = format!(
             r#"
             SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-            FROM objects o
+            FROM objects o {}
             WHERE id = ANY($1) AND type = $2
             "#,
-        )
-        .bind(ids.into_iter().map(|id| id).collect::<Vec<Uuid>>())
-        .bind(type_name)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|err| Error::Storage(err.to_string()))?;
+            self.as_of_system_time(None)
+        );
+        let rows = sqlx::query(&sql)
+            .bind(ids.into_iter().map(|id| id).collect::<Vec<Uuid>>())
+            .bind(type_name)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
 
         rows.into_iter()
             .map(Self::map_row_to_object_record_slim)
@@ -1100,6 +1518,47 @@ impl Adapter for CockroachAdapter {
         Ok(())
     }
 
+    async fn update_object_partial(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+        data_patch: serde_json::Value,
+        index_meta_patch: serde_json::Value,
+        index_meta_removed: Vec<String>,
+        updated_at: DateTime<Utc>,
+    ) -> Result<(), Error> {
+        // CockroachDB has no `jsonb - text[]`, drop removed keys one by one
+        let removals: String = (0..index_meta_removed.len())
+            .map(|i| format!(" - ${}", i + 6))
+            .collect();
+        let sql = format!(
+            r#"
+            UPDATE objects
+            SET updated_at = $3,
+                data = data || $4,
+                index_meta = (index_meta || $5){}
+            WHERE id = $1 AND type = $2
+            "#,
+            removals
+        );
+
+        let mut query = sqlx::query(&sql)
+            .bind(id)
+            .bind(type_name)
+            .bind(updated_at)
+            .bind(data_patch)
+            .bind(index_meta_patch);
+        for key in index_meta_removed {
+            query = query.bind(key);
+        }
+        query
+            .execute(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
     async fn transfer_object(
         &self,
         type_name: &'static str,
@@ -1130,10 +1589,126 @@ impl Adapter for CockroachAdapter {
         Self::map_row_to_object_record_slim(row)
     }
 
-    async fn delete_object(
+    async fn swap_owners(
         &self,
         type_name: &'static str,
-        id: Uuid,
+        id_a: Uuid,
+        owner_a: Uuid,
+        id_b: Uuid,
+        owner_b: Uuid,
+    ) -> Result<(ObjectRecord, ObjectRecord), Error> {
+        // Dropping the transaction on an early return rolls back the first move
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let now = Utc::now();
+        let mut records = Vec::with_capacity(2);
+        for (id, from_owner, to_owner) in [(id_a, owner_a, owner_b), (id_b, owner_b, owner_a)] {
+            let row = sqlx::query(
+                r#"
+                UPDATE objects
+                SET updated_at = $3, owner = $4
+                WHERE id = $1 AND owner = $2 AND type = $5
+                RETURNING id, type, owner, created_at, updated_at, data
+                "#,
+            )
+            .bind(id)
+            .bind(from_owner)
+            .bind(now)
+            .bind(to_owner)
+            .bind(type_name)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|err| match err {
+                sqlx::Error::RowNotFound => Error::NotFound,
+                _ => Error::Storage(err.to_string()),
+            })?;
+            records.push(Self::map_row_to_object_record_slim(row)?);
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let b = records.pop().ok_or(Error::NotFound)?;
+        let a = records.pop().ok_or(Error::NotFound)?;
+        Ok((a, b))
+    }
+
+    async fn retype_object(
+        &self,
+        old_type: &'static str,
+        record: ObjectRecord,
+        hashes: Vec<(String, &str)>,
+    ) -> Result<(), Error> {
+        // Dropping the transaction on an early return keeps the old row and hashes
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE objects
+            SET type = $3, updated_at = $4, data = $5, index_meta = $6
+            WHERE id = $1 AND type = $2
+            "#,
+        )
+        .bind(record.id)
+        .bind(old_type)
+        .bind(record.type_name.as_ref())
+        .bind(record.updated_at)
+        .bind(&record.data)
+        .bind(&record.index_meta)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        if result.rows_affected() == 0 {
+            return Err(Error::NotFound);
+        }
+
+        sqlx::query("DELETE FROM unique_constraints WHERE id = $1")
+            .bind(record.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        for (hash, field) in hashes {
+            sqlx::query(
+                r#"
+                INSERT INTO unique_constraints (id, type, key, field)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(record.id)
+            .bind(record.type_name.as_ref())
+            .bind(hash)
+            .bind(field)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| {
+                let msg = err.to_string();
+                if msg.contains("unique constraint") || msg.contains("duplicate") {
+                    Error::unique_violation(field)
+                } else {
+                    Error::Storage(msg)
+                }
+            })?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(())
+    }
+
+    async fn delete_object(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
         owner: Uuid,
     ) -> Result<Option<ObjectRecord>, Error> {
         let row = sqlx::query(
@@ -1159,6 +1734,17 @@ impl Adapter for CockroachAdapter {
         }
     }
 
+    async fn touch_objects(&self, type_name: &'static str, ids: Vec<Uuid>) -> Result<u64, Error> {
+        let result =
+            sqlx::query("UPDATE objects SET updated_at = now() WHERE id = ANY($1) AND type = $2")
+                .bind(ids)
+                .bind(type_name)
+                .execute(&self.pool)
+                .await
+                .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
     async fn delete_bulk_objects(
         &self,
         type_name: &'static str,
@@ -1191,6 +1777,31 @@ impl Adapter for CockroachAdapter {
         Ok(result.rows_affected())
     }
 
+    async fn delete_owned_objects_with_hashes(
+        &self,
+        type_name: &'static str,
+        owner: Uuid,
+    ) -> Result<u64, Error> {
+        let deleted = sqlx::query_scalar::<_, i64>(
+            r#"
+            WITH deleted AS (
+                DELETE FROM objects WHERE type = $1 AND owner = $2 RETURNING id
+            ), released AS (
+                DELETE FROM unique_constraints
+                WHERE type = $1 AND id IN (SELECT id FROM deleted)
+            )
+            SELECT COUNT(*) FROM deleted
+            "#,
+        )
+        .bind(type_name)
+        .bind(owner)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(deleted as u64)
+    }
+
     async fn find_object(
         &self,
         type_name: &'static str,
@@ -1228,28 +1839,8 @@ impl Adapter for CockroachAdapter {
         type_name: &'static str,
         plan: Query,
     ) -> Result<Vec<ObjectRecord>, Error> {
-        let mut where_clause = Self::build_object_query_conditions(&plan.filters, plan.cursor);
-        let order_clause = Self::build_order_clause(&plan.filters);
-
-        if plan.owner.is_nil() {
-            where_clause = where_clause.replace("o.owner = ", "o.owner > ");
-        }
-
-        let mut sql = format!(
-            r#"
-                SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-                FROM objects o
-                {}
-                {}
-                "#,
-            where_clause, order_clause
-        );
-
-        if let Some(limit) = plan.limit {
-            sql.push_str(&format!(" LIMIT {}", limit));
-        }
-
-        let mut query = sqlx::query(&sql).bind(type_name).bind(plan.owner);
+        let sql = self.object_query_sql(&plan);
+        let mut query = sqlx::query(&sql).bind(type_name).bind(plan.owner_param());
 
         if let Some(cursor) = plan.cursor {
             query = query.bind(cursor.last_id);
@@ -1268,6 +1859,42 @@ impl Adapter for CockroachAdapter {
             .collect())
     }
 
+    async fn estimate_query_cost(
+        &self,
+        type_name: &'static str,
+        plan: Query,
+    ) -> Result<QueryCost, Error> {
+        let sql = format!("EXPLAIN {}", self.object_query_sql(&plan));
+        let mut query = sqlx::query(&sql).bind(type_name).bind(plan.owner_param());
+        if let Some(cursor) = plan.cursor {
+            query = query.bind(cursor.last_id);
+        }
+        query = Self::query_bind_filters(query, &plan.filters);
+        let mut tx = self.begin_read_only().await?;
+        let lines = Self::explain_lines(query, &mut tx).await?;
+        Ok(Self::parse_plan_cost(&lines))
+    }
+
+    async fn estimate_edge_query_cost(
+        &self,
+        type_name: &'static str,
+        owner: Uuid,
+        plan: EdgeQuery,
+    ) -> Result<QueryCost, Error> {
+        let sql = format!(
+            "EXPLAIN {}",
+            Self::edge_query_sql(&plan, TraversalDirection::Forward)
+        );
+        let mut query = sqlx::query(&sql).bind(type_name).bind(owner);
+        if let Some(cursor) = plan.cursor {
+            query = query.bind(cursor.last_id);
+        }
+        query = Self::query_bind_filters(query, &plan.filters);
+        let mut tx = self.begin_read_only().await?;
+        let lines = Self::explain_lines(query, &mut tx).await?;
+        Ok(Self::parse_plan_cost(&lines))
+    }
+
     async fn count_objects(
         &self,
         type_name: &'static str,
@@ -1275,13 +1902,18 @@ impl Adapter for CockroachAdapter {
     ) -> Result<u64, Error> {
         match plan {
             Some(plan) => {
-                let where_clause = Self::build_object_query_conditions(&plan.filters, None);
+                let mut where_clause = Self::build_object_query_conditions(&plan.filters, None);
+
+                if plan.is_global() {
+                    where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+                }
 
                 let mut sql = format!(
                     r#"
-                    SELECT COUNT(*) FROM objects o
+                    SELECT COUNT(*) FROM objects o {}
                     {}
                     "#,
+                    self.as_of_system_time(plan.stale),
                     where_clause
                 );
 
@@ -1291,7 +1923,7 @@ impl Adapter for CockroachAdapter {
 
                 let mut query = sqlx::query_scalar::<_, i64>(&sql)
                     .bind(type_name)
-                    .bind(plan.owner);
+                    .bind(plan.owner_param());
 
                 query = Self::query_scalar_bind_filters(query, &plan.filters);
 
@@ -1303,7 +1935,11 @@ impl Adapter for CockroachAdapter {
                 Ok(count as u64)
             }
             None => {
-                let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM objects WHERE type = $1")
+                let sql = format!(
+                    "SELECT COUNT(*) FROM objects {} WHERE type = $1",
+                    self.as_of_system_time(None)
+                );
+                let count: i64 = sqlx::query_scalar(&sql)
                     .bind(type_name)
                     .fetch_one(&self.pool)
                     .await
@@ -1314,169 +1950,360 @@ impl Adapter for CockroachAdapter {
         }
     }
 
-    async fn fetch_owned_objects_batch(
+    async fn time_bucket_count(
         &self,
         type_name: &'static str,
-        owner_ids: &[Uuid],
-    ) -> Result<Vec<ObjectRecord>, Error> {
-        let rows = sqlx::query(
+        owner: Option<Uuid>,
+        granularity: Granularity,
+    ) -> Result<Vec<(DateTime<Utc>, u64)>, Error> {
+        // Sessions default to UTC, so buckets line up with the Postgres adapter
+        let rows: Vec<(DateTime<Utc>, i64)> = sqlx::query_as(
             r#"
-            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-            FROM objects o
-            WHERE type = $1 AND owner = ANY($2)
+            SELECT date_trunc($2, created_at) AS bucket, COUNT(*)
+            FROM objects
+            WHERE type = $1 AND ($3::UUID IS NULL OR owner = $3)
+            GROUP BY bucket
+            ORDER BY bucket
             "#,
         )
         .bind(type_name)
-        .bind(owner_ids)
+        .bind(granularity.as_str())
+        .bind(owner)
         .fetch_all(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
-        rows.into_iter()
-            .map(Self::map_row_to_object_record_slim)
-            .collect()
+        Ok(rows
+            .into_iter()
+            .map(|(bucket, count)| (bucket, count as u64))
+            .collect())
     }
 
-    async fn fetch_owned_objects(
+    async fn group_by_field(
         &self,
         type_name: &'static str,
-        owner: Uuid,
-    ) -> Result<Vec<ObjectRecord>, Error> {
-        let rows = sqlx::query(
+        field: &'static str,
+        plan: Query,
+    ) -> Result<Vec<GroupCount>, Error> {
+        let mut where_clause = Self::build_object_query_conditions(&plan.filters, None);
+        if plan.is_global() {
+            where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+        }
+        let sql = format!(
             r#"
-            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-            FROM objects o
-            WHERE owner = $1 AND type = $2
+            SELECT o.index_meta->'{}' AS val, COUNT(*) AS n FROM objects o {}
+            {}
+            GROUP BY val
+            ORDER BY n DESC, val
             "#,
-        )
-        .bind(owner)
-        .bind(type_name)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|err| Error::Storage(err.to_string()))?;
+            field,
+            self.as_of_system_time(plan.stale),
+            where_clause
+        );
+        let query = sqlx::query(&sql).bind(type_name).bind(plan.owner_param());
+        let rows = Self::query_bind_filters(query, &plan.filters)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
 
         rows.into_iter()
-            .map(Self::map_row_to_object_record_slim)
+            .map(|row| {
+                let val: Option<serde_json::Value> = row
+                    .try_get("val")
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let count: i64 = row
+                    .try_get("n")
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let value = serde_json::from_value(val.unwrap_or_default())
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                Ok(GroupCount {
+                    value,
+                    count: count as u64,
+                })
+            })
             .collect()
     }
 
-    async fn fetch_owned_object(
+    async fn group_count_by_owner(
         &self,
         type_name: &'static str,
-        owner: Uuid,
-    ) -> Result<Option<ObjectRecord>, Error> {
-        let row = sqlx::query(
+        plan: Query,
+    ) -> Result<Vec<(Uuid, u64)>, Error> {
+        let mut where_clause = Self::build_object_query_conditions(&plan.filters, None);
+        if plan.is_global() {
+            where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+        }
+        let sql = format!(
+            "SELECT o.owner, COUNT(*) FROM objects o {} {} GROUP BY o.owner",
+            self.as_of_system_time(plan.stale),
+            where_clause
+        );
+        let query = sqlx::query(&sql).bind(type_name).bind(plan.owner_param());
+        let rows = Self::query_bind_filters(query, &plan.filters)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let owner: Uuid = row
+                    .try_get(0)
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let count: i64 = row
+                    .try_get(1)
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                Ok((owner, count as u64))
+            })
+            .collect()
+    }
+
+    async fn top_owners_by_type(
+        &self,
+        type_name: &'static str,
+        limit: u32,
+    ) -> Result<Vec<(Uuid, u64)>, Error> {
+        let rows = sqlx::query(
             r#"
-            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-            FROM objects o
-            WHERE owner = $1 AND type = $2
+            SELECT owner, COUNT(*)
+            FROM objects
+            WHERE type = $1
+            GROUP BY owner
+            ORDER BY COUNT(*) DESC, owner
+            LIMIT $2
             "#,
         )
-        .bind(owner)
         .bind(type_name)
-        .fetch_optional(&self.pool)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
-        match row {
-            Some(r) => Self::map_row_to_object_record_slim(r).map(|o| Some(o)),
-            None => Ok(None),
-        }
+        rows.into_iter()
+            .map(|row| {
+                let owner: Uuid = row
+                    .try_get(0)
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let count: i64 = row
+                    .try_get(1)
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                Ok((owner, count as u64))
+            })
+            .collect()
     }
 
-    async fn fetch_union_object(
+    async fn fetch_object_record(
         &self,
-        a_type_name: &'static str,
-        b_type_name: &'static str,
+        type_name: &'static str,
         id: Uuid,
     ) -> Result<Option<ObjectRecord>, Error> {
         let row = sqlx::query(
             r#"
-            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-            FROM objects o
-            WHERE id = $1 AND (type = $2 OR type = $3)
+            SELECT id, type, owner, created_at, updated_at, data, index_meta
+            FROM objects
+            WHERE id = $1 AND type = $2
             "#,
         )
         .bind(id)
-        .bind(a_type_name)
-        .bind(b_type_name)
+        .bind(type_name)
         .fetch_optional(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
 
-        match row {
-            Some(r) => Self::map_row_to_object_record_slim(r).map(|o| Some(o)),
-            None => Ok(None),
-        }
+        let index_meta: serde_json::Value = row
+            .try_get("index_meta")
+            .map_err(|err| Error::Deserialize(err.to_string()))?;
+        let mut record = Self::map_row_to_object_record_slim(row)?;
+        record.index_meta = index_meta;
+        Ok(Some(record))
     }
 
-    async fn fetch_union_objects(
+    async fn sample_object_records(
         &self,
-        a_type_name: &'static str,
-        b_type_name: &'static str,
-        ids: Vec<Uuid>,
+        type_name: &'static str,
+        limit: u32,
     ) -> Result<Vec<ObjectRecord>, Error> {
         let rows = sqlx::query(
             r#"
-            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-            FROM objects o
-            WHERE id = ANY($1) AND (type = $2 OR type = $3)
+            SELECT id, type, owner, created_at, updated_at, data, index_meta
+            FROM objects
+            WHERE type = $1
+            ORDER BY RANDOM()
+            LIMIT $2
             "#,
         )
-        .bind(ids.into_iter().map(|id| id).collect::<Vec<Uuid>>())
-        .bind(a_type_name)
-        .bind(b_type_name)
+        .bind(type_name)
+        .bind(limit as i64)
         .fetch_all(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
         rows.into_iter()
-            .map(Self::map_row_to_object_record_slim)
+            .map(|row| {
+                let index_meta: serde_json::Value = row
+                    .try_get("index_meta")
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let mut record = Self::map_row_to_object_record_slim(row)?;
+                record.index_meta = index_meta;
+                Ok(record)
+            })
             .collect()
     }
 
-    async fn fetch_owned_union_object(
+    async fn insert_history_snapshot(
         &self,
-        a_type_name: &'static str,
-        b_type_name: &'static str,
-        owner: Uuid,
-    ) -> Result<Option<ObjectRecord>, Error> {
-        let row = sqlx::query(
+        record: ObjectRecord,
+        version: u64,
+    ) -> Result<(), Error> {
+        sqlx::query(
             r#"
-            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-            FROM objects o
-            WHERE owner = $1 AND (type = $2 OR type = $3)
+            INSERT INTO object_history (id, version, type, owner, created_at, updated_at, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             "#,
         )
-        .bind(owner)
-        .bind(a_type_name)
-        .bind(b_type_name)
-        .fetch_optional(&self.pool)
-        .await
+        .bind(record.id)
+        .bind(version as i64)
+        .bind(record.type_name.as_ref())
+        .bind(record.owner)
+        .bind(record.created_at)
+        .bind(record.updated_at)
+        .bind(record.data)
+        .bind(record.index_meta)
+        .execute(&self.pool)
+        .await
         .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(())
+    }
 
-        match row {
-            Some(r) => Self::map_row_to_object_record_slim(r).map(|o| Some(o)),
-            None => Ok(None),
+    async fn update_object_with_history(
+        &self,
+        type_name: &'static str,
+        record: ObjectRecord,
+        previous: ObjectRecord,
+    ) -> Result<u64, Error> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let updated = sqlx::query(
+            r#"
+            UPDATE objects
+            SET updated_at = $3, data = $4, index_meta = $5
+            WHERE id = $1 AND type = $2
+            "#,
+        )
+        .bind(record.id)
+        .bind(type_name)
+        .bind(record.updated_at)
+        .bind(record.data)
+        .bind(record.index_meta)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        if updated.rows_affected() == 0 {
+            return Ok(0);
         }
+
+        // Concurrent updates of the object wait on the row lock taken above,
+        // so this reads the versions they committed
+        let version: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(MAX(version), 0) + 1 FROM object_history WHERE id = $1",
+        )
+        .bind(previous.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO object_history (id, version, type, owner, created_at, updated_at, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(previous.id)
+        .bind(version)
+        .bind(previous.type_name.as_ref())
+        .bind(previous.owner)
+        .bind(previous.created_at)
+        .bind(previous.updated_at)
+        .bind(previous.data)
+        .bind(previous.index_meta)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(version as u64)
     }
 
-    async fn fetch_owned_union_objects(
+    async fn latest_history_version(
         &self,
-        a_type_name: &'static str,
-        b_type_name: &'static str,
-        owner: Uuid,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<u64, Error> {
+        let version: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(MAX(version), 0) FROM object_history
+            WHERE id = $1 AND type = $2
+            "#,
+        )
+        .bind(id)
+        .bind(type_name)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(version as u64)
+    }
+
+    async fn fetch_history(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Vec<(u64, ObjectRecord)>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, version, type, owner, created_at, updated_at, data
+            FROM object_history
+            WHERE id = $1 AND type = $2
+            ORDER BY version
+            "#,
+        )
+        .bind(id)
+        .bind(type_name)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let version: i64 = row
+                    .try_get("version")
+                    .map_err(|e| Error::Deserialize(e.to_string()))?;
+                Ok((version as u64, Self::map_row_to_object_record_slim(row)?))
+            })
+            .collect()
+    }
+
+    async fn fetch_owned_objects_batch(
+        &self,
+        type_name: &'static str,
+        owner_ids: &[Uuid],
     ) -> Result<Vec<ObjectRecord>, Error> {
         let rows = sqlx::query(
             r#"
             SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
             FROM objects o
-            WHERE owner = $1 AND (type = $2 OR type = $3)
+            WHERE type = $1 AND owner = ANY($2)
             "#,
         )
-        .bind(owner)
-        .bind(a_type_name)
-        .bind(b_type_name)
+        .bind(type_name)
+        .bind(owner_ids)
         .fetch_all(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
@@ -1486,317 +2313,1392 @@ impl Adapter for CockroachAdapter {
             .collect()
     }
 
-    /* ---------------- EDGES ---------------- */
-    async fn insert_edge(&self, record: EdgeRecord) -> Result<(), Error> {
-        let EdgeRecord {
-            from,
-            to,
-            type_name,
-            data,
-            index_meta,
-        } = record;
-        let _ = sqlx::query(
+    async fn list_namespaces(&self, type_name: &'static str) -> Result<Vec<String>, Error> {
+        sqlx::query_scalar(
             r#"
-            INSERT INTO edges ("from", "to", type, data, index_meta)
-            VALUES ($1, $2, $3, $4, $5)
-            ON CONFLICT ("from", type, "to")
-            DO UPDATE SET data = $4, index_meta = $5;
+            SELECT DISTINCT data->>'__namespace'
+            FROM objects
+            WHERE type = $1 AND data ? '__namespace'
+            ORDER BY 1
             "#,
         )
-        .bind(from)
-        .bind(to)
-        .bind(type_name.as_ref())
-        .bind(data)
-        .bind(index_meta)
-        .execute(&self.pool)
+        .bind(type_name)
+        .fetch_all(&self.pool)
         .await
-        .map_err(|err| Error::Storage(err.to_string()))?;
-
-        Ok(())
+        .map_err(|err| Error::Storage(err.to_string()))
     }
 
-    async fn update_edge(
+    async fn fetch_owned_objects(
         &self,
-        record: EdgeRecord,
-        old_to: Uuid,
-        to: Option<Uuid>,
-    ) -> Result<(), Error> {
-        let EdgeRecord {
-            from,
-            type_name,
-            data,
-            ..
-        } = record;
-        let _ = sqlx::query(
+        type_name: &'static str,
+        owner: Uuid,
+    ) -> Result<Vec<ObjectRecord>, Error> {
+        let rows = sqlx::query(
             r#"
-        UPDATE edges SET data = $1, "to" = $2
-        WHERE "from" = $3 AND type = $4 AND "to" = $5
-        "#,
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE owner = $1 AND type = $2
+            "#,
         )
-        .bind(data)
-        .bind(to.unwrap_or(old_to))
-        .bind(from)
-        .bind(type_name.as_ref())
-        .bind(old_to)
-        .execute(&self.pool)
+        .bind(owner)
+        .bind(type_name)
+        .fetch_all(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
-        Ok(())
+        rows.into_iter()
+            .map(Self::map_row_to_object_record_slim)
+            .collect()
     }
 
-    async fn delete_edge(
+    async fn fetch_owned_object(
         &self,
         type_name: &'static str,
-        from: Uuid,
-        to: Uuid,
-    ) -> Result<(), Error> {
-        let _ = sqlx::query(
+        owner: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error> {
+        let row = sqlx::query(
             r#"
-            DELETE FROM edges
-            WHERE type = $1 AND "from" = $2 AND "to" = $3
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE owner = $1 AND type = $2
             "#,
         )
+        .bind(owner)
         .bind(type_name)
-        .bind(from)
-        .bind(to)
-        .execute(&self.pool)
+        .fetch_optional(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
-        Ok(())
+        match row {
+            Some(r) => Self::map_row_to_object_record_slim(r).map(|o| Some(o)),
+            None => Ok(None),
+        }
     }
 
-    async fn delete_object_edge(&self, type_name: &'static str, from: Uuid) -> Result<(), Error> {
-        let _ = sqlx::query(
+    async fn fetch_union_object(
+        &self,
+        a_type_name: &'static str,
+        b_type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error> {
+        let row = sqlx::query(
             r#"
-            DELETE FROM edges
-            WHERE type = $1 AND "from" = $2
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE id = $1 AND (type = $2 OR type = $3)
             "#,
         )
-        .bind(type_name)
-        .bind(from)
-        .execute(&self.pool)
+        .bind(id)
+        .bind(a_type_name)
+        .bind(b_type_name)
+        .fetch_optional(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
-        Ok(())
+        match row {
+            Some(r) => Self::map_row_to_object_record_slim(r).map(|o| Some(o)),
+            None => Ok(None),
+        }
     }
 
-    async fn fetch_edge(
+    async fn fetch_union_objects(
         &self,
-        type_name: &'static str,
-        from: Uuid,
-        to: Uuid,
-    ) -> Result<Option<EdgeRecord>, Error> {
-        let row = sqlx::query(
+        a_type_name: &'static str,
+        b_type_name: &'static str,
+        ids: Vec<Uuid>,
+    ) -> Result<Vec<ObjectRecord>, Error> {
+        let rows = sqlx::query(
             r#"
-        SELECT e."from", e."to", e.type, e.data
-        FROM edges e
-        WHERE type = $1 AND "from" = $2 AND "to" = $3
-        "#,
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE id = ANY($1) AND (type = $2 OR type = $3)
+            "#,
         )
-        .bind(type_name)
-        .bind(from)
-        .bind(to)
-        .fetch_optional(&self.pool)
+        .bind(ids.into_iter().map(|id| id).collect::<Vec<Uuid>>())
+        .bind(a_type_name)
+        .bind(b_type_name)
+        .fetch_all(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
-        let Some(row) = row else {
-            return Ok(None);
-        };
-
-        Self::map_row_to_edge_record(row).map(|e| Some(e))
+        rows.into_iter()
+            .map(Self::map_row_to_object_record_slim)
+            .collect()
     }
 
-    async fn query_edges(
+    async fn fetch_owned_union_object(
         &self,
-        type_name: &'static str,
+        a_type_name: &'static str,
+        b_type_name: &'static str,
         owner: Uuid,
-        plan: EdgeQuery,
-    ) -> Result<Vec<EdgeRecord>, Error> {
-        self.query_edges_internal(type_name, owner, plan, TraversalDirection::Forward)
-            .await
+    ) -> Result<Option<ObjectRecord>, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE owner = $1 AND (type = $2 OR type = $3)
+            "#,
+        )
+        .bind(owner)
+        .bind(a_type_name)
+        .bind(b_type_name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        match row {
+            Some(r) => Self::map_row_to_object_record_slim(r).map(|o| Some(o)),
+            None => Ok(None),
+        }
+    }
+
+    async fn fetch_owned_union_objects(
+        &self,
+        a_type_name: &'static str,
+        b_type_name: &'static str,
+        owner: Uuid,
+    ) -> Result<Vec<ObjectRecord>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE owner = $1 AND (type = $2 OR type = $3)
+            "#,
+        )
+        .bind(owner)
+        .bind(a_type_name)
+        .bind(b_type_name)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(Self::map_row_to_object_record_slim)
+            .collect()
+    }
+
+    async fn query_union_objects(
+        &self,
+        a_type_name: &'static str,
+        b_type_name: &'static str,
+        plan: Query,
+    ) -> Result<Vec<ObjectRecord>, Error> {
+        let mut where_clause = Self::build_object_query_conditions(&plan.filters, plan.cursor)
+            .replacen("o.type = $1", "o.type = ANY($1)", 1);
+        let order_clause = if plan.sample_size.is_some() {
+            "ORDER BY RANDOM()".to_string()
+        } else {
+            Self::build_order_clause(&plan.filters)
+        };
+
+        if plan.is_global() {
+            where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+        }
+
+        // DISTINCT ON needs `id` as its leading ORDER BY column, so dedupe in a
+        // subquery and apply the plan's own order outside it
+        let mut sql = if plan.distinct {
+            format!(
+                r#"
+                SELECT id, type, owner, created_at, updated_at, data
+                FROM (
+                    SELECT DISTINCT ON (o.id) o.*
+                    FROM objects o {}
+                    {}
+                    ORDER BY o.id
+                ) o
+                {}
+                "#,
+                self.as_of_system_time(plan.stale),
+                where_clause,
+                order_clause
+            )
+        } else {
+            format!(
+                r#"
+                SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+                FROM objects o {}
+                {}
+                {}
+                "#,
+                self.as_of_system_time(plan.stale),
+                where_clause,
+                order_clause
+            )
+        };
+
+        if let Some(limit) = plan.effective_limit() {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut query = sqlx::query(&sql)
+            .bind(vec![a_type_name, b_type_name])
+            .bind(plan.owner_param());
+
+        if let Some(cursor) = plan.cursor {
+            query = query.bind(cursor.last_id);
+        }
+
+        query = Self::query_bind_filters(query, &plan.filters);
+
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(Self::map_row_to_object_record_slim)
+            .collect()
+    }
+
+    /* ---------------- EDGES ---------------- */
+    async fn insert_edge(&self, record: EdgeRecord) -> Result<(), Error> {
+        let EdgeRecord {
+            from,
+            to,
+            type_name,
+            data,
+            index_meta,
+        } = record;
+        let _ = sqlx::query(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT ("from", type, "to")
+            DO UPDATE SET data = $4, index_meta = $5;
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(type_name.as_ref())
+        .bind(data)
+        .bind(index_meta)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn insert_edge_or_ignore(&self, record: EdgeRecord) -> Result<bool, Error> {
+        let EdgeRecord {
+            from,
+            to,
+            type_name,
+            data,
+            index_meta,
+        } = record;
+        let inserted: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT ("from", type, "to") DO NOTHING
+            RETURNING "from"
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(type_name.as_ref())
+        .bind(data)
+        .bind(index_meta)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(inserted.is_some())
+    }
+
+    async fn upsert_edge(&self, record: EdgeRecord) -> Result<UpsertOutcome, Error> {
+        let EdgeRecord {
+            from,
+            to,
+            type_name,
+            data,
+            index_meta,
+        } = record;
+        // No xmax in CockroachDB: try the insert, fall back to an update
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT ("from", "to", type) DO NOTHING
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(type_name.as_ref())
+        .bind(&data)
+        .bind(&index_meta)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?
+        .rows_affected();
+
+        if inserted == 0 {
+            sqlx::query(
+                r#"
+                UPDATE edges SET data = $4, index_meta = $5
+                WHERE "from" = $1 AND "to" = $2 AND type = $3
+                "#,
+            )
+            .bind(from)
+            .bind(to)
+            .bind(type_name.as_ref())
+            .bind(&data)
+            .bind(&index_meta)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(UpsertOutcome {
+            created: inserted > 0,
+        })
+    }
+
+    async fn upsert_edges_batch(&self, records: Vec<EdgeRecord>) -> Result<u64, Error> {
+        if records.is_empty() {
+            return Ok(0);
+        }
+
+        let mut froms = Vec::with_capacity(records.len());
+        let mut tos = Vec::with_capacity(records.len());
+        let mut types = Vec::with_capacity(records.len());
+        let mut datas = Vec::with_capacity(records.len());
+        let mut index_metas = Vec::with_capacity(records.len());
+        for record in records {
+            froms.push(record.from);
+            tos.push(record.to);
+            types.push(record.type_name.into_owned());
+            datas.push(record.data);
+            index_metas.push(record.index_meta);
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            SELECT * FROM unnest($1::uuid[], $2::uuid[], $3::text[], $4::jsonb[], $5::jsonb[])
+            ON CONFLICT ("from", "to", type)
+            DO UPDATE SET data = EXCLUDED.data, index_meta = EXCLUDED.index_meta
+            "#,
+        )
+        .bind(froms)
+        .bind(tos)
+        .bind(types)
+        .bind(datas)
+        .bind(index_metas)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn insert_edges_batch(
+        &self,
+        records: Vec<EdgeRecord>,
+        on_conflict: OnConflict,
+    ) -> Result<BatchEdgeResult, Error> {
+        let total = records.len() as u64;
+        if total == 0 {
+            return Ok(BatchEdgeResult::default());
+        }
+
+        let mut froms = Vec::with_capacity(records.len());
+        let mut tos = Vec::with_capacity(records.len());
+        let mut types = Vec::with_capacity(records.len());
+        let mut datas = Vec::with_capacity(records.len());
+        let mut index_metas = Vec::with_capacity(records.len());
+        for record in records {
+            froms.push(record.from);
+            tos.push(record.to);
+            types.push(record.type_name.into_owned());
+            datas.push(record.data);
+            index_metas.push(record.index_meta);
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        // No xmax in CockroachDB: count the edges already present up front
+        let existing: i64 = if on_conflict == OnConflict::Replace {
+            sqlx::query_scalar(
+                r#"
+                SELECT COUNT(*) FROM edges e
+                JOIN unnest($1::uuid[], $2::uuid[], $3::text[]) AS k(f, t, ty)
+                  ON e."from" = k.f AND e."to" = k.t AND e.type = k.ty
+                "#,
+            )
+            .bind(&froms)
+            .bind(&tos)
+            .bind(&types)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?
+        } else {
+            0
+        };
+
+        let conflict_clause = match on_conflict {
+            OnConflict::Ignore => r#"ON CONFLICT ("from", "to", type) DO NOTHING"#,
+            OnConflict::Replace => {
+                r#"ON CONFLICT ("from", "to", type)
+            DO UPDATE SET data = EXCLUDED.data, index_meta = EXCLUDED.index_meta"#
+            }
+            OnConflict::Error => "",
+        };
+        let sql = format!(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            SELECT * FROM unnest($1::uuid[], $2::uuid[], $3::text[], $4::jsonb[], $5::jsonb[])
+            {conflict_clause}
+            "#
+        );
+        let written = sqlx::query(&sql)
+            .bind(froms)
+            .bind(tos)
+            .bind(types)
+            .bind(datas)
+            .bind(index_metas)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| {
+                if err
+                    .as_database_error()
+                    .is_some_and(|e| e.is_unique_violation())
+                {
+                    Error::unique_violation("from+to")
+                } else {
+                    Error::Storage(err.to_string())
+                }
+            })?
+            .rows_affected();
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let replaced = existing as u64;
+        let created = written.saturating_sub(replaced);
+        Ok(BatchEdgeResult {
+            created,
+            replaced,
+            skipped: total - created - replaced,
+        })
+    }
+
+    async fn update_edge(
+        &self,
+        record: EdgeRecord,
+        old_to: Uuid,
+        to: Option<Uuid>,
+    ) -> Result<(), Error> {
+        let EdgeRecord {
+            from,
+            type_name,
+            data,
+            ..
+        } = record;
+        let _ = sqlx::query(
+            r#"
+        UPDATE edges SET data = $1, "to" = $2
+        WHERE "from" = $3 AND type = $4 AND "to" = $5
+        "#,
+        )
+        .bind(data)
+        .bind(to.unwrap_or(old_to))
+        .bind(from)
+        .bind(type_name.as_ref())
+        .bind(old_to)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete_edge(
+        &self,
+        type_name: &'static str,
+        from: Uuid,
+        to: Uuid,
+    ) -> Result<(), Error> {
+        let _ = sqlx::query(
+            r#"
+            DELETE FROM edges
+            WHERE type = $1 AND "from" = $2 AND "to" = $3
+            "#,
+        )
+        .bind(type_name)
+        .bind(from)
+        .bind(to)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete_object_edge(&self, type_name: &'static str, from: Uuid) -> Result<(), Error> {
+        let _ = sqlx::query(
+            r#"
+            DELETE FROM edges
+            WHERE type = $1 AND "from" = $2
+            "#,
+        )
+        .bind(type_name)
+        .bind(from)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete_edges_for_node(&self, node_id: Uuid) -> Result<u64, Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM edges
+            WHERE "from" = $1 OR "to" = $1
+            "#,
+        )
+        .bind(node_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn fetch_edge(
+        &self,
+        type_name: &'static str,
+        from: Uuid,
+        to: Uuid,
+    ) -> Result<Option<EdgeRecord>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT e."from", e."to", e.type, e.data
+        FROM edges e
+        WHERE type = $1 AND "from" = $2 AND "to" = $3
+        "#,
+        )
+        .bind(type_name)
+        .bind(from)
+        .bind(to)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        Self::map_row_to_edge_record(row).map(|e| Some(e))
+    }
+
+    async fn query_edges(
+        &self,
+        type_name: &'static str,
+        owner: Uuid,
+        plan: EdgeQuery,
+    ) -> Result<Vec<EdgeRecord>, Error> {
+        self.query_edges_internal(type_name, owner, plan, TraversalDirection::Forward)
+            .await
+    }
+
+    async fn query_reverse_edges(
+        &self,
+        type_name: &'static str,
+        owner_reverse: Uuid,
+        plan: EdgeQuery,
+    ) -> Result<Vec<EdgeRecord>, Error> {
+        self.query_edges_internal(type_name, owner_reverse, plan, TraversalDirection::Reverse)
+            .await
+    }
+
+    async fn query_edges_with_targets(
+        &self,
+        edge_type: &'static str,
+        obj_type: &'static str,
+        owner: Uuid,
+        obj_filters: &[QueryFilter],
+        plan: EdgeQuery,
+    ) -> Result<Vec<(EdgeRecord, ObjectRecord)>, Error> {
+        self.query_edges_with_objects_inner(
+            edge_type,
+            obj_type,
+            owner,
+            obj_filters,
+            plan,
+            TraversalDirection::Forward,
+        )
+        .await
+    }
+
+    async fn query_reverse_edges_with_sources(
+        &self,
+        edge_type: &'static str,
+        obj_type: &'static str,
+        owner: Uuid,
+        obj_filters: &[QueryFilter],
+        plan: EdgeQuery,
+    ) -> Result<Vec<(EdgeRecord, ObjectRecord)>, Error> {
+        self.query_edges_with_objects_inner(
+            edge_type,
+            obj_type,
+            owner,
+            obj_filters,
+            plan,
+            TraversalDirection::Reverse,
+        )
+        .await
+    }
+
+    async fn count_edges(
+        &self,
+        type_name: &'static str,
+        owner: Uuid,
+        plan: Option<EdgeQuery>,
+    ) -> Result<u64, Error> {
+        match plan {
+            Some(plan) => {
+                let where_clause = Self::build_edge_query_conditions(
+                    &plan.filters,
+                    None,
+                    TraversalDirection::Forward,
+                );
+
+                let mut sql = format!(
+                    r#"
+                SELECT COUNT(*) FROM edges
+                {}
+                "#,
+                    where_clause
+                );
+
+                if let Some(limit) = plan.limit {
+                    sql.push_str(&format!(" LIMIT {}", limit));
+                }
+
+                let mut query = sqlx::query_scalar::<_, i64>(&sql)
+                    .bind(type_name)
+                    .bind(owner);
+
+                query = Self::query_scalar_bind_filters(query, &plan.filters);
+
+                let count = query
+                    .fetch_one(&self.pool)
+                    .await
+                    .map_err(|e| Error::Storage(e.to_string()))?;
+
+                Ok(count as u64)
+            }
+            None => {
+                let count: i64 = sqlx::query_scalar(
+                    r#"SELECT COUNT(*) FROM edges WHERE type = $1 AND "from" = $2"#,
+                )
+                .bind(type_name)
+                .bind(owner)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|err| Error::Storage(err.to_string()))?;
+
+                Ok(count as u64)
+            }
+        }
+    }
+
+    async fn count_reverse_edges(
+        &self,
+        type_name: &'static str,
+        to: Uuid,
+        plan: Option<EdgeQuery>,
+    ) -> Result<u64, Error> {
+        match plan {
+            Some(plan) => {
+                let where_clause = Self::build_edge_query_conditions(
+                    &plan.filters,
+                    None,
+                    TraversalDirection::Reverse,
+                );
+
+                let mut sql = format!(
+                    r#"
+                SELECT COUNT(*) FROM edges
+                {}
+                "#,
+                    where_clause
+                );
+
+                if let Some(limit) = plan.limit {
+                    sql.push_str(&format!(" LIMIT {}", limit));
+                }
+
+                let mut query = sqlx::query_scalar::<_, i64>(&sql).bind(type_name).bind(to);
+
+                query = Self::query_scalar_bind_filters(query, &plan.filters);
+
+                let count = query
+                    .fetch_one(&self.pool)
+                    .await
+                    .map_err(|e| Error::Storage(e.to_string()))?;
+
+                Ok(count as u64)
+            }
+            None => {
+                let count: i64 = sqlx::query_scalar(
+                    r#"
+                    SELECT COUNT(*) FROM edges WHERE type = $1 AND "to" = $2
+                    "#,
+                )
+                .bind(type_name)
+                .bind(to)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|err| Error::Storage(err.to_string()))?;
+
+                Ok(count as u64)
+            }
+        }
+    }
+
+    async fn aggregate_edge_field(
+        &self,
+        type_name: &'static str,
+        from: Uuid,
+        field: &'static str,
+        agg: Aggregation,
+    ) -> Result<f64, Error> {
+        let sql = format!(
+            r#"SELECT COALESCE({}((index_meta->>$3)::FLOAT8), 0) FROM edges WHERE type = $1 AND "from" = $2"#,
+            agg.as_sql()
+        );
+        sqlx::query_scalar(&sql)
+            .bind(type_name)
+            .bind(from)
+            .bind(field)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+
+    async fn aggregate_reverse_edge_field(
+        &self,
+        type_name: &'static str,
+        to: Uuid,
+        field: &'static str,
+        agg: Aggregation,
+    ) -> Result<f64, Error> {
+        let sql = format!(
+            r#"SELECT COALESCE({}((index_meta->>$3)::FLOAT8), 0) FROM edges WHERE type = $1 AND "to" = $2"#,
+            agg.as_sql()
+        );
+        sqlx::query_scalar(&sql)
+            .bind(type_name)
+            .bind(to)
+            .bind(field)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
     }
 
-    async fn query_reverse_edges(
+    async fn aggregate_edge_field_batch(
         &self,
         type_name: &'static str,
-        owner_reverse: Uuid,
-        plan: EdgeQuery,
-    ) -> Result<Vec<EdgeRecord>, Error> {
-        self.query_edges_internal(type_name, owner_reverse, plan, TraversalDirection::Reverse)
+        from_ids: &[Uuid],
+        field: &'static str,
+        agg: Aggregation,
+    ) -> Result<Vec<(Uuid, f64)>, Error> {
+        let sql = format!(
+            r#"
+            SELECT "from", COALESCE({}((index_meta->>$3)::FLOAT8), 0)
+            FROM edges
+            WHERE type = $1 AND "from" = ANY($2)
+            GROUP BY "from"
+            "#,
+            agg.as_sql()
+        );
+        sqlx::query_as(&sql)
+            .bind(type_name)
+            .bind(from_ids)
+            .bind(field)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+
+    async fn sequence_value(&self, sq: String) -> u64 {
+        let val: i64 =
+            sqlx::query_scalar("SELECT COALESCE((SELECT value FROM sequences WHERE name = $1), 1)")
+                .bind(&sq)
+                .fetch_one(&self.pool)
+                .await
+                .expect("Failed to fetch the current sequence value");
+        val as u64
+    }
+
+    async fn try_sequence_next_value(&self, sq: String) -> Result<u64, Error> {
+        let next_val: i64 = sqlx::query_scalar(
+            "INSERT INTO sequences (name, value) VALUES ($1, 2)
+             ON CONFLICT (name) DO UPDATE SET value = sequences.value + 1
+             RETURNING value",
+        )
+        .bind(&sq)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(next_val as u64)
+    }
+
+    async fn sequence_reset(&self, sq: String, to: u64) -> Result<(), Error> {
+        sqlx::query(
+            "INSERT INTO sequences (name, value) VALUES ($1, $2)
+             ON CONFLICT (name) DO UPDATE SET value = EXCLUDED.value",
+        )
+        .bind(&sq)
+        .bind(to as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(())
+    }
+
+    async fn sequence_list(&self) -> Result<Vec<(String, u64)>, Error> {
+        let rows: Vec<(String, i64)> =
+            sqlx::query_as("SELECT name, value FROM sequences ORDER BY name")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(rows
+            .into_iter()
+            .map(|(name, value)| (name, value as u64))
+            .collect())
+    }
+
+    async fn vacuum(&self) -> Result<(), Error> {
+        // CockroachDB garbage-collects MVCC garbage itself (gc.ttlseconds);
+        // there is no VACUUM statement.
+        Ok(())
+    }
+
+    async fn reindex(&self) -> Result<(), Error> {
+        // Indexes live in the same LSM storage as the rows and are compacted
+        // with them; there is no REINDEX statement.
+        Ok(())
+    }
+
+    async fn delete_orphan_unique_constraints(&self) -> Result<u64, Error> {
+        // Object hashes are keyed by object ID, edge hashes by the edge's `from`
+        let result = sqlx::query(
+            r#"
+            DELETE FROM unique_constraints u
+            WHERE NOT EXISTS (SELECT 1 FROM objects o WHERE o.id = u.id AND o.type = u.type)
+              AND NOT EXISTS (SELECT 1 FROM edges e WHERE e."from" = u.id AND e.type = u.type)
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn set_retention_policy(&self, type_name: &str, ttl_seconds: u64) -> Result<(), Error> {
+        sqlx::query(
+            "INSERT INTO retention_policies (type, ttl_seconds) VALUES ($1, $2)
+             ON CONFLICT (type) DO UPDATE SET ttl_seconds = EXCLUDED.ttl_seconds",
+        )
+        .bind(type_name)
+        .bind(ttl_seconds as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn retention_policies(&self) -> Result<Vec<(String, u64)>, Error> {
+        let rows: Vec<(String, i64)> =
+            sqlx::query_as("SELECT type, ttl_seconds FROM retention_policies ORDER BY type")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(rows
+            .into_iter()
+            .map(|(type_name, ttl)| (type_name, ttl as u64))
+            .collect())
+    }
+
+    async fn delete_objects_created_before(
+        &self,
+        type_name: &str,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<Uuid>, Error> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+        let ids: Vec<Uuid> = sqlx::query_scalar(
+            "DELETE FROM objects WHERE type = $1 AND created_at < $2 RETURNING id",
+        )
+        .bind(type_name)
+        .bind(cutoff)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        if !ids.is_empty() {
+            sqlx::query("DELETE FROM unique_constraints WHERE type = $1 AND id = ANY($2)")
+                .bind(type_name)
+                .bind(&ids)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::Storage(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(ids)
+    }
+
+    async fn table_stats(&self) -> Result<TableStats, Error> {
+        let (objects, edges, uniques): (i64, i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM objects),
+                (SELECT COUNT(*) FROM edges),
+                (SELECT COUNT(*) FROM unique_constraints)
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(TableStats {
+            objects_count: objects as u64,
+            edges_count: edges as u64,
+            unique_constraints_count: uniques as u64,
+            // Range sizes aren't exposed through a stable API
+            estimated_size_bytes: None,
+        })
+    }
+
+    async fn export_schema(&self) -> Result<SchemaExport, Error> {
+        // Rebuilt from the catalog, like the Postgres adapter
+        let rows: Vec<(String, String, String, bool, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT table_name::TEXT, column_name::TEXT, data_type::TEXT,
+                   is_nullable = 'YES', column_default::TEXT
+            FROM information_schema.columns
+            WHERE table_schema = 'public' AND table_name = ANY($1)
+            ORDER BY array_position($1, table_name::TEXT), ordinal_position
+            "#,
+        )
+        .bind(&["objects", "edges", "sequences"][..])
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        let columns: Vec<SchemaColumn> = rows
+            .into_iter()
+            .map(|(table, name, data_type, nullable, default)| SchemaColumn {
+                table,
+                name,
+                data_type,
+                nullable,
+                default,
+            })
+            .collect();
+
+        let indexes_ddl: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT indexdef FROM pg_indexes
+            WHERE schemaname = 'public' AND tablename = ANY($1)
+            ORDER BY indexname
+            "#,
+        )
+        .bind(SCHEMA_TABLES)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(SchemaExport {
+            objects_ddl: table_ddl("objects", &columns),
+            edges_ddl: table_ddl("edges", &columns),
+            indexes_ddl,
+            sequences_ddl: table_ddl("sequences", &columns),
+            columns,
+        })
+    }
+
+    async fn verify_schema(&self) -> Result<SchemaVerification, Error> {
+        let tables: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT table_name::TEXT FROM information_schema.tables
+            WHERE table_schema = 'public' AND table_name = ANY($1)
+            "#,
+        )
+        .bind(SCHEMA_TABLES)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        // Primary keys and UNIQUE constraints come with the tables
+        let indexes: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT c.relname::TEXT
+            FROM pg_index i
+            JOIN pg_class c ON c.oid = i.indexrelid
+            JOIN pg_class t ON t.oid = i.indrelid
+            JOIN pg_namespace n ON n.oid = t.relnamespace
+            WHERE n.nspname = 'public' AND t.relname = ANY($1)
+              AND NOT i.indisprimary
+              AND NOT EXISTS (SELECT 1 FROM pg_constraint k WHERE k.conindid = i.indexrelid)
+            ORDER BY c.relname
+            "#,
+        )
+        .bind(SCHEMA_TABLES)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(SchemaVerification::compare(
+            &tables,
+            &indexes,
+            SCHEMA_TABLES,
+            SCHEMA_INDEXES,
+        ))
+    }
+
+    async fn rename_edge_type(&self, old_type: &str, new_type: &str) -> Result<u64, Error> {
+        // UPDATE has no ON CONFLICT; leave colliding pairs under the old type
+        let result = sqlx::query(
+            r#"
+            UPDATE edges SET type = $2
+            WHERE type = $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM edges n
+                  WHERE n.type = $2 AND n."from" = edges."from" AND n."to" = edges."to"
+              )
+            "#,
+        )
+        .bind(old_type)
+        .bind(new_type)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn copy_edge_type(&self, old_type: &str, new_type: &str) -> Result<u64, Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            SELECT "from", "to", $2, data, index_meta FROM edges WHERE type = $1
+            ON CONFLICT ("from", "to", type) DO NOTHING
+            "#,
+        )
+        .bind(old_type)
+        .bind(new_type)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn count_edges_by_type(&self, type_name: &str) -> Result<u64, Error> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM edges WHERE type = $1")
+            .bind(type_name)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(count as u64)
+    }
+
+    async fn type_stats(&self) -> Result<Vec<TypeStats>, Error> {
+        let rows: Vec<(String, i64, DateTime<Utc>)> = sqlx::query_as(
+            r#"
+            SELECT type, COUNT(*), MAX(created_at)
+            FROM objects
+            GROUP BY type
+            ORDER BY type
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(type_name, count, latest_created_at)| TypeStats {
+                type_name,
+                count: count as u64,
+                latest_created_at,
+            })
+            .collect())
+    }
+
+    async fn begin(&self) -> Result<DatabaseTransaction, Error> {
+        let tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(DatabaseTransaction::new(CockroachTransaction::new(tx)))
+    }
+
+    async fn lock_object(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+        timeout: Duration,
+    ) -> Result<Box<dyn LockGuard>, Error> {
+        // CockroachDB has no advisory locks. Write an intent on a row of
+        // object_locks instead: competing writers queue behind it until the
+        // guard rolls the transaction back on drop. The object row itself
+        // stays unlocked so the holder can still update it.
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+        sqlx::query(&format!(
+            "SET LOCAL lock_timeout = '{}ms'",
+            timeout.as_millis().max(1)
+        ))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        sqlx::query("UPSERT INTO object_locks (type, id) VALUES ($1, $2)")
+            .bind(type_name)
+            .bind(id)
+            .execute(&mut *tx)
             .await
+            .map_err(|err| match err.as_database_error().and_then(|e| e.code()) {
+                Some(code) if code == "55P03" => Error::LockTimeout,
+                _ => Error::Storage(err.to_string()),
+            })?;
+
+        Ok(Box::new(CockroachObjectLock { _tx: tx }))
+    }
+}
+
+/// Holds the transaction that owns an object's row lock
+struct CockroachObjectLock {
+    _tx: Transaction<'static, Postgres>,
+}
+
+impl LockGuard for CockroachObjectLock {}
+
+/// Holds the open transaction; `None` once committed or rolled back
+struct CockroachTransaction {
+    tx: tokio::sync::Mutex<Option<Transaction<'static, Postgres>>>,
+}
+
+impl CockroachTransaction {
+    fn new(tx: Transaction<'static, Postgres>) -> Self {
+        Self {
+            tx: tokio::sync::Mutex::new(Some(tx)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TransactionBackend for CockroachTransaction {
+    async fn insert_object(&self, record: ObjectRecord) -> Result<(), Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
+        let ObjectRecord {
+            id,
+            type_name,
+            owner,
+            created_at,
+            updated_at,
+            data,
+            index_meta,
+        } = record;
+        sqlx::query(
+            r#"
+            INSERT INTO public.objects (id, type, owner, created_at, updated_at, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(id)
+        .bind(type_name.as_ref())
+        .bind(owner)
+        .bind(created_at)
+        .bind(updated_at)
+        .bind(data)
+        .bind(index_meta)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| {
+            if err.to_string().contains("unique") {
+                Error::unique_violation("id")
+            } else {
+                Error::Storage(err.to_string())
+            }
+        })?;
+        Ok(())
     }
 
-    async fn query_edges_with_targets(
+    async fn fetch_object(
         &self,
-        edge_type: &'static str,
-        obj_type: &'static str,
-        owner: Uuid,
-        obj_filters: &[QueryFilter],
-        plan: EdgeQuery,
-    ) -> Result<Vec<(EdgeRecord, ObjectRecord)>, Error> {
-        self.query_edges_with_objects_inner(
-            edge_type,
-            obj_type,
-            owner,
-            obj_filters,
-            plan,
-            TraversalDirection::Forward,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE id = $1 AND type = $2
+            "#,
         )
+        .bind(id)
+        .bind(type_name)
+        .fetch_optional(&mut **tx)
         .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        row.map(CockroachAdapter::map_row_to_object_record_slim)
+            .transpose()
     }
 
-    async fn query_reverse_edges_with_sources(
-        &self,
-        edge_type: &'static str,
-        obj_type: &'static str,
-        owner: Uuid,
-        obj_filters: &[QueryFilter],
-        plan: EdgeQuery,
-    ) -> Result<Vec<(EdgeRecord, ObjectRecord)>, Error> {
-        self.query_edges_with_objects_inner(
-            edge_type,
-            obj_type,
-            owner,
-            obj_filters,
-            plan,
-            TraversalDirection::Reverse,
+    async fn update_object(&self, record: ObjectRecord) -> Result<(), Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
+        sqlx::query(
+            r#"
+            UPDATE objects
+            SET updated_at = $2, data = $3, index_meta = $4
+            WHERE id = $1
+            "#,
         )
+        .bind(record.id)
+        .bind(record.updated_at)
+        .bind(record.data)
+        .bind(record.index_meta)
+        .execute(&mut **tx)
         .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
     }
 
-    async fn count_edges(
+    async fn delete_object(
         &self,
         type_name: &'static str,
+        id: Uuid,
         owner: Uuid,
-        plan: Option<EdgeQuery>,
-    ) -> Result<u64, Error> {
-        match plan {
-            Some(plan) => {
-                let where_clause = Self::build_edge_query_conditions(
-                    &plan.filters,
-                    None,
-                    TraversalDirection::Forward,
-                );
-
-                let mut sql = format!(
-                    r#"
-                SELECT COUNT(*) FROM edges
-                {}
-                "#,
-                    where_clause
-                );
-
-                if let Some(limit) = plan.limit {
-                    sql.push_str(&format!(" LIMIT {}", limit));
-                }
+    ) -> Result<Option<ObjectRecord>, Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
 
-                let mut query = sqlx::query_scalar::<_, i64>(&sql)
-                    .bind(type_name)
-                    .bind(owner);
+        let row = sqlx::query(
+            r#"
+            DELETE FROM objects
+            WHERE id = $1 AND owner = $2 AND type = $3
+            RETURNING id, type, owner, created_at, updated_at, data
+            "#,
+        )
+        .bind(id)
+        .bind(owner)
+        .bind(type_name)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
 
-                query = Self::query_scalar_bind_filters(query, &plan.filters);
+        row.map(CockroachAdapter::map_row_to_object_record_slim)
+            .transpose()
+    }
 
-                let count = query
-                    .fetch_one(&self.pool)
-                    .await
-                    .map_err(|e| Error::Storage(e.to_string()))?;
+    async fn insert_edge(&self, record: EdgeRecord) -> Result<(), Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
 
-                Ok(count as u64)
-            }
-            None => {
-                let count: i64 = sqlx::query_scalar(
-                    r#"SELECT COUNT(*) FROM edges WHERE type = $1 AND "from" = $2"#,
-                )
-                .bind(type_name)
-                .bind(owner)
-                .fetch_one(&self.pool)
-                .await
-                .map_err(|err| Error::Storage(err.to_string()))?;
+        let EdgeRecord {
+            from,
+            to,
+            type_name,
+            data,
+            index_meta,
+        } = record;
+        sqlx::query(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT ("from", type, "to")
+            DO UPDATE SET data = $4, index_meta = $5;
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(type_name.as_ref())
+        .bind(data)
+        .bind(index_meta)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
 
-                Ok(count as u64)
-            }
-        }
+        Ok(())
     }
 
-    async fn count_reverse_edges(
+    async fn delete_edge(
         &self,
         type_name: &'static str,
+        from: Uuid,
         to: Uuid,
-        plan: Option<EdgeQuery>,
-    ) -> Result<u64, Error> {
-        match plan {
-            Some(plan) => {
-                let where_clause = Self::build_edge_query_conditions(
-                    &plan.filters,
-                    None,
-                    TraversalDirection::Reverse,
-                );
-
-                let mut sql = format!(
-                    r#"
-                SELECT COUNT(*) FROM edges
-                {}
-                "#,
-                    where_clause
-                );
-
-                if let Some(limit) = plan.limit {
-                    sql.push_str(&format!(" LIMIT {}", limit));
-                }
-
-                let mut query = sqlx::query_scalar::<_, i64>(&sql).bind(type_name).bind(to);
-
-                query = Self::query_scalar_bind_filters(query, &plan.filters);
-
-                let count = query
-                    .fetch_one(&self.pool)
-                    .await
-                    .map_err(|e| Error::Storage(e.to_string()))?;
+    ) -> Result<(), Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
 
-                Ok(count as u64)
-            }
-            None => {
-                let count: i64 = sqlx::query_scalar(
-                    r#"
-                    SELECT COUNT(*) FROM edges WHERE type = $1 AND "to" = $2
-                    "#,
-                )
-                .bind(type_name)
-                .bind(to)
-                .fetch_one(&self.pool)
-                .await
-                .map_err(|err| Error::Storage(err.to_string()))?;
+        sqlx::query(
+            r#"
+            DELETE FROM edges
+            WHERE type = $1 AND "from" = $2 AND "to" = $3
+            "#,
+        )
+        .bind(type_name)
+        .bind(from)
+        .bind(to)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
 
-                Ok(count as u64)
-            }
-        }
+        Ok(())
     }
 
-    async fn sequence_value(&self, sq: String) -> u64 {
-        let val: i64 =
-            sqlx::query_scalar("SELECT COALESCE((SELECT value FROM sequences WHERE name = $1), 1)")
-                .bind(&sq)
-                .fetch_one(&self.pool)
-                .await
-                .expect("Failed to fetch the current sequence value");
-        val as u64
+    async fn commit(&self) -> Result<(), Error> {
+        let tx = self.tx.lock().await.take().ok_or_else(finished)?;
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
     }
 
-    async fn sequence_next_value(&self, sq: String) -> u64 {
-        let next_val: i64 = sqlx::query_scalar(
-            "INSERT INTO sequences (name, value) VALUES ($1, 2)
-             ON CONFLICT (name) DO UPDATE SET value = sequences.value + 1
-             RETURNING value",
-        )
-        .bind(&sq)
-        .fetch_one(&self.pool)
-        .await
-        .expect("Failed to fetch the next sequence value");
-        next_val as u64
+    async fn rollback(&self) -> Result<(), Error> {
+        let tx = self.tx.lock().await.take().ok_or_else(finished)?;
+        tx.rollback()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
     }
 }
 
@@ -1841,7 +3743,7 @@ impl UniqueAdapter for CockroachAdapter {
                     .await
                     .unwrap_or(None);
                     let field = existing.unwrap_or_else(|| "unknown".to_string());
-                    Err(Error::UniqueConstraintViolation(field))
+                    Err(Error::unique_violation(field))
                 } else {
                     Err(Error::Storage(msg))
                 }
@@ -1877,6 +3779,54 @@ impl UniqueAdapter for CockroachAdapter {
         Ok(())
     }
 
+    async fn backfill_unique_constraints(
+        &self,
+        type_name: &str,
+        entries: Vec<(Uuid, Vec<(String, &str)>)>,
+    ) -> Result<u64, Error> {
+        let mut ids: Vec<Uuid> = Vec::new();
+        let mut keys: Vec<String> = Vec::new();
+        let mut fields: Vec<&str> = Vec::new();
+        for (id, hashes) in entries {
+            for (key, field) in hashes {
+                ids.push(id);
+                keys.push(key);
+                fields.push(field);
+            }
+        }
+        if keys.is_empty() {
+            return Ok(0);
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO unique_constraints (id, type, key, field)
+            SELECT t.id, $2, t.key, t.field
+            FROM unnest($1::uuid[], $3::text[], $4::text[]) AS t(id, key, field)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(&ids)
+        .bind(type_name)
+        .bind(&keys)
+        .bind(&fields)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_unique_for_type(&self, type_name: &str) -> Result<(), Error> {
+        sqlx::query("DELETE FROM unique_constraints WHERE type = $1")
+            .bind(type_name)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(())
+    }
+
     async fn get_hashes_for_object(&self, object_id: Uuid) -> Result<Vec<String>, Error> {
         let rows = sqlx::query(
             r#"
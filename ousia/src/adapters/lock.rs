@@ -0,0 +1,60 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use once_cell::sync::Lazy;
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// Keeps an object lock held. Dropping it releases the lock.
+pub trait LockGuard: Send {}
+
+type LockKey = (&'static str, Uuid);
+
+static LOCAL_LOCKS: Lazy<Mutex<HashMap<LockKey, Arc<AsyncMutex<()>>>>> =
+    Lazy::new(Default::default);
+
+/// In-process lock table for adapters without row-level locking (SQLite).
+/// Only excludes tasks within the same process.
+pub(crate) struct LocalLock {
+    key: LockKey,
+    guard: Option<OwnedMutexGuard<()>>,
+}
+
+impl LockGuard for LocalLock {}
+
+impl LocalLock {
+    pub(crate) async fn acquire(
+        type_name: &'static str,
+        id: Uuid,
+        timeout: Duration,
+    ) -> Result<Self, Error> {
+        let key = (type_name, id);
+        let mutex = LOCAL_LOCKS.lock().unwrap().entry(key).or_default().clone();
+        let guard = tokio::time::timeout(timeout, mutex.lock_owned())
+            .await
+            .map_err(|_| Error::LockTimeout)?;
+        Ok(Self {
+            key,
+            guard: Some(guard),
+        })
+    }
+}
+
+impl Drop for LocalLock {
+    fn drop(&mut self) {
+        self.guard.take();
+        let mut locks = LOCAL_LOCKS.lock().unwrap();
+        // Only the table's reference left: nobody is waiting, drop the entry
+        if locks
+            .get(&self.key)
+            .is_some_and(|m| Arc::strong_count(m) == 1)
+        {
+            locks.remove(&self.key);
+        }
+    }
+}
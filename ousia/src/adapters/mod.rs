@@ -7,18 +7,191 @@ pub mod postgres;
 #[cfg(feature = "sqlite")]
 pub mod sqlite;
 
+pub mod lock;
 pub mod query;
 pub mod record;
+pub mod schema;
+pub mod transaction;
 
 #[cfg(feature = "ledger")]
 use std::sync::Arc;
 
+use std::time::Duration;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+pub use lock::LockGuard;
 pub use query::*;
 pub use record::*;
+pub use schema::{SchemaColumn, SchemaExport, SchemaVerification};
+pub use transaction::{DatabaseTransaction, TransactionBackend};
 use uuid::Uuid;
 
-use crate::{Object, edge::query::EdgeQuery, error::Error, query::QueryFilter};
+use crate::{
+    ChangeLogEntry, Object,
+    edge::query::EdgeQuery,
+    error::Error,
+    query::{IndexValue, QueryFilter},
+};
+
+/// Row counts (and size, where the backend can tell) of the core tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TableStats {
+    pub objects_count: u64,
+    pub edges_count: u64,
+    pub unique_constraints_count: u64,
+    /// Total on-disk size including indexes. `None` when the backend can't estimate it.
+    pub estimated_size_bytes: Option<u64>,
+}
+
+/// What [`Engine::run_maintenance`](crate::Engine::run_maintenance) should do.
+/// Everything is off by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MaintenanceOptions {
+    pub vacuum: bool,
+    pub reindex: bool,
+    /// Delete burned ledger value objects; needs the `ledger` feature
+    pub expire_ledger_objects: bool,
+    pub cleanup_orphan_unique_constraints: bool,
+}
+
+/// Outcome of [`Engine::run_maintenance`](crate::Engine::run_maintenance)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MaintenanceReport {
+    pub vacuumed: bool,
+    pub reindexed: bool,
+    /// Burned ledger value objects deleted
+    pub rows_expired: u64,
+    /// Unique hashes deleted because their object or edge was gone
+    pub orphans_removed: u64,
+    pub duration: Duration,
+}
+
+/// Outcome of [`Engine::run_retention_policies`](crate::Engine::run_retention_policies)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RetentionReport {
+    pub policies_applied: u32,
+    pub objects_deleted: u64,
+    /// Edges to or from the deleted objects
+    pub edges_deleted: u64,
+}
+
+/// Planner estimate for a query, taken without running it.
+/// Fields an adapter's planner doesn't report are left at zero.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct QueryCost {
+    pub estimated_rows: u64,
+    /// Some part of the plan reads through an index
+    pub uses_index: bool,
+    /// Some part of the plan reads the whole table
+    pub sequential_scan: bool,
+    /// Total cost in the planner's own units; only comparable within one database
+    pub planner_cost: f64,
+}
+
+/// Object count and newest creation time of one stored type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeStats {
+    pub type_name: String,
+    pub count: u64,
+    pub latest_created_at: DateTime<Utc>,
+}
+
+/// Outcome of sampling a type's stored `index_meta` against its current
+/// definition; see `Engine::audit_index_meta`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IndexAuditReport {
+    pub sampled: u64,
+    /// Sampled records whose index entry differs from the expected one
+    pub stale: u64,
+    /// `(id, diff)` for every stale record
+    pub diffs: Vec<(Uuid, IndexMetaDiff)>,
+}
+
+impl IndexAuditReport {
+    /// Share of sampled records that are stale, from 0.0 to 1.0
+    pub fn corruption_rate(&self) -> f64 {
+        if self.sampled == 0 {
+            return 0.0;
+        }
+        self.stale as f64 / self.sampled as f64
+    }
+}
+
+/// Number of objects sharing one value of an indexed field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupCount {
+    pub value: IndexValue,
+    pub count: u64,
+}
+
+/// Bucket width for [`Adapter::time_bucket_count`]. Weeks start on Monday;
+/// buckets are aligned in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    Day,
+    Week,
+    Month,
+}
+
+impl Granularity {
+    /// Field name understood by `date_trunc`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Granularity::Day => "day",
+            Granularity::Week => "week",
+            Granularity::Month => "month",
+        }
+    }
+}
+
+/// Aggregate function for [`Adapter::aggregate_edge_field`] and friends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl Aggregation {
+    /// SQL function name
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            Aggregation::Sum => "SUM",
+            Aggregation::Avg => "AVG",
+            Aggregation::Min => "MIN",
+            Aggregation::Max => "MAX",
+        }
+    }
+}
+
+/// Result of an edge upsert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpsertOutcome {
+    /// `false` when an existing edge was overwritten
+    pub created: bool,
+}
+
+/// What a batch edge insert does with an edge whose `from → to` already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnConflict {
+    /// Keep the stored edge
+    Ignore,
+    /// Overwrite the stored edge's data
+    Replace,
+    /// Fail the whole batch with a unique violation
+    Error,
+}
+
+/// Counts from [`Engine::batch_create_edges`](crate::Engine::batch_create_edges).
+/// They add up to the number of edges passed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BatchEdgeResult {
+    pub created: u64,
+    pub replaced: u64,
+    pub skipped: u64,
+}
 
 /// -----------------------------
 /// Adapter contract
@@ -36,6 +209,17 @@ pub trait UniqueAdapter {
     async fn delete_unique(&self, hash: &str) -> Result<(), Error>;
     async fn delete_unique_hashes(&self, hashes: Vec<String>) -> Result<(), Error>;
 
+    /// Insert unique hashes for objects stored before the constraint existed,
+    /// skipping keys that are already taken. Returns the number inserted.
+    async fn backfill_unique_constraints(
+        &self,
+        type_name: &str,
+        entries: Vec<(Uuid, Vec<(String, &str)>)>,
+    ) -> Result<u64, Error>;
+
+    /// Remove every unique hash recorded for `type_name`
+    async fn delete_unique_for_type(&self, type_name: &str) -> Result<(), Error>;
+
     async fn get_hashes_for_object(&self, object_id: Uuid) -> Result<Vec<String>, Error>;
 }
 
@@ -144,17 +328,43 @@ pub trait EdgeTraversal {
 pub trait Adapter: UniqueAdapter + EdgeTraversal + Send + Sync + 'static {
     /* ---------------- OBJECTS ---------------- */
     async fn insert_object(&self, record: ObjectRecord) -> Result<(), Error>;
+    /// Insert unless an object with the same id already exists; the existing
+    /// row is left untouched. Returns whether a row was written.
+    async fn insert_object_or_ignore(&self, record: ObjectRecord) -> Result<bool, Error>;
     async fn fetch_object(
         &self,
         type_name: &'static str,
         id: Uuid,
     ) -> Result<Option<ObjectRecord>, Error>;
+    /// Fetch an object by ID whatever its type; `record.type_name` tells which
+    async fn fetch_object_any_type(&self, id: Uuid) -> Result<Option<ObjectRecord>, Error>;
+    /// Owner of object `id` without loading its data
+    async fn fetch_object_owner(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Option<Uuid>, Error>;
     async fn fetch_bulk_objects(
         &self,
         type_name: &'static str,
         ids: Vec<Uuid>,
     ) -> Result<Vec<ObjectRecord>, Error>;
     async fn update_object(&self, record: ObjectRecord) -> Result<(), Error>;
+    /// Merge the top-level keys of `data_patch` and `index_meta_patch` into the
+    /// stored row and drop `index_meta_removed`; other keys are left as stored.
+    async fn update_object_partial(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+        data_patch: serde_json::Value,
+        index_meta_patch: serde_json::Value,
+        index_meta_removed: Vec<String>,
+        updated_at: DateTime<Utc>,
+    ) -> Result<(), Error>;
+
+    /// Set `updated_at` to now on the listed objects without touching `data`
+    /// or `index_meta`. Returns how many rows were updated.
+    async fn touch_objects(&self, type_name: &'static str, ids: Vec<Uuid>) -> Result<u64, Error>;
 
     /// Explicit ownership transfer
     async fn transfer_object(
@@ -165,6 +375,29 @@ pub trait Adapter: UniqueAdapter + EdgeTraversal + Send + Sync + 'static {
         to_owner: Uuid,
     ) -> Result<ObjectRecord, Error>;
 
+    /// Exchange the owners of two objects in a single transaction. Fails with
+    /// `NotFound` (and changes nothing) if either object isn't held by the
+    /// expected owner.
+    async fn swap_owners(
+        &self,
+        type_name: &'static str,
+        id_a: Uuid,
+        owner_a: Uuid,
+        id_b: Uuid,
+        owner_b: Uuid,
+    ) -> Result<(ObjectRecord, ObjectRecord), Error>;
+
+    /// Change the `old_type` object `record.id` to `record.type_name` in place,
+    /// storing the data and index values of `record` and replacing the
+    /// object's unique hashes with `hashes`, in a single transaction. Fails
+    /// with `NotFound` (and changes nothing) if there's no such object.
+    async fn retype_object(
+        &self,
+        old_type: &'static str,
+        record: ObjectRecord,
+        hashes: Vec<(String, &str)>,
+    ) -> Result<(), Error>;
+
     async fn delete_object(
         &self,
         type_name: &'static str,
@@ -184,6 +417,66 @@ pub trait Adapter: UniqueAdapter + EdgeTraversal + Send + Sync + 'static {
         type_name: &'static str,
         owner: Uuid,
     ) -> Result<u64, Error>;
+    /// `delete_owned_objects` that also releases the deleted objects' unique
+    /// keys of this type, in one statement or transaction
+    async fn delete_owned_objects_with_hashes(
+        &self,
+        type_name: &'static str,
+        owner: Uuid,
+    ) -> Result<u64, Error>;
+
+    /* ---------------- HISTORY ---------------- */
+    /// Store `record` as snapshot `version` of its object in `object_history`
+    async fn insert_history_snapshot(
+        &self,
+        record: ObjectRecord,
+        version: u64,
+    ) -> Result<(), Error>;
+    /// Replace the object with `record` and store `previous`, its state before
+    /// the update, as the object's next snapshot version. Versions count per
+    /// object ID. Returns the version written, or 0 if no object was updated.
+    ///
+    /// The default runs the two writes separately; adapters with transactions
+    /// override it so a failed snapshot also undoes the update.
+    async fn update_object_with_history(
+        &self,
+        type_name: &'static str,
+        record: ObjectRecord,
+        previous: ObjectRecord,
+    ) -> Result<u64, Error> {
+        self.update_object(record).await?;
+        let version = self.latest_history_version(type_name, previous.id).await? + 1;
+        self.insert_history_snapshot(previous, version).await?;
+        Ok(version)
+    }
+    /// Highest snapshot version stored for the object, 0 if it has none
+    async fn latest_history_version(&self, type_name: &'static str, id: Uuid)
+    -> Result<u64, Error>;
+    /// Every snapshot of the object, oldest version first
+    async fn fetch_history(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Vec<(u64, ObjectRecord)>, Error>;
+
+    /* ---------------- AUDIT LOG ---------------- */
+    /// The latest `limit` entries of `object_audit_log` for the object, oldest first
+    async fn fetch_change_log(
+        &self,
+        _type_name: &'static str,
+        _id: Uuid,
+        _limit: u32,
+    ) -> Result<Vec<ChangeLogEntry>, Error> {
+        Err(Error::NotSupported) // default — adapters that install the audit trigger opt in
+    }
+    /// Every entry of `object_audit_log` for `type_name` written at or after `since`, oldest first
+    async fn fetch_type_change_log(
+        &self,
+        _type_name: &'static str,
+        _since: DateTime<Utc>,
+    ) -> Result<Vec<ChangeLogEntry>, Error> {
+        Err(Error::NotSupported)
+    }
 
     /* ---------------- QUERIES ---------------- */
     /// Fetch ALL objects matching `plan`. Filters by owner.
@@ -206,6 +499,83 @@ pub trait Adapter: UniqueAdapter + EdgeTraversal + Send + Sync + 'static {
         plan: Option<Query>,
     ) -> Result<u64, Error>;
 
+    /// Objects of `type_name` created per `granularity` bucket, oldest first.
+    /// Empty buckets are omitted; `owner: None` counts every owner.
+    async fn time_bucket_count(
+        &self,
+        type_name: &'static str,
+        owner: Option<Uuid>,
+        granularity: Granularity,
+    ) -> Result<Vec<(DateTime<Utc>, u64)>, Error>;
+
+    /// Objects matching `plan` grouped by the index value of `field`, largest
+    /// group first. Objects without the field form a `IndexValue::Null` group.
+    async fn group_by_field(
+        &self,
+        type_name: &'static str,
+        field: &'static str,
+        plan: Query,
+    ) -> Result<Vec<GroupCount>, Error>;
+
+    /// Objects matching `plan` counted per owner — Vec<(owner, count)>.
+    async fn group_count_by_owner(
+        &self,
+        type_name: &'static str,
+        plan: Query,
+    ) -> Result<Vec<(Uuid, u64)>, Error>;
+
+    /// The `limit` owners with the most objects of `type_name`, largest first.
+    /// Ties are ordered by owner.
+    async fn top_owners_by_type(
+        &self,
+        type_name: &'static str,
+        limit: u32,
+    ) -> Result<Vec<(Uuid, u64)>, Error>;
+
+    /// The object exactly as stored, with `index_meta` filled in. The default
+    /// falls back to [`fetch_object`](Self::fetch_object), leaving it `Null`.
+    async fn fetch_object_record(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error> {
+        self.fetch_object(type_name, id).await
+    }
+
+    /// Up to `limit` random objects of `type_name` exactly as stored, with
+    /// `index_meta` filled in (other reads leave it `Null`).
+    async fn sample_object_records(
+        &self,
+        type_name: &'static str,
+        limit: u32,
+    ) -> Result<Vec<ObjectRecord>, Error>;
+
+    /// Full-text search over the string values of objects' data, best match
+    /// first. `text` uses the backend's match syntax.
+    async fn fts_search(
+        &self,
+        _type_name: &'static str,
+        _text: &str,
+        _limit: u32,
+    ) -> Result<Vec<ObjectRecord>, Error> {
+        Err(Error::NotSupported) // default — adapters with a text index opt in
+    }
+
+    /// Full-text search for the plain words in `text`, restricted by the
+    /// filters of `plan`, paired with a relevance score (higher is better).
+    /// Sort filters in `plan` replace the relevance order.
+    async fn object_search(
+        &self,
+        _type_name: &'static str,
+        _text: &str,
+        _plan: Query,
+    ) -> Result<Vec<(ObjectRecord, f64)>, Error> {
+        Err(Error::NotSupported) // default — adapters with a text index opt in
+    }
+
+    /// Distinct `__namespace` values among objects of `type_name`, sorted.
+    async fn list_namespaces(&self, type_name: &'static str) -> Result<Vec<String>, Error>;
+
     /// Fetch ALL objects owned by `owner`
     async fn fetch_owned_objects(
         &self,
@@ -257,8 +627,31 @@ pub trait Adapter: UniqueAdapter + EdgeTraversal + Send + Sync + 'static {
         owner: Uuid,
     ) -> Result<Vec<ObjectRecord>, Error>;
 
+    /// `query_objects` over two types at once. Filters apply to both, so they
+    /// should name index fields the two types share.
+    async fn query_union_objects(
+        &self,
+        a_type_name: &'static str,
+        b_type_name: &'static str,
+        plan: Query,
+    ) -> Result<Vec<ObjectRecord>, Error>;
+
     /* ---------------- EDGES ---------------- */
     async fn insert_edge(&self, record: EdgeRecord) -> Result<(), Error>;
+    /// Insert unless a `(from, to, type)` row already exists; the existing row is
+    /// left untouched. Returns whether a row was written.
+    async fn insert_edge_or_ignore(&self, record: EdgeRecord) -> Result<bool, Error>;
+    /// Insert the edge or overwrite `data`/`index_meta` of the existing `(from, to, type)` row.
+    async fn upsert_edge(&self, record: EdgeRecord) -> Result<UpsertOutcome, Error>;
+    /// Upsert many edges in one round trip. Keys must be distinct; returns rows written.
+    async fn upsert_edges_batch(&self, records: Vec<EdgeRecord>) -> Result<u64, Error>;
+    /// Insert `records` in one statement (or transaction), resolving existing
+    /// `from → to` edges per `on_conflict`. `records` hold no repeated key.
+    async fn insert_edges_batch(
+        &self,
+        records: Vec<EdgeRecord>,
+        on_conflict: OnConflict,
+    ) -> Result<BatchEdgeResult, Error>;
     async fn update_edge(
         &self,
         record: EdgeRecord,
@@ -270,6 +663,10 @@ pub trait Adapter: UniqueAdapter + EdgeTraversal + Send + Sync + 'static {
 
     async fn delete_object_edge(&self, type_name: &'static str, from: Uuid) -> Result<(), Error>;
 
+    /// Delete every edge, of any type, where `node_id` is `from` or `to`.
+    /// Returns the number of edges removed.
+    async fn delete_edges_for_node(&self, node_id: Uuid) -> Result<u64, Error>;
+
     async fn fetch_edge(
         &self,
         type_name: &'static str,
@@ -325,9 +722,194 @@ pub trait Adapter: UniqueAdapter + EdgeTraversal + Send + Sync + 'static {
         plan: Option<EdgeQuery>,
     ) -> Result<u64, Error>;
 
+    /// `agg` over the numeric index field `field` of `type_name` edges leaving
+    /// `from`. `0.0` when no edge has the field.
+    async fn aggregate_edge_field(
+        &self,
+        type_name: &'static str,
+        from: Uuid,
+        field: &'static str,
+        agg: Aggregation,
+    ) -> Result<f64, Error>;
+
+    /// Like [`Adapter::aggregate_edge_field`] over edges arriving at `to`
+    async fn aggregate_reverse_edge_field(
+        &self,
+        type_name: &'static str,
+        to: Uuid,
+        field: &'static str,
+        agg: Aggregation,
+    ) -> Result<f64, Error>;
+
+    /// [`Adapter::aggregate_edge_field`] per source in one GROUP BY query.
+    /// Sources without edges are absent.
+    async fn aggregate_edge_field_batch(
+        &self,
+        type_name: &'static str,
+        from_ids: &[Uuid],
+        field: &'static str,
+        agg: Aggregation,
+    ) -> Result<Vec<(Uuid, f64)>, Error>;
+
+    /// Targets reachable from both `a` and `b` over `type_name` edges.
+    /// Default intersects one batch edge query in memory; adapters may push it down.
+    async fn common_neighbor_ids(
+        &self,
+        type_name: &'static str,
+        a: Uuid,
+        b: Uuid,
+        filters: &[QueryFilter],
+    ) -> Result<Vec<Uuid>, Error> {
+        let plan = EdgeQuery {
+            filters: filters.to_vec(),
+            ..Default::default()
+        };
+        let edges = self.query_edges_batch(type_name, &[a, b], plan).await?;
+
+        let from_b: std::collections::HashSet<Uuid> =
+            edges.iter().filter(|e| e.from == b).map(|e| e.to).collect();
+        let mut seen = std::collections::HashSet::new();
+        Ok(edges
+            .into_iter()
+            .filter(|e| e.from == a && from_b.contains(&e.to) && seen.insert(e.to))
+            .map(|e| e.to)
+            .collect())
+    }
+
     /* ---------------- SEQUENCE ---------------- */
     async fn sequence_value(&self, sq: String) -> u64;
-    async fn sequence_next_value(&self, sq: String) -> u64;
+
+    /// Increment the sequence and return the new value, creating it if missing.
+    async fn try_sequence_next_value(&self, sq: String) -> Result<u64, Error>;
+
+    async fn sequence_next_value(&self, sq: String) -> u64 {
+        self.try_sequence_next_value(sq)
+            .await
+            .expect("Failed to fetch the next sequence value")
+    }
+
+    /// Set the sequence to `to`, creating it if missing.
+    async fn sequence_reset(&self, sq: String, to: u64) -> Result<(), Error>;
+
+    /// Every stored sequence with its current value, ordered by name.
+    async fn sequence_list(&self) -> Result<Vec<(String, u64)>, Error>;
+
+    /* ---------------- MAINTENANCE ---------------- */
+    /// Reclaim dead rows and refresh planner statistics.
+    async fn vacuum(&self) -> Result<(), Error>;
+
+    /// Rebuild the indexes of the core tables.
+    async fn reindex(&self) -> Result<(), Error>;
+
+    /// Delete unique hashes whose object or edge no longer exists.
+    /// Returns how many were removed.
+    async fn delete_orphan_unique_constraints(&self) -> Result<u64, Error>;
+
+    /// Store the TTL of `type_name`, replacing any earlier one.
+    async fn set_retention_policy(&self, type_name: &str, ttl_seconds: u64) -> Result<(), Error>;
+
+    /// Every retention policy as `(type, ttl_seconds)`, ordered by type.
+    async fn retention_policies(&self) -> Result<Vec<(String, u64)>, Error>;
+
+    /// Delete the `type_name` objects created before `cutoff` and their unique
+    /// hashes, in one transaction. Returns the IDs deleted.
+    async fn delete_objects_created_before(
+        &self,
+        type_name: &str,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<Uuid>, Error>;
+
+    async fn table_stats(&self) -> Result<TableStats, Error>;
+
+    /// Every object type present in storage with its count, ordered by type name.
+    async fn type_stats(&self) -> Result<Vec<TypeStats>, Error>;
+
+    /// Retype `old_type` edges as `new_type`, skipping pairs that already have a
+    /// `new_type` edge. Returns the number of edges moved.
+    async fn rename_edge_type(&self, old_type: &str, new_type: &str) -> Result<u64, Error>;
+
+    /// Insert a `new_type` copy of every `old_type` edge, skipping pairs that
+    /// already have one. Returns the number of edges inserted.
+    async fn copy_edge_type(&self, old_type: &str, new_type: &str) -> Result<u64, Error>;
+
+    async fn count_edges_by_type(&self, type_name: &str) -> Result<u64, Error>;
+
+    /// Planner output (`EXPLAIN ANALYZE`) for the SQL `query_objects` would run.
+    /// The query is executed but its rows are discarded.
+    async fn explain_query(&self, _type_name: &'static str, _plan: Query) -> Result<String, Error> {
+        Err(Error::NotSupported) // default — adapters that can explain opt in
+    }
+
+    /// Planner estimate for the SQL `query_objects` would run. Nothing is executed.
+    async fn estimate_query_cost(
+        &self,
+        _type_name: &'static str,
+        _plan: Query,
+    ) -> Result<QueryCost, Error> {
+        Err(Error::NotSupported) // default — adapters that can explain opt in
+    }
+
+    /// Planner estimate for the SQL `query_edges` would run. Nothing is executed.
+    async fn estimate_edge_query_cost(
+        &self,
+        _type_name: &'static str,
+        _owner: Uuid,
+        _plan: EdgeQuery,
+    ) -> Result<QueryCost, Error> {
+        Err(Error::NotSupported) // default — adapters that can explain opt in
+    }
+
+    /* ---------------- SCHEMA ---------------- */
+    /// DDL and columns of the core tables, read back from the database.
+    async fn export_schema(&self) -> Result<SchemaExport, Error> {
+        Err(Error::NotSupported) // default — adapters that can introspect opt in
+    }
+
+    /// Tables and indexes of `init_schema` missing from the database, and
+    /// indexes on its tables that `init_schema` doesn't create.
+    async fn verify_schema(&self) -> Result<SchemaVerification, Error> {
+        Err(Error::NotSupported) // default — adapters that can introspect opt in
+    }
+
+    /* ---------------- TRANSACTIONS ---------------- */
+    /// Open a transaction. Nothing written through it is visible to other
+    /// connections until it's committed.
+    async fn begin(&self) -> Result<DatabaseTransaction, Error> {
+        Err(Error::NotSupported) // default — adapters with transactions opt in
+    }
+
+    /* ---------------- LOCKS ---------------- */
+    /// Exclusive lock on one object, held until the guard is dropped.
+    /// Fails with `Error::LockTimeout` if it isn't granted within `timeout`.
+    async fn lock_object(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+        timeout: Duration,
+    ) -> Result<Box<dyn LockGuard>, Error> {
+        // default — in-process only; adapters with row/advisory locks override
+        let lock = lock::LocalLock::acquire(type_name, id, timeout).await?;
+        Ok(Box::new(lock))
+    }
+
+    /* ---------------- WATCH ---------------- */
+    /// Native feed of object writes. `None` makes the engine fall back to polling.
+    #[cfg(feature = "watch")]
+    async fn change_stream(&self) -> Result<Option<crate::watch::ChangeStream>, Error> {
+        Ok(None) // default — adapters with pub/sub opt in
+    }
+
+    /* ---------------- RAW ---------------- */
+    /// Run hand-written SQL with positional `params`, returning each row as a
+    /// JSON object keyed by column name.
+    #[cfg(feature = "raw-queries")]
+    async fn execute_raw(
+        &self,
+        _sql: &str,
+        _params: Vec<serde_json::Value>,
+    ) -> Result<Vec<serde_json::Value>, Error> {
+        Err(Error::NotSupported) // default — SQL adapters opt in
+    }
 
     /* ---------------- LEDGER ---------------- */
     #[cfg(feature = "ledger")]
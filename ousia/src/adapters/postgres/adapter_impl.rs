@@ -1,16 +1,40 @@
 #[cfg(feature = "ledger")]
 use std::sync::Arc;
 
-use chrono::Utc;
+use std::time::Duration;
 
-use super::PostgresAdapter;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Row, Transaction};
+
+use super::{PostgresAdapter, SCHEMA_INDEXES, SCHEMA_TABLES, transaction_impl::PgTransaction};
 use uuid::Uuid;
 
 use crate::{
-    adapters::{Adapter, EdgeQuery, EdgeRecord, Error, ObjectRecord, Query, TraversalDirection},
+    ChangeLogEntry,
+    adapters::{
+        Adapter, Aggregation, BatchEdgeResult, DatabaseTransaction, EdgeQuery, EdgeRecord, Error,
+        Granularity, GroupCount, LockGuard, ObjectRecord, OnConflict, Query, QueryCost,
+        SchemaColumn, SchemaExport, SchemaVerification, TableStats, TraversalDirection, TypeStats,
+        UpsertOutcome, schema::table_ddl,
+    },
     query::QueryFilter,
 };
 
+/// Holds the transaction that owns an advisory lock
+struct PgObjectLock {
+    _tx: Transaction<'static, Postgres>,
+}
+
+impl LockGuard for PgObjectLock {}
+
+/// `lock_not_available` is raised when `lock_timeout` expires
+fn map_lock_error(err: sqlx::Error) -> Error {
+    match err.as_database_error().and_then(|e| e.code()) {
+        Some(code) if code == "55P03" => Error::LockTimeout,
+        _ => Error::Storage(err.to_string()),
+    }
+}
+
 #[async_trait::async_trait]
 impl Adapter for PostgresAdapter {
     async fn insert_object(&self, record: ObjectRecord) -> Result<(), Error> {
@@ -40,7 +64,7 @@ impl Adapter for PostgresAdapter {
         .await
         .map_err(|err| {
             if err.to_string().contains("unique") {
-                Error::UniqueConstraintViolation("id".to_string())
+                Error::unique_violation("id")
             } else {
                 Error::Storage(err.to_string())
             }
@@ -48,6 +72,37 @@ impl Adapter for PostgresAdapter {
         Ok(())
     }
 
+    async fn insert_object_or_ignore(&self, record: ObjectRecord) -> Result<bool, Error> {
+        let ObjectRecord {
+            id,
+            type_name,
+            owner,
+            created_at,
+            updated_at,
+            data,
+            index_meta,
+        } = record;
+        let inserted: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            INSERT INTO public.objects (id, type, owner, created_at, updated_at, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (id) DO NOTHING
+            RETURNING id
+            "#,
+        )
+        .bind(id)
+        .bind(type_name.as_ref())
+        .bind(owner)
+        .bind(created_at)
+        .bind(updated_at)
+        .bind(data)
+        .bind(index_meta)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(inserted.is_some())
+    }
+
     async fn fetch_object(
         &self,
         type_name: &'static str,
@@ -66,12 +121,44 @@ impl Adapter for PostgresAdapter {
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
+        match row {
+            Some(r) => Self::map_row_to_object_record_slim(r).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn fetch_object_any_type(&self, id: Uuid) -> Result<Option<ObjectRecord>, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
         match row {
             Some(r) => Self::map_row_to_object_record_slim(r).map(|o| Some(o)),
             None => Ok(None),
         }
     }
 
+    async fn fetch_object_owner(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Option<Uuid>, Error> {
+        sqlx::query_scalar::<_, Uuid>("SELECT owner FROM objects WHERE id = $1 AND type = $2")
+            .bind(id)
+            .bind(type_name)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+
     async fn fetch_bulk_objects(
         &self,
         type_name: &'static str,
@@ -114,6 +201,37 @@ impl Adapter for PostgresAdapter {
         Ok(())
     }
 
+    async fn update_object_partial(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+        data_patch: serde_json::Value,
+        index_meta_patch: serde_json::Value,
+        index_meta_removed: Vec<String>,
+        updated_at: DateTime<Utc>,
+    ) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE objects
+            SET updated_at = $3,
+                data = data || $4,
+                index_meta = (index_meta || $5) - $6::text[]
+            WHERE id = $1 AND type = $2
+            "#,
+        )
+        .bind(id)
+        .bind(type_name)
+        .bind(updated_at)
+        .bind(data_patch)
+        .bind(index_meta_patch)
+        .bind(index_meta_removed)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
     async fn transfer_object(
         &self,
         type_name: &'static str,
@@ -144,6 +262,122 @@ impl Adapter for PostgresAdapter {
         Self::map_row_to_object_record_slim(row)
     }
 
+    async fn swap_owners(
+        &self,
+        type_name: &'static str,
+        id_a: Uuid,
+        owner_a: Uuid,
+        id_b: Uuid,
+        owner_b: Uuid,
+    ) -> Result<(ObjectRecord, ObjectRecord), Error> {
+        // Dropping the transaction on an early return rolls back the first move
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let now = Utc::now();
+        let mut records = Vec::with_capacity(2);
+        for (id, from_owner, to_owner) in [(id_a, owner_a, owner_b), (id_b, owner_b, owner_a)] {
+            let row = sqlx::query(
+                r#"
+                UPDATE objects
+                SET updated_at = $3, owner = $4
+                WHERE id = $1 AND owner = $2 AND type = $5
+                RETURNING id, type, owner, created_at, updated_at, data
+                "#,
+            )
+            .bind(id)
+            .bind(from_owner)
+            .bind(now)
+            .bind(to_owner)
+            .bind(type_name)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|err| match err {
+                sqlx::Error::RowNotFound => Error::NotFound,
+                _ => Error::Storage(err.to_string()),
+            })?;
+            records.push(Self::map_row_to_object_record_slim(row)?);
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let b = records.pop().ok_or(Error::NotFound)?;
+        let a = records.pop().ok_or(Error::NotFound)?;
+        Ok((a, b))
+    }
+
+    async fn retype_object(
+        &self,
+        old_type: &'static str,
+        record: ObjectRecord,
+        hashes: Vec<(String, &str)>,
+    ) -> Result<(), Error> {
+        // Dropping the transaction on an early return keeps the old row and hashes
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE objects
+            SET type = $3, updated_at = $4, data = $5, index_meta = $6
+            WHERE id = $1 AND type = $2
+            "#,
+        )
+        .bind(record.id)
+        .bind(old_type)
+        .bind(record.type_name.as_ref())
+        .bind(record.updated_at)
+        .bind(&record.data)
+        .bind(&record.index_meta)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        if result.rows_affected() == 0 {
+            return Err(Error::NotFound);
+        }
+
+        sqlx::query("DELETE FROM unique_constraints WHERE id = $1")
+            .bind(record.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        for (hash, field) in hashes {
+            sqlx::query(
+                r#"
+                INSERT INTO unique_constraints (id, type, key, field)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(record.id)
+            .bind(record.type_name.as_ref())
+            .bind(hash)
+            .bind(field)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| {
+                let msg = err.to_string();
+                if msg.contains("unique constraint") || msg.contains("duplicate") {
+                    Error::unique_violation(field)
+                } else {
+                    Error::Storage(msg)
+                }
+            })?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(())
+    }
+
     async fn delete_object(
         &self,
         type_name: &'static str,
@@ -170,6 +404,17 @@ impl Adapter for PostgresAdapter {
         }
     }
 
+    async fn touch_objects(&self, type_name: &'static str, ids: Vec<Uuid>) -> Result<u64, Error> {
+        let result =
+            sqlx::query("UPDATE objects SET updated_at = now() WHERE id = ANY($1) AND type = $2")
+                .bind(ids)
+                .bind(type_name)
+                .execute(&self.pool)
+                .await
+                .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
     async fn delete_bulk_objects(
         &self,
         type_name: &'static str,
@@ -202,6 +447,31 @@ impl Adapter for PostgresAdapter {
         Ok(result.rows_affected())
     }
 
+    async fn delete_owned_objects_with_hashes(
+        &self,
+        type_name: &'static str,
+        owner: Uuid,
+    ) -> Result<u64, Error> {
+        let deleted = sqlx::query_scalar::<_, i64>(
+            r#"
+            WITH deleted AS (
+                DELETE FROM objects WHERE type = $1 AND owner = $2 RETURNING id
+            ), released AS (
+                DELETE FROM unique_constraints
+                WHERE type = $1 AND id IN (SELECT id FROM deleted)
+            )
+            SELECT COUNT(*) FROM deleted
+            "#,
+        )
+        .bind(type_name)
+        .bind(owner)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(deleted as u64)
+    }
+
     async fn find_object(
         &self,
         type_name: &'static str,
@@ -239,44 +509,65 @@ impl Adapter for PostgresAdapter {
         type_name: &'static str,
         plan: Query,
     ) -> Result<Vec<ObjectRecord>, Error> {
-        let mut where_clause = Self::build_object_query_conditions(&plan.filters, plan.cursor);
-        let order_clause = Self::build_order_clause(&plan.filters, false);
+        let sql = Self::object_query_sql(&plan);
+        let query = Self::bind_object_query(sqlx::query(&sql), type_name, &plan);
+
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| Self::map_row_to_object_record_slim(row).ok())
+            .collect())
+    }
 
-        if plan.owner.is_nil() {
+    async fn object_search(
+        &self,
+        type_name: &'static str,
+        text: &str,
+        plan: Query,
+    ) -> Result<Vec<(ObjectRecord, f64)>, Error> {
+        let (mut where_clause, text_idx) =
+            Self::build_object_query_conditions_numbered(&plan.filters, plan.cursor);
+        if plan.is_global() {
             where_clause = where_clause.replace("owner = ", "owner > ");
         }
+        let order_clause = if plan.filters.iter().any(|f| f.mode.as_sort().is_some()) {
+            Self::build_order_clause_aliased(&plan.filters, "o", false)
+        } else {
+            "ORDER BY score DESC, o.id DESC".to_string()
+        };
 
         let mut sql = format!(
             r#"
-                SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-                FROM objects o
-                {}
-                {}
-                "#,
-            where_clause, order_clause
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data,
+                ts_rank(to_tsvector('english', o.data::text), search)::float8 AS score
+            FROM objects o, plainto_tsquery('english', ${text_idx}) AS search
+            {where_clause}
+                AND to_tsvector('english', o.data::text) @@ search
+            {order_clause}
+            "#
         );
-
-        if let Some(limit) = plan.limit {
+        if let Some(limit) = plan.effective_limit() {
             sql.push_str(&format!(" LIMIT {}", limit));
         }
 
-        let mut query = sqlx::query(&sql).bind(type_name).bind(plan.owner);
-
-        if let Some(cursor) = plan.cursor {
-            query = query.bind(cursor.last_id);
-        }
-
-        query = Self::query_bind_filters(query, &plan.filters);
-
-        let rows = query
+        let rows = Self::bind_object_query(sqlx::query(&sql), type_name, &plan)
+            .bind(text)
             .fetch_all(&self.pool)
             .await
             .map_err(|err| Error::Storage(err.to_string()))?;
 
-        Ok(rows
-            .into_iter()
-            .filter_map(|row| Self::map_row_to_object_record_slim(row).ok())
-            .collect())
+        rows.into_iter()
+            .map(|row| {
+                let score: f64 = row
+                    .try_get("score")
+                    .map_err(|e| Error::Deserialize(e.to_string()))?;
+                Ok((Self::map_row_to_object_record_slim(row)?, score))
+            })
+            .collect()
     }
 
     async fn count_objects(
@@ -286,7 +577,11 @@ impl Adapter for PostgresAdapter {
     ) -> Result<u64, Error> {
         match plan {
             Some(plan) => {
-                let where_clause = Self::build_object_query_conditions(&plan.filters, None);
+                let mut where_clause = Self::build_object_query_conditions(&plan.filters, None);
+
+                if plan.is_global() {
+                    where_clause = where_clause.replace("owner = ", "owner > ");
+                }
 
                 let mut sql = format!(
                     r#"
@@ -302,7 +597,7 @@ impl Adapter for PostgresAdapter {
 
                 let mut query = sqlx::query_scalar::<_, i64>(&sql)
                     .bind(type_name)
-                    .bind(plan.owner);
+                    .bind(plan.owner_param());
 
                 query = Self::query_scalar_bind_filters(query, &plan.filters);
 
@@ -325,83 +620,488 @@ impl Adapter for PostgresAdapter {
         }
     }
 
-    async fn fetch_owned_objects_batch(
+    async fn time_bucket_count(
         &self,
         type_name: &'static str,
-        owner_ids: &[Uuid],
-    ) -> Result<Vec<ObjectRecord>, Error> {
-        let rows = sqlx::query(
+        owner: Option<Uuid>,
+        granularity: Granularity,
+    ) -> Result<Vec<(DateTime<Utc>, u64)>, Error> {
+        let rows: Vec<(DateTime<Utc>, i64)> = sqlx::query_as(
             r#"
-            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-            FROM objects o
-            WHERE type = $1 AND owner = ANY($2)
+            SELECT date_trunc($2, created_at, 'UTC') AS bucket, COUNT(*)
+            FROM objects
+            WHERE type = $1 AND ($3::uuid IS NULL OR owner = $3)
+            GROUP BY bucket
+            ORDER BY bucket
             "#,
         )
         .bind(type_name)
-        .bind(owner_ids)
+        .bind(granularity.as_str())
+        .bind(owner)
         .fetch_all(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
+        Ok(rows
+            .into_iter()
+            .map(|(bucket, count)| (bucket, count as u64))
+            .collect())
+    }
+
+    async fn group_by_field(
+        &self,
+        type_name: &'static str,
+        field: &'static str,
+        plan: Query,
+    ) -> Result<Vec<GroupCount>, Error> {
+        let mut where_clause = Self::build_object_query_conditions(&plan.filters, None);
+        if plan.is_global() {
+            where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+        }
+        let sql = format!(
+            r#"
+            SELECT o.index_meta->'{}' AS val, COUNT(*) AS n FROM objects o
+            {}
+            GROUP BY val
+            ORDER BY n DESC, val
+            "#,
+            field, where_clause
+        );
+        let query = sqlx::query(&sql).bind(type_name).bind(plan.owner_param());
+        let rows = Self::query_bind_filters(query, &plan.filters)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
         rows.into_iter()
-            .map(Self::map_row_to_object_record_slim)
+            .map(|row| {
+                let val: Option<serde_json::Value> = row
+                    .try_get("val")
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let count: i64 = row
+                    .try_get("n")
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let value = serde_json::from_value(val.unwrap_or_default())
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                Ok(GroupCount {
+                    value,
+                    count: count as u64,
+                })
+            })
             .collect()
     }
 
-    async fn fetch_owned_objects(
+    async fn group_count_by_owner(
         &self,
         type_name: &'static str,
-        owner: Uuid,
-    ) -> Result<Vec<ObjectRecord>, Error> {
+        plan: Query,
+    ) -> Result<Vec<(Uuid, u64)>, Error> {
+        let mut where_clause = Self::build_object_query_conditions(&plan.filters, None);
+        if plan.is_global() {
+            where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+        }
+        let sql = format!(
+            "SELECT o.owner, COUNT(*) FROM objects o {} GROUP BY o.owner",
+            where_clause
+        );
+        let query = sqlx::query(&sql).bind(type_name).bind(plan.owner_param());
+        let rows = Self::query_bind_filters(query, &plan.filters)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let owner: Uuid = row
+                    .try_get(0)
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let count: i64 = row
+                    .try_get(1)
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                Ok((owner, count as u64))
+            })
+            .collect()
+    }
+
+    async fn top_owners_by_type(
+        &self,
+        type_name: &'static str,
+        limit: u32,
+    ) -> Result<Vec<(Uuid, u64)>, Error> {
         let rows = sqlx::query(
             r#"
-            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-            FROM objects o
-            WHERE owner = $1 AND type = $2
+            SELECT owner, COUNT(*)
+            FROM objects
+            WHERE type = $1
+            GROUP BY owner
+            ORDER BY COUNT(*) DESC, owner
+            LIMIT $2
             "#,
         )
-        .bind(owner)
         .bind(type_name)
+        .bind(limit as i64)
         .fetch_all(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
         rows.into_iter()
-            .map(Self::map_row_to_object_record_slim)
+            .map(|row| {
+                let owner: Uuid = row
+                    .try_get(0)
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let count: i64 = row
+                    .try_get(1)
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                Ok((owner, count as u64))
+            })
             .collect()
     }
 
-    async fn fetch_owned_object(
+    async fn fetch_object_record(
         &self,
         type_name: &'static str,
-        owner: Uuid,
+        id: Uuid,
     ) -> Result<Option<ObjectRecord>, Error> {
         let row = sqlx::query(
             r#"
-            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-            FROM objects o
-            WHERE owner = $1 AND type = $2
+            SELECT id, type, owner, created_at, updated_at, data, index_meta
+            FROM objects
+            WHERE id = $1 AND type = $2
             "#,
         )
-        .bind(owner)
+        .bind(id)
         .bind(type_name)
         .fetch_optional(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
 
-        match row {
-            Some(r) => Self::map_row_to_object_record_slim(r).map(|o| Some(o)),
-            None => Ok(None),
-        }
+        let index_meta: serde_json::Value = row
+            .try_get("index_meta")
+            .map_err(|err| Error::Deserialize(err.to_string()))?;
+        let mut record = Self::map_row_to_object_record_slim(row)?;
+        record.index_meta = index_meta;
+        Ok(Some(record))
     }
 
-    async fn fetch_union_object(
+    async fn sample_object_records(
         &self,
-        a_type_name: &'static str,
-        b_type_name: &'static str,
-        id: Uuid,
-    ) -> Result<Option<ObjectRecord>, Error> {
-        let row = sqlx::query(
+        type_name: &'static str,
+        limit: u32,
+    ) -> Result<Vec<ObjectRecord>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, type, owner, created_at, updated_at, data, index_meta
+            FROM objects
+            WHERE type = $1
+            ORDER BY RANDOM()
+            LIMIT $2
+            "#,
+        )
+        .bind(type_name)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let index_meta: serde_json::Value = row
+                    .try_get("index_meta")
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let mut record = Self::map_row_to_object_record_slim(row)?;
+                record.index_meta = index_meta;
+                Ok(record)
+            })
+            .collect()
+    }
+
+    async fn insert_history_snapshot(
+        &self,
+        record: ObjectRecord,
+        version: u64,
+    ) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO object_history (id, version, type, owner, created_at, updated_at, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(record.id)
+        .bind(version as i64)
+        .bind(record.type_name.as_ref())
+        .bind(record.owner)
+        .bind(record.created_at)
+        .bind(record.updated_at)
+        .bind(record.data)
+        .bind(record.index_meta)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(())
+    }
+
+    async fn update_object_with_history(
+        &self,
+        type_name: &'static str,
+        record: ObjectRecord,
+        previous: ObjectRecord,
+    ) -> Result<u64, Error> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let updated = sqlx::query(
+            r#"
+            UPDATE objects
+            SET updated_at = $3, data = $4, index_meta = $5
+            WHERE id = $1 AND type = $2
+            "#,
+        )
+        .bind(record.id)
+        .bind(type_name)
+        .bind(record.updated_at)
+        .bind(record.data)
+        .bind(record.index_meta)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        if updated.rows_affected() == 0 {
+            return Ok(0);
+        }
+
+        // Concurrent updates of the object wait on the row lock taken above,
+        // so this reads the versions they committed
+        let version: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(MAX(version), 0) + 1 FROM object_history WHERE id = $1",
+        )
+        .bind(previous.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO object_history (id, version, type, owner, created_at, updated_at, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(previous.id)
+        .bind(version)
+        .bind(previous.type_name.as_ref())
+        .bind(previous.owner)
+        .bind(previous.created_at)
+        .bind(previous.updated_at)
+        .bind(previous.data)
+        .bind(previous.index_meta)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(version as u64)
+    }
+
+    async fn latest_history_version(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<u64, Error> {
+        let version: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(MAX(version), 0) FROM object_history
+            WHERE id = $1 AND type = $2
+            "#,
+        )
+        .bind(id)
+        .bind(type_name)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(version as u64)
+    }
+
+    async fn fetch_history(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Vec<(u64, ObjectRecord)>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, version, type, owner, created_at, updated_at, data
+            FROM object_history
+            WHERE id = $1 AND type = $2
+            ORDER BY version
+            "#,
+        )
+        .bind(id)
+        .bind(type_name)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let version: i64 = row
+                    .try_get("version")
+                    .map_err(|e| Error::Deserialize(e.to_string()))?;
+                Ok((version as u64, Self::map_row_to_object_record_slim(row)?))
+            })
+            .collect()
+    }
+
+    async fn fetch_change_log(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<ChangeLogEntry>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM (
+                SELECT id, object_id, type, operation, old_data, new_data, changed_at, changed_by
+                FROM object_audit_log
+                WHERE object_id = $1 AND type = $2
+                ORDER BY id DESC
+                LIMIT $3
+            ) latest
+            ORDER BY id
+            "#,
+        )
+        .bind(id)
+        .bind(type_name)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(Self::map_row_to_change_log_entry)
+            .collect()
+    }
+
+    async fn fetch_type_change_log(
+        &self,
+        type_name: &'static str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<ChangeLogEntry>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, object_id, type, operation, old_data, new_data, changed_at, changed_by
+            FROM object_audit_log
+            WHERE type = $1 AND changed_at >= $2
+            ORDER BY id
+            "#,
+        )
+        .bind(type_name)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(Self::map_row_to_change_log_entry)
+            .collect()
+    }
+
+    async fn fetch_owned_objects_batch(
+        &self,
+        type_name: &'static str,
+        owner_ids: &[Uuid],
+    ) -> Result<Vec<ObjectRecord>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE type = $1 AND owner = ANY($2)
+            "#,
+        )
+        .bind(type_name)
+        .bind(owner_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(Self::map_row_to_object_record_slim)
+            .collect()
+    }
+
+    async fn list_namespaces(&self, type_name: &'static str) -> Result<Vec<String>, Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT data->>'__namespace'
+            FROM objects
+            WHERE type = $1 AND data ? '__namespace'
+            ORDER BY 1
+            "#,
+        )
+        .bind(type_name)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))
+    }
+
+    async fn fetch_owned_objects(
+        &self,
+        type_name: &'static str,
+        owner: Uuid,
+    ) -> Result<Vec<ObjectRecord>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE owner = $1 AND type = $2
+            "#,
+        )
+        .bind(owner)
+        .bind(type_name)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(Self::map_row_to_object_record_slim)
+            .collect()
+    }
+
+    async fn fetch_owned_object(
+        &self,
+        type_name: &'static str,
+        owner: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE owner = $1 AND type = $2
+            "#,
+        )
+        .bind(owner)
+        .bind(type_name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        match row {
+            Some(r) => Self::map_row_to_object_record_slim(r).map(|o| Some(o)),
+            None => Ok(None),
+        }
+    }
+
+    async fn fetch_union_object(
+        &self,
+        a_type_name: &'static str,
+        b_type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error> {
+        let row = sqlx::query(
             r#"
             SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
             FROM objects o
@@ -497,6 +1197,31 @@ impl Adapter for PostgresAdapter {
             .collect()
     }
 
+    async fn query_union_objects(
+        &self,
+        a_type_name: &'static str,
+        b_type_name: &'static str,
+        plan: Query,
+    ) -> Result<Vec<ObjectRecord>, Error> {
+        let sql = Self::object_query_sql(&plan).replacen("o.type = $1", "o.type = ANY($1)", 1);
+        let mut query = sqlx::query(&sql)
+            .bind(vec![a_type_name, b_type_name])
+            .bind(plan.owner_param());
+        if let Some(cursor) = plan.cursor {
+            query = query.bind(cursor.last_id);
+        }
+        query = Self::query_bind_filters(query, &plan.filters);
+
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(Self::map_row_to_object_record_slim)
+            .collect()
+    }
+
     /* ---------------- EDGES ---------------- */
     async fn insert_edge(&self, record: EdgeRecord) -> Result<(), Error> {
         let EdgeRecord {
@@ -511,19 +1236,182 @@ impl Adapter for PostgresAdapter {
             INSERT INTO edges ("from", "to", type, data, index_meta)
             VALUES ($1, $2, $3, $4, $5)
             ON CONFLICT ("from", type, "to")
-            DO UPDATE SET data = $4, index_meta = $5;
+            DO UPDATE SET data = $4, index_meta = $5;
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(type_name.as_ref())
+        .bind(data)
+        .bind(index_meta)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn insert_edge_or_ignore(&self, record: EdgeRecord) -> Result<bool, Error> {
+        let EdgeRecord {
+            from,
+            to,
+            type_name,
+            data,
+            index_meta,
+        } = record;
+        let inserted: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT ("from", type, "to") DO NOTHING
+            RETURNING "from"
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(type_name.as_ref())
+        .bind(data)
+        .bind(index_meta)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(inserted.is_some())
+    }
+
+    async fn upsert_edge(&self, record: EdgeRecord) -> Result<UpsertOutcome, Error> {
+        let EdgeRecord {
+            from,
+            to,
+            type_name,
+            data,
+            index_meta,
+        } = record;
+        // xmax is 0 only on a freshly inserted row version
+        let created: bool = sqlx::query_scalar(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT ("from", type, "to")
+            DO UPDATE SET data = EXCLUDED.data, index_meta = EXCLUDED.index_meta
+            RETURNING (xmax = 0)
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(type_name.as_ref())
+        .bind(data)
+        .bind(index_meta)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(UpsertOutcome { created })
+    }
+
+    async fn upsert_edges_batch(&self, records: Vec<EdgeRecord>) -> Result<u64, Error> {
+        if records.is_empty() {
+            return Ok(0);
+        }
+
+        let mut froms = Vec::with_capacity(records.len());
+        let mut tos = Vec::with_capacity(records.len());
+        let mut types = Vec::with_capacity(records.len());
+        let mut datas = Vec::with_capacity(records.len());
+        let mut index_metas = Vec::with_capacity(records.len());
+        for record in records {
+            froms.push(record.from);
+            tos.push(record.to);
+            types.push(record.type_name.into_owned());
+            datas.push(record.data);
+            index_metas.push(record.index_meta);
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            SELECT * FROM unnest($1::uuid[], $2::uuid[], $3::text[], $4::jsonb[], $5::jsonb[])
+            ON CONFLICT ("from", type, "to")
+            DO UPDATE SET data = EXCLUDED.data, index_meta = EXCLUDED.index_meta
             "#,
         )
-        .bind(from)
-        .bind(to)
-        .bind(type_name.as_ref())
-        .bind(data)
-        .bind(index_meta)
+        .bind(froms)
+        .bind(tos)
+        .bind(types)
+        .bind(datas)
+        .bind(index_metas)
         .execute(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
-        Ok(())
+        Ok(result.rows_affected())
+    }
+
+    async fn insert_edges_batch(
+        &self,
+        records: Vec<EdgeRecord>,
+        on_conflict: OnConflict,
+    ) -> Result<BatchEdgeResult, Error> {
+        let total = records.len() as u64;
+        if total == 0 {
+            return Ok(BatchEdgeResult::default());
+        }
+
+        let mut froms = Vec::with_capacity(records.len());
+        let mut tos = Vec::with_capacity(records.len());
+        let mut types = Vec::with_capacity(records.len());
+        let mut datas = Vec::with_capacity(records.len());
+        let mut index_metas = Vec::with_capacity(records.len());
+        for record in records {
+            froms.push(record.from);
+            tos.push(record.to);
+            types.push(record.type_name.into_owned());
+            datas.push(record.data);
+            index_metas.push(record.index_meta);
+        }
+
+        let conflict_clause = match on_conflict {
+            OnConflict::Ignore => r#"ON CONFLICT ("from", type, "to") DO NOTHING"#,
+            OnConflict::Replace => {
+                r#"ON CONFLICT ("from", type, "to")
+            DO UPDATE SET data = EXCLUDED.data, index_meta = EXCLUDED.index_meta"#
+            }
+            OnConflict::Error => "",
+        };
+        // xmax is 0 only on a freshly inserted row version
+        let sql = format!(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            SELECT * FROM unnest($1::uuid[], $2::uuid[], $3::text[], $4::jsonb[], $5::jsonb[])
+            {conflict_clause}
+            RETURNING (xmax = 0)
+            "#
+        );
+        let written: Vec<bool> = sqlx::query_scalar(&sql)
+            .bind(froms)
+            .bind(tos)
+            .bind(types)
+            .bind(datas)
+            .bind(index_metas)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| {
+                if err
+                    .as_database_error()
+                    .is_some_and(|e| e.is_unique_violation())
+                {
+                    Error::unique_violation("from+to")
+                } else {
+                    Error::Storage(err.to_string())
+                }
+            })?;
+
+        let created = written.iter().filter(|created| **created).count() as u64;
+        let replaced = written.len() as u64 - created;
+        Ok(BatchEdgeResult {
+            created,
+            replaced,
+            skipped: total - created - replaced,
+        })
     }
 
     async fn update_edge(
@@ -594,6 +1482,21 @@ impl Adapter for PostgresAdapter {
         Ok(())
     }
 
+    async fn delete_edges_for_node(&self, node_id: Uuid) -> Result<u64, Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM edges
+            WHERE "from" = $1 OR "to" = $1
+            "#,
+        )
+        .bind(node_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
     async fn fetch_edge(
         &self,
         type_name: &'static str,
@@ -787,6 +1690,112 @@ impl Adapter for PostgresAdapter {
         }
     }
 
+    async fn aggregate_edge_field(
+        &self,
+        type_name: &'static str,
+        from: Uuid,
+        field: &'static str,
+        agg: Aggregation,
+    ) -> Result<f64, Error> {
+        let sql = format!(
+            r#"SELECT COALESCE({}((index_meta->>$3)::double precision), 0) FROM edges WHERE type = $1 AND "from" = $2"#,
+            agg.as_sql()
+        );
+        sqlx::query_scalar(&sql)
+            .bind(type_name)
+            .bind(from)
+            .bind(field)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+
+    async fn aggregate_reverse_edge_field(
+        &self,
+        type_name: &'static str,
+        to: Uuid,
+        field: &'static str,
+        agg: Aggregation,
+    ) -> Result<f64, Error> {
+        let sql = format!(
+            r#"SELECT COALESCE({}((index_meta->>$3)::double precision), 0) FROM edges WHERE type = $1 AND "to" = $2"#,
+            agg.as_sql()
+        );
+        sqlx::query_scalar(&sql)
+            .bind(type_name)
+            .bind(to)
+            .bind(field)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+
+    async fn aggregate_edge_field_batch(
+        &self,
+        type_name: &'static str,
+        from_ids: &[Uuid],
+        field: &'static str,
+        agg: Aggregation,
+    ) -> Result<Vec<(Uuid, f64)>, Error> {
+        let sql = format!(
+            r#"
+            SELECT "from", COALESCE({}((index_meta->>$3)::double precision), 0)
+            FROM edges
+            WHERE type = $1 AND "from" = ANY($2)
+            GROUP BY "from"
+            "#,
+            agg.as_sql()
+        );
+        sqlx::query_as(&sql)
+            .bind(type_name)
+            .bind(from_ids)
+            .bind(field)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+
+    async fn common_neighbor_ids(
+        &self,
+        type_name: &'static str,
+        a: Uuid,
+        b: Uuid,
+        filters: &[QueryFilter],
+    ) -> Result<Vec<Uuid>, Error> {
+        // Both branches share $1=type, $4+=filters so the filters bind once
+        let mut conditions: Vec<(String, &str)> = Vec::new();
+        let mut param_idx = 4;
+        for f in filters {
+            if let Some((c, op)) = Self::build_filter_condition("e", f, &mut param_idx) {
+                conditions.push((c, op));
+            }
+        }
+        let extra = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("AND ({})", Self::join_conditions(&conditions))
+        };
+
+        let sql = format!(
+            r#"
+            SELECT e."to" FROM edges e WHERE e.type = $1 AND e."from" = $2 {extra}
+            INTERSECT
+            SELECT e."to" FROM edges e WHERE e.type = $1 AND e."from" = $3 {extra}
+            "#,
+        );
+
+        let mut query = sqlx::query_scalar::<_, Uuid>(&sql)
+            .bind(type_name)
+            .bind(a)
+            .bind(b);
+        query = Self::query_scalar_bind_filters(query, filters);
+
+        query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))
+    }
+
     async fn sequence_value(&self, sq: String) -> u64 {
         let val: i64 =
             sqlx::query_scalar("SELECT COALESCE((SELECT value FROM sequences WHERE name = $1), 1)")
@@ -797,7 +1806,7 @@ impl Adapter for PostgresAdapter {
         val as u64
     }
 
-    async fn sequence_next_value(&self, sq: String) -> u64 {
+    async fn try_sequence_next_value(&self, sq: String) -> Result<u64, Error> {
         // Upsert: insert with value=2 on first call, otherwise increment.
         // This matches SQLite semantics: first sequence_value = 1, first next = 2.
         let next_val: i64 = sqlx::query_scalar(
@@ -810,8 +1819,410 @@ impl Adapter for PostgresAdapter {
         .bind(&sq)
         .fetch_one(&self.pool)
         .await
-        .expect("Failed to fetch next sequence value");
-        next_val as u64
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(next_val as u64)
+    }
+
+    async fn sequence_reset(&self, sq: String, to: u64) -> Result<(), Error> {
+        sqlx::query(
+            "INSERT INTO sequences (name, value) VALUES ($1, $2)
+             ON CONFLICT (name) DO UPDATE SET value = EXCLUDED.value",
+        )
+        .bind(&sq)
+        .bind(to as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn sequence_list(&self) -> Result<Vec<(String, u64)>, Error> {
+        let rows: Vec<(String, i64)> =
+            sqlx::query_as("SELECT name, value FROM sequences ORDER BY name")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(rows
+            .into_iter()
+            .map(|(name, value)| (name, value as u64))
+            .collect())
+    }
+
+    async fn vacuum(&self) -> Result<(), Error> {
+        // VACUUM cannot run inside a transaction block; raw_sql sends it as-is
+        sqlx::raw_sql("VACUUM ANALYZE objects, edges, unique_constraints")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn reindex(&self) -> Result<(), Error> {
+        sqlx::raw_sql(
+            "REINDEX TABLE objects; REINDEX TABLE edges; REINDEX TABLE unique_constraints",
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn delete_orphan_unique_constraints(&self) -> Result<u64, Error> {
+        // Object hashes are keyed by object ID, edge hashes by the edge's `from`
+        let result = sqlx::query(
+            r#"
+            DELETE FROM unique_constraints u
+            WHERE NOT EXISTS (SELECT 1 FROM objects o WHERE o.id = u.id AND o.type = u.type)
+              AND NOT EXISTS (SELECT 1 FROM edges e WHERE e."from" = u.id AND e.type = u.type)
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn set_retention_policy(&self, type_name: &str, ttl_seconds: u64) -> Result<(), Error> {
+        sqlx::query(
+            "INSERT INTO retention_policies (type, ttl_seconds) VALUES ($1, $2)
+             ON CONFLICT (type) DO UPDATE SET ttl_seconds = EXCLUDED.ttl_seconds",
+        )
+        .bind(type_name)
+        .bind(ttl_seconds as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn retention_policies(&self) -> Result<Vec<(String, u64)>, Error> {
+        let rows: Vec<(String, i64)> =
+            sqlx::query_as("SELECT type, ttl_seconds FROM retention_policies ORDER BY type")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(rows
+            .into_iter()
+            .map(|(type_name, ttl)| (type_name, ttl as u64))
+            .collect())
+    }
+
+    async fn delete_objects_created_before(
+        &self,
+        type_name: &str,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<Uuid>, Error> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+        let ids: Vec<Uuid> = sqlx::query_scalar(
+            "DELETE FROM objects WHERE type = $1 AND created_at < $2 RETURNING id",
+        )
+        .bind(type_name)
+        .bind(cutoff)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        if !ids.is_empty() {
+            sqlx::query("DELETE FROM unique_constraints WHERE type = $1 AND id = ANY($2)")
+                .bind(type_name)
+                .bind(&ids)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::Storage(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(ids)
+    }
+
+    async fn table_stats(&self) -> Result<TableStats, Error> {
+        // Exact counts; pg_stat_user_tables.n_live_tup lags behind the stats collector
+        let (objects, edges, uniques): (i64, i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM objects),
+                (SELECT COUNT(*) FROM edges),
+                (SELECT COUNT(*) FROM unique_constraints)
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        let size: Option<i64> = sqlx::query_scalar(
+            r#"
+            SELECT SUM(pg_total_relation_size(relid))::BIGINT
+            FROM pg_catalog.pg_stat_user_tables
+            WHERE schemaname = 'public'
+              AND relname IN ('objects', 'edges', 'unique_constraints')
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(TableStats {
+            objects_count: objects as u64,
+            edges_count: edges as u64,
+            unique_constraints_count: uniques as u64,
+            estimated_size_bytes: size.map(|s| s as u64),
+        })
+    }
+
+    async fn export_schema(&self) -> Result<SchemaExport, Error> {
+        // Postgres keeps no CREATE TABLE text; rebuild it from the catalog
+        let rows: Vec<(String, String, String, bool, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT table_name::TEXT, column_name::TEXT, data_type::TEXT,
+                   is_nullable = 'YES', column_default::TEXT
+            FROM information_schema.columns
+            WHERE table_schema = 'public' AND table_name = ANY($1)
+            ORDER BY array_position($1, table_name::TEXT), ordinal_position
+            "#,
+        )
+        .bind(&["objects", "edges", "sequences"][..])
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        let columns: Vec<SchemaColumn> = rows
+            .into_iter()
+            .map(|(table, name, data_type, nullable, default)| SchemaColumn {
+                table,
+                name,
+                data_type,
+                nullable,
+                default,
+            })
+            .collect();
+
+        let indexes_ddl: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT indexdef FROM pg_indexes
+            WHERE schemaname = 'public' AND tablename = ANY($1)
+            ORDER BY indexname
+            "#,
+        )
+        .bind(SCHEMA_TABLES)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(SchemaExport {
+            objects_ddl: table_ddl("objects", &columns),
+            edges_ddl: table_ddl("edges", &columns),
+            indexes_ddl,
+            sequences_ddl: table_ddl("sequences", &columns),
+            columns,
+        })
+    }
+
+    async fn verify_schema(&self) -> Result<SchemaVerification, Error> {
+        let tables: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT table_name::TEXT FROM information_schema.tables
+            WHERE table_schema = 'public' AND table_name = ANY($1)
+            "#,
+        )
+        .bind(SCHEMA_TABLES)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        // Primary keys and UNIQUE constraints come with the tables
+        let indexes: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT c.relname::TEXT
+            FROM pg_index i
+            JOIN pg_class c ON c.oid = i.indexrelid
+            JOIN pg_class t ON t.oid = i.indrelid
+            JOIN pg_namespace n ON n.oid = t.relnamespace
+            WHERE n.nspname = 'public' AND t.relname = ANY($1)
+              AND NOT i.indisprimary
+              AND NOT EXISTS (SELECT 1 FROM pg_constraint k WHERE k.conindid = i.indexrelid)
+            ORDER BY c.relname
+            "#,
+        )
+        .bind(SCHEMA_TABLES)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(SchemaVerification::compare(
+            &tables,
+            &indexes,
+            SCHEMA_TABLES,
+            SCHEMA_INDEXES,
+        ))
+    }
+
+    async fn rename_edge_type(&self, old_type: &str, new_type: &str) -> Result<u64, Error> {
+        // UPDATE has no ON CONFLICT; leave colliding pairs under the old type
+        let result = sqlx::query(
+            r#"
+            UPDATE edges SET type = $2
+            WHERE type = $1
+              AND NOT EXISTS (
+                  SELECT 1 FROM edges n
+                  WHERE n.type = $2 AND n."from" = edges."from" AND n."to" = edges."to"
+              )
+            "#,
+        )
+        .bind(old_type)
+        .bind(new_type)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn copy_edge_type(&self, old_type: &str, new_type: &str) -> Result<u64, Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            SELECT "from", "to", $2, data, index_meta FROM edges WHERE type = $1
+            ON CONFLICT ("from", type, "to") DO NOTHING
+            "#,
+        )
+        .bind(old_type)
+        .bind(new_type)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn count_edges_by_type(&self, type_name: &str) -> Result<u64, Error> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM edges WHERE type = $1")
+            .bind(type_name)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(count as u64)
+    }
+
+    async fn type_stats(&self) -> Result<Vec<TypeStats>, Error> {
+        let rows: Vec<(String, i64, DateTime<Utc>)> = sqlx::query_as(
+            r#"
+            SELECT type, COUNT(*), MAX(created_at)
+            FROM objects
+            GROUP BY type
+            ORDER BY type
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(type_name, count, latest_created_at)| TypeStats {
+                type_name,
+                count: count as u64,
+                latest_created_at,
+            })
+            .collect())
+    }
+
+    async fn explain_query(&self, type_name: &'static str, plan: Query) -> Result<String, Error> {
+        self.explain_analyze_query(type_name, &plan).await
+    }
+
+    async fn estimate_query_cost(
+        &self,
+        type_name: &'static str,
+        plan: Query,
+    ) -> Result<QueryCost, Error> {
+        PostgresAdapter::estimate_query_cost(self, type_name, &plan).await
+    }
+
+    async fn estimate_edge_query_cost(
+        &self,
+        type_name: &'static str,
+        owner: Uuid,
+        plan: EdgeQuery,
+    ) -> Result<QueryCost, Error> {
+        PostgresAdapter::estimate_edge_query_cost(self, type_name, owner, &plan).await
+    }
+
+    #[cfg(feature = "raw-queries")]
+    async fn execute_raw(
+        &self,
+        sql: &str,
+        params: Vec<serde_json::Value>,
+    ) -> Result<Vec<serde_json::Value>, Error> {
+        let rows = Self::bind_raw_params(sqlx::query(sql), params)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.iter().map(Self::map_row_to_json).collect()
+    }
+
+    async fn begin(&self) -> Result<DatabaseTransaction, Error> {
+        let tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(DatabaseTransaction::new(PgTransaction::new(tx)))
+    }
+
+    async fn lock_object(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+        timeout: Duration,
+    ) -> Result<Box<dyn LockGuard>, Error> {
+        // Transaction-scoped advisory lock: released when the guard's
+        // transaction is rolled back on drop
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+        sqlx::query("SELECT set_config('lock_timeout', $1, true)")
+            .bind(format!("{}ms", timeout.as_millis().max(1)))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1 || ':' || $2::text, 0))")
+            .bind(type_name)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_lock_error)?;
+
+        Ok(Box::new(PgObjectLock { _tx: tx }))
+    }
+
+    #[cfg(feature = "watch")]
+    async fn change_stream(&self) -> Result<Option<crate::watch::ChangeStream>, Error> {
+        use futures::StreamExt;
+
+        let mut listener = sqlx::postgres::PgListener::connect_with(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        listener
+            .listen(crate::watch::WATCH_CHANNEL)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+        let stream = listener.into_stream().map(|notification| {
+            let notification = notification.map_err(|e| Error::Storage(e.to_string()))?;
+            serde_json::from_str::<crate::watch::ChangeEvent>(notification.payload())
+                .map_err(|e| Error::Deserialize(e.to_string()))
+        });
+
+        Ok(Some(Box::pin(stream)))
     }
 
     #[cfg(feature = "ledger")]
@@ -7,7 +7,8 @@ use sqlx::{
 use uuid::Uuid;
 
 use crate::{
-    adapters::{EdgeQuery, EdgeRecord, Error, ObjectRecord, TraversalDirection},
+    ChangeLogEntry, ChangeOperation,
+    adapters::{EdgeQuery, EdgeRecord, Error, ObjectRecord, Query, TraversalDirection},
     query::{Cursor, IndexValue, IndexValueInner, QueryFilter},
 };
 
@@ -43,6 +44,21 @@ impl PostgresAdapter {
         })
     }
 
+    pub(super) fn map_row_to_change_log_entry(row: PgRow) -> Result<ChangeLogEntry, Error> {
+        let de = |e: sqlx::Error| Error::Deserialize(e.to_string());
+        let operation: String = row.try_get("operation").map_err(de)?;
+        Ok(ChangeLogEntry {
+            id: row.try_get("id").map_err(de)?,
+            object_id: row.try_get("object_id").map_err(de)?,
+            type_name: row.try_get("type").map_err(de)?,
+            operation: ChangeOperation::parse(&operation)?,
+            old_data: row.try_get("old_data").map_err(de)?,
+            new_data: row.try_get("new_data").map_err(de)?,
+            changed_at: row.try_get("changed_at").map_err(de)?,
+            changed_by: row.try_get("changed_by").map_err(de)?,
+        })
+    }
+
     pub(super) fn map_row_to_edge_record(row: PgRow) -> Result<EdgeRecord, Error> {
         let type_name = row
             .try_get::<String, _>("type")
@@ -102,7 +118,7 @@ impl PostgresAdapter {
         direction: TraversalDirection,
     ) -> Result<Vec<(EdgeRecord, ObjectRecord)>, Error> {
         let where_clause = Self::build_object_traversal_query_conditions(
-            direction.clone(),
+            direction,
             obj_filters,
             &plan.filters,
             plan.cursor,
@@ -193,6 +209,51 @@ impl PostgresAdapter {
 
         use crate::query::Comparison::*;
 
+        // Labels live in data, covered by the GIN index on (data->'__labels')
+        if let Some((_, value)) = filter.as_label() {
+            let op = if value.is_some() { "@>" } else { "?" };
+            let cond = format!("{}.data->'__labels' {} ${}", alias, op, param_idx);
+            *param_idx += 1;
+            return Some((cond, operator));
+        }
+
+        if filter.as_namespace().is_some() {
+            let cond = format!("{}.data->>'__namespace' = ${}", alias, param_idx);
+            *param_idx += 1;
+            return Some((cond, operator));
+        }
+
+        // JSON null and a missing key both read as SQL NULL through ->>
+        if let Some(is_null) = filter.as_null_check() {
+            let check = if is_null { "IS NULL" } else { "IS NOT NULL" };
+            let cond = format!("{}.index_meta->>'{}' {}", alias, filter.field.name, check);
+            return Some((cond, operator));
+        }
+
+        // Meta timestamps are native columns covered by the (type, owner, *_at) B-tree indexes
+        if alias == "o"
+            && matches!(filter.field.name, "created_at" | "updated_at")
+            && matches!(filter.value, IndexValue::Timestamp(_))
+        {
+            let comparison = match qs.comparison {
+                Equal => Some("="),
+                NotEqual => Some("<>"),
+                GreaterThan => Some(">"),
+                LessThan => Some("<"),
+                GreaterThanOrEqual => Some(">="),
+                LessThanOrEqual => Some("<="),
+                BeginsWith | Contains | ContainsAll | IsNull | IsNotNull | Regex => None,
+            };
+            if let Some(comparison) = comparison {
+                let cond = format!(
+                    "{}.{} {} ${}",
+                    alias, filter.field.name, comparison, param_idx
+                );
+                *param_idx += 1;
+                return Some((cond, operator));
+            }
+        }
+
         // GIN jsonb_path_ops @> path: hits the index for equality and array containment
         match (&qs.comparison, &filter.value) {
             // Scalar equality for types with safe JSON value semantics
@@ -245,6 +306,9 @@ impl PostgresAdapter {
             BeginsWith => "ILIKE",
             Contains => "ILIKE",
             ContainsAll => "ILIKE",
+            Regex if qs.case_sensitive => "~",
+            Regex => "~*",
+            IsNull | IsNotNull => unreachable!("null checks handled above"),
         };
 
         let condition = format!(
@@ -277,6 +341,7 @@ impl PostgresAdapter {
             IndexValue::Bool(_) => "boolean",
             IndexValue::Timestamp(_) => "timestamptz",
             IndexValue::Uuid(_) => "uuid",
+            IndexValue::Null => "text",
             IndexValue::Array(arr) => match arr.first() {
                 Some(IndexValueInner::String(_)) => "text[]",
                 Some(IndexValueInner::Int(_)) => "bigint[]",
@@ -290,6 +355,15 @@ impl PostgresAdapter {
         filters: &[QueryFilter],
         cursor: Option<Cursor>,
     ) -> String {
+        Self::build_object_query_conditions_numbered(filters, cursor).0
+    }
+
+    /// [`Self::build_object_query_conditions`] plus the number of the first
+    /// placeholder left free for the caller's own parameters
+    pub(super) fn build_object_query_conditions_numbered(
+        filters: &[QueryFilter],
+        cursor: Option<Cursor>,
+    ) -> (String, usize) {
         // $1 = type, $2 = owner, $3 = cursor (optional), $4+ = filter values
         let mut conditions: Vec<(String, &str)> = vec![
             ("o.type = $1".to_string(), "AND"),
@@ -308,7 +382,10 @@ impl PostgresAdapter {
             }
         }
 
-        format!("WHERE {}", Self::join_conditions(&conditions))
+        (
+            format!("WHERE {}", Self::join_conditions(&conditions)),
+            param_idx,
+        )
     }
 
     pub(super) fn build_edge_query_conditions(
@@ -354,6 +431,22 @@ impl PostgresAdapter {
         Self::build_order_clause_aliased(filters, "e", true)
     }
 
+    /// Edge order for plain edge listings: explicit sorts win, otherwise the
+    /// cursor column descending so `limit` + `cursor` pages are stable
+    pub(super) fn build_edge_listing_order_clause(
+        filters: &[QueryFilter],
+        direction: TraversalDirection,
+    ) -> String {
+        let order_clause = Self::build_edge_order_clause(filters);
+        if !order_clause.is_empty() {
+            return order_clause;
+        }
+        match direction {
+            TraversalDirection::Forward => r#"ORDER BY e."to" DESC"#.to_string(),
+            TraversalDirection::Reverse => r#"ORDER BY e."from" DESC"#.to_string(),
+        }
+    }
+
     pub(super) fn build_order_clause_aliased(
         filters: &[QueryFilter],
         alias: &str,
@@ -390,6 +483,10 @@ impl PostgresAdapter {
                 if matches!(s.field.name, "created_at" | "updated_at") {
                     return format!("{}{} {}", prefix, s.field.name, direction);
                 }
+                // Sort values are placeholders; the sequence is always numeric
+                if s.field.name == "sequence" {
+                    return format!("({}index_meta->>'sequence')::bigint {}", prefix, direction);
+                }
                 let index_type = match &s.value {
                     IndexValue::String(_) => "text",
                     IndexValue::Int(_) => "bigint",
@@ -463,6 +560,21 @@ impl PostgresAdapter {
     ) -> PgQuery<'a, Postgres, PgArguments> {
         use crate::query::Comparison::*;
         for filter in filters.iter().filter(|f| f.mode.as_search().is_some()) {
+            if let Some((key, value)) = filter.as_label() {
+                query = match value {
+                    Some(value) => query.bind(serde_json::json!({ key: value })),
+                    None => query.bind(key.to_string()),
+                };
+                continue;
+            }
+            if let Some(namespace) = filter.as_namespace() {
+                query = query.bind(namespace.to_string());
+                continue;
+            }
+            // IS NULL / IS NOT NULL take no parameter
+            if filter.as_null_check().is_some() {
+                continue;
+            }
             let search = filter.mode.as_search().unwrap();
             match (&search.comparison, &filter.value) {
                 // GIN @> binds: {"field": value}
@@ -520,6 +632,7 @@ impl PostgresAdapter {
                 }
                 // Empty arrays and remaining array cases: condition was skipped, no bind
                 (_, IndexValue::Array(_)) => {}
+                (_, IndexValue::Null) => {}
             }
         }
         query
@@ -531,6 +644,21 @@ impl PostgresAdapter {
     ) -> QueryScalar<'a, Postgres, O, PgArguments> {
         use crate::query::Comparison::*;
         for filter in filters.iter().filter(|f| f.mode.as_search().is_some()) {
+            if let Some((key, value)) = filter.as_label() {
+                query = match value {
+                    Some(value) => query.bind(serde_json::json!({ key: value })),
+                    None => query.bind(key.to_string()),
+                };
+                continue;
+            }
+            if let Some(namespace) = filter.as_namespace() {
+                query = query.bind(namespace.to_string());
+                continue;
+            }
+            // IS NULL / IS NOT NULL take no parameter
+            if filter.as_null_check().is_some() {
+                continue;
+            }
             let search = filter.mode.as_search().unwrap();
             match (&search.comparison, &filter.value) {
                 // GIN @> binds: {"field": value}
@@ -587,6 +715,7 @@ impl PostgresAdapter {
                     query = query.bind(uid);
                 }
                 (_, IndexValue::Array(_)) => {}
+                (_, IndexValue::Null) => {}
             }
         }
         query
@@ -604,7 +733,7 @@ impl PostgresAdapter {
         direction: TraversalDirection,
     ) -> Result<Vec<ObjectRecord>, Error> {
         let where_clause = Self::build_object_traversal_query_conditions(
-            direction.clone(),
+            direction,
             filters,
             &plan.filters,
             plan.cursor,
@@ -782,15 +911,75 @@ impl PostgresAdapter {
         format!("WHERE {}", Self::join_conditions(&conditions))
     }
 
-    pub(super) async fn query_edges_internal(
-        &self,
-        type_name: &'static str,
-        owner: Uuid,
-        plan: EdgeQuery,
-        direction: TraversalDirection,
-    ) -> Result<Vec<EdgeRecord>, Error> {
+    /// SQL of `query_objects`; bind with [`Self::bind_object_query`]
+    pub(super) fn object_query_sql(plan: &Query) -> String {
+        let mut where_clause = Self::build_object_query_conditions(&plan.filters, plan.cursor);
+        let mut order_clause = if plan.sample_size.is_some() {
+            "ORDER BY RANDOM()".to_string()
+        } else {
+            Self::build_order_clause(&plan.filters, false)
+        };
+
+        if plan.is_global() {
+            where_clause = where_clause.replace("owner = ", "owner > ");
+        }
+        if plan.ascending {
+            where_clause = where_clause.replace("o.id < ", "o.id > ");
+            if order_clause == "ORDER BY id DESC" {
+                order_clause = "ORDER BY id ASC".to_string();
+            }
+        }
+
+        // DISTINCT ON needs `id` as its leading ORDER BY column, so dedupe in a
+        // subquery and apply the plan's own order outside it
+        let mut sql = if plan.distinct {
+            format!(
+                r#"
+                SELECT id, type, owner, created_at, updated_at, data
+                FROM (
+                    SELECT DISTINCT ON (o.id) o.*
+                    FROM objects o
+                    {}
+                    ORDER BY o.id
+                ) o
+                {}
+                "#,
+                where_clause, order_clause
+            )
+        } else {
+            format!(
+                r#"
+                SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+                FROM objects o
+                {}
+                {}
+                "#,
+                where_clause, order_clause
+            )
+        };
+
+        if let Some(limit) = plan.effective_limit() {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+        sql
+    }
+
+    pub(super) fn bind_object_query<'a>(
+        mut query: PgQuery<'a, Postgres, PgArguments>,
+        type_name: &'a str,
+        plan: &'a Query,
+    ) -> PgQuery<'a, Postgres, PgArguments> {
+        query = query.bind(type_name).bind(plan.owner_param());
+        if let Some(cursor) = plan.cursor {
+            query = query.bind(cursor.last_id);
+        }
+        Self::query_bind_filters(query, &plan.filters)
+    }
+
+    /// SQL of `query_edges` / `query_reverse_edges`; bind with [`Self::bind_edge_query`]
+    pub(super) fn edge_query_sql(plan: &EdgeQuery, direction: TraversalDirection) -> String {
         let where_clause = Self::build_edge_query_conditions(&plan.filters, plan.cursor, direction);
-        let order_clause = Self::build_edge_order_clause(&plan.filters);
+        let order_clause = Self::build_edge_listing_order_clause(&plan.filters, direction);
 
         let mut sql = format!(
             r#"
@@ -805,13 +994,31 @@ impl PostgresAdapter {
         if let Some(limit) = plan.limit {
             sql.push_str(&format!(" LIMIT {}", limit));
         }
+        sql
+    }
 
-        let mut query = sqlx::query(&sql).bind(type_name).bind(owner);
+    pub(super) fn bind_edge_query<'a>(
+        mut query: PgQuery<'a, Postgres, PgArguments>,
+        type_name: &'a str,
+        owner: Uuid,
+        plan: &'a EdgeQuery,
+    ) -> PgQuery<'a, Postgres, PgArguments> {
+        query = query.bind(type_name).bind(owner);
         if let Some(cursor) = plan.cursor {
             query = query.bind(cursor.last_id);
         }
+        Self::query_bind_filters(query, &plan.filters)
+    }
 
-        query = Self::query_bind_filters(query, &plan.filters);
+    pub(super) async fn query_edges_internal(
+        &self,
+        type_name: &'static str,
+        owner: Uuid,
+        plan: EdgeQuery,
+        direction: TraversalDirection,
+    ) -> Result<Vec<EdgeRecord>, Error> {
+        let sql = Self::edge_query_sql(&plan, direction);
+        let query = Self::bind_edge_query(sqlx::query(&sql), type_name, owner, &plan);
 
         let rows = query
             .fetch_all(&self.pool)
@@ -824,3 +1031,70 @@ impl PostgresAdapter {
             .collect())
     }
 }
+
+#[cfg(feature = "raw-queries")]
+impl PostgresAdapter {
+    /// Bind JSON params by their natural SQL type. Arrays and objects go in as JSONB.
+    pub(super) fn bind_raw_params<'q>(
+        mut query: PgQuery<'q, Postgres, PgArguments>,
+        params: Vec<serde_json::Value>,
+    ) -> PgQuery<'q, Postgres, PgArguments> {
+        use serde_json::Value;
+
+        for param in params {
+            query = match param {
+                Value::Null => query.bind(None::<String>),
+                Value::Bool(b) => query.bind(b),
+                Value::Number(n) => match n.as_i64() {
+                    Some(i) => query.bind(i),
+                    None => query.bind(n.as_f64()),
+                },
+                Value::String(s) => query.bind(s),
+                other => query.bind(other),
+            };
+        }
+        query
+    }
+
+    /// Row as a JSON object keyed by column name
+    pub(super) fn map_row_to_json(row: &PgRow) -> Result<serde_json::Value, Error> {
+        use chrono::{DateTime, NaiveDateTime, Utc};
+        use serde_json::Value;
+        use sqlx::{Column, TypeInfo, ValueRef};
+
+        let mut out = serde_json::Map::new();
+        for column in row.columns() {
+            let i = column.ordinal();
+            let is_null = row
+                .try_get_raw(i)
+                .map_err(|e| Error::Deserialize(e.to_string()))?
+                .is_null();
+            let value = if is_null {
+                Value::Null
+            } else {
+                match column.type_info().name() {
+                    "BOOL" => row.try_get::<bool, _>(i).map(Value::from),
+                    "INT2" => row.try_get::<i16, _>(i).map(Value::from),
+                    "INT4" => row.try_get::<i32, _>(i).map(Value::from),
+                    "INT8" => row.try_get::<i64, _>(i).map(Value::from),
+                    "FLOAT4" => row.try_get::<f32, _>(i).map(Value::from),
+                    "FLOAT8" => row.try_get::<f64, _>(i).map(Value::from),
+                    "JSON" | "JSONB" => row.try_get::<Value, _>(i),
+                    "UUID" => row
+                        .try_get::<Uuid, _>(i)
+                        .map(|v| Value::from(v.to_string())),
+                    "TIMESTAMPTZ" => row
+                        .try_get::<DateTime<Utc>, _>(i)
+                        .map(|v| Value::from(v.to_rfc3339())),
+                    "TIMESTAMP" => row
+                        .try_get::<NaiveDateTime, _>(i)
+                        .map(|v| Value::from(v.to_string())),
+                    _ => row.try_get::<String, _>(i).map(Value::from),
+                }
+                .map_err(|e| Error::Deserialize(e.to_string()))?
+            };
+            out.insert(column.name().to_string(), value);
+        }
+        Ok(Value::Object(out))
+    }
+}
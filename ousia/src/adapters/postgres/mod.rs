@@ -1,14 +1,51 @@
 mod adapter_impl;
 mod helper;
+mod transaction_impl;
 mod traversal_impl;
 mod unique_impl;
 
 #[cfg(feature = "ledger")]
 mod ledger_impl;
 
-use sqlx::PgPool;
+use std::{str::FromStr, time::Duration};
 
-use crate::adapters::Error;
+use sqlx::{
+    PgPool, Row,
+    postgres::{PgConnectOptions, PgPoolOptions, PgRow},
+};
+use uuid::Uuid;
+
+use crate::{
+    adapters::{Error, Query, QueryCost, TraversalDirection},
+    edge::query::EdgeQuery,
+};
+
+/// Pool settings for [`PostgresAdapter::connect`]
+#[derive(Debug, Clone)]
+pub struct PostgresAdapterConfig {
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout_secs: u64,
+    pub idle_timeout_secs: u64,
+    pub max_lifetime_secs: u64,
+    pub statement_cache_capacity: usize,
+    /// Open every session with `default_transaction_read_only = on`
+    pub read_only: bool,
+}
+
+impl Default for PostgresAdapterConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout_secs: 30,
+            idle_timeout_secs: 600,
+            max_lifetime_secs: 1800,
+            statement_cache_capacity: 100,
+            read_only: false,
+        }
+    }
+}
 
 /// PostgreSQL adapter using a unified JSON storage model
 ///
@@ -29,18 +66,288 @@ use crate::adapters::Error;
 /// -- composite date indexes: planner uses these when user sorts by created_at / updated_at
 /// CREATE INDEX idx_objects_type_owner_created ON objects(type, owner, created_at DESC);
 /// CREATE INDEX idx_objects_type_owner_updated ON objects(type, owner, updated_at DESC);
+/// -- creation order per type (Engine::fetch_objects_in_sequence_order)
+/// CREATE INDEX idx_objects_type_sequence ON objects(type, ((index_meta->>'sequence')::bigint));
 /// -- GIN index for index_meta search/filter operations
 /// CREATE INDEX idx_objects_index_meta ON public.objects USING GIN (index_meta);
+/// -- label filters (Query::where_label / where_has_label); default opclass keeps `?`
+/// CREATE INDEX idx_objects_labels ON public.objects USING GIN ((data->'__labels'));
 /// ```
 pub struct PostgresAdapter {
     pub(crate) pool: PgPool,
 }
 
+/// Tables and indexes `init_schema` creates; checked by `verify_schema`
+const SCHEMA_TABLES: &[&str] = &[
+    "objects",
+    "edges",
+    "unique_constraints",
+    "sequences",
+    "retention_policies",
+    "object_history",
+    "object_audit_log",
+];
+const SCHEMA_INDEXES: &[&str] = &[
+    "idx_objects_type_owner",
+    "idx_objects_type_owner_created",
+    "idx_objects_type_owner_updated",
+    "idx_objects_type_sequence",
+    "idx_objects_index_meta",
+    "idx_objects_labels",
+    "idx_edges_key",
+    "idx_edges_from_key",
+    "idx_edges_to_key",
+    "idx_edges_index_meta",
+    "idx_unique_id",
+    "idx_unique_type_key",
+    "idx_object_history_id_version",
+    "idx_object_audit_log_object",
+    "idx_object_audit_log_type_changed",
+];
+
 impl PostgresAdapter {
     pub fn from_pool(pool: PgPool) -> Self {
         Self { pool }
     }
 
+    pub async fn connect(url: &str, config: PostgresAdapterConfig) -> Result<Self, Error> {
+        let mut options = PgConnectOptions::from_str(url)
+            .map_err(|e| Error::Storage(e.to_string()))?
+            .statement_cache_capacity(config.statement_cache_capacity);
+        if config.read_only {
+            options = options.options([("default_transaction_read_only", "on")]);
+        }
+
+        let pool = PgPoolOptions::new()
+            .max_connections(config.max_connections)
+            .min_connections(config.min_connections)
+            .acquire_timeout(Duration::from_secs(config.acquire_timeout_secs))
+            .idle_timeout(Duration::from_secs(config.idle_timeout_secs))
+            .max_lifetime(Duration::from_secs(config.max_lifetime_secs))
+            .connect_with(options)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(Self { pool })
+    }
+
+    /// 20 connections, 30s acquire timeout
+    pub async fn for_production(url: &str) -> Result<Self, Error> {
+        Self::connect(
+            url,
+            PostgresAdapterConfig {
+                max_connections: 20,
+                acquire_timeout_secs: 30,
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// 5 connections
+    pub async fn for_testing(url: &str) -> Result<Self, Error> {
+        Self::connect(
+            url,
+            PostgresAdapterConfig {
+                max_connections: 5,
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// 50 connections, every transaction read-only
+    pub async fn for_read_replica(url: &str) -> Result<Self, Error> {
+        Self::connect(
+            url,
+            PostgresAdapterConfig {
+                max_connections: 50,
+                read_only: true,
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Connections currently open, idle or in use
+    pub fn pool_size(&self) -> u32 {
+        self.pool.size()
+    }
+
+    pub fn idle_connections(&self) -> u32 {
+        self.pool.num_idle() as u32
+    }
+
+    /// `EXPLAIN (ANALYZE, BUFFERS)` of the SQL `query_objects` runs for `plan`.
+    /// Returns only the planner output, one plan line per line.
+    pub async fn explain_analyze_query(
+        &self,
+        type_name: &str,
+        plan: &Query,
+    ) -> Result<String, Error> {
+        let sql = Self::explain_sql(&Self::object_query_sql(plan));
+        let query = Self::bind_object_query(sqlx::query(&sql), type_name, plan);
+        Self::collect_plan(query.fetch_all(&self.pool).await)
+    }
+
+    /// `EXPLAIN (ANALYZE, BUFFERS)` of the SQL `query_edges` runs for `owner` and `plan`
+    pub async fn explain_analyze_edges(
+        &self,
+        type_name: &str,
+        owner: Uuid,
+        plan: &EdgeQuery,
+    ) -> Result<String, Error> {
+        let sql = Self::explain_sql(&Self::edge_query_sql(plan, TraversalDirection::Forward));
+        let query = Self::bind_edge_query(sqlx::query(&sql), type_name, owner, plan);
+        Self::collect_plan(query.fetch_all(&self.pool).await)
+    }
+
+    /// Planner estimate for the SQL `query_objects` runs for `plan`. The `EXPLAIN`
+    /// runs without `ANALYZE` in a read-only transaction, so nothing is executed.
+    pub async fn estimate_query_cost(
+        &self,
+        type_name: &str,
+        plan: &Query,
+    ) -> Result<QueryCost, Error> {
+        let sql = Self::estimate_sql(&Self::object_query_sql(plan));
+        let query = Self::bind_object_query(sqlx::query(&sql), type_name, plan);
+        let mut tx = self.begin_read_only().await?;
+        let row = query
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Self::parse_plan_cost(&row)
+    }
+
+    /// Planner estimate for the SQL `query_edges` runs for `owner` and `plan`
+    pub async fn estimate_edge_query_cost(
+        &self,
+        type_name: &str,
+        owner: Uuid,
+        plan: &EdgeQuery,
+    ) -> Result<QueryCost, Error> {
+        let sql = Self::estimate_sql(&Self::edge_query_sql(plan, TraversalDirection::Forward));
+        let query = Self::bind_edge_query(sqlx::query(&sql), type_name, owner, plan);
+        let mut tx = self.begin_read_only().await?;
+        let row = query
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Self::parse_plan_cost(&row)
+    }
+
+    fn explain_sql(sql: &str) -> String {
+        format!("EXPLAIN (ANALYZE, BUFFERS, FORMAT TEXT) {}", sql)
+    }
+
+    fn estimate_sql(sql: &str) -> String {
+        format!("EXPLAIN (FORMAT JSON) {}", sql)
+    }
+
+    // Rolled back when dropped
+    async fn begin_read_only(&self) -> Result<sqlx::Transaction<'static, sqlx::Postgres>, Error> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        sqlx::query("SET TRANSACTION READ ONLY")
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(tx)
+    }
+
+    // `EXPLAIN (FORMAT JSON)` returns `[{"Plan": {...}}]`; child nodes sit under `Plans`
+    fn parse_plan_cost(row: &PgRow) -> Result<QueryCost, Error> {
+        let explain: serde_json::Value = row
+            .try_get(0)
+            .map_err(|e| Error::Deserialize(e.to_string()))?;
+        let plan = explain
+            .get(0)
+            .and_then(|e| e.get("Plan"))
+            .ok_or_else(|| Error::Deserialize("EXPLAIN output has no plan".to_string()))?;
+
+        let mut node_types = Vec::new();
+        let mut stack = vec![plan];
+        while let Some(node) = stack.pop() {
+            if let Some(node_type) = node.get("Node Type").and_then(|t| t.as_str()) {
+                node_types.push(node_type);
+            }
+            if let Some(children) = node.get("Plans").and_then(|p| p.as_array()) {
+                stack.extend(children);
+            }
+        }
+
+        Ok(QueryCost {
+            estimated_rows: plan
+                .get("Plan Rows")
+                .and_then(|r| r.as_f64())
+                .unwrap_or(0.0) as u64,
+            // Index Scan, Index Only Scan and Bitmap Index Scan
+            uses_index: node_types.iter().any(|t| t.contains("Index")),
+            sequential_scan: node_types.contains(&"Seq Scan"),
+            planner_cost: plan
+                .get("Total Cost")
+                .and_then(|c| c.as_f64())
+                .unwrap_or(0.0),
+        })
+    }
+
+    fn collect_plan(rows: Result<Vec<PgRow>, sqlx::Error>) -> Result<String, Error> {
+        let rows = rows.map_err(|e| Error::Storage(e.to_string()))?;
+        let lines = rows
+            .iter()
+            .map(|row| row.try_get::<String, _>(0))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Error::Deserialize(e.to_string()))?;
+        Ok(lines.join("\n"))
+    }
+
+    /// B-tree index on `(type, (index_meta->>'field')::bigint)` so range filters and
+    /// sorts on an integer index field avoid the GIN index. Call after `init_schema`.
+    /// Every object storing `field_name` must store it as an integer.
+    pub async fn create_numeric_expression_index(&self, field_name: &str) -> Result<(), Error> {
+        self.create_expression_index(field_name, "num", "bigint")
+            .await
+    }
+
+    /// B-tree index on `(type, index_meta->>'field')` for sorting and range filters on a
+    /// text index field. Call after `init_schema`.
+    pub async fn create_text_expression_index(&self, field_name: &str) -> Result<(), Error> {
+        self.create_expression_index(field_name, "text", "text")
+            .await
+    }
+
+    // The expression must match what `build_filter_condition` and `build_order_clause_aliased`
+    // emit, or the planner won't use the index. Queries always filter on `type`, which
+    // leads the index as in `idx_objects_type_sequence`.
+    async fn create_expression_index(
+        &self,
+        field_name: &str,
+        suffix: &str,
+        cast: &str,
+    ) -> Result<(), Error> {
+        if field_name.is_empty()
+            || !field_name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'_')
+        {
+            return Err(Error::InvalidQuery(format!(
+                "invalid index field name: {field_name}"
+            )));
+        }
+        let sql = format!(
+            "CREATE INDEX IF NOT EXISTS idx_objects_{field_name}_{suffix} \
+             ON public.objects(type, ((index_meta->>'{field_name}')::{cast}))"
+        );
+        sqlx::query(&sql)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(())
+    }
+
     /// Initialize the database schema
     pub async fn init_schema(&self) -> Result<(), Error> {
         let mut tx = self
@@ -99,6 +406,16 @@ impl PostgresAdapter {
         .await
         .map_err(|e| Error::Storage(e.to_string()))?;
 
+        sqlx::query(
+            r#"
+            CREATE INDEX IF NOT EXISTS idx_objects_type_sequence
+                ON objects(type, ((index_meta->>'sequence')::bigint));
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
         sqlx::query(
             r#"
             CREATE INDEX IF NOT EXISTS idx_objects_index_meta
@@ -109,6 +426,16 @@ impl PostgresAdapter {
         .await
         .map_err(|e| Error::Storage(e.to_string()))?;
 
+        sqlx::query(
+            r#"
+            CREATE INDEX IF NOT EXISTS idx_objects_labels
+                ON public.objects USING GIN ((data->'__labels'));
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
         sqlx::query(
             r#"
             CREATE TABLE IF NOT EXISTS public.edges (
@@ -208,6 +535,163 @@ impl PostgresAdapter {
         .await
         .map_err(|e| Error::Storage(e.to_string()))?;
 
+        // TTLs applied by Engine::run_retention_policies
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS retention_policies (
+                type TEXT PRIMARY KEY,
+                ttl_seconds BIGINT NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        // Pre-update snapshots of versioned objects; see Engine::object_timeline
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS public.object_history (
+                id uuid NOT NULL,
+                version BIGINT NOT NULL,
+                type TEXT NOT NULL,
+                owner uuid NOT NULL,
+                created_at TIMESTAMPTZ NOT NULL,
+                updated_at TIMESTAMPTZ NOT NULL,
+                data JSONB NOT NULL,
+                index_meta JSONB NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            CREATE UNIQUE INDEX IF NOT EXISTS idx_object_history_id_version
+                ON public.object_history(id, version)
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        // Every write to `objects`, logged by trigger; see Engine::object_change_log
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS public.object_audit_log (
+                id BIGSERIAL PRIMARY KEY,
+                object_id uuid NOT NULL,
+                type TEXT NOT NULL,
+                operation TEXT NOT NULL CHECK (operation IN ('create', 'update', 'delete')),
+                old_data JSONB,
+                new_data JSONB,
+                changed_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                changed_by uuid NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        for index in [
+            "CREATE INDEX IF NOT EXISTS idx_object_audit_log_object ON public.object_audit_log(object_id, id)",
+            "CREATE INDEX IF NOT EXISTS idx_object_audit_log_type_changed ON public.object_audit_log(type, changed_at)",
+        ] {
+            sqlx::query(index)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::Storage(e.to_string()))?;
+        }
+
+        sqlx::query(
+            r#"
+            CREATE OR REPLACE FUNCTION log_object_change() RETURNS trigger AS $$
+            BEGIN
+                IF TG_OP = 'INSERT' THEN
+                    INSERT INTO public.object_audit_log
+                        (object_id, type, operation, old_data, new_data, changed_by)
+                    VALUES (NEW.id, NEW.type, 'create', NULL, NEW.data, NEW.owner);
+                ELSIF TG_OP = 'UPDATE' THEN
+                    INSERT INTO public.object_audit_log
+                        (object_id, type, operation, old_data, new_data, changed_by)
+                    VALUES (NEW.id, NEW.type, 'update', OLD.data, NEW.data, NEW.owner);
+                ELSE
+                    INSERT INTO public.object_audit_log
+                        (object_id, type, operation, old_data, new_data, changed_by)
+                    VALUES (OLD.id, OLD.type, 'delete', OLD.data, NULL, OLD.owner);
+                END IF;
+                RETURN NULL;
+            END;
+            $$ LANGUAGE plpgsql
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        sqlx::query("DROP TRIGGER IF EXISTS ousia_objects_audit ON public.objects")
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            CREATE TRIGGER ousia_objects_audit
+            AFTER INSERT OR UPDATE OR DELETE ON public.objects
+            FOR EACH ROW EXECUTE PROCEDURE log_object_change()
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        // Change notifications for Engine::watch_object / watch_type
+        #[cfg(feature = "watch")]
+        {
+            sqlx::query(
+                r#"
+                CREATE OR REPLACE FUNCTION ousia_notify_object_change() RETURNS trigger AS $$
+                DECLARE
+                    rec RECORD;
+                BEGIN
+                    IF TG_OP = 'DELETE' THEN
+                        rec := OLD;
+                    ELSE
+                        rec := NEW;
+                    END IF;
+                    PERFORM pg_notify(
+                        'ousia_changes',
+                        json_build_object('type', rec.type, 'id', rec.id, 'op', lower(TG_OP))::text
+                    );
+                    RETURN NULL;
+                END;
+                $$ LANGUAGE plpgsql
+                "#,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+            sqlx::query("DROP TRIGGER IF EXISTS ousia_objects_notify ON public.objects")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::Storage(e.to_string()))?;
+
+            sqlx::query(
+                r#"
+                CREATE TRIGGER ousia_objects_notify
+                AFTER INSERT OR UPDATE OR DELETE ON public.objects
+                FOR EACH ROW EXECUTE FUNCTION ousia_notify_object_change()
+                "#,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        }
+
         tx.commit()
             .await
             .map_err(|e| Error::Storage(e.to_string()))?;
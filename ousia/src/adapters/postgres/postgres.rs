@@ -340,7 +340,7 @@ impl PostgresAdapter {
         direction: TraversalDirection,
     ) -> Result<Vec<(EdgeRecord, ObjectRecord)>, Error> {
         let where_clause = Self::build_object_traversal_query_conditions(
-            direction.clone(),
+            direction,
             obj_filters,
             &plan.filters,
             plan.cursor,
@@ -834,7 +834,7 @@ impl PostgresAdapter {
         direction: TraversalDirection,
     ) -> Result<Vec<ObjectRecord>, Error> {
         let where_clause = Self::build_object_traversal_query_conditions(
-            direction.clone(),
+            direction,
             filters,
             &plan.filters,
             plan.cursor,
@@ -0,0 +1,204 @@
+use sqlx::{Postgres, Transaction};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use super::PostgresAdapter;
+use crate::adapters::{EdgeRecord, Error, ObjectRecord, TransactionBackend, transaction::finished};
+
+/// Holds the open transaction; `None` once committed or rolled back
+pub(super) struct PgTransaction {
+    tx: Mutex<Option<Transaction<'static, Postgres>>>,
+}
+
+impl PgTransaction {
+    pub(super) fn new(tx: Transaction<'static, Postgres>) -> Self {
+        Self {
+            tx: Mutex::new(Some(tx)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TransactionBackend for PgTransaction {
+    async fn insert_object(&self, record: ObjectRecord) -> Result<(), Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
+        let ObjectRecord {
+            id,
+            type_name,
+            owner,
+            created_at,
+            updated_at,
+            data,
+            index_meta,
+        } = record;
+        sqlx::query(
+            r#"
+            INSERT INTO public.objects (id, type, owner, created_at, updated_at, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(id)
+        .bind(type_name.as_ref())
+        .bind(owner)
+        .bind(created_at)
+        .bind(updated_at)
+        .bind(data)
+        .bind(index_meta)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| {
+            if err.to_string().contains("unique") {
+                Error::unique_violation("id")
+            } else {
+                Error::Storage(err.to_string())
+            }
+        })?;
+        Ok(())
+    }
+
+    async fn fetch_object(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE id = $1 AND type = $2
+            "#,
+        )
+        .bind(id)
+        .bind(type_name)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        row.map(PostgresAdapter::map_row_to_object_record_slim)
+            .transpose()
+    }
+
+    async fn update_object(&self, record: ObjectRecord) -> Result<(), Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
+        sqlx::query(
+            r#"
+            UPDATE objects
+            SET updated_at = $2, data = $3, index_meta = $4
+            WHERE id = $1
+            "#,
+        )
+        .bind(record.id)
+        .bind(record.updated_at)
+        .bind(record.data)
+        .bind(record.index_meta)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete_object(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+        owner: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
+        let row = sqlx::query(
+            r#"
+            DELETE FROM objects
+            WHERE id = $1 AND owner = $2 AND type = $3
+            RETURNING id, type, owner, created_at, updated_at, data
+            "#,
+        )
+        .bind(id)
+        .bind(owner)
+        .bind(type_name)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        row.map(PostgresAdapter::map_row_to_object_record_slim)
+            .transpose()
+    }
+
+    async fn insert_edge(&self, record: EdgeRecord) -> Result<(), Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
+        let EdgeRecord {
+            from,
+            to,
+            type_name,
+            data,
+            index_meta,
+        } = record;
+        sqlx::query(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT ("from", type, "to")
+            DO UPDATE SET data = $4, index_meta = $5;
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(type_name.as_ref())
+        .bind(data)
+        .bind(index_meta)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete_edge(
+        &self,
+        type_name: &'static str,
+        from: Uuid,
+        to: Uuid,
+    ) -> Result<(), Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM edges
+            WHERE type = $1 AND "from" = $2 AND "to" = $3
+            "#,
+        )
+        .bind(type_name)
+        .bind(from)
+        .bind(to)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn commit(&self) -> Result<(), Error> {
+        let tx = self.tx.lock().await.take().ok_or_else(finished)?;
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+
+    async fn rollback(&self) -> Result<(), Error> {
+        let tx = self.tx.lock().await.take().ok_or_else(finished)?;
+        tx.rollback()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+}
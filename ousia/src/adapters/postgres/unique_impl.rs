@@ -49,7 +49,7 @@ impl UniqueAdapter for PostgresAdapter {
                     .map(|(_, f)| *f)
                     .unwrap_or("unknown");
 
-                Err(Error::UniqueConstraintViolation(field.to_string()))
+                Err(Error::unique_violation(field))
             }
             Err(err) => Err(Error::Storage(err.to_string())),
         }
@@ -83,6 +83,54 @@ impl UniqueAdapter for PostgresAdapter {
         Ok(())
     }
 
+    async fn backfill_unique_constraints(
+        &self,
+        type_name: &str,
+        entries: Vec<(Uuid, Vec<(String, &str)>)>,
+    ) -> Result<u64, Error> {
+        let mut ids: Vec<Uuid> = Vec::new();
+        let mut keys: Vec<String> = Vec::new();
+        let mut fields: Vec<&str> = Vec::new();
+        for (id, hashes) in entries {
+            for (key, field) in hashes {
+                ids.push(id);
+                keys.push(key);
+                fields.push(field);
+            }
+        }
+        if keys.is_empty() {
+            return Ok(0);
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO unique_constraints (id, type, key, field)
+            SELECT t.id, $2, t.key, t.field
+            FROM unnest($1::uuid[], $3::text[], $4::text[]) AS t(id, key, field)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(&ids)
+        .bind(type_name)
+        .bind(&keys)
+        .bind(&fields)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_unique_for_type(&self, type_name: &str) -> Result<(), Error> {
+        sqlx::query("DELETE FROM unique_constraints WHERE type = $1")
+            .bind(type_name)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(())
+    }
+
     async fn get_hashes_for_object(&self, object_id: Uuid) -> Result<Vec<String>, Error> {
         let rows = sqlx::query(
             r#"
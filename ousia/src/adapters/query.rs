@@ -1,4 +1,6 @@
 use super::Adapter;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
 use uuid::Uuid;
 
 use crate::{
@@ -9,13 +11,28 @@ use crate::{
     },
     error::Error,
     query::{
-        Comparison, Cursor, IndexField, Operator, QueryFilter, QueryMode, QuerySearch, QuerySort,
-        ToIndexValue,
+        Comparison, Cursor, IndexField, IndexKind, IndexValue, IndexValueInner, LABELS, NAMESPACE,
+        Operator, QueryFilter, QueryMode, QuerySearch, QuerySort, ToIndexValue,
     },
     system_owner,
 };
 
-#[derive(Debug, Clone)]
+static CREATED_AT: IndexField = IndexField {
+    name: "created_at",
+    kinds: &[IndexKind::Search, IndexKind::Sort],
+};
+
+static UPDATED_AT: IndexField = IndexField {
+    name: "updated_at",
+    kinds: &[IndexKind::Search, IndexKind::Sort],
+};
+
+static SEQUENCE: IndexField = IndexField {
+    name: "sequence",
+    kinds: &[IndexKind::Search, IndexKind::Sort],
+};
+
+#[derive(Debug, Clone, Copy)]
 pub(crate) enum TraversalDirection {
     /// Forward: edges where e."from" = owner  →  fetch e."to" objects
     Forward,
@@ -33,6 +50,18 @@ pub struct Query {
     pub filters: Vec<QueryFilter>,
     pub limit: Option<u32>,
     pub cursor: Option<Cursor>,
+    /// Return `n` rows in random order; exclusive with sort and cursor
+    pub sample_size: Option<u32>,
+    /// Ignore `owner` and match every owner; only set by the engine's
+    /// global queries, which check `Engine::with_global_queries`
+    pub(crate) all_owners: bool,
+    /// How old the results may be; lets CockroachDB answer from a follower replica
+    pub stale: Option<Duration>,
+    /// Return each object at most once, even if the plan matches it twice
+    pub distinct: bool,
+    /// Walk IDs oldest first: the cursor matches IDs after it, and the default
+    /// order is `id ASC`
+    pub ascending: bool,
 }
 
 impl Default for Query {
@@ -44,6 +73,11 @@ impl Default for Query {
             filters: Vec::new(),
             limit: None,
             cursor: None,
+            sample_size: None,
+            all_owners: false,
+            stale: None,
+            distinct: false,
+            ascending: false,
         }
     }
 }
@@ -55,6 +89,11 @@ impl Query {
             filters: Vec::new(),
             limit: None,
             cursor: None,
+            sample_size: None,
+            all_owners: false,
+            stale: None,
+            distinct: false,
+            ascending: false,
         }
     }
 
@@ -66,6 +105,11 @@ impl Query {
             filters: Vec::new(),
             limit: None,
             cursor: None,
+            sample_size: None,
+            all_owners: false,
+            stale: None,
+            distinct: false,
+            ascending: false,
         }
     }
 
@@ -93,6 +137,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Equal,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -107,6 +152,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::NotEqual,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -121,6 +167,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThan,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -135,6 +182,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThanOrEqual,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -149,6 +197,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThan,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -163,6 +212,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThanOrEqual,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -177,11 +227,21 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Contains,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
     }
 
+    /// Array field holds `value`; shorthand for `where_contains` with a one-element array
+    pub fn where_array_contains(
+        self,
+        field: &'static IndexField,
+        value: impl ToIndexValue,
+    ) -> Self {
+        self.where_contains(field, vec![value.to_index_value_inner()])
+    }
+
     // Contains All
     pub fn where_contains_all(self, field: &'static IndexField, value: impl ToIndexValue) -> Self {
         let mut consumed_self = self;
@@ -191,6 +251,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::ContainsAll,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -205,6 +266,145 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::BeginsWith,
                 operator: Operator::default(),
+                case_sensitive: true,
+            }),
+        });
+        consumed_self
+    }
+
+    /// `field` matches the regular expression `pattern`. The pattern is checked
+    /// when the query runs; SQLite returns `Error::NotSupported`.
+    pub fn where_regex(
+        self,
+        field: &'static IndexField,
+        pattern: impl Into<String>,
+        case_sensitive: bool,
+    ) -> Self {
+        let mut consumed_self = self;
+        consumed_self.filters.push(QueryFilter {
+            field,
+            value: IndexValue::String(pattern.into()),
+            mode: QueryMode::Search(QuerySearch {
+                comparison: Comparison::Regex,
+                operator: Operator::default(),
+                case_sensitive,
+            }),
+        });
+        consumed_self
+    }
+
+    /// Objects whose `field` is null or missing, e.g. an `Option` left as `None`
+    pub fn where_null(self, field: &'static IndexField) -> Self {
+        let mut consumed_self = self;
+        consumed_self.filters.push(QueryFilter {
+            field,
+            value: IndexValue::Null,
+            mode: QueryMode::Search(QuerySearch {
+                comparison: Comparison::IsNull,
+                operator: Operator::default(),
+                case_sensitive: true,
+            }),
+        });
+        consumed_self
+    }
+
+    /// Objects whose `field` holds a value
+    pub fn where_not_null(self, field: &'static IndexField) -> Self {
+        let mut consumed_self = self;
+        consumed_self.filters.push(QueryFilter {
+            field,
+            value: IndexValue::Null,
+            mode: QueryMode::Search(QuerySearch {
+                comparison: Comparison::IsNotNull,
+                operator: Operator::default(),
+                case_sensitive: true,
+            }),
+        });
+        consumed_self
+    }
+
+    /// `created_at > dt`, served by the native column index
+    pub fn where_created_after(self, dt: DateTime<Utc>) -> Self {
+        self.where_gt(&CREATED_AT, dt)
+    }
+
+    /// `created_at < dt`, served by the native column index
+    pub fn where_created_before(self, dt: DateTime<Utc>) -> Self {
+        self.where_lt(&CREATED_AT, dt)
+    }
+
+    /// `updated_at > dt`, oldest change first, served by the native column index
+    pub fn where_updated_after(self, dt: DateTime<Utc>) -> Self {
+        self.where_gt(&UPDATED_AT, dt).sort_asc(&UPDATED_AT)
+    }
+
+    /// `start <= sequence <= end`, oldest first
+    pub fn where_sequence_between(self, start: u64, end: u64) -> Self {
+        self.where_gte(&SEQUENCE, start as i64)
+            .where_lte(&SEQUENCE, end as i64)
+            .sort_asc(&SEQUENCE)
+    }
+
+    /// `start <= sequence < end_exclusive`, oldest first
+    pub fn where_sequence_range(self, start: u64, end_exclusive: u64) -> Self {
+        self.where_gte(&SEQUENCE, start as i64)
+            .where_lt(&SEQUENCE, end_exclusive as i64)
+            .sort_asc(&SEQUENCE)
+    }
+
+    /// Newest sequence first
+    pub fn sort_sequence_desc(self) -> Self {
+        self.sort_desc(&SEQUENCE)
+    }
+
+    /// Newest first, served by the native column index
+    pub fn sort_created_desc(self) -> Self {
+        self.sort_desc(&CREATED_AT)
+    }
+
+    /// Objects whose label `key` equals `value`
+    pub fn where_label(self, key: &str, value: &str) -> Self {
+        let mut consumed_self = self;
+        consumed_self.filters.push(QueryFilter {
+            field: &LABELS,
+            value: IndexValue::Array(vec![
+                IndexValueInner::String(key.to_string()),
+                IndexValueInner::String(value.to_string()),
+            ]),
+            mode: QueryMode::Search(QuerySearch {
+                comparison: Comparison::Equal,
+                operator: Operator::default(),
+                case_sensitive: true,
+            }),
+        });
+        consumed_self
+    }
+
+    /// Objects stored in namespace `namespace`; see [`Engine::with_namespace`](crate::Engine::with_namespace)
+    pub fn where_namespace(self, namespace: &str) -> Self {
+        let mut consumed_self = self;
+        consumed_self.filters.push(QueryFilter {
+            field: &NAMESPACE,
+            value: IndexValue::String(namespace.to_string()),
+            mode: QueryMode::Search(QuerySearch {
+                comparison: Comparison::Equal,
+                operator: Operator::default(),
+                case_sensitive: true,
+            }),
+        });
+        consumed_self
+    }
+
+    /// Objects carrying label `key`, whatever its value
+    pub fn where_has_label(self, key: &str) -> Self {
+        let mut consumed_self = self;
+        consumed_self.filters.push(QueryFilter {
+            field: &LABELS,
+            value: IndexValue::String(key.to_string()),
+            mode: QueryMode::Search(QuerySearch {
+                comparison: Comparison::Equal,
+                operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -240,6 +440,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Equal,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -253,6 +454,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::NotEqual,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -266,6 +468,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThan,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -279,6 +482,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThanOrEqual,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -292,6 +496,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThan,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -305,6 +510,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThanOrEqual,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -318,6 +524,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Contains,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -331,6 +538,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::ContainsAll,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -344,6 +552,7 @@ impl Query {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::BeginsWith,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -358,6 +567,108 @@ impl Query {
         self.cursor = Some(Cursor { last_id: cursor });
         self
     }
+
+    /// Match objects of every owner. Only reachable through
+    /// `Engine::find_global` and friends, which check that global queries are
+    /// enabled.
+    pub(crate) fn all_owners(mut self, enabled: bool) -> Self {
+        self.all_owners = enabled;
+        self
+    }
+
+    /// Accept results up to `staleness` old. CockroachDB serves such reads
+    /// `AS OF SYSTEM TIME` from the nearest replica; other adapters ignore it.
+    pub fn allow_stale(mut self, staleness: Duration) -> Self {
+        self.stale = Some(staleness);
+        self
+    }
+
+    /// Deduplicate results by object ID in the database (`DISTINCT ON` on
+    /// Postgres and CockroachDB, `GROUP BY` on SQLite)
+    pub fn distinct(mut self, enabled: bool) -> Self {
+        self.distinct = enabled;
+        self
+    }
+
+    /// Page through IDs in ascending order instead of the default newest first
+    pub fn ascending(mut self, enabled: bool) -> Self {
+        self.ascending = enabled;
+        self
+    }
+
+    /// True when the owner predicate should be dropped
+    pub(crate) fn is_global(&self) -> bool {
+        self.all_owners || self.owner.is_nil()
+    }
+
+    /// Owner value bound by adapters; nil for global queries
+    pub(crate) fn owner_param(&self) -> Uuid {
+        if self.all_owners {
+            Uuid::nil()
+        } else {
+            self.owner
+        }
+    }
+
+    /// Pick `n` matching objects at random (`ORDER BY RANDOM() LIMIT n`)
+    pub fn random_sample(mut self, n: u32) -> Self {
+        self.sample_size = Some(n);
+        self
+    }
+
+    /// Row limit after applying the sample size
+    pub(crate) fn effective_limit(&self) -> Option<u32> {
+        match (self.sample_size, self.limit) {
+            (Some(n), Some(limit)) => Some(n.min(limit)),
+            (sample, limit) => sample.or(limit),
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        validate_regex_filters(&self.filters)?;
+        if self.sample_size.is_none() {
+            return Ok(());
+        }
+        if self.cursor.is_some() {
+            return Err(Error::InvalidQuery(
+                "random_sample cannot be combined with a cursor".to_string(),
+            ));
+        }
+        if self.filters.iter().any(|f| f.mode.as_sort().is_some()) {
+            return Err(Error::InvalidQuery(
+                "random_sample cannot be combined with sort".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Regex filters need a search-indexed field and a pattern that compiles
+pub(crate) fn validate_regex_filters(filters: &[QueryFilter]) -> Result<(), Error> {
+    for filter in filters {
+        let Some(search) = filter.mode.as_search() else {
+            continue;
+        };
+        if search.comparison != Comparison::Regex {
+            continue;
+        }
+        if !filter.field.kinds.contains(&IndexKind::Search) {
+            return Err(Error::InvalidQuery(format!(
+                "field `{}` is not indexed for search",
+                filter.field.name
+            )));
+        }
+        let IndexValue::String(pattern) = &filter.value else {
+            return Err(Error::InvalidQuery(format!(
+                "regex on `{}` needs a string pattern",
+                filter.field.name
+            )));
+        };
+        regex::Regex::new(pattern).map_err(|err| {
+            Error::InvalidQuery(format!("invalid regex on `{}`: {}", filter.field.name, err))
+        })?;
+    }
+    Ok(())
 }
 
 #[macro_export]
@@ -370,6 +681,7 @@ macro_rules! filter {
             mode: $crate::query::QueryMode::Search($crate::query::QuerySearch {
                 comparison: $crate::query::Comparison::Equal,
                 operator: $crate::query::Operator::default(),
+                case_sensitive: true,
             }),
         }
     }};
@@ -402,6 +714,11 @@ impl<'a, T: Object> QueryContext<'a, T> {
         EdgeQueryContext::new(self.adapter, self.root)
     }
 
+    /// Same as [`edge`](Self::edge), for chains that read as "this object, then its edges"
+    pub fn then_edges<E: Edge, O: Object>(self) -> EdgeQueryContext<'a, E, O> {
+        self.edge()
+    }
+
     pub fn preload<C: Object>(self) -> OwnedContext<'a, T, C> {
         OwnedContext::new(self.adapter, self.root)
     }
@@ -456,6 +773,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Equal,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -469,6 +787,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::NotEqual,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -482,6 +801,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThan,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -495,6 +815,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThanOrEqual,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -508,6 +829,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThan,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -521,6 +843,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThanOrEqual,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -534,6 +857,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Contains,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -550,6 +874,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::ContainsAll,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -567,6 +892,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::BeginsWith,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -580,6 +906,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Equal,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -592,6 +919,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::NotEqual,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -604,6 +932,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThan,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -616,6 +945,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThanOrEqual,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -628,6 +958,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThan,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -640,6 +971,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThanOrEqual,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -652,6 +984,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Contains,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -664,6 +997,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::ContainsAll,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -676,6 +1010,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::BeginsWith,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -708,6 +1043,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Equal,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -721,6 +1057,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::NotEqual,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -734,6 +1071,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThan,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -747,6 +1085,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThanOrEqual,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -760,6 +1099,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThan,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -773,6 +1113,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThanOrEqual,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -786,6 +1127,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Contains,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -802,6 +1144,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::ContainsAll,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -819,6 +1162,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::BeginsWith,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -832,6 +1176,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Equal,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -844,6 +1189,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::NotEqual,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -856,6 +1202,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThan,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -868,6 +1215,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThanOrEqual,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -880,6 +1228,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThan,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -892,6 +1241,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThanOrEqual,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -908,6 +1258,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Contains,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -924,6 +1275,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::ContainsAll,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -940,6 +1292,7 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::BeginsWith,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         self
@@ -1122,6 +1475,16 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
             .collect()
     }
 
+    /// Like [`collect_with_target`](Self::collect_with_target), as `(edge, target)` tuples.
+    pub async fn collect_with_edges(&self) -> Result<Vec<(E, O)>, Error> {
+        Ok(self
+            .collect_with_target()
+            .await?
+            .into_iter()
+            .map(ObjectEdge::into_parts)
+            .collect())
+    }
+
     /// Collect edges with their reverse sources in a single JOIN query.
     pub async fn collect_reverse_with_target(&self) -> Result<Vec<ObjectEdge<E, O>>, Error> {
         let mut edge_query = EdgeQuery::default();
@@ -1242,6 +1605,48 @@ impl<'a, E: Edge, O: Object> EdgeQueryContext<'a, E, O> {
     }
 }
 
+/// An object plus on-demand access to its edges of any type.
+/// Created via `Engine::fetch_object_with_edge_map::<T>(id)`; each
+/// `.edges::<E>()` call runs one query for that edge type.
+pub struct ObjectWithEdgeMap<'a, T: Object> {
+    adapter: &'a dyn Adapter,
+    object: T,
+}
+
+impl<'a, T: Object> ObjectWithEdgeMap<'a, T> {
+    pub(crate) fn new(adapter: &'a dyn Adapter, object: T) -> Self {
+        Self { adapter, object }
+    }
+
+    pub fn object(&self) -> &T {
+        &self.object
+    }
+
+    pub fn into_object(self) -> T {
+        self.object
+    }
+
+    /// Forward edges of type `E` from the object
+    pub async fn edges<E: Edge>(&self) -> Result<Vec<E>, Error> {
+        self.adapter
+            .query_edges(E::TYPE, self.object.meta().id(), EdgeQuery::default())
+            .await?
+            .into_iter()
+            .map(|r| r.to_edge::<E>())
+            .collect()
+    }
+
+    /// Reverse edges of type `E` into the object
+    pub async fn reverse_edges<E: Edge>(&self) -> Result<Vec<E>, Error> {
+        self.adapter
+            .query_reverse_edges(E::TYPE, self.object.meta().id(), EdgeQuery::default())
+            .await?
+            .into_iter()
+            .map(|r| r.to_edge::<E>())
+            .collect()
+    }
+}
+
 // ============================================================
 // Multi-Pivot Preload API
 // ============================================================
@@ -1309,6 +1714,7 @@ impl<'a, E: Edge, P: Object, C: Object> MultiEdgeContext<'a, E, P, C> {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Equal,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         self
@@ -1663,6 +2069,11 @@ impl<'a, P: Object, C: Object> MultiOwnedContext<'a, P, C> {
         }
     }
 
+    /// Also side-load each parent's outgoing `E` edges. Finish with `.execute()`.
+    pub fn with_edges<E: Edge>(self) -> MultiBatchContext<'a, P, C, E> {
+        MultiBatchContext::new(self.adapter, self.parent_query)
+    }
+
     /// Fetch all children owned by each parent.
     /// Returns Vec<(P, Vec<C>)> — exactly 2 queries.
     pub async fn collect(self) -> Result<Vec<(P, Vec<C>)>, Error> {
@@ -1696,3 +2107,87 @@ impl<'a, P: Object, C: Object> MultiOwnedContext<'a, P, C> {
             .collect()
     }
 }
+
+/// Parents with their owned children and outgoing edges, keyed by parent ID.
+/// Parents without children or edges have no entry.
+#[derive(Debug)]
+pub struct BatchResult<P, C, E> {
+    pub parents: Vec<P>,
+    pub children: std::collections::HashMap<Uuid, Vec<C>>,
+    pub edges: std::collections::HashMap<Uuid, Vec<E>>,
+}
+
+/// Multi-pivot side-load of owned children and edges together.
+/// Executes 3 queries — parents first, then children and edges concurrently.
+pub struct MultiBatchContext<'a, P: Object, C: Object, E: Edge> {
+    adapter: &'a dyn Adapter,
+    parent_query: Query,
+    edge_query: EdgeQuery,
+    _marker: std::marker::PhantomData<(P, C, E)>,
+}
+
+impl<'a, P: Object, C: Object, E: Edge> MultiBatchContext<'a, P, C, E> {
+    pub(crate) fn new(adapter: &'a dyn Adapter, parent_query: Query) -> Self {
+        Self {
+            adapter,
+            parent_query,
+            edge_query: EdgeQuery::default(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Apply a complete EdgeQuery (filters + limit + cursor) to the edge side-load.
+    pub fn with_edge_query(mut self, edge_query: EdgeQuery) -> Self {
+        self.edge_query = edge_query;
+        self
+    }
+
+    pub async fn execute(self) -> Result<BatchResult<P, C, E>, Error> {
+        let parents = self
+            .adapter
+            .query_objects(P::TYPE, self.parent_query)
+            .await?;
+        if parents.is_empty() {
+            return Ok(BatchResult {
+                parents: Vec::new(),
+                children: Default::default(),
+                edges: Default::default(),
+            });
+        }
+        let parent_ids: Vec<Uuid> = parents.iter().map(|p| p.id).collect();
+
+        let (children, edges) = tokio::join!(
+            self.adapter.fetch_owned_objects_batch(C::TYPE, &parent_ids),
+            self.adapter
+                .query_edges_batch(E::TYPE, &parent_ids, self.edge_query),
+        );
+
+        let mut grouped_children: std::collections::HashMap<Uuid, Vec<C>> =
+            std::collections::HashMap::new();
+        for cr in children? {
+            let owner = cr.owner;
+            grouped_children
+                .entry(owner)
+                .or_default()
+                .push(cr.to_object::<C>()?);
+        }
+
+        let mut grouped_edges: std::collections::HashMap<Uuid, Vec<E>> =
+            std::collections::HashMap::new();
+        for er in edges? {
+            grouped_edges
+                .entry(er.from)
+                .or_default()
+                .push(er.to_edge::<E>()?);
+        }
+
+        Ok(BatchResult {
+            parents: parents
+                .into_iter()
+                .map(|pr| pr.to_object::<P>())
+                .collect::<Result<_, _>>()?,
+            children: grouped_children,
+            edges: grouped_edges,
+        })
+    }
+}
@@ -1,11 +1,16 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::BTreeMap};
 
-use crate::{Object, Union, edge::Edge, error::Error};
+use crate::{
+    Either, Object, Union,
+    edge::Edge,
+    error::Error,
+    query::{IndexMeta, IndexValue, LABELS_KEY, NAMESPACE_KEY, SEQUENCE_KEY},
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ObjectRecord {
     pub id: Uuid,
     pub type_name: Cow<'static, str>,
@@ -18,6 +23,9 @@ pub struct ObjectRecord {
 
 impl ObjectRecord {
     pub fn to_object<T: Object>(self) -> Result<T, Error> {
+        let sequence = self.sequence();
+        let labels = self.labels();
+        let namespace = self.namespace().unwrap_or_default().to_string();
         let mut val = serde_json::from_value::<T>(self.data)
             .map_err(|e| Error::Deserialize(e.to_string()))?;
         let meta = val.meta_mut();
@@ -25,12 +33,29 @@ impl ObjectRecord {
         meta.owner = self.owner;
         meta.created_at = self.created_at;
         meta.updated_at = self.updated_at;
+        meta.sequence = sequence;
+        meta.labels = labels;
+        meta.namespace = namespace;
         Ok(val)
     }
 
+    /// Deserialize into whichever of `A` / `B` the stored type name names
+    pub fn to_either<A: Object, B: Object>(self) -> Result<Either<A, B>, Error> {
+        match self.type_name.as_ref() {
+            t if t == A::TYPE => self.to_object().map(Either::Left),
+            t if t == B::TYPE => self.to_object().map(Either::Right),
+            other => Err(Error::Deserialize(format!(
+                "type `{}` is neither `{}` nor `{}`",
+                other,
+                A::TYPE,
+                B::TYPE
+            ))),
+        }
+    }
+
     pub fn from_object<'a, T: Object>(obj: &'a T) -> Self {
         let meta = obj.meta();
-        Self {
+        let mut record = Self {
             id: meta.id,
             type_name: Cow::Borrowed(obj.type_name()),
             owner: meta.owner,
@@ -39,7 +64,125 @@ impl ObjectRecord {
             data: obj.__serialize_internal(),
             created_at: meta.created_at,
             updated_at: meta.updated_at,
+        };
+        if meta.sequence > 0 {
+            record.set_sequence(meta.sequence);
+        }
+        if !meta.labels.is_empty() {
+            record.set_labels(&meta.labels);
+        }
+        if !meta.namespace.is_empty() {
+            record.set_namespace(&meta.namespace);
+        }
+        record
+    }
+
+    /// Creation sequence stored alongside the data under `__sequence`; `0` if never assigned
+    pub fn sequence(&self) -> u64 {
+        self.data
+            .get(SEQUENCE_KEY)
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0)
+    }
+
+    /// Stamp the sequence into `data` (read back on fetch) and `index_meta` (filter/sort)
+    pub(crate) fn set_sequence(&mut self, sequence: u64) {
+        if let Some(data) = self.data.as_object_mut() {
+            data.insert(SEQUENCE_KEY.to_string(), sequence.into());
+        }
+        if let Some(index_meta) = self.index_meta.as_object_mut() {
+            index_meta.insert("sequence".to_string(), sequence.into());
+        }
+    }
+
+    /// Labels stored alongside the data under `__labels`
+    pub fn labels(&self) -> BTreeMap<String, String> {
+        self.data
+            .get(LABELS_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn set_labels(&mut self, labels: &BTreeMap<String, String>) {
+        if let Some(data) = self.data.as_object_mut() {
+            data.insert(
+                LABELS_KEY.to_string(),
+                serde_json::to_value(labels).unwrap_or_default(),
+            );
+        }
+    }
+
+    /// Namespace stored alongside the data under `__namespace`, if any
+    pub fn namespace(&self) -> Option<&str> {
+        self.data
+            .get(NAMESPACE_KEY)
+            .and_then(serde_json::Value::as_str)
+    }
+
+    pub(crate) fn set_namespace(&mut self, namespace: &str) {
+        if let Some(data) = self.data.as_object_mut() {
+            data.insert(NAMESPACE_KEY.to_string(), namespace.into());
+        }
+    }
+}
+
+/// Where a record's stored `index_meta` disagrees with what its type computes now.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IndexMetaDiff {
+    /// Expected keys absent from the stored entry
+    pub missing: Vec<String>,
+    /// Stored keys the type no longer indexes
+    pub extra: Vec<String>,
+    /// `(key, stored, expected)` where the stored JSON type differs
+    pub type_mismatch: Vec<(String, IndexValue, IndexValue)>,
+}
+
+impl IndexMetaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && self.type_mismatch.is_empty()
+    }
+}
+
+/// JSON shape of an index value; ints and floats count as different types
+fn json_kind(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "bool",
+        serde_json::Value::Number(n) if n.is_f64() => "float",
+        serde_json::Value::Number(_) => "int",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+}
+
+impl ObjectRecord {
+    /// Compare the stored `index_meta` with `expected`. `sequence` is written by
+    /// the engine rather than the type, so it is never reported as extra.
+    pub fn diff_index_meta(&self, expected: &IndexMeta) -> IndexMetaDiff {
+        let empty = serde_json::Map::new();
+        let stored = self.index_meta.as_object().unwrap_or(&empty);
+        let mut diff = IndexMetaDiff::default();
+
+        for (key, expected_value) in expected.meta() {
+            let Some(stored_value) = stored.get(key) else {
+                diff.missing.push(key.clone());
+                continue;
+            };
+            let expected_json = serde_json::to_value(expected_value).unwrap_or_default();
+            if json_kind(stored_value) != json_kind(&expected_json) {
+                let stored_index =
+                    serde_json::from_value(stored_value.clone()).unwrap_or(IndexValue::Null);
+                diff.type_mismatch
+                    .push((key.clone(), stored_index, expected_value.clone()));
+            }
         }
+        diff.extra = stored
+            .keys()
+            .filter(|key| key.as_str() != "sequence" && !expected.meta().contains_key(*key))
+            .cloned()
+            .collect();
+        diff
     }
 }
 
@@ -0,0 +1,154 @@
+use std::fmt::Write as _;
+
+/// One column of a core table, as the database reports it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaColumn {
+    pub table: String,
+    pub name: String,
+    pub data_type: String,
+    pub nullable: bool,
+    pub default: Option<String>,
+}
+
+/// The storage schema read back from the database, from
+/// [`Engine::export_schema`](crate::Engine::export_schema). DDL strings are
+/// empty for tables that don't exist yet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaExport {
+    pub objects_ddl: String,
+    pub edges_ddl: String,
+    /// Every index on Ousia's tables, ordered by name
+    pub indexes_ddl: Vec<String>,
+    pub sequences_ddl: String,
+    /// Columns of `objects`, `edges` and `sequences`, in table order
+    pub columns: Vec<SchemaColumn>,
+}
+
+impl SchemaExport {
+    /// One markdown table per core table, listing each column's type and what
+    /// Ousia stores in it, followed by the index definitions.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let mut table = "";
+        for column in &self.columns {
+            if column.table != table {
+                if !table.is_empty() {
+                    out.push('\n');
+                }
+                table = &column.table;
+                let _ = writeln!(out, "### `{}`\n", table);
+                out.push_str("| Column | Type | Nullable | Description |\n");
+                out.push_str("|--------|------|----------|-------------|\n");
+            }
+            let _ = writeln!(
+                out,
+                "| `{}` | `{}` | {} | {} |",
+                column.name,
+                column.data_type,
+                if column.nullable { "yes" } else { "no" },
+                describe(&column.table, &column.name),
+            );
+        }
+
+        if !self.indexes_ddl.is_empty() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str("### Indexes\n\n```sql\n");
+            for index in &self.indexes_ddl {
+                let _ = writeln!(out, "{};", index.trim_end_matches(';'));
+            }
+            out.push_str("```\n");
+        }
+        out
+    }
+}
+
+/// Result of [`Engine::verify_schema`](crate::Engine::verify_schema): tables
+/// and indexes `init_schema` creates that are absent, and indexes on Ousia's
+/// tables that it doesn't create. Tables belonging to anything else sharing
+/// the database are not reported.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaVerification {
+    pub missing: Vec<String>,
+    pub extra: Vec<String>,
+}
+
+impl SchemaVerification {
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty()
+    }
+
+    /// Compare the names found in the database with the ones `init_schema`
+    /// creates. `live_indexes` must already be limited to Ousia's tables.
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "cockroach"))]
+    pub(crate) fn compare(
+        live_tables: &[String],
+        live_indexes: &[String],
+        tables: &[&str],
+        indexes: &[&str],
+    ) -> Self {
+        let live: std::collections::BTreeSet<&str> = live_tables
+            .iter()
+            .chain(live_indexes)
+            .map(String::as_str)
+            .collect();
+        let missing = tables
+            .iter()
+            .chain(indexes)
+            .filter(|name| !live.contains(*name))
+            .map(|name| name.to_string())
+            .collect();
+        let extra = live_indexes
+            .iter()
+            .filter(|name| !indexes.contains(&name.as_str()))
+            .cloned()
+            .collect();
+        Self { missing, extra }
+    }
+}
+
+/// `CREATE TABLE` rebuilt from `information_schema.columns`, for backends
+/// that can't return a table's original DDL. Empty if `columns` has none
+/// for `table`.
+#[cfg(any(feature = "postgres", feature = "cockroach"))]
+pub(crate) fn table_ddl(table: &str, columns: &[SchemaColumn]) -> String {
+    let lines: Vec<String> = columns
+        .iter()
+        .filter(|c| c.table == table)
+        .map(|c| {
+            let mut line = format!("    \"{}\" {}", c.name, c.data_type);
+            if !c.nullable {
+                line.push_str(" NOT NULL");
+            }
+            if let Some(default) = &c.default {
+                let _ = write!(line, " DEFAULT {}", default);
+            }
+            line
+        })
+        .collect();
+    if lines.is_empty() {
+        return String::new();
+    }
+    format!("CREATE TABLE {} (\n{}\n)", table, lines.join(",\n"))
+}
+
+fn describe(table: &str, column: &str) -> &'static str {
+    match (table, column) {
+        ("objects", "id") => "Object ID (UUIDv7, so ordered by creation)",
+        ("objects", "type") => "`Object::TYPE` of the stored struct",
+        ("objects", "owner") => "Owning object's ID; the system owner for unowned objects",
+        ("objects", "created_at") => "Creation time",
+        ("objects", "updated_at") => "Time of the last write",
+        ("objects", "data") => "The object serialized as JSON",
+        ("objects", "index_meta") => "Indexed field values that queries filter and sort on",
+        ("edges", "from") => "Source object ID",
+        ("edges", "to") => "Target object ID",
+        ("edges", "type") => "`Edge::TYPE` of the stored struct",
+        ("edges", "data") => "The edge serialized as JSON",
+        ("edges", "index_meta") => "Indexed field values that edge queries filter on",
+        ("sequences", "name") => "Sequence name",
+        ("sequences", "value") => "Current value; `sequence_next_value` increments it",
+        _ => "",
+    }
+}
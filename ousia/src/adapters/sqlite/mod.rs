@@ -1,15 +1,18 @@
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sqlx::{
-    Row, Sqlite,
+    Row, Sqlite, Transaction,
     query::{Query as SqlxQuery, QueryScalar},
     sqlite::{SqliteArguments, SqlitePool, SqlitePoolOptions, SqliteRow},
 };
 use uuid::Uuid;
 
 use crate::{
+    ChangeLogEntry, ChangeOperation,
     adapters::{
-        Adapter, EdgeQuery, EdgeRecord, EdgeTraversal, Error, ObjectRecord, Query,
-        TraversalDirection, UniqueAdapter,
+        Adapter, Aggregation, BatchEdgeResult, DatabaseTransaction, EdgeQuery, EdgeRecord,
+        EdgeTraversal, Error, Granularity, GroupCount, ObjectRecord, OnConflict, Query, QueryCost,
+        SchemaColumn, SchemaExport, SchemaVerification, TableStats, TransactionBackend,
+        TraversalDirection, TypeStats, UniqueAdapter, UpsertOutcome, transaction::finished,
     },
     query::{Cursor, IndexValue, IndexValueInner, QueryFilter},
 };
@@ -36,6 +39,30 @@ pub struct SqliteAdapter {
     pub(crate) pool: SqlitePool,
 }
 
+/// Tables and indexes `init_schema` creates; checked by `verify_schema`
+const SCHEMA_TABLES: &[&str] = &[
+    "objects",
+    "edges",
+    "unique_constraints",
+    "objects_fts",
+    "sequences",
+    "retention_policies",
+    "object_history",
+    "object_audit_log",
+];
+const SCHEMA_INDEXES: &[&str] = &[
+    "idx_objects_type_owner",
+    "idx_objects_type_owner_created",
+    "idx_objects_type_owner_updated",
+    "idx_edges_from",
+    "idx_edges_to",
+    "idx_unique_id",
+    "idx_unique_type_key",
+    "idx_object_history_id_version",
+    "idx_object_audit_log_object",
+    "idx_object_audit_log_type_changed",
+];
+
 impl SqliteAdapter {
     /// Create a new SQLite adapter with a file-based database
     pub async fn new_file(path: &str) -> Result<Self, Error> {
@@ -185,6 +212,69 @@ impl SqliteAdapter {
         .await
         .map_err(|e| Error::Storage(e.to_string()))?;
 
+        // Full-text index over every string value in `data`, kept in sync by
+        // triggers. Rows are matched by id: VACUUM may renumber rowids.
+        sqlx::query(
+            r#"
+            CREATE VIRTUAL TABLE IF NOT EXISTS objects_fts
+            USING fts5(id UNINDEXED, type UNINDEXED, content)
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        for trigger in [
+            r#"
+            CREATE TRIGGER IF NOT EXISTS objects_fts_insert AFTER INSERT ON objects BEGIN
+                INSERT INTO objects_fts (id, type, content)
+                VALUES (
+                    NEW.id,
+                    NEW.type,
+                    (SELECT coalesce(group_concat(value, ' '), '')
+                     FROM json_tree(NEW.data) WHERE type = 'text')
+                );
+            END
+            "#,
+            r#"
+            CREATE TRIGGER IF NOT EXISTS objects_fts_update AFTER UPDATE ON objects BEGIN
+                DELETE FROM objects_fts WHERE id = OLD.id;
+                INSERT INTO objects_fts (id, type, content)
+                VALUES (
+                    NEW.id,
+                    NEW.type,
+                    (SELECT coalesce(group_concat(value, ' '), '')
+                     FROM json_tree(NEW.data) WHERE type = 'text')
+                );
+            END
+            "#,
+            r#"
+            CREATE TRIGGER IF NOT EXISTS objects_fts_delete AFTER DELETE ON objects BEGIN
+                DELETE FROM objects_fts WHERE id = OLD.id;
+            END
+            "#,
+        ] {
+            sqlx::query(trigger)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::Storage(e.to_string()))?;
+        }
+
+        // Backfill databases created before the index existed
+        sqlx::query(
+            r#"
+            INSERT INTO objects_fts (id, type, content)
+            SELECT o.id, o.type,
+                (SELECT coalesce(group_concat(value, ' '), '')
+                 FROM json_tree(o.data) WHERE type = 'text')
+            FROM objects o
+            WHERE NOT EXISTS (SELECT 1 FROM objects_fts)
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
         sqlx::query(
             r#"
             CREATE TABLE IF NOT EXISTS sequences (
@@ -197,6 +287,108 @@ impl SqliteAdapter {
         .await
         .map_err(|e| Error::Storage(e.to_string()))?;
 
+        // TTLs applied by Engine::run_retention_policies
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS retention_policies (
+                type TEXT PRIMARY KEY,
+                ttl_seconds INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        // Pre-update snapshots of versioned objects; see Engine::object_timeline
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS object_history (
+                id BLOB NOT NULL,
+                version INTEGER NOT NULL,
+                type TEXT NOT NULL,
+                owner BLOB NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                data TEXT NOT NULL,
+                index_meta TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            CREATE UNIQUE INDEX IF NOT EXISTS idx_object_history_id_version
+                ON object_history(id, version)
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        // Every write to `objects`, logged by trigger; see Engine::object_change_log
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS object_audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                object_id BLOB NOT NULL,
+                type TEXT NOT NULL,
+                operation TEXT NOT NULL CHECK (operation IN ('create', 'update', 'delete')),
+                old_data TEXT,
+                new_data TEXT,
+                changed_at TEXT NOT NULL,
+                changed_by BLOB NOT NULL
+            )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        // `changed_at` is written in the RFC 3339 form the other timestamp columns use
+        for statement in [
+            "CREATE INDEX IF NOT EXISTS idx_object_audit_log_object ON object_audit_log(object_id, id)",
+            "CREATE INDEX IF NOT EXISTS idx_object_audit_log_type_changed ON object_audit_log(type, changed_at)",
+            r#"
+            CREATE TRIGGER IF NOT EXISTS objects_audit_insert AFTER INSERT ON objects BEGIN
+                INSERT INTO object_audit_log
+                    (object_id, type, operation, old_data, new_data, changed_at, changed_by)
+                VALUES (
+                    NEW.id, NEW.type, 'create', NULL, NEW.data,
+                    strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'), NEW.owner
+                );
+            END
+            "#,
+            r#"
+            CREATE TRIGGER IF NOT EXISTS objects_audit_update AFTER UPDATE ON objects BEGIN
+                INSERT INTO object_audit_log
+                    (object_id, type, operation, old_data, new_data, changed_at, changed_by)
+                VALUES (
+                    NEW.id, NEW.type, 'update', OLD.data, NEW.data,
+                    strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'), NEW.owner
+                );
+            END
+            "#,
+            r#"
+            CREATE TRIGGER IF NOT EXISTS objects_audit_delete AFTER DELETE ON objects BEGIN
+                INSERT INTO object_audit_log
+                    (object_id, type, operation, old_data, new_data, changed_at, changed_by)
+                VALUES (
+                    OLD.id, OLD.type, 'delete', OLD.data, NULL,
+                    strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'), OLD.owner
+                );
+            END
+            "#,
+        ] {
+            sqlx::query(statement)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::Storage(e.to_string()))?;
+        }
+
         tx.commit()
             .await
             .map_err(|e| Error::Storage(e.to_string()))?;
@@ -206,6 +398,29 @@ impl SqliteAdapter {
 }
 
 impl SqliteAdapter {
+    fn map_row_to_change_log_entry(row: SqliteRow) -> Result<ChangeLogEntry, Error> {
+        let de = |e: sqlx::Error| Error::Deserialize(e.to_string());
+        let json = |text: Option<String>| {
+            text.map(|text| serde_json::from_str(&text))
+                .transpose()
+                .map_err(|e| Error::Deserialize(e.to_string()))
+        };
+        let operation: String = row.try_get("operation").map_err(de)?;
+        let changed_at: String = row.try_get("changed_at").map_err(de)?;
+        Ok(ChangeLogEntry {
+            id: row.try_get("id").map_err(de)?,
+            object_id: row.try_get("object_id").map_err(de)?,
+            type_name: row.try_get("type").map_err(de)?,
+            operation: ChangeOperation::parse(&operation)?,
+            old_data: json(row.try_get("old_data").map_err(de)?)?,
+            new_data: json(row.try_get("new_data").map_err(de)?)?,
+            changed_at: chrono::DateTime::parse_from_rfc3339(&changed_at)
+                .map_err(|e| Error::Deserialize(e.to_string()))?
+                .with_timezone(&chrono::Utc),
+            changed_by: row.try_get("changed_by").map_err(de)?,
+        })
+    }
+
     fn map_row_to_object_record_slim(row: SqliteRow) -> Result<ObjectRecord, Error> {
         let data_str: String = row
             .try_get("data")
@@ -324,7 +539,7 @@ impl SqliteAdapter {
         direction: TraversalDirection,
     ) -> Result<Vec<(EdgeRecord, ObjectRecord)>, Error> {
         let where_clause = Self::build_object_traversal_query_conditions(
-            direction.clone(),
+            direction,
             obj_filters,
             &plan.filters,
             plan.cursor,
@@ -351,14 +566,12 @@ impl SqliteAdapter {
         if let Some(limit) = plan.limit {
             sql.push_str(&format!(" LIMIT {}", limit));
         }
-        let mut query = sqlx::query(&sql)
-            .bind(type_name)
-            .bind(edge_type_name)
-            .bind(owner);
+        let mut query = sqlx::query(&sql).bind(type_name);
         if let Some(cursor) = plan.cursor {
             query = query.bind(cursor.last_id);
         }
         query = Self::query_bind_filters(query, obj_filters);
+        query = query.bind(edge_type_name).bind(owner);
         query = Self::query_bind_filters(query, &plan.filters);
         let rows = query
             .fetch_all(&self.pool)
@@ -376,6 +589,39 @@ impl SqliteAdapter {
         let crate::query::QueryMode::Search(ref qs) = filter.mode else {
             return None;
         };
+        if let Some((_, value)) = filter.as_label() {
+            let cond = if value.is_some() {
+                format!("json_extract({}.data, ?) = ?", alias)
+            } else {
+                format!("json_extract({}.data, ?) IS NOT NULL", alias)
+            };
+            let operator = match qs.operator {
+                crate::query::Operator::And => "AND",
+                _ => "OR",
+            };
+            return Some((cond, operator));
+        }
+        if filter.as_namespace().is_some() {
+            let cond = format!("json_extract({}.data, '$.__namespace') = ?", alias);
+            let operator = match qs.operator {
+                crate::query::Operator::And => "AND",
+                _ => "OR",
+            };
+            return Some((cond, operator));
+        }
+        // JSON null and a missing key both extract as NULL
+        if let Some(is_null) = filter.as_null_check() {
+            let check = if is_null { "IS NULL" } else { "IS NOT NULL" };
+            let cond = format!(
+                "json_extract({}.index_meta, '$.{}') {}",
+                alias, filter.field.name, check
+            );
+            let operator = match qs.operator {
+                crate::query::Operator::And => "AND",
+                _ => "OR",
+            };
+            return Some((cond, operator));
+        }
         let comparison = match qs.comparison {
             crate::query::Comparison::Equal => "=",
             crate::query::Comparison::NotEqual => "!=",
@@ -384,23 +630,50 @@ impl SqliteAdapter {
             crate::query::Comparison::GreaterThanOrEqual => ">=",
             crate::query::Comparison::LessThanOrEqual => "<=",
             crate::query::Comparison::BeginsWith => "LIKE",
-            crate::query::Comparison::Contains | crate::query::Comparison::ContainsAll => {
+            crate::query::Comparison::Contains => {
                 if matches!(filter.value, IndexValue::Array(_)) {
                     "ARRAY_CONTAINS"
                 } else {
                     "LIKE"
                 }
             }
+            crate::query::Comparison::ContainsAll => {
+                if matches!(filter.value, IndexValue::Array(_)) {
+                    "ARRAY_CONTAINS_ALL"
+                } else {
+                    "LIKE"
+                }
+            }
+            // Rejected by `reject_regex` on the query paths; SQLite has no REGEXP
+            crate::query::Comparison::Regex => "REGEXP",
+            crate::query::Comparison::IsNull | crate::query::Comparison::IsNotNull => {
+                unreachable!("null checks handled above")
+            }
+        };
+        // Meta timestamps are native columns covered by the (type, owner, *_at) indexes
+        let native = alias == "o"
+            && matches!(filter.field.name, "created_at" | "updated_at")
+            && matches!(filter.value, IndexValue::Timestamp(_))
+            && comparison != "LIKE";
+        let col = if native {
+            format!("{}.{}", alias, filter.field.name)
+        } else {
+            format!(
+                "json_extract({}.index_meta, '$.{}')",
+                alias, filter.field.name
+            )
         };
-        let col = format!(
-            "json_extract({}.index_meta, '$.{}')",
-            alias, filter.field.name
-        );
         let condition = if comparison == "ARRAY_CONTAINS" {
             format!(
                 "EXISTS (SELECT 1 FROM json_each({col}) WHERE value IN (SELECT value FROM json_each(?)))",
                 col = col
             )
+        } else if comparison == "ARRAY_CONTAINS_ALL" {
+            // No requested element may be missing from the stored array
+            format!(
+                "NOT EXISTS (SELECT 1 FROM json_each(?) WHERE value NOT IN (SELECT value FROM json_each({col})))",
+                col = col
+            )
         } else {
             format!("{} {} ?", col, comparison)
         };
@@ -411,6 +684,25 @@ impl SqliteAdapter {
         Some((condition, operator))
     }
 
+    /// SQLite ships without a REGEXP function, so regex filters can't run here
+    fn reject_regex(filters: &[QueryFilter]) -> Result<(), Error> {
+        let has_regex = filters.iter().any(|filter| {
+            filter
+                .mode
+                .as_search()
+                .is_some_and(|search| search.comparison == crate::query::Comparison::Regex)
+        });
+        if has_regex {
+            return Err(Error::NotSupported);
+        }
+        Ok(())
+    }
+
+    /// JSON path of one label; the key is quoted so dots stay literal
+    fn label_path(key: &str) -> String {
+        format!("$.__labels.\"{}\"", key)
+    }
+
     fn join_conditions(conditions: &[(String, &str)]) -> String {
         let mut out = String::new();
         for (i, (cond, op)) in conditions.iter().enumerate() {
@@ -475,6 +767,22 @@ impl SqliteAdapter {
         Self::build_order_clause_aliased(filters, "e", true)
     }
 
+    /// Edge order for plain edge listings: explicit sorts win, otherwise the
+    /// cursor column descending so `limit` + `cursor` pages are stable
+    fn build_edge_listing_order_clause(
+        filters: &[QueryFilter],
+        direction: TraversalDirection,
+    ) -> String {
+        let order_clause = Self::build_edge_order_clause(filters);
+        if !order_clause.is_empty() {
+            return order_clause;
+        }
+        match direction {
+            TraversalDirection::Forward => r#"ORDER BY e."to" DESC"#.to_string(),
+            TraversalDirection::Reverse => r#"ORDER BY e."from" DESC"#.to_string(),
+        }
+    }
+
     fn build_order_clause_aliased(filters: &[QueryFilter], alias: &str, is_edge: bool) -> String {
         let prefix = if alias.is_empty() {
             String::new()
@@ -521,9 +829,8 @@ impl SqliteAdapter {
         edge_filters: &[QueryFilter],
         cursor: Option<Cursor>,
     ) -> String {
-        // $1 = object type_name
-        // $2 = edge type_name
-        // $3 = owner
+        // Placeholders, in order: object type_name, cursor (if any),
+        // obj_filters, edge type_name, owner, edge_filters
 
         // ── Object conditions ────────────────────────────────────────────────────
         let mut obj_conditions: Vec<(String, &str)> = vec![("o.type = ?".to_string(), "AND")];
@@ -567,6 +874,17 @@ impl SqliteAdapter {
         filters: &'a [QueryFilter],
     ) -> SqlxQuery<'a, Sqlite, SqliteArguments<'a>> {
         for filter in filters.iter().filter(|f| f.mode.as_search().is_some()) {
+            if let Some((key, value)) = filter.as_label() {
+                query = query.bind(Self::label_path(key));
+                if let Some(value) = value {
+                    query = query.bind(value);
+                }
+                continue;
+            }
+            // IS NULL / IS NOT NULL take no parameter
+            if filter.as_null_check().is_some() {
+                continue;
+            }
             query = match &filter.value {
                 IndexValue::String(s) => {
                     use crate::query::Comparison::*;
@@ -580,7 +898,9 @@ impl SqliteAdapter {
                 IndexValue::Float(f) => query.bind(f),
                 IndexValue::Bool(b) => query.bind(b),
                 IndexValue::Timestamp(t) => query.bind(t.to_rfc3339()),
-                IndexValue::Uuid(uid) => query.bind(uid),
+                // index_meta stores UUIDs as hyphenated text
+                IndexValue::Uuid(uid) => query.bind(uid.to_string()),
+                IndexValue::Null => query,
                 IndexValue::Array(arr) => {
                     // Convert array to JSON string for SQLite
                     if let Some(first) = arr.first() {
@@ -628,6 +948,17 @@ impl SqliteAdapter {
         filters: &'a [QueryFilter],
     ) -> QueryScalar<'a, Sqlite, O, SqliteArguments<'a>> {
         for filter in filters.iter().filter(|f| f.mode.as_search().is_some()) {
+            if let Some((key, value)) = filter.as_label() {
+                query = query.bind(Self::label_path(key));
+                if let Some(value) = value {
+                    query = query.bind(value);
+                }
+                continue;
+            }
+            // IS NULL / IS NOT NULL take no parameter
+            if filter.as_null_check().is_some() {
+                continue;
+            }
             query = match &filter.value {
                 IndexValue::String(s) => {
                     use crate::query::Comparison::*;
@@ -641,7 +972,9 @@ impl SqliteAdapter {
                 IndexValue::Float(f) => query.bind(f),
                 IndexValue::Bool(b) => query.bind(b),
                 IndexValue::Timestamp(t) => query.bind(t.to_rfc3339()),
-                IndexValue::Uuid(uid) => query.bind(uid),
+                // index_meta stores UUIDs as hyphenated text
+                IndexValue::Uuid(uid) => query.bind(uid.to_string()),
+                IndexValue::Null => query,
                 IndexValue::Array(arr) => {
                     // Convert array to JSON string for SQLite
                     if let Some(first) = arr.first() {
@@ -685,6 +1018,64 @@ impl SqliteAdapter {
     }
 }
 
+#[cfg(feature = "raw-queries")]
+impl SqliteAdapter {
+    /// Bind JSON params by their natural SQL type. Arrays and objects go in as JSON text.
+    fn bind_raw_params<'q>(
+        mut query: SqlxQuery<'q, Sqlite, SqliteArguments<'q>>,
+        params: Vec<serde_json::Value>,
+    ) -> SqlxQuery<'q, Sqlite, SqliteArguments<'q>> {
+        use serde_json::Value;
+
+        for param in params {
+            query = match param {
+                Value::Null => query.bind(None::<String>),
+                Value::Bool(b) => query.bind(b),
+                Value::Number(n) => match n.as_i64() {
+                    Some(i) => query.bind(i),
+                    None => query.bind(n.as_f64()),
+                },
+                Value::String(s) => query.bind(s),
+                other => query.bind(other.to_string()),
+            };
+        }
+        query
+    }
+
+    /// Row as a JSON object keyed by column name. BLOB columns holding an id
+    /// come back as UUID strings.
+    fn map_row_to_json(row: &SqliteRow) -> Result<serde_json::Value, Error> {
+        use serde_json::Value;
+        use sqlx::{Column, TypeInfo, ValueRef};
+
+        let mut out = serde_json::Map::new();
+        for column in row.columns() {
+            let i = column.ordinal();
+            let raw = row
+                .try_get_raw(i)
+                .map_err(|e| Error::Deserialize(e.to_string()))?;
+            let (is_null, type_name) = (raw.is_null(), raw.type_info().name().to_string());
+            let value = if is_null {
+                Value::Null
+            } else {
+                match type_name.as_str() {
+                    "INTEGER" => row.try_get::<i64, _>(i).map(Value::from),
+                    "REAL" => row.try_get::<f64, _>(i).map(Value::from),
+                    "BOOLEAN" => row.try_get::<bool, _>(i).map(Value::from),
+                    "BLOB" => match row.try_get::<Uuid, _>(i) {
+                        Ok(id) => Ok(Value::from(id.to_string())),
+                        Err(_) => row.try_get::<Vec<u8>, _>(i).map(Value::from),
+                    },
+                    _ => row.try_get::<String, _>(i).map(Value::from),
+                }
+                .map_err(|e| Error::Deserialize(e.to_string()))?
+            };
+            out.insert(column.name().to_string(), value);
+        }
+        Ok(Value::Object(out))
+    }
+}
+
 impl SqliteAdapter {
     async fn edge_traversal_inner(
         &self,
@@ -696,7 +1087,7 @@ impl SqliteAdapter {
         direction: TraversalDirection,
     ) -> Result<Vec<ObjectRecord>, Error> {
         let where_clause = Self::build_object_traversal_query_conditions(
-            direction.clone(),
+            direction,
             filters,
             &plan.filters,
             plan.cursor,
@@ -723,16 +1114,14 @@ impl SqliteAdapter {
             sql.push_str(&format!(" LIMIT {}", limit));
         }
 
-        let mut query = sqlx::query(&sql)
-            .bind(type_name)
-            .bind(edge_type_name)
-            .bind(owner);
+        let mut query = sqlx::query(&sql).bind(type_name);
 
         if let Some(cursor) = plan.cursor {
             query = query.bind(cursor.last_id);
         }
 
         query = Self::query_bind_filters(query, filters);
+        query = query.bind(edge_type_name).bind(owner);
         query = Self::query_bind_filters(query, &plan.filters);
 
         let rows = query
@@ -873,8 +1262,59 @@ impl SqliteAdapter {
         plan: EdgeQuery,
         direction: TraversalDirection,
     ) -> Result<Vec<EdgeRecord>, Error> {
+        let sql = Self::edge_query_sql(&plan, direction);
+        let mut query = sqlx::query(&sql).bind(type_name).bind(owner);
+        if let Some(cursor) = plan.cursor {
+            query = query.bind(cursor.last_id);
+        }
+        query = Self::query_bind_filters(query, &plan.filters);
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        rows.into_iter().map(Self::map_row_to_edge_record).collect()
+    }
+
+    fn object_query_sql(plan: &Query) -> String {
+        let mut where_clause = Self::build_object_query_conditions(&plan.filters, plan.cursor);
+        let mut order_clause = if plan.sample_size.is_some() {
+            "ORDER BY RANDOM()".to_string()
+        } else {
+            Self::build_order_clause(&plan.filters)
+        };
+
+        if plan.is_global() {
+            where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+        }
+        if plan.ascending {
+            where_clause = where_clause.replace("o.id < ", "o.id > ");
+            if order_clause == "ORDER BY id DESC" {
+                order_clause = "ORDER BY id ASC".to_string();
+            }
+        }
+
+        let group_clause = if plan.distinct { "GROUP BY o.id" } else { "" };
+
+        let mut sql = format!(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            {}
+            {}
+            {}
+            "#,
+            where_clause, group_clause, order_clause
+        );
+
+        if let Some(limit) = plan.effective_limit() {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+        sql
+    }
+
+    fn edge_query_sql(plan: &EdgeQuery, direction: TraversalDirection) -> String {
         let where_clause = Self::build_edge_query_conditions(&plan.filters, plan.cursor, direction);
-        let order_clause = Self::build_edge_order_clause(&plan.filters);
+        let order_clause = Self::build_edge_listing_order_clause(&plan.filters, direction);
         let mut sql = format!(
             r#"
             SELECT e."from" AS "from", e."to" AS "to", e.type AS "type", e.data, e.index_meta
@@ -887,22 +1327,50 @@ impl SqliteAdapter {
         if let Some(limit) = plan.limit {
             sql.push_str(&format!(" LIMIT {}", limit));
         }
-        let mut query = sqlx::query(&sql).bind(type_name).bind(owner);
-        if let Some(cursor) = plan.cursor {
-            query = query.bind(cursor.last_id);
+        sql
+    }
+
+    // `EXPLAIN QUERY PLAN` describes each table access as `SCAN <table>` or
+    // `SEARCH <table> USING INDEX ...` (older versions add `TABLE`). It gives
+    // no row or cost estimates.
+    fn query_plan_cost(rows: Vec<SqliteRow>) -> Result<QueryCost, Error> {
+        let details = rows
+            .iter()
+            .map(|row| row.try_get::<String, _>("detail"))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Error::Deserialize(e.to_string()))?;
+        Ok(QueryCost {
+            estimated_rows: 0,
+            uses_index: details.iter().any(|d| {
+                d.contains(" USING ") && (d.contains("INDEX") || d.contains("PRIMARY KEY"))
+            }),
+            sequential_scan: details
+                .iter()
+                .any(|d| d.starts_with("SCAN ") && !d.contains(" USING ")),
+            planner_cost: 0.0,
+        })
+    }
+}
+
+/// Holds the open transaction; `None` once committed or rolled back
+struct SqliteTransaction {
+    tx: tokio::sync::Mutex<Option<Transaction<'static, Sqlite>>>,
+}
+
+impl SqliteTransaction {
+    fn new(tx: Transaction<'static, Sqlite>) -> Self {
+        Self {
+            tx: tokio::sync::Mutex::new(Some(tx)),
         }
-        query = Self::query_bind_filters(query, &plan.filters);
-        let rows = query
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| Error::Storage(e.to_string()))?;
-        rows.into_iter().map(Self::map_row_to_edge_record).collect()
     }
 }
 
 #[async_trait::async_trait]
-impl Adapter for SqliteAdapter {
+impl TransactionBackend for SqliteTransaction {
     async fn insert_object(&self, record: ObjectRecord) -> Result<(), Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
         let ObjectRecord {
             id,
             type_name,
@@ -912,7 +1380,7 @@ impl Adapter for SqliteAdapter {
             data,
             index_meta,
         } = record;
-        let _ = sqlx::query(
+        sqlx::query(
             r#"
             INSERT INTO objects (id, type, owner, created_at, updated_at, data, index_meta)
             VALUES (?, ?, ?, ?, ?, ?, ?)
@@ -925,11 +1393,11 @@ impl Adapter for SqliteAdapter {
         .bind(updated_at.to_rfc3339())
         .bind(serde_json::to_string(&data).map_err(|e| Error::Serialize(e.to_string()))?)
         .bind(serde_json::to_string(&index_meta).map_err(|e| Error::Serialize(e.to_string()))?)
-        .execute(&self.pool)
+        .execute(&mut **tx)
         .await
         .map_err(|err| {
             if err.to_string().contains("unique") {
-                Error::UniqueConstraintViolation("id".to_string())
+                Error::unique_violation("id")
             } else {
                 Error::Storage(err.to_string())
             }
@@ -942,6 +1410,9 @@ impl Adapter for SqliteAdapter {
         type_name: &'static str,
         id: Uuid,
     ) -> Result<Option<ObjectRecord>, Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
         let row = sqlx::query(
             r#"
             SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
@@ -951,44 +1422,18 @@ impl Adapter for SqliteAdapter {
         )
         .bind(id)
         .bind(type_name)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut **tx)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
-        match row {
-            Some(r) => Self::map_row_to_object_record_slim(r).map(Some),
-            None => Ok(None),
-        }
-    }
-
-    async fn fetch_bulk_objects(
-        &self,
-        type_name: &'static str,
-        ids: Vec<Uuid>,
-    ) -> Result<Vec<ObjectRecord>, Error> {
-        let placeholders = ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
-        let sql = format!(
-            "SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data FROM objects o WHERE id IN ({}) AND type = ?",
-            placeholders
-        );
-
-        let mut query = sqlx::query(&sql);
-        for id in ids {
-            query = query.bind(id);
-        }
-        query = query.bind(type_name);
-
-        let rows = query
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|err| Error::Storage(err.to_string()))?;
-
-        rows.into_iter()
-            .map(Self::map_row_to_object_record_slim)
-            .collect()
+        row.map(SqliteAdapter::map_row_to_object_record_slim)
+            .transpose()
     }
 
     async fn update_object(&self, record: ObjectRecord) -> Result<(), Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
         sqlx::query(
             r#"
             UPDATE objects
@@ -1003,23 +1448,376 @@ impl Adapter for SqliteAdapter {
                 .map_err(|e| Error::Serialize(e.to_string()))?,
         )
         .bind(record.id)
-        .execute(&self.pool)
+        .execute(&mut **tx)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
         Ok(())
     }
 
-    async fn transfer_object(
+    async fn delete_object(
         &self,
         type_name: &'static str,
         id: Uuid,
-        from_owner: Uuid,
-        to_owner: Uuid,
-    ) -> Result<ObjectRecord, Error> {
-        // SQLite doesn't support RETURNING, so we update then fetch
-        let result = sqlx::query(
-            r#"
+        owner: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
+        // Fetch first, then delete (SQLite doesn't have RETURNING)
+        let row = sqlx::query(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE id = ? AND owner = ? AND type = ?
+            "#,
+        )
+        .bind(id)
+        .bind(owner)
+        .bind(type_name)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        sqlx::query("DELETE FROM objects WHERE id = ? AND owner = ?")
+            .bind(id)
+            .bind(owner)
+            .execute(&mut **tx)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        SqliteAdapter::map_row_to_object_record_slim(row).map(Some)
+    }
+
+    async fn insert_edge(&self, record: EdgeRecord) -> Result<(), Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
+        let EdgeRecord {
+            from,
+            to,
+            type_name,
+            data,
+            index_meta,
+        } = record;
+        let data_str = serde_json::to_string(&data).map_err(|e| Error::Serialize(e.to_string()))?;
+        let index_meta_str =
+            serde_json::to_string(&index_meta).map_err(|e| Error::Serialize(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT ("from", type, "to")
+            DO UPDATE SET data = ?, index_meta = ?;
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(type_name.as_ref())
+        .bind(&data_str)
+        .bind(&index_meta_str)
+        .bind(&data_str)
+        .bind(&index_meta_str)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete_edge(
+        &self,
+        type_name: &'static str,
+        from: Uuid,
+        to: Uuid,
+    ) -> Result<(), Error> {
+        let mut guard = self.tx.lock().await;
+        let tx = guard.as_mut().ok_or_else(finished)?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM edges
+            WHERE type = ? AND "from" = ? AND "to" = ?
+            "#,
+        )
+        .bind(type_name)
+        .bind(from)
+        .bind(to)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn commit(&self) -> Result<(), Error> {
+        let tx = self.tx.lock().await.take().ok_or_else(finished)?;
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+
+    async fn rollback(&self) -> Result<(), Error> {
+        let tx = self.tx.lock().await.take().ok_or_else(finished)?;
+        tx.rollback()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl Adapter for SqliteAdapter {
+    async fn insert_object(&self, record: ObjectRecord) -> Result<(), Error> {
+        let ObjectRecord {
+            id,
+            type_name,
+            owner,
+            created_at,
+            updated_at,
+            data,
+            index_meta,
+        } = record;
+        let _ = sqlx::query(
+            r#"
+            INSERT INTO objects (id, type, owner, created_at, updated_at, data, index_meta)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id)
+        .bind(type_name.as_ref())
+        .bind(owner)
+        .bind(created_at.to_rfc3339())
+        .bind(updated_at.to_rfc3339())
+        .bind(serde_json::to_string(&data).map_err(|e| Error::Serialize(e.to_string()))?)
+        .bind(serde_json::to_string(&index_meta).map_err(|e| Error::Serialize(e.to_string()))?)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| {
+            if err.to_string().contains("unique") {
+                Error::unique_violation("id")
+            } else {
+                Error::Storage(err.to_string())
+            }
+        })?;
+        Ok(())
+    }
+
+    async fn insert_object_or_ignore(&self, record: ObjectRecord) -> Result<bool, Error> {
+        let ObjectRecord {
+            id,
+            type_name,
+            owner,
+            created_at,
+            updated_at,
+            data,
+            index_meta,
+        } = record;
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO objects (id, type, owner, created_at, updated_at, data, index_meta)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id)
+        .bind(type_name.as_ref())
+        .bind(owner)
+        .bind(created_at.to_rfc3339())
+        .bind(updated_at.to_rfc3339())
+        .bind(serde_json::to_string(&data).map_err(|e| Error::Serialize(e.to_string()))?)
+        .bind(serde_json::to_string(&index_meta).map_err(|e| Error::Serialize(e.to_string()))?)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn fetch_object(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE id = ? AND type = ?
+            "#,
+        )
+        .bind(id)
+        .bind(type_name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        match row {
+            Some(r) => Self::map_row_to_object_record_slim(r).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn fetch_object_any_type(&self, id: Uuid) -> Result<Option<ObjectRecord>, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        match row {
+            Some(r) => Self::map_row_to_object_record_slim(r).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn fetch_object_owner(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Option<Uuid>, Error> {
+        sqlx::query_scalar::<_, Uuid>("SELECT owner FROM objects WHERE id = ? AND type = ?")
+            .bind(id)
+            .bind(type_name)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+
+    async fn fetch_bulk_objects(
+        &self,
+        type_name: &'static str,
+        ids: Vec<Uuid>,
+    ) -> Result<Vec<ObjectRecord>, Error> {
+        let placeholders = ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        let sql = format!(
+            "SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data FROM objects o WHERE id IN ({}) AND type = ?",
+            placeholders
+        );
+
+        let mut query = sqlx::query(&sql);
+        for id in ids {
+            query = query.bind(id);
+        }
+        query = query.bind(type_name);
+
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(Self::map_row_to_object_record_slim)
+            .collect()
+    }
+
+    async fn update_object(&self, record: ObjectRecord) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            UPDATE objects
+            SET updated_at = ?, data = ?, index_meta = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(record.updated_at.to_rfc3339())
+        .bind(serde_json::to_string(&record.data).map_err(|e| Error::Serialize(e.to_string()))?)
+        .bind(
+            serde_json::to_string(&record.index_meta)
+                .map_err(|e| Error::Serialize(e.to_string()))?,
+        )
+        .bind(record.id)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn update_object_partial(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+        data_patch: serde_json::Value,
+        index_meta_patch: serde_json::Value,
+        index_meta_removed: Vec<String>,
+        updated_at: DateTime<Utc>,
+    ) -> Result<(), Error> {
+        // json_patch drops keys patched to null, so merge in Rust instead
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let row = sqlx::query("SELECT data, index_meta FROM objects WHERE id = ? AND type = ?")
+            .bind(id)
+            .bind(type_name)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        let Some(row) = row else {
+            return Ok(());
+        };
+
+        let parse = |column: &str| -> Result<serde_json::Value, Error> {
+            let raw: String = row
+                .try_get(column)
+                .map_err(|err| Error::Storage(err.to_string()))?;
+            serde_json::from_str(&raw).map_err(|e| Error::Deserialize(e.to_string()))
+        };
+        let mut data = parse("data")?;
+        let mut index_meta = parse("index_meta")?;
+
+        if let (Some(data), Some(patch)) = (data.as_object_mut(), data_patch.as_object()) {
+            data.extend(patch.clone());
+        }
+        if let (Some(index_meta), Some(patch)) =
+            (index_meta.as_object_mut(), index_meta_patch.as_object())
+        {
+            index_meta.extend(patch.clone());
+            for key in &index_meta_removed {
+                index_meta.remove(key);
+            }
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE objects
+            SET updated_at = ?, data = ?, index_meta = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(updated_at.to_rfc3339())
+        .bind(serde_json::to_string(&data).map_err(|e| Error::Serialize(e.to_string()))?)
+        .bind(serde_json::to_string(&index_meta).map_err(|e| Error::Serialize(e.to_string()))?)
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(())
+    }
+
+    async fn transfer_object(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+        from_owner: Uuid,
+        to_owner: Uuid,
+    ) -> Result<ObjectRecord, Error> {
+        // SQLite doesn't support RETURNING, so we update then fetch
+        let result = sqlx::query(
+            r#"
             UPDATE objects
             SET updated_at = ?, owner = ?
             WHERE id = ? AND owner = ? AND type = ?
@@ -1043,6 +1841,128 @@ impl Adapter for SqliteAdapter {
             .ok_or(Error::NotFound)
     }
 
+    async fn swap_owners(
+        &self,
+        type_name: &'static str,
+        id_a: Uuid,
+        owner_a: Uuid,
+        id_b: Uuid,
+        owner_b: Uuid,
+    ) -> Result<(ObjectRecord, ObjectRecord), Error> {
+        // Dropping the transaction on an early return rolls back the first move
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let now = Utc::now().to_rfc3339();
+        for (id, from_owner, to_owner) in [(id_a, owner_a, owner_b), (id_b, owner_b, owner_a)] {
+            let result = sqlx::query(
+                r#"
+                UPDATE objects
+                SET updated_at = ?, owner = ?
+                WHERE id = ? AND owner = ? AND type = ?
+                "#,
+            )
+            .bind(&now)
+            .bind(to_owner)
+            .bind(id)
+            .bind(from_owner)
+            .bind(type_name)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+            if result.rows_affected() == 0 {
+                return Err(Error::NotFound);
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let a = self
+            .fetch_object(type_name, id_a)
+            .await?
+            .ok_or(Error::NotFound)?;
+        let b = self
+            .fetch_object(type_name, id_b)
+            .await?
+            .ok_or(Error::NotFound)?;
+        Ok((a, b))
+    }
+
+    async fn retype_object(
+        &self,
+        old_type: &'static str,
+        record: ObjectRecord,
+        hashes: Vec<(String, &str)>,
+    ) -> Result<(), Error> {
+        // Dropping the transaction on an early return keeps the old row and hashes
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE objects
+            SET type = ?, updated_at = ?, data = ?, index_meta = ?
+            WHERE id = ? AND type = ?
+            "#,
+        )
+        .bind(record.type_name.as_ref())
+        .bind(record.updated_at.to_rfc3339())
+        .bind(serde_json::to_string(&record.data).map_err(|e| Error::Serialize(e.to_string()))?)
+        .bind(
+            serde_json::to_string(&record.index_meta)
+                .map_err(|e| Error::Serialize(e.to_string()))?,
+        )
+        .bind(record.id)
+        .bind(old_type)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        if result.rows_affected() == 0 {
+            return Err(Error::NotFound);
+        }
+
+        sqlx::query("DELETE FROM unique_constraints WHERE id = ?")
+            .bind(record.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        for (hash, field) in hashes {
+            sqlx::query(
+                r#"
+                INSERT INTO unique_constraints (id, type, key, field)
+                VALUES (?, ?, ?, ?)
+                "#,
+            )
+            .bind(record.id)
+            .bind(record.type_name.as_ref())
+            .bind(hash)
+            .bind(field)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| {
+                if err.to_string().contains("unique") {
+                    Error::unique_violation(field)
+                } else {
+                    Error::Storage(err.to_string())
+                }
+            })?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(())
+    }
+
     async fn delete_object(
         &self,
         type_name: &'static str,
@@ -1073,6 +1993,30 @@ impl Adapter for SqliteAdapter {
         Ok(record)
     }
 
+    async fn touch_objects(&self, type_name: &'static str, ids: Vec<Uuid>) -> Result<u64, Error> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        // Written like every other timestamp here: RFC 3339 text, which
+        // datetime('now') is not
+        let placeholders = ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        let sql = format!(
+            "UPDATE objects SET updated_at = ? WHERE id IN ({}) AND type = ?",
+            placeholders
+        );
+
+        let mut query = sqlx::query(&sql).bind(Utc::now().to_rfc3339());
+        for id in ids {
+            query = query.bind(id);
+        }
+        let result = query
+            .bind(type_name)
+            .execute(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
     async fn delete_bulk_objects(
         &self,
         type_name: &'static str,
@@ -1114,6 +2058,43 @@ impl Adapter for SqliteAdapter {
         Ok(result.rows_affected())
     }
 
+    async fn delete_owned_objects_with_hashes(
+        &self,
+        type_name: &'static str,
+        owner: Uuid,
+    ) -> Result<u64, Error> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM unique_constraints
+            WHERE type = ? AND id IN (SELECT id FROM objects WHERE type = ? AND owner = ?)
+            "#,
+        )
+        .bind(type_name)
+        .bind(type_name)
+        .bind(owner)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let result = sqlx::query("DELETE FROM objects WHERE type = ? AND owner = ?")
+            .bind(type_name)
+            .bind(owner)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
     async fn find_object(
         &self,
         type_name: &'static str,
@@ -1153,43 +2134,65 @@ impl Adapter for SqliteAdapter {
         type_name: &'static str,
         plan: Query,
     ) -> Result<Vec<ObjectRecord>, Error> {
-        let mut where_clause = Self::build_object_query_conditions(&plan.filters, plan.cursor);
-        let order_clause = Self::build_order_clause(&plan.filters);
+        Self::reject_regex(&plan.filters)?;
+        let sql = Self::object_query_sql(&plan);
+        let mut query = sqlx::query(&sql).bind(type_name).bind(plan.owner_param());
 
-        if plan.owner.is_nil() {
-            where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+        if let Some(cursor) = plan.cursor {
+            query = query.bind(cursor.last_id);
         }
 
-        let mut sql = format!(
-            r#"
-            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
-            FROM objects o
-            {}
-            {}
-            "#,
-            where_clause, order_clause
-        );
+        query = Self::query_bind_filters(query, &plan.filters);
 
-        if let Some(limit) = plan.limit {
-            sql.push_str(&format!(" LIMIT {}", limit));
-        }
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(Self::map_row_to_object_record_slim)
+            .collect()
+    }
 
-        let mut query = sqlx::query(&sql).bind(type_name).bind(plan.owner);
+    async fn estimate_query_cost(
+        &self,
+        type_name: &'static str,
+        plan: Query,
+    ) -> Result<QueryCost, Error> {
+        Self::reject_regex(&plan.filters)?;
+        let sql = format!("EXPLAIN QUERY PLAN {}", Self::object_query_sql(&plan));
+        let mut query = sqlx::query(&sql).bind(type_name).bind(plan.owner_param());
+        if let Some(cursor) = plan.cursor {
+            query = query.bind(cursor.last_id);
+        }
+        query = Self::query_bind_filters(query, &plan.filters);
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Self::query_plan_cost(rows)
+    }
 
+    async fn estimate_edge_query_cost(
+        &self,
+        type_name: &'static str,
+        owner: Uuid,
+        plan: EdgeQuery,
+    ) -> Result<QueryCost, Error> {
+        let sql = format!(
+            "EXPLAIN QUERY PLAN {}",
+            Self::edge_query_sql(&plan, TraversalDirection::Forward)
+        );
+        let mut query = sqlx::query(&sql).bind(type_name).bind(owner);
         if let Some(cursor) = plan.cursor {
             query = query.bind(cursor.last_id);
         }
-
         query = Self::query_bind_filters(query, &plan.filters);
-
         let rows = query
             .fetch_all(&self.pool)
             .await
-            .map_err(|err| Error::Storage(err.to_string()))?;
-
-        rows.into_iter()
-            .map(Self::map_row_to_object_record_slim)
-            .collect()
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Self::query_plan_cost(rows)
     }
 
     async fn count_objects(
@@ -1199,7 +2202,12 @@ impl Adapter for SqliteAdapter {
     ) -> Result<u64, Error> {
         match plan {
             Some(plan) => {
-                let where_clause = Self::build_object_query_conditions(&plan.filters, None);
+                Self::reject_regex(&plan.filters)?;
+                let mut where_clause = Self::build_object_query_conditions(&plan.filters, None);
+
+                if plan.is_global() {
+                    where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+                }
 
                 let mut sql = format!(
                     r#"
@@ -1215,7 +2223,7 @@ impl Adapter for SqliteAdapter {
 
                 let mut query = sqlx::query_scalar::<_, i64>(&sql)
                     .bind(type_name)
-                    .bind(plan.owner);
+                    .bind(plan.owner_param());
 
                 query = Self::query_scalar_bind_filters(query, &plan.filters);
 
@@ -1233,9 +2241,423 @@ impl Adapter for SqliteAdapter {
                     .await
                     .map_err(|err| Error::Storage(err.to_string()))?;
 
-                Ok(count as u64)
-            }
-        }
+                Ok(count as u64)
+            }
+        }
+    }
+
+    async fn time_bucket_count(
+        &self,
+        type_name: &'static str,
+        owner: Option<Uuid>,
+        granularity: Granularity,
+    ) -> Result<Vec<(DateTime<Utc>, u64)>, Error> {
+        // created_at is RFC 3339 text; date() normalises it to a UTC day
+        let bucket = match granularity {
+            Granularity::Day => "date(created_at)",
+            Granularity::Week => "date(created_at, 'weekday 0', '-6 days')",
+            Granularity::Month => "date(created_at, 'start of month')",
+        };
+        let owner_clause = if owner.is_some() {
+            " AND owner = ?"
+        } else {
+            ""
+        };
+        let sql = format!(
+            "SELECT {bucket} AS bucket, COUNT(*) FROM objects WHERE type = ?{owner_clause} GROUP BY bucket ORDER BY bucket"
+        );
+        let mut query = sqlx::query_as::<_, (String, i64)>(&sql).bind(type_name);
+        if let Some(owner) = owner {
+            query = query.bind(owner);
+        }
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(|(bucket, count)| {
+                let day = chrono::NaiveDate::parse_from_str(&bucket, "%Y-%m-%d")
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                Ok((day.and_time(chrono::NaiveTime::MIN).and_utc(), count as u64))
+            })
+            .collect()
+    }
+
+    async fn group_by_field(
+        &self,
+        type_name: &'static str,
+        field: &'static str,
+        plan: Query,
+    ) -> Result<Vec<GroupCount>, Error> {
+        let mut where_clause = Self::build_object_query_conditions(&plan.filters, None);
+        if plan.is_global() {
+            where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+        }
+        let sql = format!(
+            r#"
+            SELECT o.index_meta -> '$.{}' AS val, COUNT(*) AS n FROM objects o
+            {}
+            GROUP BY val
+            ORDER BY n DESC, val
+            "#,
+            field, where_clause
+        );
+        let query = sqlx::query(&sql).bind(type_name).bind(plan.owner_param());
+        let rows = Self::query_bind_filters(query, &plan.filters)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                // `->` yields the JSON text of the value, NULL when absent
+                let val: Option<String> = row
+                    .try_get("val")
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let count: i64 = row
+                    .try_get("n")
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let value = serde_json::from_str(val.as_deref().unwrap_or("null"))
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                Ok(GroupCount {
+                    value,
+                    count: count as u64,
+                })
+            })
+            .collect()
+    }
+
+    async fn group_count_by_owner(
+        &self,
+        type_name: &'static str,
+        plan: Query,
+    ) -> Result<Vec<(Uuid, u64)>, Error> {
+        let mut where_clause = Self::build_object_query_conditions(&plan.filters, None);
+        if plan.is_global() {
+            where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+        }
+        let sql = format!(
+            "SELECT o.owner, COUNT(*) FROM objects o {} GROUP BY o.owner",
+            where_clause
+        );
+        let query = sqlx::query(&sql).bind(type_name).bind(plan.owner_param());
+        let rows = Self::query_bind_filters(query, &plan.filters)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let owner: Uuid = row
+                    .try_get(0)
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let count: i64 = row
+                    .try_get(1)
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                Ok((owner, count as u64))
+            })
+            .collect()
+    }
+
+    async fn top_owners_by_type(
+        &self,
+        type_name: &'static str,
+        limit: u32,
+    ) -> Result<Vec<(Uuid, u64)>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT owner, COUNT(*)
+            FROM objects
+            WHERE type = ?
+            GROUP BY owner
+            ORDER BY COUNT(*) DESC, owner
+            LIMIT ?
+            "#,
+        )
+        .bind(type_name)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let owner: Uuid = row
+                    .try_get(0)
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let count: i64 = row
+                    .try_get(1)
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                Ok((owner, count as u64))
+            })
+            .collect()
+    }
+
+    async fn fetch_object_record(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, type, owner, created_at, updated_at, data, index_meta
+            FROM objects
+            WHERE id = ? AND type = ?
+            "#,
+        )
+        .bind(id)
+        .bind(type_name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let index_meta: String = row
+            .try_get("index_meta")
+            .map_err(|err| Error::Deserialize(err.to_string()))?;
+        let index_meta =
+            serde_json::from_str(&index_meta).map_err(|err| Error::Deserialize(err.to_string()))?;
+        let mut record = Self::map_row_to_object_record_slim(row)?;
+        record.index_meta = index_meta;
+        Ok(Some(record))
+    }
+
+    async fn sample_object_records(
+        &self,
+        type_name: &'static str,
+        limit: u32,
+    ) -> Result<Vec<ObjectRecord>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, type, owner, created_at, updated_at, data, index_meta
+            FROM objects
+            WHERE type = ?
+            ORDER BY RANDOM()
+            LIMIT ?
+            "#,
+        )
+        .bind(type_name)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let index_meta: String = row
+                    .try_get("index_meta")
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let index_meta = serde_json::from_str(&index_meta)
+                    .map_err(|err| Error::Deserialize(err.to_string()))?;
+                let mut record = Self::map_row_to_object_record_slim(row)?;
+                record.index_meta = index_meta;
+                Ok(record)
+            })
+            .collect()
+    }
+
+    async fn insert_history_snapshot(
+        &self,
+        record: ObjectRecord,
+        version: u64,
+    ) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO object_history (id, version, type, owner, created_at, updated_at, data, index_meta)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(record.id)
+        .bind(version as i64)
+        .bind(record.type_name.as_ref())
+        .bind(record.owner)
+        .bind(record.created_at.to_rfc3339())
+        .bind(record.updated_at.to_rfc3339())
+        .bind(serde_json::to_string(&record.data).map_err(|e| Error::Serialize(e.to_string()))?)
+        .bind(
+            serde_json::to_string(&record.index_meta)
+                .map_err(|e| Error::Serialize(e.to_string()))?,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(())
+    }
+
+    async fn update_object_with_history(
+        &self,
+        type_name: &'static str,
+        record: ObjectRecord,
+        previous: ObjectRecord,
+    ) -> Result<u64, Error> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let updated = sqlx::query(
+            r#"
+            UPDATE objects
+            SET updated_at = ?, data = ?, index_meta = ?
+            WHERE id = ? AND type = ?
+            "#,
+        )
+        .bind(record.updated_at.to_rfc3339())
+        .bind(serde_json::to_string(&record.data).map_err(|e| Error::Serialize(e.to_string()))?)
+        .bind(
+            serde_json::to_string(&record.index_meta)
+                .map_err(|e| Error::Serialize(e.to_string()))?,
+        )
+        .bind(record.id)
+        .bind(type_name)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        if updated.rows_affected() == 0 {
+            return Ok(0);
+        }
+
+        // The UPDATE holds the write lock, so no other snapshot can slip in
+        let version: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(MAX(version), 0) + 1 FROM object_history WHERE id = ?",
+        )
+        .bind(previous.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO object_history (id, version, type, owner, created_at, updated_at, data, index_meta)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(previous.id)
+        .bind(version)
+        .bind(previous.type_name.as_ref())
+        .bind(previous.owner)
+        .bind(previous.created_at.to_rfc3339())
+        .bind(previous.updated_at.to_rfc3339())
+        .bind(serde_json::to_string(&previous.data).map_err(|e| Error::Serialize(e.to_string()))?)
+        .bind(
+            serde_json::to_string(&previous.index_meta)
+                .map_err(|e| Error::Serialize(e.to_string()))?,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(version as u64)
+    }
+
+    async fn latest_history_version(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<u64, Error> {
+        let version: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(MAX(version), 0) FROM object_history
+            WHERE id = ? AND type = ?
+            "#,
+        )
+        .bind(id)
+        .bind(type_name)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(version as u64)
+    }
+
+    async fn fetch_history(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Vec<(u64, ObjectRecord)>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, version, type, owner, created_at, updated_at, data
+            FROM object_history
+            WHERE id = ? AND type = ?
+            ORDER BY version
+            "#,
+        )
+        .bind(id)
+        .bind(type_name)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let version: i64 = row
+                    .try_get("version")
+                    .map_err(|e| Error::Deserialize(e.to_string()))?;
+                Ok((version as u64, Self::map_row_to_object_record_slim(row)?))
+            })
+            .collect()
+    }
+
+    async fn fetch_change_log(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<ChangeLogEntry>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM (
+                SELECT id, object_id, type, operation, old_data, new_data, changed_at, changed_by
+                FROM object_audit_log
+                WHERE object_id = ? AND type = ?
+                ORDER BY id DESC
+                LIMIT ?
+            )
+            ORDER BY id
+            "#,
+        )
+        .bind(id)
+        .bind(type_name)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(Self::map_row_to_change_log_entry)
+            .collect()
+    }
+
+    async fn fetch_type_change_log(
+        &self,
+        type_name: &'static str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<ChangeLogEntry>, Error> {
+        // julianday() compares the timestamps as instants, not as strings
+        let rows = sqlx::query(
+            r#"
+            SELECT id, object_id, type, operation, old_data, new_data, changed_at, changed_by
+            FROM object_audit_log
+            WHERE type = ? AND julianday(changed_at) >= julianday(?)
+            ORDER BY id
+            "#,
+        )
+        .bind(type_name)
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(Self::map_row_to_change_log_entry)
+            .collect()
     }
 
     async fn fetch_owned_objects_batch(
@@ -1265,6 +2687,114 @@ impl Adapter for SqliteAdapter {
             .collect()
     }
 
+    async fn fts_search(
+        &self,
+        type_name: &'static str,
+        text: &str,
+        limit: u32,
+    ) -> Result<Vec<ObjectRecord>, Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects_fts
+            JOIN objects o ON o.id = objects_fts.id
+            WHERE objects_fts MATCH ? AND objects_fts.type = ?
+            ORDER BY objects_fts.rank
+            LIMIT ?
+            "#,
+        )
+        .bind(text)
+        .bind(type_name)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(Self::map_row_to_object_record_slim)
+            .collect()
+    }
+
+    async fn object_search(
+        &self,
+        type_name: &'static str,
+        text: &str,
+        plan: Query,
+    ) -> Result<Vec<(ObjectRecord, f64)>, Error> {
+        Self::reject_regex(&plan.filters)?;
+        // Quote every word so `text` is matched as plain terms, not FTS5 syntax
+        let terms = text
+            .split_whitespace()
+            .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut where_clause = Self::build_object_query_conditions(&plan.filters, plan.cursor);
+        if plan.is_global() {
+            where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+        }
+        let order_clause = if plan.filters.iter().any(|f| f.mode.as_sort().is_some()) {
+            Self::build_order_clause_aliased(&plan.filters, "o", false)
+        } else {
+            "ORDER BY objects_fts.rank, o.id DESC".to_string()
+        };
+
+        // FTS5 ranks with bm25, where lower is better; negate it into a score
+        let mut sql = format!(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data,
+                -objects_fts.rank AS score
+            FROM objects_fts
+            JOIN objects o ON o.id = objects_fts.id
+            {where_clause}
+                AND objects_fts MATCH ?
+            {order_clause}
+            "#
+        );
+        if let Some(limit) = plan.effective_limit() {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut query = sqlx::query(&sql).bind(type_name).bind(plan.owner_param());
+        if let Some(cursor) = plan.cursor {
+            query = query.bind(cursor.last_id);
+        }
+        query = Self::query_bind_filters(query, &plan.filters);
+
+        let rows = query
+            .bind(terms)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let score: f64 = row
+                    .try_get("score")
+                    .map_err(|e| Error::Deserialize(e.to_string()))?;
+                Ok((Self::map_row_to_object_record_slim(row)?, score))
+            })
+            .collect()
+    }
+
+    async fn list_namespaces(&self, type_name: &'static str) -> Result<Vec<String>, Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT json_extract(data, '$.__namespace')
+            FROM objects
+            WHERE type = ? AND json_extract(data, '$.__namespace') IS NOT NULL
+            ORDER BY 1
+            "#,
+        )
+        .bind(type_name)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))
+    }
+
     async fn fetch_owned_objects(
         &self,
         type_name: &'static str,
@@ -1423,6 +2953,63 @@ impl Adapter for SqliteAdapter {
             .collect()
     }
 
+    async fn query_union_objects(
+        &self,
+        a_type_name: &'static str,
+        b_type_name: &'static str,
+        plan: Query,
+    ) -> Result<Vec<ObjectRecord>, Error> {
+        Self::reject_regex(&plan.filters)?;
+        let mut where_clause = Self::build_object_query_conditions(&plan.filters, plan.cursor)
+            .replacen("o.type = ?", "(o.type = ? OR o.type = ?)", 1);
+        let order_clause = if plan.sample_size.is_some() {
+            "ORDER BY RANDOM()".to_string()
+        } else {
+            Self::build_order_clause(&plan.filters)
+        };
+
+        if plan.is_global() {
+            where_clause = where_clause.replace("o.owner = ", "o.owner > ");
+        }
+
+        let group_clause = if plan.distinct { "GROUP BY o.id" } else { "" };
+
+        let mut sql = format!(
+            r#"
+            SELECT o.id, o.type, o.owner, o.created_at, o.updated_at, o.data
+            FROM objects o
+            {}
+            {}
+            {}
+            "#,
+            where_clause, group_clause, order_clause
+        );
+
+        if let Some(limit) = plan.effective_limit() {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut query = sqlx::query(&sql)
+            .bind(a_type_name)
+            .bind(b_type_name)
+            .bind(plan.owner_param());
+
+        if let Some(cursor) = plan.cursor {
+            query = query.bind(cursor.last_id);
+        }
+
+        query = Self::query_bind_filters(query, &plan.filters);
+
+        let rows = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        rows.into_iter()
+            .map(Self::map_row_to_object_record_slim)
+            .collect()
+    }
+
     /* ---------------- EDGES ---------------- */
     async fn insert_edge(&self, record: EdgeRecord) -> Result<(), Error> {
         let EdgeRecord {
@@ -1458,6 +3045,207 @@ impl Adapter for SqliteAdapter {
         Ok(())
     }
 
+    async fn insert_edge_or_ignore(&self, record: EdgeRecord) -> Result<bool, Error> {
+        let EdgeRecord {
+            from,
+            to,
+            type_name,
+            data,
+            index_meta,
+        } = record;
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO edges ("from", "to", type, data, index_meta)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(type_name.as_ref())
+        .bind(serde_json::to_string(&data).map_err(|e| Error::Serialize(e.to_string()))?)
+        .bind(serde_json::to_string(&index_meta).map_err(|e| Error::Serialize(e.to_string()))?)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn upsert_edge(&self, record: EdgeRecord) -> Result<UpsertOutcome, Error> {
+        let EdgeRecord {
+            from,
+            to,
+            type_name,
+            data,
+            index_meta,
+        } = record;
+        let data_str = serde_json::to_string(&data).map_err(|e| Error::Serialize(e.to_string()))?;
+        let index_meta_str =
+            serde_json::to_string(&index_meta).map_err(|e| Error::Serialize(e.to_string()))?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO edges ("from", "to", type, data, index_meta)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT ("from", "to", type) DO NOTHING
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(type_name.as_ref())
+        .bind(&data_str)
+        .bind(&index_meta_str)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?
+        .rows_affected();
+
+        if inserted == 0 {
+            sqlx::query(
+                r#"
+                UPDATE edges SET data = ?, index_meta = ?
+                WHERE "from" = ? AND "to" = ? AND type = ?
+                "#,
+            )
+            .bind(&data_str)
+            .bind(&index_meta_str)
+            .bind(from)
+            .bind(to)
+            .bind(type_name.as_ref())
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(UpsertOutcome {
+            created: inserted > 0,
+        })
+    }
+
+    async fn upsert_edges_batch(&self, records: Vec<EdgeRecord>) -> Result<u64, Error> {
+        // No array binding in SQLite: one statement per edge, one transaction
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let mut written = 0;
+        for record in records {
+            let data_str =
+                serde_json::to_string(&record.data).map_err(|e| Error::Serialize(e.to_string()))?;
+            let index_meta_str = serde_json::to_string(&record.index_meta)
+                .map_err(|e| Error::Serialize(e.to_string()))?;
+
+            written += sqlx::query(
+                r#"
+                INSERT INTO edges ("from", "to", type, data, index_meta)
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT ("from", "to", type)
+                DO UPDATE SET data = excluded.data, index_meta = excluded.index_meta
+                "#,
+            )
+            .bind(record.from)
+            .bind(record.to)
+            .bind(record.type_name.as_ref())
+            .bind(&data_str)
+            .bind(&index_meta_str)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?
+            .rows_affected();
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(written)
+    }
+
+    async fn insert_edges_batch(
+        &self,
+        records: Vec<EdgeRecord>,
+        on_conflict: OnConflict,
+    ) -> Result<BatchEdgeResult, Error> {
+        let insert = match on_conflict {
+            OnConflict::Ignore => "INSERT OR IGNORE",
+            OnConflict::Replace => "INSERT OR REPLACE",
+            OnConflict::Error => "INSERT",
+        };
+        let sql = format!(
+            r#"{insert} INTO edges ("from", "to", type, data, index_meta) VALUES (?, ?, ?, ?, ?)"#
+        );
+
+        // One statement per edge, one transaction; dropping it on error rolls back
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        let mut result = BatchEdgeResult::default();
+        for record in records {
+            let data_str =
+                serde_json::to_string(&record.data).map_err(|e| Error::Serialize(e.to_string()))?;
+            let index_meta_str = serde_json::to_string(&record.index_meta)
+                .map_err(|e| Error::Serialize(e.to_string()))?;
+
+            // INSERT OR REPLACE reports one row either way
+            let exists = on_conflict == OnConflict::Replace
+                && sqlx::query_scalar::<_, i64>(
+                    r#"SELECT COUNT(*) FROM edges WHERE "from" = ? AND "to" = ? AND type = ?"#,
+                )
+                .bind(record.from)
+                .bind(record.to)
+                .bind(record.type_name.as_ref())
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|err| Error::Storage(err.to_string()))?
+                    > 0;
+
+            let written = sqlx::query(&sql)
+                .bind(record.from)
+                .bind(record.to)
+                .bind(record.type_name.as_ref())
+                .bind(&data_str)
+                .bind(&index_meta_str)
+                .execute(&mut *tx)
+                .await
+                .map_err(|err| {
+                    if err
+                        .as_database_error()
+                        .is_some_and(|e| e.is_unique_violation())
+                    {
+                        Error::unique_violation("from+to")
+                    } else {
+                        Error::Storage(err.to_string())
+                    }
+                })?
+                .rows_affected();
+
+            if written == 0 {
+                result.skipped += 1;
+            } else if exists {
+                result.replaced += 1;
+            } else {
+                result.created += 1;
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(result)
+    }
+
     async fn update_edge(
         &self,
         record: EdgeRecord,
@@ -1514,16 +3302,32 @@ impl Adapter for SqliteAdapter {
         let _ = sqlx::query(
             r#"
             DELETE FROM edges
-            WHERE type = ? AND "from" = ?
+            WHERE type = ? AND "from" = ?
+            "#,
+        )
+        .bind(type_name)
+        .bind(from.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete_edges_for_node(&self, node_id: Uuid) -> Result<u64, Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM edges
+            WHERE "from" = ? OR "to" = ?
             "#,
         )
-        .bind(type_name)
-        .bind(from.to_string())
+        .bind(node_id)
+        .bind(node_id)
         .execute(&self.pool)
         .await
         .map_err(|err| Error::Storage(err.to_string()))?;
 
-        Ok(())
+        Ok(result.rows_affected())
     }
 
     async fn fetch_edge(
@@ -1716,6 +3520,78 @@ impl Adapter for SqliteAdapter {
         }
     }
 
+    async fn aggregate_edge_field(
+        &self,
+        type_name: &'static str,
+        from: Uuid,
+        field: &'static str,
+        agg: Aggregation,
+    ) -> Result<f64, Error> {
+        let sql = format!(
+            r#"SELECT COALESCE({}(CAST(json_extract(index_meta, ?) AS REAL)), 0.0) FROM edges WHERE type = ? AND "from" = ?"#,
+            agg.as_sql()
+        );
+        sqlx::query_scalar(&sql)
+            .bind(format!("$.{field}"))
+            .bind(type_name)
+            .bind(from)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+
+    async fn aggregate_reverse_edge_field(
+        &self,
+        type_name: &'static str,
+        to: Uuid,
+        field: &'static str,
+        agg: Aggregation,
+    ) -> Result<f64, Error> {
+        let sql = format!(
+            r#"SELECT COALESCE({}(CAST(json_extract(index_meta, ?) AS REAL)), 0.0) FROM edges WHERE type = ? AND "to" = ?"#,
+            agg.as_sql()
+        );
+        sqlx::query_scalar(&sql)
+            .bind(format!("$.{field}"))
+            .bind(type_name)
+            .bind(to)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+
+    async fn aggregate_edge_field_batch(
+        &self,
+        type_name: &'static str,
+        from_ids: &[Uuid],
+        field: &'static str,
+        agg: Aggregation,
+    ) -> Result<Vec<(Uuid, f64)>, Error> {
+        if from_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let placeholders = vec!["?"; from_ids.len()].join(", ");
+        let sql = format!(
+            r#"
+            SELECT "from", COALESCE({}(CAST(json_extract(index_meta, ?) AS REAL)), 0.0)
+            FROM edges
+            WHERE type = ? AND "from" IN ({placeholders})
+            GROUP BY "from"
+            "#,
+            agg.as_sql()
+        );
+        let mut query = sqlx::query_as(&sql)
+            .bind(format!("$.{field}"))
+            .bind(type_name);
+        for id in from_ids {
+            query = query.bind(*id);
+        }
+        query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))
+    }
+
     async fn sequence_value(&self, sq: String) -> u64 {
         let val: i64 =
             sqlx::query_scalar("SELECT COALESCE((SELECT value FROM sequences WHERE name = ?), 1)")
@@ -1726,12 +3602,12 @@ impl Adapter for SqliteAdapter {
         val as u64
     }
 
-    async fn sequence_next_value(&self, sq: String) -> u64 {
+    async fn try_sequence_next_value(&self, sq: String) -> Result<u64, Error> {
         let mut tx = self
             .pool
             .begin()
             .await
-            .expect("Failed to begin transaction");
+            .map_err(|err| Error::Storage(err.to_string()))?;
 
         sqlx::query(
             "INSERT INTO sequences (name, value) VALUES (?, 2)
@@ -1740,17 +3616,353 @@ impl Adapter for SqliteAdapter {
         .bind(&sq)
         .execute(&mut *tx)
         .await
-        .expect("Failed to upsert sequence");
+        .map_err(|err| Error::Storage(err.to_string()))?;
 
         let next_val: i64 = sqlx::query_scalar("SELECT value FROM sequences WHERE name = ?")
             .bind(&sq)
             .fetch_one(&mut *tx)
             .await
-            .expect("Failed to fetch the next sequence value");
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+
+        Ok(next_val as u64)
+    }
+
+    async fn sequence_reset(&self, sq: String, to: u64) -> Result<(), Error> {
+        sqlx::query(
+            "INSERT INTO sequences (name, value) VALUES (?, ?)
+             ON CONFLICT (name) DO UPDATE SET value = excluded.value",
+        )
+        .bind(&sq)
+        .bind(to as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn sequence_list(&self) -> Result<Vec<(String, u64)>, Error> {
+        let rows: Vec<(String, i64)> =
+            sqlx::query_as("SELECT name, value FROM sequences ORDER BY name")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(rows
+            .into_iter()
+            .map(|(name, value)| (name, value as u64))
+            .collect())
+    }
+
+    async fn vacuum(&self) -> Result<(), Error> {
+        sqlx::raw_sql("VACUUM")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn reindex(&self) -> Result<(), Error> {
+        sqlx::raw_sql("REINDEX objects; REINDEX edges; REINDEX unique_constraints")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn delete_orphan_unique_constraints(&self) -> Result<u64, Error> {
+        // Object hashes are keyed by object ID, edge hashes by the edge's `from`
+        let result = sqlx::query(
+            r#"
+            DELETE FROM unique_constraints
+            WHERE NOT EXISTS (
+                SELECT 1 FROM objects o
+                WHERE o.id = unique_constraints.id AND o.type = unique_constraints.type
+            )
+            AND NOT EXISTS (
+                SELECT 1 FROM edges e
+                WHERE e."from" = unique_constraints.id AND e.type = unique_constraints.type
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn set_retention_policy(&self, type_name: &str, ttl_seconds: u64) -> Result<(), Error> {
+        sqlx::query(
+            "INSERT INTO retention_policies (type, ttl_seconds) VALUES (?, ?)
+             ON CONFLICT (type) DO UPDATE SET ttl_seconds = excluded.ttl_seconds",
+        )
+        .bind(type_name)
+        .bind(ttl_seconds as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn retention_policies(&self) -> Result<Vec<(String, u64)>, Error> {
+        let rows: Vec<(String, i64)> =
+            sqlx::query_as("SELECT type, ttl_seconds FROM retention_policies ORDER BY type")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(rows
+            .into_iter()
+            .map(|(type_name, ttl)| (type_name, ttl as u64))
+            .collect())
+    }
+
+    async fn delete_objects_created_before(
+        &self,
+        type_name: &str,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<Uuid>, Error> {
+        // created_at is RFC 3339 text; julianday compares it as a point in time
+        let cutoff = cutoff.to_rfc3339();
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+        let ids: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM objects WHERE type = ? AND julianday(created_at) < julianday(?)",
+        )
+        .bind(type_name)
+        .bind(&cutoff)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        if !ids.is_empty() {
+            sqlx::query(
+                r#"
+                DELETE FROM unique_constraints
+                WHERE type = ?1 AND id IN (
+                    SELECT id FROM objects
+                    WHERE type = ?1 AND julianday(created_at) < julianday(?2)
+                )
+                "#,
+            )
+            .bind(type_name)
+            .bind(&cutoff)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+            sqlx::query(
+                "DELETE FROM objects WHERE type = ? AND julianday(created_at) < julianday(?)",
+            )
+            .bind(type_name)
+            .bind(&cutoff)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(ids)
+    }
+
+    async fn table_stats(&self) -> Result<TableStats, Error> {
+        let (objects, edges, uniques): (i64, i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM objects),
+                (SELECT COUNT(*) FROM edges),
+                (SELECT COUNT(*) FROM unique_constraints)
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(TableStats {
+            objects_count: objects as u64,
+            edges_count: edges as u64,
+            unique_constraints_count: uniques as u64,
+            // SQLite can't attribute file size to individual tables
+            estimated_size_bytes: None,
+        })
+    }
+
+    async fn export_schema(&self) -> Result<SchemaExport, Error> {
+        // SQLite keeps the original CREATE statements
+        let rows: Vec<(String, String, String, String)> = sqlx::query_as(
+            r#"
+            SELECT type, name, tbl_name, sql FROM sqlite_master
+            WHERE type IN ('table', 'index') AND sql IS NOT NULL
+            ORDER BY name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        let mut export = SchemaExport::default();
+        for (kind, name, table, sql) in rows {
+            match (kind.as_str(), name.as_str()) {
+                ("table", "objects") => export.objects_ddl = sql,
+                ("table", "edges") => export.edges_ddl = sql,
+                ("table", "sequences") => export.sequences_ddl = sql,
+                ("index", _) if SCHEMA_TABLES.contains(&table.as_str()) => {
+                    export.indexes_ddl.push(sql)
+                }
+                _ => {}
+            }
+        }
+
+        for table in ["objects", "edges", "sequences"] {
+            let columns: Vec<(String, String, bool, Option<String>)> = sqlx::query_as(
+                r#"SELECT name, type, "notnull", dflt_value FROM pragma_table_info(?) ORDER BY cid"#,
+            )
+            .bind(table)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+            export.columns.extend(columns.into_iter().map(
+                |(name, data_type, not_null, default)| SchemaColumn {
+                    table: table.to_string(),
+                    name,
+                    data_type,
+                    nullable: !not_null,
+                    default,
+                },
+            ));
+        }
+        Ok(export)
+    }
+
+    async fn verify_schema(&self) -> Result<SchemaVerification, Error> {
+        let rows: Vec<(String, String, String)> = sqlx::query_as(
+            r#"
+            SELECT type, name, tbl_name FROM sqlite_master
+            WHERE type IN ('table', 'index') AND name NOT LIKE 'sqlite_%'
+            ORDER BY name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        let mut tables = Vec::new();
+        let mut indexes = Vec::new();
+        for (kind, name, table) in rows {
+            if kind == "table" {
+                tables.push(name);
+            } else if SCHEMA_TABLES.contains(&table.as_str()) {
+                indexes.push(name);
+            }
+        }
+        Ok(SchemaVerification::compare(
+            &tables,
+            &indexes,
+            SCHEMA_TABLES,
+            SCHEMA_INDEXES,
+        ))
+    }
+
+    async fn rename_edge_type(&self, old_type: &str, new_type: &str) -> Result<u64, Error> {
+        // UPDATE has no ON CONFLICT; leave colliding pairs under the old type
+        let result = sqlx::query(
+            r#"
+            UPDATE edges SET type = ?2
+            WHERE type = ?1
+              AND NOT EXISTS (
+                  SELECT 1 FROM edges n
+                  WHERE n.type = ?2 AND n."from" = edges."from" AND n."to" = edges."to"
+              )
+            "#,
+        )
+        .bind(old_type)
+        .bind(new_type)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn copy_edge_type(&self, old_type: &str, new_type: &str) -> Result<u64, Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO edges ("from", "to", type, data, index_meta)
+            SELECT "from", "to", ?2, data, index_meta FROM edges WHERE type = ?1
+            "#,
+        )
+        .bind(old_type)
+        .bind(new_type)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn count_edges_by_type(&self, type_name: &str) -> Result<u64, Error> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM edges WHERE type = ?1")
+            .bind(type_name)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(count as u64)
+    }
+
+    async fn type_stats(&self) -> Result<Vec<TypeStats>, Error> {
+        // created_at is RFC 3339 in UTC, so the text MAX is the latest
+        let rows: Vec<(String, i64, String)> = sqlx::query_as(
+            r#"
+            SELECT type, COUNT(*), MAX(created_at)
+            FROM objects
+            GROUP BY type
+            ORDER BY type
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Error::Storage(e.to_string()))?;
+
+        rows.into_iter()
+            .map(|(type_name, count, latest)| {
+                let latest_created_at = DateTime::parse_from_rfc3339(&latest)
+                    .map_err(|e| Error::Deserialize(e.to_string()))?
+                    .with_timezone(&Utc);
+                Ok(TypeStats {
+                    type_name,
+                    count: count as u64,
+                    latest_created_at,
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "raw-queries")]
+    async fn execute_raw(
+        &self,
+        sql: &str,
+        params: Vec<serde_json::Value>,
+    ) -> Result<Vec<serde_json::Value>, Error> {
+        let rows = Self::bind_raw_params(sqlx::query(sql), params)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
 
-        tx.commit().await.expect("Failed to commit transaction");
+        rows.iter().map(Self::map_row_to_json).collect()
+    }
 
-        next_val as u64
+    async fn begin(&self) -> Result<DatabaseTransaction, Error> {
+        let tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(DatabaseTransaction::new(SqliteTransaction::new(tx)))
     }
 }
 
@@ -1783,7 +3995,7 @@ impl UniqueAdapter for SqliteAdapter {
             .map_err(|err| {
                 // Check if it's a uniqueness violation
                 if err.to_string().contains("unique") {
-                    Error::UniqueConstraintViolation(field.to_string())
+                    Error::unique_violation(field)
                 } else {
                     Error::Storage(err.to_string())
                 }
@@ -1831,6 +4043,52 @@ impl UniqueAdapter for SqliteAdapter {
         Ok(())
     }
 
+    async fn backfill_unique_constraints(
+        &self,
+        type_name: &str,
+        entries: Vec<(Uuid, Vec<(String, &str)>)>,
+    ) -> Result<u64, Error> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        let mut inserted = 0;
+        for (object_id, hashes) in entries {
+            for (hash, field) in hashes {
+                let result = sqlx::query(
+                    r#"
+                    INSERT OR IGNORE INTO unique_constraints (id, type, key, field)
+                    VALUES (?, ?, ?, ?)
+                    "#,
+                )
+                .bind(object_id)
+                .bind(type_name)
+                .bind(hash)
+                .bind(field)
+                .execute(&mut *tx)
+                .await
+                .map_err(|err| Error::Storage(err.to_string()))?;
+                inserted += result.rows_affected();
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(inserted)
+    }
+
+    async fn delete_unique_for_type(&self, type_name: &str) -> Result<(), Error> {
+        sqlx::query("DELETE FROM unique_constraints WHERE type = ?")
+            .bind(type_name)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+
+        Ok(())
+    }
+
     async fn get_hashes_for_object(&self, object_id: Uuid) -> Result<Vec<String>, Error> {
         let rows = sqlx::query(
             r#"
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    adapters::{EdgeRecord, ObjectRecord},
+    error::Error,
+};
+
+/// Backend side of a [`DatabaseTransaction`]: the same operations as
+/// [`Adapter`](super::Adapter), run on the connection the transaction holds.
+#[async_trait]
+pub trait TransactionBackend: Send + Sync {
+    async fn insert_object(&self, record: ObjectRecord) -> Result<(), Error>;
+    async fn fetch_object(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error>;
+    async fn update_object(&self, record: ObjectRecord) -> Result<(), Error>;
+    async fn delete_object(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+        owner: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error>;
+
+    async fn insert_edge(&self, record: EdgeRecord) -> Result<(), Error>;
+    async fn delete_edge(&self, type_name: &'static str, from: Uuid, to: Uuid)
+    -> Result<(), Error>;
+
+    async fn commit(&self) -> Result<(), Error>;
+    async fn rollback(&self) -> Result<(), Error>;
+}
+
+/// An open database transaction, from [`Adapter::begin`](super::Adapter::begin).
+///
+/// Writes made through it are invisible to other connections until
+/// [`commit`](Self::commit). Dropping the last clone without committing
+/// rolls back: the sqlx transaction underneath queues a `ROLLBACK` on its
+/// connection, which runs before the pool hands that connection out again,
+/// so `Drop` never has to block.
+#[must_use = "dropping a transaction without committing rolls it back"]
+#[derive(Clone)]
+pub struct DatabaseTransaction {
+    inner: Arc<dyn TransactionBackend>,
+}
+
+impl DatabaseTransaction {
+    pub fn new(backend: impl TransactionBackend + 'static) -> Self {
+        Self {
+            inner: Arc::new(backend),
+        }
+    }
+
+    pub async fn insert_object(&self, record: ObjectRecord) -> Result<(), Error> {
+        self.inner.insert_object(record).await
+    }
+
+    pub async fn fetch_object(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error> {
+        self.inner.fetch_object(type_name, id).await
+    }
+
+    pub async fn update_object(&self, record: ObjectRecord) -> Result<(), Error> {
+        self.inner.update_object(record).await
+    }
+
+    pub async fn delete_object(
+        &self,
+        type_name: &'static str,
+        id: Uuid,
+        owner: Uuid,
+    ) -> Result<Option<ObjectRecord>, Error> {
+        self.inner.delete_object(type_name, id, owner).await
+    }
+
+    pub async fn insert_edge(&self, record: EdgeRecord) -> Result<(), Error> {
+        self.inner.insert_edge(record).await
+    }
+
+    pub async fn delete_edge(
+        &self,
+        type_name: &'static str,
+        from: Uuid,
+        to: Uuid,
+    ) -> Result<(), Error> {
+        self.inner.delete_edge(type_name, from, to).await
+    }
+
+    pub async fn commit(self) -> Result<(), Error> {
+        self.inner.commit().await
+    }
+
+    pub async fn rollback(self) -> Result<(), Error> {
+        self.inner.rollback().await
+    }
+}
+
+/// Returned when a transaction is used after `commit` or `rollback`
+pub(crate) fn finished() -> Error {
+    Error::Storage("transaction already finished".to_string())
+}
@@ -1,4 +1,5 @@
 pub mod meta;
+pub mod page;
 pub mod query;
 pub mod traits;
 
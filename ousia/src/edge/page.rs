@@ -0,0 +1,44 @@
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// Opaque position in an edge listing: the last node id of the previous page.
+///
+/// Round-trips through a URL-safe base64 string so it can be handed to
+/// clients as-is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeCursor(Uuid);
+
+impl EdgeCursor {
+    pub fn new(last: Uuid) -> Self {
+        Self(last)
+    }
+
+    pub fn last(&self) -> Uuid {
+        self.0
+    }
+
+    pub fn to_base64(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.0.as_bytes())
+    }
+
+    pub fn from_base64(s: &str) -> Result<Self, Error> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(s)
+            .map_err(|err| Error::InvalidQuery(format!("invalid edge cursor: {}", err)))?;
+        Uuid::from_slice(&bytes)
+            .map(Self)
+            .map_err(|err| Error::InvalidQuery(format!("invalid edge cursor: {}", err)))
+    }
+}
+
+/// One page of edges from [`Engine::paginate_edges`](crate::Engine::paginate_edges)
+#[derive(Debug, Clone)]
+pub struct Page<E> {
+    pub items: Vec<E>,
+    /// Pass back to fetch the next page; `None` on the last page
+    pub next_cursor: Option<EdgeCursor>,
+    pub has_next: bool,
+}
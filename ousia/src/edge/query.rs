@@ -1,8 +1,8 @@
 use uuid::Uuid;
 
 use crate::query::{
-    Comparison, Cursor, IndexField, Operator, QueryFilter, QueryMode, QuerySearch, QuerySort,
-    ToIndexValue,
+    Comparison, Cursor, IndexField, IndexValue, Operator, QueryFilter, QueryMode, QuerySearch,
+    QuerySort, ToIndexValue,
 };
 
 /// -----------------------------
@@ -74,6 +74,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Equal,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -88,6 +89,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::NotEqual,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -102,6 +104,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThan,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -116,6 +119,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThanOrEqual,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -130,6 +134,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThan,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -144,6 +149,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThanOrEqual,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -158,6 +164,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Contains,
                 operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -172,6 +179,37 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::BeginsWith,
                 operator: Operator::default(),
+                case_sensitive: true,
+            }),
+        });
+        consumed_self
+    }
+
+    /// Edges whose `field` is null or missing, e.g. an `Option` left as `None`
+    pub fn where_null(self, field: &'static IndexField) -> Self {
+        let mut consumed_self = self;
+        consumed_self.filters.push(QueryFilter {
+            field,
+            value: IndexValue::Null,
+            mode: QueryMode::Search(QuerySearch {
+                comparison: Comparison::IsNull,
+                operator: Operator::default(),
+                case_sensitive: true,
+            }),
+        });
+        consumed_self
+    }
+
+    /// Edges whose `field` holds a value
+    pub fn where_not_null(self, field: &'static IndexField) -> Self {
+        let mut consumed_self = self;
+        consumed_self.filters.push(QueryFilter {
+            field,
+            value: IndexValue::Null,
+            mode: QueryMode::Search(QuerySearch {
+                comparison: Comparison::IsNotNull,
+                operator: Operator::default(),
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -207,6 +245,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Equal,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -220,6 +259,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::NotEqual,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -233,6 +273,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThan,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -246,6 +287,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::GreaterThanOrEqual,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -259,6 +301,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThan,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -272,6 +315,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::LessThanOrEqual,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -285,6 +329,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::Contains,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
@@ -298,6 +343,7 @@ impl EdgeQuery {
             mode: QueryMode::Search(QuerySearch {
                 comparison: Comparison::BeginsWith,
                 operator: Operator::Or,
+                case_sensitive: true,
             }),
         });
         consumed_self
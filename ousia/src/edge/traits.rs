@@ -10,6 +10,9 @@ pub trait Edge: Serialize + for<'de> Deserialize<'de> + Sized + Send + Sync + 's
     /// Edge logical type (e.g. "Follow", "Member", "Like")
     const TYPE: &'static str;
 
+    /// Set by `#[ousia(unique = "from")]` / `#[ousia(unique = "from+to")]`
+    const HAS_UNIQUE_FIELDS: bool = false;
+
     /// Object type name helper
     fn type_name(&self) -> &'static str {
         Self::TYPE
@@ -21,6 +24,11 @@ pub trait Edge: Serialize + for<'de> Deserialize<'de> + Sized + Send + Sync + 's
 
     /// Indexable fields
     fn index_meta(&self) -> IndexMeta;
+
+    /// Uniqueness hashes an edge `from → to` of this type claims
+    fn unique_hashes(_from: Uuid, _to: Uuid) -> Vec<(String, &'static str)> {
+        Vec::new()
+    }
 }
 
 pub trait EdgeMetaTrait {
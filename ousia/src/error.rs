@@ -1,12 +1,63 @@
 use std::fmt::Display;
 
+use serde::Serialize;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
     NotFound,
     Serialize(String),
     Deserialize(String),
     Storage(String),
-    UniqueConstraintViolation(String),
+    /// One or more fields were rejected; unique constraint violations land here
+    ValidationFailed(Vec<FieldError>),
+    InvalidQuery(String),
+    /// The operation doesn't make sense for the given arguments
+    InvalidOperation(String),
+    /// A JSON patch that isn't an object or touches fields it may not
+    InvalidPatch(String),
+    PermissionDenied,
+    LockTimeout,
+    /// The adapter doesn't implement this operation
+    NotSupported,
+    /// The nil UUID was given where an object ID is required
+    InvalidId,
+    /// A sequence can't be set to this value (zero, or beyond `i64::MAX`)
+    InvalidSequenceValue,
+}
+
+/// Why a field failed validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationCode {
+    Required,
+    TooLong,
+    TooShort,
+    OutOfRange,
+    InvalidFormat,
+    UniqueViolation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+    pub code: ValidationCode,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>, code: ValidationCode) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+            code,
+        }
+    }
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
 }
 
 impl Display for Error {
@@ -16,9 +67,24 @@ impl Display for Error {
             Error::Serialize(err) => write!(f, "Serialization error: {}", err),
             Error::Deserialize(err) => write!(f, "Deserialization error: {}", err),
             Error::Storage(err) => write!(f, "Storage error: {}", err),
-            Error::UniqueConstraintViolation(field) => {
-                write!(f, "Unique constraint violation on field: {}", field)
+            Error::ValidationFailed(errors) => {
+                write!(f, "Validation failed: ")?;
+                for (i, err) in errors.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}", err)?;
+                }
+                Ok(())
             }
+            Error::InvalidQuery(err) => write!(f, "Invalid query: {}", err),
+            Error::InvalidOperation(err) => write!(f, "Invalid operation: {}", err),
+            Error::InvalidPatch(err) => write!(f, "Invalid patch: {}", err),
+            Error::PermissionDenied => write!(f, "Permission denied"),
+            Error::LockTimeout => write!(f, "Timed out waiting for lock"),
+            Error::NotSupported => write!(f, "Operation not supported by this adapter"),
+            Error::InvalidId => write!(f, "Invalid ID: the nil UUID can't identify an object"),
+            Error::InvalidSequenceValue => write!(f, "Invalid sequence value"),
         }
     }
 }
@@ -26,10 +92,65 @@ impl Display for Error {
 impl std::error::Error for Error {}
 
 impl Error {
+    /// `field` (or a `+`-joined composite like `"username+email"`) is already taken
+    pub fn unique_violation(field: impl Into<String>) -> Self {
+        Error::ValidationFailed(vec![FieldError::new(
+            field,
+            "must be unique",
+            ValidationCode::UniqueViolation,
+        )])
+    }
+
     pub fn is_unique_constraint_violation(&self) -> bool {
+        self.validation_errors().is_some_and(|errors| {
+            errors
+                .iter()
+                .any(|err| err.code == ValidationCode::UniqueViolation)
+        })
+    }
+
+    pub fn is_validation(&self) -> bool {
+        matches!(self, Error::ValidationFailed(_))
+    }
+
+    pub fn validation_errors(&self) -> Option<&[FieldError]> {
         match self {
-            Error::UniqueConstraintViolation(_) => true,
-            _ => false,
+            Error::ValidationFailed(errors) => Some(errors),
+            _ => None,
         }
     }
 }
+
+#[cfg(feature = "axum")]
+impl From<Error> for axum::response::Response {
+    fn from(err: Error) -> Self {
+        use axum::{Json, http::StatusCode, response::IntoResponse};
+
+        let status = match &err {
+            Error::ValidationFailed(errors) => {
+                return (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(serde_json::json!({ "errors": errors })),
+                )
+                    .into_response();
+            }
+            Error::NotFound => StatusCode::NOT_FOUND,
+            Error::PermissionDenied => StatusCode::FORBIDDEN,
+            Error::InvalidQuery(_)
+            | Error::InvalidOperation(_)
+            | Error::InvalidPatch(_)
+            | Error::InvalidId
+            | Error::InvalidSequenceValue => StatusCode::BAD_REQUEST,
+            Error::LockTimeout => StatusCode::CONFLICT,
+            Error::NotSupported => StatusCode::NOT_IMPLEMENTED,
+            Error::Serialize(_) | Error::Deserialize(_) | Error::Storage(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        (
+            status,
+            Json(serde_json::json!({ "error": err.to_string() })),
+        )
+            .into_response()
+    }
+}
@@ -0,0 +1,9 @@
+/// Outcome of [`crate::Engine::import_objects`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportResult {
+    pub imported: u64,
+    /// Lines that were read but not stored (bad JSON, unique conflicts, ...)
+    pub skipped: u64,
+    /// `(line number, message)` for every skipped line; lines are numbered from 1
+    pub errors: Vec<(u64, String)>,
+}
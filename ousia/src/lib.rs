@@ -70,11 +70,19 @@
 //!
 //! ## Feature flags
 //!
+//! Flags are additive; adapters can be combined freely.
+//!
 //! | Flag       | Default | Description                        |
 //! |------------|---------|------------------------------------|
-//! | `postgres` | ✓       | PostgreSQL adapter via sqlx         |
-//! | `cockroach` | ✓       | CockroachDB adapter via sqlx         |
-//! | `sqlite`   |         | SQLite adapter (in-memory or file)  |
+//! | `sqlite`   | ✓       | SQLite adapter (in-memory or file)  |
+//! | `derive`   | ✓       | `#[derive(OusiaObject, OusiaEdge)]` |
+//! | `postgres` |         | PostgreSQL adapter via sqlx         |
+//! | `cockroach` |        | CockroachDB adapter via sqlx         |
+//! | `ledger`   |         | Re-exports the `ledger` crate as `ousia::ledger` |
+//! | `watch`    |         | `Engine::watch_object` / `watch_type` / `watch_query` change streams |
+//! | `test-utils` |       | `ousia::testing`: `TestEngine` and the ledger's `MemoryAdapter` |
+//! | `server`   |         | `postgres` + `ledger` + `derive`    |
+//! | `full`     |         | Every adapter and optional feature  |
 //!
 //! ## Ousia
 //!
@@ -87,33 +95,94 @@
 pub mod adapters;
 pub mod edge;
 pub mod error;
+pub mod import;
+pub mod middleware;
+pub mod namespace;
 pub mod object;
+pub mod pipeline;
 pub mod query;
+pub mod registry;
+#[cfg(feature = "relay")]
+pub mod relay;
+pub mod search;
+#[cfg(feature = "tracing")]
+pub mod slow_query;
+#[cfg(feature = "test-utils")]
+pub mod testing;
+#[cfg(feature = "watch")]
+pub mod watch;
+
+// Lets derive output, which names `::ousia`, compile inside this crate too
+extern crate self as ousia;
 
 #[cfg(feature = "ledger")]
 pub use ledger;
 use metrics::histogram;
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 pub use crate::adapters::{
-    Adapter, EdgeRecord, MultiEdgeContext, MultiOwnedContext, MultiPreloadContext, ObjectRecord,
-    Query, QueryContext,
+    Adapter, Aggregation, BatchEdgeResult, BatchResult, EdgeRecord, Granularity, GroupCount,
+    IndexAuditReport, IndexMetaDiff, MaintenanceOptions, MaintenanceReport, MultiBatchContext,
+    MultiEdgeContext, MultiOwnedContext, MultiPreloadContext, ObjectRecord, ObjectWithEdgeMap,
+    OnConflict, Query, QueryContext, QueryCost, RetentionReport, SchemaExport, SchemaVerification,
+    TableStats, TypeStats, UpsertOutcome,
 };
 pub use crate::edge::meta::*;
+pub use crate::edge::page::{EdgeCursor, Page};
 pub use crate::edge::query::EdgeQuery;
 pub use crate::edge::traits::*;
-pub use crate::error::Error;
+pub use crate::error::{Error, FieldError, ValidationCode};
+pub use crate::import::ImportResult;
+use crate::middleware::{ChangePublisher, ChangeSubscriber, EngineMiddleware};
+pub use crate::namespace::NamespacedEngine;
 pub use crate::object::*;
-use crate::query::QueryFilter;
+pub use crate::pipeline::{Deferred, Pipeline, PipelineResult};
+use crate::query::{IndexField, IndexKind, QueryFilter};
+#[cfg(feature = "relay")]
+pub use crate::relay::{PageInfo, RelayEdge, RelayPage};
+pub use crate::search::{SearchResult, SearchSort};
+#[cfg(feature = "tracing")]
+pub use crate::slow_query::SlowQueryLog;
 use chrono::Utc;
 pub use query::IndexQuery;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
 use uuid::Uuid;
 
 #[cfg(feature = "derive")]
 pub use ousia_derive::*;
 
+/// Lookups + inserts tried by [`Engine::find_or_create_object`] before giving up
+const FIND_OR_CREATE_ATTEMPTS: u32 = 3;
+
+/// Keys [`Engine::apply_patch_from_json`] strips: object meta and the
+/// bookkeeping stored next to the data
+const JSON_PATCH_META_KEYS: &[&str] = &[
+    "id",
+    "owner",
+    "type",
+    "created_at",
+    "updated_at",
+    query::SEQUENCE_KEY,
+    query::LABELS_KEY,
+    query::NAMESPACE_KEY,
+];
+
+/// Prefix of the per-type object sequence names, kept apart from user counters
+const TYPE_SEQUENCE_PREFIX: &str = "__type:";
+
+/// Objects fetched per round trip by [`Engine::migrate_type`] and
+/// [`Engine::backfill_unique_constraints`]
+const MAINTENANCE_BATCH_SIZE: u32 = 100;
+
+/// Proof that the caller opted into raw SQL, from [`Engine::raw_query_guard`].
+#[cfg(feature = "raw-queries")]
+pub struct RawQueryGuard {
+    _private: (),
+}
+
 pub struct ReplicaConfig {
     pub url: String,
 }
@@ -123,6 +192,13 @@ pub struct ReplicaConfig {
 #[derive(Clone)]
 pub struct Engine {
     inner: Arc<Ousia>,
+    allow_global: bool,
+    lock_timeout: Duration,
+    stale_reads: Option<Duration>,
+    namespace: Option<Arc<str>>,
+    #[cfg(feature = "tracing")]
+    slow_queries: Option<Arc<slow_query::SlowQueryTracker>>,
+    middleware: Vec<Arc<dyn EngineMiddleware>>,
 }
 
 pub struct Ousia {
@@ -142,59 +218,537 @@ impl Engine {
                 #[cfg(feature = "ledger")]
                 ledger,
             }),
+            allow_global: false,
+            lock_timeout: Duration::from_secs(5),
+            stale_reads: None,
+            namespace: None,
+            #[cfg(feature = "tracing")]
+            slow_queries: None,
+            middleware: Vec::new(),
+        }
+    }
+
+    /// Allow [`Engine::find_global`] and [`Engine::count_global`].
+    /// Off by default so tenant data can't leak across owners by accident.
+    pub fn with_global_queries(mut self, enabled: bool) -> Engine {
+        self.allow_global = enabled;
+        self
+    }
+
+    /// How long [`Engine::lock_object`] waits before `Error::LockTimeout`. Defaults to 5s.
+    pub fn with_lock_timeout(mut self, timeout: Duration) -> Engine {
+        self.lock_timeout = timeout;
+        self
+    }
+
+    /// Let `query_objects` and `count_objects` return results up to `staleness`
+    /// old unless the query sets [`Query::allow_stale`] itself. On CockroachDB
+    /// these become follower reads; other adapters read as usual.
+    pub fn with_follower_reads(mut self, staleness: Duration) -> Engine {
+        self.stale_reads = Some(staleness);
+        self
+    }
+
+    /// Log adapter calls slower than `threshold` with `tracing::warn!` and keep
+    /// the most recent ones in [`Engine::slow_query_log`]. Covers object CRUD,
+    /// `query_objects` / `count_objects` and edge queries and counts.
+    #[cfg(feature = "tracing")]
+    pub fn with_slow_query_threshold(mut self, threshold: Duration) -> Engine {
+        self.slow_queries = Some(Arc::new(slow_query::SlowQueryTracker::new(threshold)));
+        self
+    }
+
+    /// The last [`slow_query::SLOW_QUERY_LOG_CAPACITY`] slow calls, oldest
+    /// first. Stays empty unless [`Engine::with_slow_query_threshold`] is set.
+    #[cfg(feature = "tracing")]
+    pub fn slow_query_log(
+        &self,
+    ) -> Arc<std::sync::Mutex<std::collections::VecDeque<SlowQueryLog>>> {
+        match &self.slow_queries {
+            Some(tracker) => tracker.log(),
+            None => Default::default(),
+        }
+    }
+
+    /// Run `middleware` after every write to a single object: the
+    /// `create_object*` family, `update_object` (and the patch methods built
+    /// on it), `delete_object[_cascade]`, `transfer_object` and `swap_owners`.
+    /// Middleware added earlier runs first.
+    ///
+    /// Set-based writes that never load the rows are not reported:
+    /// `delete_objects`, `delete_owned_objects`, `touch_object[s_batch]`,
+    /// `run_retention_policies`, `migrate_type` and raw SQL.
+    pub fn with_middleware(mut self, middleware: impl EngineMiddleware) -> Engine {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// In-process feed of writes to `T` objects, for any adapter. Attach the
+    /// publisher with [`Engine::with_middleware`]; the subscriber (and its
+    /// clones) then receive a [`ChangeEvent`](middleware::ChangeEvent) per
+    /// write made through that engine, for the writes listed on
+    /// [`Engine::with_middleware`].
+    pub fn subscribe<T: Object>(&self) -> (ChangePublisher<T>, ChangeSubscriber<T>) {
+        middleware::channel()
+    }
+
+    /// Await an adapter call, recording it if it exceeds the slow-query threshold
+    async fn observed<F: std::future::Future>(
+        &self,
+        operation: &'static str,
+        type_name: &str,
+        call: F,
+    ) -> F::Output {
+        #[cfg(feature = "tracing")]
+        if let Some(tracker) = &self.slow_queries {
+            return tracker.observe(operation, type_name, call).await;
         }
+        let _ = (operation, type_name);
+        call.await
+    }
+
+    /// A view of this engine confined to `namespace`, for keeping tenants
+    /// apart in one database. The engine itself still sees every namespace.
+    pub fn with_namespace(&self, namespace: &str) -> NamespacedEngine {
+        NamespacedEngine::new(self.clone(), namespace)
+    }
+
+    /// Namespaces holding at least one object of `T`, sorted.
+    pub async fn list_namespaces<T: Object>(&self) -> Result<Vec<String>, Error> {
+        self.inner.adapter.list_namespaces(T::TYPE).await
     }
 
     // ==================== Object CRUD ====================
     /// Create a new object in storage
     pub async fn create_object<T: Object>(&self, obj: &T) -> Result<(), Error> {
-        if !T::HAS_UNIQUE_FIELDS {
+        let mut claimed: Vec<String> = Vec::new();
+        if T::HAS_UNIQUE_FIELDS {
+            let unique_hashes = obj.derive_unique_hashes();
+            let keys = unique_hashes
+                .iter()
+                .map(|(hash, _)| hash.clone())
+                .collect::<Vec<_>>();
+
             self.inner
                 .adapter
-                .insert_object(ObjectRecord::from_object(obj))
+                .insert_unique_hashes(obj.type_name(), obj.id(), unique_hashes)
                 .await?;
-        } else {
-            let unique_hashes = obj.derive_unique_hashes();
+            claimed = keys;
+        }
+
+        let record = match self.new_object_record(obj).await {
+            Ok(record) => record,
+            Err(err) => {
+                if !claimed.is_empty() {
+                    let _ = self.inner.adapter.delete_unique_hashes(claimed).await;
+                }
+                return Err(err);
+            }
+        };
+        let created = (!self.middleware.is_empty()).then(|| record.clone());
+        self.observed(
+            "create_object",
+            T::TYPE,
+            self.inner.adapter.insert_object(record),
+        )
+        .await?;
 
+        if let Some(record) = created {
+            for middleware in &self.middleware {
+                middleware.on_create(&record);
+            }
+        }
+        Ok(())
+    }
+
+    /// [`create_object`](Self::create_object), storing the object under `id`
+    /// instead of `obj.id()`. For IDs assigned elsewhere: replicas, imports or
+    /// [`Engine::reserve_id`]. `obj` itself is left as is. The nil UUID is
+    /// rejected with `Error::InvalidId`.
+    pub async fn create_object_with_id<T: Object>(&self, obj: &T, id: Uuid) -> Result<(), Error> {
+        if id.is_nil() {
+            return Err(Error::InvalidId);
+        }
+
+        let unique_hashes = if T::HAS_UNIQUE_FIELDS {
+            let hashes = obj.derive_unique_hashes();
+            let keys: Vec<String> = hashes.iter().map(|(hash, _)| hash.clone()).collect();
             self.inner
                 .adapter
-                .insert_unique_hashes(obj.type_name(), obj.id(), unique_hashes)
+                .insert_unique_hashes(obj.type_name(), id, hashes)
                 .await?;
+            keys
+        } else {
+            Vec::new()
+        };
+
+        let mut created = None;
+        let result = match self.new_object_record(obj).await {
+            Ok(mut record) => {
+                record.id = id;
+                created = (!self.middleware.is_empty()).then(|| record.clone());
+                self.observed(
+                    "create_object",
+                    T::TYPE,
+                    self.inner.adapter.insert_object(record),
+                )
+                .await
+            }
+            Err(err) => Err(err),
+        };
+        if result.is_err() && !unique_hashes.is_empty() {
+            let _ = self.inner.adapter.delete_unique_hashes(unique_hashes).await;
+        }
+        if let (Ok(()), Some(record)) = (&result, created) {
+            for middleware in &self.middleware {
+                middleware.on_create(&record);
+            }
+        }
+        result
+    }
+
+    /// A fresh object ID, generated like `Meta::default()` does (UUID v7).
+    /// Nothing is stored; pass it to [`Engine::create_object_with_id`] later.
+    pub fn reserve_id(&self) -> Uuid {
+        Uuid::now_v7()
+    }
+
+    /// [`create_object`](Self::create_object), returning the stored object's ID
+    pub async fn create_object_returning_id<T: Object>(&self, obj: &T) -> Result<Uuid, Error> {
+        self.create_object(obj).await?;
+        Ok(obj.id())
+    }
+
+    /// Create `obj` and an `edge` from `from` to it in one transaction: if
+    /// either insert fails, neither is stored. The edge's endpoints are
+    /// overwritten with `from` and the object's ID.
+    pub async fn create_object_and_link<T: Object, E: Edge>(
+        &self,
+        obj: &T,
+        from: Uuid,
+        mut edge: E,
+    ) -> Result<(T, E), Error> {
+        let meta = edge.meta_mut();
+        meta.from = from;
+        meta.to = obj.id();
+
+        // Unique hashes live outside the transaction, so claim them up front
+        // and release them if anything below fails
+        let mut claimed: Vec<String> = Vec::new();
+        if T::HAS_UNIQUE_FIELDS {
+            let hashes = obj.derive_unique_hashes();
+            let keys = hashes
+                .iter()
+                .map(|(hash, _)| hash.clone())
+                .collect::<Vec<_>>();
             self.inner
                 .adapter
-                .insert_object(ObjectRecord::from_object(obj))
+                .insert_unique_hashes(T::TYPE, obj.id(), hashes)
                 .await?;
+            claimed.extend(keys);
+        }
+        if E::HAS_UNIQUE_FIELDS {
+            let hashes = E::unique_hashes(from, obj.id());
+            let keys = hashes
+                .iter()
+                .map(|(hash, _)| hash.clone())
+                .collect::<Vec<_>>();
+            if let Err(err) = self
+                .inner
+                .adapter
+                .insert_unique_hashes(E::TYPE, from, hashes)
+                .await
+            {
+                self.inner.adapter.delete_unique_hashes(claimed).await?;
+                return Err(err);
+            }
+            claimed.extend(keys);
         }
 
-        Ok(())
+        let record = match self.new_object_record(obj).await {
+            Ok(record) => record,
+            Err(err) => {
+                if !claimed.is_empty() {
+                    self.inner.adapter.delete_unique_hashes(claimed).await?;
+                }
+                return Err(err);
+            }
+        };
+        let stored = record.clone();
+        let result = async {
+            let tx = self.inner.adapter.begin().await?;
+            tx.insert_object(record).await?;
+            tx.insert_edge(EdgeRecord::from_edge(&edge)).await?;
+            tx.commit().await
+        }
+        .await;
+        if let Err(err) = result {
+            if !claimed.is_empty() {
+                self.inner.adapter.delete_unique_hashes(claimed).await?;
+            }
+            return Err(err);
+        }
+
+        for middleware in &self.middleware {
+            middleware.on_create(&stored);
+        }
+        Ok((stored.to_object()?, edge))
+    }
+
+    /// Record for a new object, stamped with the next creation sequence of `T`.
+    /// The counter starts at 1; sequences of failed inserts are not reused.
+    async fn new_object_record<T: Object>(&self, obj: &T) -> Result<ObjectRecord, Error> {
+        let sequence = self
+            .inner
+            .adapter
+            .try_sequence_next_value(format!("{}{}", TYPE_SEQUENCE_PREFIX, T::TYPE))
+            .await?
+            - 1;
+        let mut record = ObjectRecord::from_object(obj);
+        record.set_sequence(sequence);
+        if let Some(namespace) = &self.namespace {
+            record.set_namespace(namespace);
+        }
+        Ok(record)
+    }
+
+    /// Create the object unless one with the same ID already exists.
+    /// Returns `false` (and leaves the stored object untouched) if it did.
+    pub async fn create_object_if_absent<T: Object>(&self, obj: &T) -> Result<bool, Error> {
+        if !T::HAS_UNIQUE_FIELDS {
+            let record = self.new_object_record(obj).await?;
+            let created = (!self.middleware.is_empty()).then(|| record.clone());
+            let inserted = self.inner.adapter.insert_object_or_ignore(record).await?;
+            if let (true, Some(record)) = (inserted, created) {
+                for middleware in &self.middleware {
+                    middleware.on_create(&record);
+                }
+            }
+            return Ok(inserted);
+        }
+
+        // Unique hashes are claimed before the row exists, so check first
+        if self
+            .inner
+            .adapter
+            .fetch_object(T::TYPE, obj.id())
+            .await?
+            .is_some()
+        {
+            return Ok(false);
+        }
+        self.create_object(obj).await?;
+        Ok(true)
+    }
+
+    /// Store a copy of object `id` under a new ID and return it. The copy is owned by
+    /// `new_owner`, or by the original owner if `None`. Labels are not copied.
+    /// Objects with unique fields need [`Engine::clone_object_with_overrides`].
+    pub async fn clone_object<T: Object>(
+        &self,
+        id: Uuid,
+        new_owner: Option<Uuid>,
+    ) -> Result<T, Error> {
+        self.clone_object_with_overrides(id, serde_json::json!({}), new_owner)
+            .await
+    }
+
+    /// Like [`Engine::clone_object`], with the top-level keys of `overrides` merged into the
+    /// copied data first. Use it to give unique fields a new value. Pass a `"__labels"`
+    /// object to label the copy.
+    pub async fn clone_object_with_overrides<T: Object>(
+        &self,
+        id: Uuid,
+        overrides: serde_json::Value,
+        new_owner: Option<Uuid>,
+    ) -> Result<T, Error> {
+        let Some(overrides) = overrides.as_object() else {
+            return Err(Error::InvalidQuery(
+                "clone overrides must be a JSON object".to_string(),
+            ));
+        };
+        let original = self
+            .inner
+            .adapter
+            .fetch_object(T::TYPE, id)
+            .await?
+            .ok_or(Error::NotFound)?;
+
+        let mut data = original.data;
+        if let Some(fields) = data.as_object_mut() {
+            // The copy gets its own sequence and starts unlabelled
+            fields.remove(query::SEQUENCE_KEY);
+            fields.remove(query::LABELS_KEY);
+            for (key, value) in overrides {
+                fields.insert(key.clone(), value.clone());
+            }
+        }
+
+        let now = Utc::now();
+        let clone: T = ObjectRecord {
+            id: Uuid::now_v7(),
+            type_name: original.type_name,
+            owner: new_owner.unwrap_or(original.owner),
+            data,
+            index_meta: serde_json::Value::Null,
+            created_at: now,
+            updated_at: now,
+        }
+        .to_object()?;
+        self.create_object(&clone).await?;
+        Ok(clone)
     }
 
-    /// Fetch an object by ID
+    /// Fetch an object by ID. Not owner-scoped: any stored `T` with this ID is returned.
     pub async fn fetch_object<T: Object>(&self, id: Uuid) -> Result<Option<T>, Error> {
-        let val = self.inner.adapter.fetch_object(T::TYPE, id).await?;
+        let val = self
+            .observed(
+                "fetch_object",
+                T::TYPE,
+                self.inner.adapter.fetch_object(T::TYPE, id),
+            )
+            .await?;
         match val {
             Some(record) => record.to_object().map(Some),
             None => Ok(None),
         }
     }
 
+    /// Fetch an object and its forward edges of type `E`, concurrently
+    pub async fn fetch_object_with_edges<T: Object, E: Edge>(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<(T, Vec<E>)>, Error> {
+        let (object, edges) = tokio::try_join!(
+            self.fetch_object::<T>(id),
+            self.query_edges::<E>(id, EdgeQuery::default()),
+        )?;
+        Ok(object.map(|object| (object, edges)))
+    }
+
+    /// Fetch an object and its reverse edges of type `E`, concurrently
+    pub async fn fetch_object_with_reverse_edges<T: Object, E: Edge>(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<(T, Vec<E>)>, Error> {
+        let (object, edges) = tokio::try_join!(
+            self.fetch_object::<T>(id),
+            self.query_reverse_edges::<E>(id, EdgeQuery::default()),
+        )?;
+        Ok(object.map(|object| (object, edges)))
+    }
+
+    /// Fetch an object; its edges are loaded per type through the returned map
+    pub async fn fetch_object_with_edge_map<T: Object>(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<ObjectWithEdgeMap<'_, T>>, Error> {
+        let object = self.fetch_object::<T>(id).await?;
+        Ok(object.map(|object| ObjectWithEdgeMap::new(self.inner.adapter.as_ref(), object)))
+    }
+
+    /// Raw stored data of object `id` when its type isn't known, with the type
+    /// name added under `"__type"`. Meant for generic admin tooling; like
+    /// [`Engine::fetch_object`] it doesn't check the owner.
+    pub async fn fetch_object_of_any_type(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<serde_json::Value>, Error> {
+        let Some(record) = self.inner.adapter.fetch_object_any_type(id).await? else {
+            return Ok(None);
+        };
+        let mut data = record.data;
+        if let Some(fields) = data.as_object_mut() {
+            fields.insert(
+                "__type".to_string(),
+                serde_json::Value::String(record.type_name.into_owned()),
+            );
+        }
+        Ok(Some(data))
+    }
+
     /// Fetch multiple objects by IDs
     pub async fn fetch_objects<T: Object>(&self, ids: Vec<Uuid>) -> Result<Vec<T>, Error> {
-        let records = self.inner.adapter.fetch_bulk_objects(T::TYPE, ids).await?;
+        let records = self
+            .observed(
+                "fetch_objects",
+                T::TYPE,
+                self.inner.adapter.fetch_bulk_objects(T::TYPE, ids),
+            )
+            .await?;
         records.into_iter().map(|r| r.to_object()).collect()
     }
 
+    /// Fetch multiple objects by IDs, keyed by ID. Missing IDs are absent from the map.
+    pub async fn fetch_objects_as_map<T: Object>(
+        &self,
+        ids: Vec<Uuid>,
+    ) -> Result<HashMap<Uuid, T>, Error> {
+        let records = self.inner.adapter.fetch_bulk_objects(T::TYPE, ids).await?;
+        records
+            .into_iter()
+            .map(|r| Ok((r.id, r.to_object()?)))
+            .collect()
+    }
+
+    /// Fetch multiple objects by IDs in input order, `None` where an ID wasn't found.
+    /// A repeated ID is filled at its first position only.
+    pub async fn fetch_objects_ordered<T: Object>(
+        &self,
+        ids: Vec<Uuid>,
+    ) -> Result<Vec<Option<T>>, Error> {
+        let mut found = self.fetch_objects_as_map::<T>(ids.clone()).await?;
+        Ok(ids.iter().map(|id| found.remove(id)).collect())
+    }
+
+    /// Bump the object's `updated_at` to now, leaving its data, index values
+    /// and history alone. Fails with `NotFound` if no such object exists.
+    pub async fn touch_object<T: Object>(&self, id: Uuid) -> Result<(), Error> {
+        match self.inner.adapter.touch_objects(T::TYPE, vec![id]).await? {
+            0 => Err(Error::NotFound),
+            _ => Ok(()),
+        }
+    }
+
+    /// [`touch_object`](Self::touch_object) for many IDs in one statement.
+    /// Returns how many objects were touched; missing IDs are skipped.
+    pub async fn touch_objects_batch<T: Object>(&self, ids: &[Uuid]) -> Result<u64, Error> {
+        self.inner
+            .adapter
+            .touch_objects(T::TYPE, ids.to_vec())
+            .await
+    }
+
     /// Update an existing object
     pub async fn update_object<T: Object>(&self, obj: &mut T) -> Result<(), Error> {
+        let stored = self
+            .inner
+            .adapter
+            .fetch_object_record(T::TYPE, obj.id())
+            .await?;
+        let replaced = if self.middleware.is_empty() {
+            None
+        } else {
+            stored.clone()
+        };
+        // An object kept in memory since `create_object` hasn't seen its sequence
+        if obj.meta().sequence == 0 {
+            if let Some(stored) = &stored {
+                obj.meta_mut().sequence = stored.sequence();
+            }
+        }
+
         let meta = obj.meta_mut();
         meta.updated_at = Utc::now();
 
         if !T::HAS_UNIQUE_FIELDS {
             // No unique fields, just update the object
-            self.inner
-                .adapter
-                .update_object(ObjectRecord::from_object(obj))
-                .await?;
+            self.observed(
+                "update_object",
+                T::TYPE,
+                self.write_object_update(obj, stored),
+            )
+            .await?;
         } else {
             let object_id = obj.id();
             let type_name = obj.type_name();
@@ -221,10 +775,12 @@ impl Engine {
             // If nothing changed in unique fields, skip uniqueness operations
             if hashes_to_add.is_empty() && hashes_to_remove.is_empty() {
                 // Just update the object
-                self.inner
-                    .adapter
-                    .update_object(ObjectRecord::from_object(obj))
-                    .await?;
+                self.observed(
+                    "update_object",
+                    T::TYPE,
+                    self.write_object_update(obj, stored),
+                )
+                .await?;
             } else {
                 // Try to insert new hashes (will fail if already taken)
                 if !hashes_to_add.is_empty() {
@@ -240,9 +796,11 @@ impl Engine {
 
                 // Update the object
                 match self
-                    .inner
-                    .adapter
-                    .update_object(ObjectRecord::from_object(obj))
+                    .observed(
+                        "update_object",
+                        T::TYPE,
+                        self.write_object_update(obj, stored),
+                    )
                     .await
                 {
                     Ok(_) => (),
@@ -268,23 +826,220 @@ impl Engine {
             }
         }
 
+        if !self.middleware.is_empty() {
+            let record = ObjectRecord::from_object(obj);
+            for middleware in &self.middleware {
+                match &replaced {
+                    Some(replaced) => middleware.on_update_from(replaced, &record),
+                    None => middleware.on_update(&record),
+                }
+            }
+        }
         Ok(())
     }
 
+    /// Write `obj` over `stored`. When fewer than half of the index keys changed,
+    /// only the changed keys are sent; otherwise the row is replaced. Versioned
+    /// types always replace the row, with `stored` snapshotted in the same
+    /// transaction.
+    async fn write_object_update<T: Object>(
+        &self,
+        obj: &T,
+        stored: Option<ObjectRecord>,
+    ) -> Result<(), Error> {
+        let mut record = ObjectRecord::from_object(obj);
+        // Keep the object in its namespace even if `obj` was built fresh
+        let namespace = self
+            .namespace
+            .as_deref()
+            .or_else(|| stored.as_ref().and_then(ObjectRecord::namespace));
+        if let Some(namespace) = namespace {
+            record.set_namespace(namespace);
+        }
+        let Some(stored) = stored else {
+            return self.inner.adapter.update_object(record).await;
+        };
+        if T::VERSIONED {
+            self.inner
+                .adapter
+                .update_object_with_history(T::TYPE, record, stored)
+                .await?;
+            return Ok(());
+        }
+
+        let (Some(old_data), Some(new_data)) = (stored.data.as_object(), record.data.as_object())
+        else {
+            return self.inner.adapter.update_object(record).await;
+        };
+        // Partial writes can't drop data keys
+        if old_data.keys().any(|key| !new_data.contains_key(key)) {
+            return self.inner.adapter.update_object(record).await;
+        }
+        let data_patch: serde_json::Map<String, serde_json::Value> = new_data
+            .iter()
+            .filter(|(key, value)| old_data.get(*key) != Some(*value))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        // Diff against the stored entry rather than one recomputed from it, so
+        // keys the type no longer indexes are dropped as a full write would
+        let (Some(old_meta), Some(new_meta)) =
+            (stored.index_meta.as_object(), record.index_meta.as_object())
+        else {
+            return self.inner.adapter.update_object(record).await;
+        };
+        let index_meta_patch: serde_json::Map<String, serde_json::Value> = new_meta
+            .iter()
+            .filter(|(key, value)| old_meta.get(*key) != Some(*value))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        let removed: Vec<String> = old_meta
+            .keys()
+            .filter(|key| !new_meta.contains_key(*key))
+            .cloned()
+            .collect();
+        let moved = index_meta_patch.len() + removed.len();
+        let total = old_meta.len().max(new_meta.len());
+        if moved * 2 >= total {
+            return self.inner.adapter.update_object(record).await;
+        }
+
+        self.inner
+            .adapter
+            .update_object_partial(
+                T::TYPE,
+                record.id,
+                serde_json::Value::Object(data_patch),
+                serde_json::Value::Object(index_meta_patch),
+                removed,
+                record.updated_at,
+            )
+            .await
+    }
+
+    /// Apply a partial update to an object owned by `owner` and return the result.
+    /// Indexes and unique constraints are re-derived as in [`Engine::update_object`].
+    pub async fn patch_object<T: Partial>(
+        &self,
+        id: Uuid,
+        owner: Uuid,
+        patch: T::Patch,
+    ) -> Result<T, Error> {
+        let mut obj = match self.fetch_object::<T>(id).await? {
+            Some(obj) if obj.owner() == owner => obj,
+            _ => return Err(Error::NotFound),
+        };
+
+        if patch.changed_fields().is_empty() {
+            return Ok(obj);
+        }
+
+        patch.apply_to(&mut obj);
+        self.update_object(&mut obj).await?;
+        Ok(obj)
+    }
+
+    /// Shallow-merge a JSON `patch` (e.g. a REST request body) into the object
+    /// owned by `owner`, then update it. Top-level keys of `patch` replace the
+    /// stored ones; meta keys (`id`, `owner`, `created_at`, ...) are dropped.
+    pub async fn apply_patch_from_json<T: Object>(
+        &self,
+        id: Uuid,
+        owner: Uuid,
+        patch: serde_json::Value,
+    ) -> Result<T, Error> {
+        let serde_json::Value::Object(mut patch) = patch else {
+            return Err(Error::InvalidPatch(
+                "patch must be a JSON object".to_string(),
+            ));
+        };
+        patch.retain(|key, _| !JSON_PATCH_META_KEYS.contains(&key.as_str()));
+
+        let obj = match self.fetch_object::<T>(id).await? {
+            Some(obj) if obj.owner() == owner => obj,
+            _ => return Err(Error::NotFound),
+        };
+        if patch.is_empty() {
+            return Ok(obj);
+        }
+
+        let mut record = ObjectRecord::from_object(&obj);
+        if let Some(data) = record.data.as_object_mut() {
+            data.extend(patch);
+        }
+        // Values of the wrong shape are the caller's fault, not a storage problem
+        let mut obj: T = record.to_object().map_err(|err| match err {
+            Error::Deserialize(msg) => Error::InvalidPatch(msg),
+            err => err,
+        })?;
+        self.update_object(&mut obj).await?;
+        Ok(obj)
+    }
+
+    /// [`Engine::apply_patch_from_json`] that rejects the whole patch with
+    /// `Error::InvalidPatch` if it has a key outside `allowed_fields`.
+    pub async fn apply_patch_from_json_strict<T: Object>(
+        &self,
+        id: Uuid,
+        owner: Uuid,
+        patch: serde_json::Value,
+        allowed_fields: &[&str],
+    ) -> Result<T, Error> {
+        if let Some(fields) = patch.as_object() {
+            let disallowed: Vec<&str> = fields
+                .keys()
+                .map(String::as_str)
+                .filter(|key| !allowed_fields.contains(key))
+                .collect();
+            if !disallowed.is_empty() {
+                return Err(Error::InvalidPatch(format!(
+                    "fields not allowed: {}",
+                    disallowed.join(", ")
+                )));
+            }
+        }
+        self.apply_patch_from_json(id, owner, patch).await
+    }
+
     /// Delete an object
     pub async fn delete_object<T: Object>(
         &self,
         id: Uuid,
         owner: Uuid,
     ) -> Result<Option<T>, Error> {
-        let record = self.inner.adapter.delete_object(T::TYPE, id, owner).await?;
+        let record = self
+            .observed(
+                "delete_object",
+                T::TYPE,
+                self.inner.adapter.delete_object(T::TYPE, id, owner),
+            )
+            .await?;
 
         match record {
-            Some(r) => r.to_object().map(Some),
+            Some(r) => {
+                for middleware in &self.middleware {
+                    middleware.on_delete(&r);
+                }
+                r.to_object().map(Some)
+            }
             None => Ok(None),
         }
     }
 
+    /// Delete the object and every edge that points to or from it.
+    /// Edges are left alone when nothing was deleted.
+    pub async fn delete_object_cascade<T: Object>(
+        &self,
+        id: Uuid,
+        owner: Uuid,
+    ) -> Result<Option<T>, Error> {
+        let deleted = self.delete_object::<T>(id, owner).await?;
+        if deleted.is_some() {
+            self.delete_edge_cascade(id).await?;
+        }
+        Ok(deleted)
+    }
+
     pub async fn delete_objects<T: Object>(
         &self,
         ids: Vec<Uuid>,
@@ -299,14 +1054,20 @@ impl Engine {
         Ok(record)
     }
 
+    /// Delete every `T` owned by `owner`, releasing their unique keys.
+    /// Returns the number of objects deleted.
     pub async fn delete_owned_objects<T: Object>(&self, owner: Uuid) -> Result<u64, Error> {
-        let record = self
-            .inner
+        if T::HAS_UNIQUE_FIELDS {
+            return self
+                .inner
+                .adapter
+                .delete_owned_objects_with_hashes(T::TYPE, owner)
+                .await;
+        }
+        self.inner
             .adapter
             .delete_owned_objects(T::TYPE, owner)
-            .await?;
-
-        Ok(record)
+            .await
     }
 
     /// Transfer ownership of an object
@@ -322,24 +1083,81 @@ impl Engine {
             .transfer_object(T::TYPE, id, from_owner, to_owner)
             .await?;
 
+        for middleware in &self.middleware {
+            middleware.on_update(&record);
+        }
         record.to_object()
     }
 
-    // ==================== Object Queries ====================
+    /// Hand a system-owned object (e.g. a template) over to `new_owner`.
+    /// `NotFound` if it isn't owned by [`SYSTEM_OWNER`].
+    pub async fn transfer_to_user<T: Object>(&self, id: Uuid, new_owner: Uuid) -> Result<T, Error> {
+        self.transfer_object(id, SYSTEM_OWNER, new_owner).await
+    }
 
-    /// Query objects with filters
-    pub async fn find_object<T: Object>(
+    /// Give an object held by `from_owner` back to [`SYSTEM_OWNER`]
+    pub async fn transfer_to_system<T: Object>(
         &self,
-        filters: &[QueryFilter],
-    ) -> Result<Option<T>, Error> {
-        let record = self
+        id: Uuid,
+        from_owner: Uuid,
+    ) -> Result<T, Error> {
+        self.transfer_object(id, from_owner, SYSTEM_OWNER).await
+    }
+
+    /// Whether object `id` is owned by [`SYSTEM_OWNER`]. Reads only the owner
+    /// column; `NotFound` if there is no such `T`.
+    pub async fn is_system_owned<T: Object>(&self, id: Uuid) -> Result<bool, Error> {
+        let owner = self
             .inner
             .adapter
-            .find_object(T::TYPE, SYSTEM_OWNER, filters)
-            .await?;
-        match record {
-            Some(r) => r.to_object().map(Some),
-            None => Ok(None),
+            .fetch_object_owner(T::TYPE, id)
+            .await?
+            .ok_or(Error::NotFound)?;
+        Ok(owner == SYSTEM_OWNER)
+    }
+
+    /// Atomically exchange owners: `id_a` moves to `owner_b` and `id_b` to
+    /// `owner_a`. If either object isn't held by its expected owner nothing
+    /// changes and `NotFound` is returned.
+    pub async fn swap_owners<T: Object>(
+        &self,
+        id_a: Uuid,
+        owner_a: Uuid,
+        id_b: Uuid,
+        owner_b: Uuid,
+    ) -> Result<(T, T), Error> {
+        if id_a == id_b {
+            return Err(Error::InvalidOperation("cannot swap with self".to_string()));
+        }
+
+        let (a, b) = self
+            .inner
+            .adapter
+            .swap_owners(T::TYPE, id_a, owner_a, id_b, owner_b)
+            .await?;
+
+        for middleware in &self.middleware {
+            middleware.on_update(&a);
+            middleware.on_update(&b);
+        }
+        Ok((a.to_object()?, b.to_object()?))
+    }
+
+    // ==================== Object Queries ====================
+
+    /// Query objects with filters
+    pub async fn find_object<T: Object>(
+        &self,
+        filters: &[QueryFilter],
+    ) -> Result<Option<T>, Error> {
+        let record = self
+            .inner
+            .adapter
+            .find_object(T::TYPE, SYSTEM_OWNER, filters)
+            .await?;
+        match record {
+            Some(r) => r.to_object().map(Some),
+            None => Ok(None),
         }
     }
 
@@ -359,9 +1177,60 @@ impl Engine {
         }
     }
 
-    pub async fn query_objects<T: Object>(&self, query: Query) -> Result<Vec<T>, Error> {
+    /// Object matching `filters`, or the one built by `factory` if there is
+    /// none. The flag is `true` when the object was created.
+    pub async fn find_or_create_object<T: Object>(
+        &self,
+        filters: &[QueryFilter],
+        factory: impl FnOnce() -> T,
+    ) -> Result<(T, bool), Error> {
+        self.find_or_create_with_owner(SYSTEM_OWNER, filters, factory)
+            .await
+    }
+
+    /// [`Engine::find_or_create_object`] scoped to `owner`. `factory` should
+    /// build an object owned by `owner`.
+    pub async fn find_or_create_with_owner<T: Object>(
+        &self,
+        owner: Uuid,
+        filters: &[QueryFilter],
+        factory: impl FnOnce() -> T,
+    ) -> Result<(T, bool), Error> {
+        if let Some(found) = self.find_object_with_owner(owner, filters).await? {
+            return Ok((found, false));
+        }
+
+        let obj = factory();
+        let mut attempt = 1;
+        loop {
+            match self.create_object(&obj).await {
+                Ok(()) => return Ok((obj, true)),
+                // Lost the race to a concurrent creator: look again
+                Err(err)
+                    if err.is_unique_constraint_violation()
+                        && attempt < FIND_OR_CREATE_ATTEMPTS =>
+                {
+                    attempt += 1;
+                    if let Some(found) = self.find_object_with_owner(owner, filters).await? {
+                        return Ok((found, false));
+                    }
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    pub async fn query_objects<T: Object>(&self, mut query: Query) -> Result<Vec<T>, Error> {
+        query.validate()?;
+        query.stale = query.stale.or(self.stale_reads);
         let start = Instant::now();
-        let records = self.inner.adapter.query_objects(T::TYPE, query).await?;
+        let records = self
+            .observed(
+                "query_objects",
+                T::TYPE,
+                self.inner.adapter.query_objects(T::TYPE, query),
+            )
+            .await?;
         histogram!("ousia.query.duration_ms",
             "type" => T::TYPE
         )
@@ -369,9 +1238,308 @@ impl Engine {
         records.into_iter().map(|r| r.to_object()).collect()
     }
 
+    /// [`query_objects`](Self::query_objects) summarised by [`ObjectStats::compute`]
+    /// over `field`. Every matching object is loaded and the statistics are
+    /// computed in memory, so keep the result set small.
+    pub async fn compute_stats<T: Object>(
+        &self,
+        query: Query,
+        field: &'static IndexField,
+    ) -> Result<ObjectStats, Error> {
+        let objects = self.query_objects::<T>(query).await?;
+        Ok(ObjectStats::compute(&objects, field))
+    }
+
+    /// A Relay connection over `query`, ordered by ID oldest first. Page
+    /// forward with `first` (and `after`), or backward with `last` (and
+    /// `before`); mixing the two directions is rejected. Sort filters on
+    /// `query` are dropped, since the cursors follow ID order.
+    ///
+    /// `has_previous_page` when paging forward, and `has_next_page` when
+    /// paging backward, only report whether a cursor was passed.
+    #[cfg(feature = "relay")]
+    pub async fn page_objects_relay<T: Object>(
+        &self,
+        mut query: Query,
+        first: Option<u32>,
+        after: Option<String>,
+        last: Option<u32>,
+        before: Option<String>,
+    ) -> Result<RelayPage<T>, Error> {
+        query.filters.retain(|f| f.mode.as_sort().is_none());
+        query.cursor = None;
+
+        let (nodes, has_next_page, has_previous_page) = match (first, last) {
+            (Some(first), None) if before.is_none() => {
+                let mut query = query.ascending(true).with_limit(first.saturating_add(1));
+                if let Some(after) = &after {
+                    query = query.with_cursor(relay::decode_cursor(after)?);
+                }
+                let mut nodes: Vec<T> = self.query_objects(query).await?;
+                let has_next = nodes.len() > first as usize;
+                nodes.truncate(first as usize);
+                (nodes, has_next, after.is_some())
+            }
+            (None, Some(last)) if after.is_none() => {
+                let mut query = query.ascending(false).with_limit(last.saturating_add(1));
+                if let Some(before) = &before {
+                    query = query.with_cursor(relay::decode_cursor(before)?);
+                }
+                let mut nodes: Vec<T> = self.query_objects(query).await?;
+                let has_previous = nodes.len() > last as usize;
+                nodes.truncate(last as usize);
+                nodes.reverse();
+                (nodes, before.is_some(), has_previous)
+            }
+            _ => {
+                return Err(Error::InvalidQuery(
+                    "relay pagination takes either `first`/`after` or `last`/`before`".to_string(),
+                ));
+            }
+        };
+
+        let edges: Vec<RelayEdge<T>> = nodes
+            .into_iter()
+            .map(|node| RelayEdge {
+                cursor: relay::encode_cursor(node.id()),
+                node,
+            })
+            .collect();
+        let page_info = PageInfo {
+            has_next_page,
+            has_previous_page,
+            start_cursor: edges.first().map(|e| e.cursor.clone()),
+            end_cursor: edges.last().map(|e| e.cursor.clone()),
+        };
+        Ok(RelayPage { edges, page_info })
+    }
+
+    /// [`query_objects`](Self::query_objects) with each object returned at most
+    /// once, deduplicated by ID in the database. See [`Query::distinct`].
+    pub async fn query_objects_distinct<T: Object>(&self, query: Query) -> Result<Vec<T>, Error> {
+        self.query_objects(query.distinct(true)).await
+    }
+
+    /// Drop repeated IDs from `objects`, keeping the first occurrence of each.
+    /// For merging results of several queries in memory.
+    pub fn deduplicate<T: Object>(objects: Vec<T>) -> Vec<T> {
+        let mut seen = HashSet::with_capacity(objects.len());
+        objects
+            .into_iter()
+            .filter(|obj| seen.insert(obj.id()))
+            .collect()
+    }
+
+    /// Random subset of at most `n` objects, optionally narrowed by `query`
+    pub async fn sample_objects<T: Object>(
+        &self,
+        n: u32,
+        query: Option<Query>,
+    ) -> Result<Vec<T>, Error> {
+        let query = query.unwrap_or_default().random_sample(n);
+        self.query_objects(query).await
+    }
+
+    /// Objects of `T` whose text matches `text`, best match first. Spans all
+    /// owners, so it needs [`Engine::with_global_queries`]. Only adapters with
+    /// a full-text index (SQLite) support it; others return `Error::NotSupported`.
+    pub async fn fts_search<T: Object>(&self, text: &str, limit: u32) -> Result<Vec<T>, Error> {
+        if !self.allow_global {
+            return Err(Error::PermissionDenied);
+        }
+        let records = self.inner.adapter.fts_search(T::TYPE, text, limit).await?;
+        records.into_iter().map(|r| r.to_object()).collect()
+    }
+
+    /// Full-text search for `text` narrowed by `field_filters`, best match
+    /// first unless `sort` orders by a field instead. `text` is plain words,
+    /// all of which must match. Spans all owners, so it needs
+    /// [`Engine::with_global_queries`]. Postgres and SQLite only.
+    pub async fn object_search<T: Object>(
+        &self,
+        text: &str,
+        field_filters: &[QueryFilter],
+        sort: Option<SearchSort>,
+        limit: u32,
+    ) -> Result<Vec<SearchResult<T>>, Error> {
+        let mut query = self.global_query(field_filters)?.with_limit(limit);
+        query = match sort {
+            Some(SearchSort::Asc(field)) => query.sort_asc(field),
+            Some(SearchSort::Desc(field)) => query.sort_desc(field),
+            None => query,
+        };
+        query.validate()?;
+        let hits = self
+            .observed(
+                "object_search",
+                T::TYPE,
+                self.inner.adapter.object_search(T::TYPE, text, query),
+            )
+            .await?;
+        hits.into_iter()
+            .map(|(record, score)| {
+                Ok(SearchResult {
+                    object: record.to_object()?,
+                    score,
+                })
+            })
+            .collect()
+    }
+
+    /// Query objects of every owner. Requires [`Engine::with_global_queries`].
+    pub async fn find_global<T: Object>(&self, filters: &[QueryFilter]) -> Result<Vec<T>, Error> {
+        self.query_objects(self.global_query(filters)?).await
+    }
+
+    /// First object of any owner matching `filters`. Requires [`Engine::with_global_queries`].
+    pub async fn find_object_globally<T: Object>(
+        &self,
+        filters: &[QueryFilter],
+    ) -> Result<Option<T>, Error> {
+        let query = self.global_query(filters)?.with_limit(1);
+        Ok(self.query_objects(query).await?.into_iter().next())
+    }
+
+    /// Count objects of every owner. Requires [`Engine::with_global_queries`].
+    pub async fn count_global<T: Object>(&self, filters: &[QueryFilter]) -> Result<u64, Error> {
+        self.count_objects::<T>(Some(self.global_query(filters)?))
+            .await
+    }
+
+    fn global_query(&self, filters: &[QueryFilter]) -> Result<Query, Error> {
+        if !self.allow_global {
+            return Err(Error::PermissionDenied);
+        }
+        let mut query = Query::new(SYSTEM_OWNER).all_owners(true);
+        query.filters = filters.to_vec();
+        Ok(query)
+    }
+
+    /// Objects of `T` created with a sequence in `start..=end`, oldest first.
+    /// Spans all owners, so it needs [`Engine::with_global_queries`].
+    pub async fn fetch_objects_in_sequence_order<T: Object>(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<T>, Error> {
+        let query = self.global_query(&[])?.where_sequence_between(start, end);
+        self.query_objects(query).await
+    }
+
+    /// Objects of `T` with a sequence in `start..end_exclusive`, oldest first.
+    /// Consumers of the object stream can pass the last sequence they saw plus
+    /// one as the next `start`. Needs [`Engine::with_global_queries`].
+    pub async fn fetch_objects_by_sequence_range<T: Object>(
+        &self,
+        start: u64,
+        end_exclusive: u64,
+    ) -> Result<Vec<T>, Error> {
+        let query = self.global_query(&[])?;
+        if end_exclusive <= start {
+            return Ok(Vec::new());
+        }
+        self.query_objects(query.where_sequence_range(start, end_exclusive))
+            .await
+    }
+
+    /// Highest sequence among stored objects of `T`, 0 if there are none.
+    /// Needs [`Engine::with_global_queries`].
+    pub async fn fetch_latest_sequence<T: Object>(&self) -> Result<u64, Error> {
+        let query = self.global_query(&[])?.sort_sequence_desc().with_limit(1);
+        let latest = self.query_objects::<T>(query).await?;
+        Ok(latest.first().map_or(0, |obj| obj.meta().sequence))
+    }
+
     /// Count objects matching query
     pub async fn count_objects<T: Object>(&self, query: Option<Query>) -> Result<u64, Error> {
-        self.inner.adapter.count_objects(T::TYPE, query).await
+        let query = query.map(|mut query| {
+            query.stale = query.stale.or(self.stale_reads);
+            query
+        });
+        self.observed(
+            "count_objects",
+            T::TYPE,
+            self.inner.adapter.count_objects(T::TYPE, query),
+        )
+        .await
+    }
+
+    /// Count objects of `T` per value of an indexed field, largest group first.
+    /// `query: None` counts every object of the type. The field must be
+    /// declared `search`.
+    pub async fn group_by_field<T: Object>(
+        &self,
+        field: &'static IndexField,
+        query: Option<Query>,
+    ) -> Result<Vec<GroupCount>, Error> {
+        if !field.kinds.contains(&IndexKind::Search) {
+            return Err(Error::InvalidQuery(format!(
+                "field `{}` is not indexed for search",
+                field.name
+            )));
+        }
+        let mut query = query.unwrap_or_else(Query::wide);
+        query.stale = query.stale.or(self.stale_reads);
+        self.inner
+            .adapter
+            .group_by_field(T::TYPE, field.name, query)
+            .await
+    }
+
+    /// Count objects of `T` per owner. `query: None` counts every object of the type.
+    pub async fn group_count_by_owner<T: Object>(
+        &self,
+        query: Option<Query>,
+    ) -> Result<HashMap<Uuid, u64>, Error> {
+        let mut query = query.unwrap_or_else(Query::wide);
+        query.stale = query.stale.or(self.stale_reads);
+        let counts = self
+            .inner
+            .adapter
+            .group_count_by_owner(T::TYPE, query)
+            .await?;
+        Ok(counts.into_iter().collect())
+    }
+
+    /// Objects of `T` matching `query`, grouped by owner with each group
+    /// newest first. Groups are ordered by owner ID. Any sort in `query` is
+    /// replaced, and its `limit` keeps the newest objects across all owners.
+    pub async fn query_objects_paged_by_owner<T: Object>(
+        &self,
+        mut query: Query,
+    ) -> Result<Vec<(Uuid, Vec<T>)>, Error> {
+        query.filters.retain(|f| f.mode.as_sort().is_none());
+        let mut objects: Vec<T> = self.query_objects(query.sort_created_desc()).await?;
+        // Stable, so each owner's objects stay newest first
+        objects.sort_by_key(|obj| obj.owner());
+
+        let mut groups: Vec<(Uuid, Vec<T>)> = Vec::new();
+        for obj in objects {
+            match groups.last_mut() {
+                Some((owner, group)) if *owner == obj.owner() => group.push(obj),
+                _ => groups.push((obj.owner(), vec![obj])),
+            }
+        }
+        Ok(groups)
+    }
+
+    /// The `limit` owners with the most objects of `T`, largest first
+    pub async fn top_owners<T: Object>(&self, limit: u32) -> Result<Vec<(Uuid, u64)>, Error> {
+        self.inner.adapter.top_owners_by_type(T::TYPE, limit).await
+    }
+
+    /// How many objects of `T` were created per day, week or month, oldest
+    /// bucket first. Buckets with no objects are left out; `owner: None`
+    /// counts every owner.
+    pub async fn object_age_distribution<T: Object>(
+        &self,
+        granularity: Granularity,
+        owner: Option<Uuid>,
+    ) -> Result<Vec<(chrono::DateTime<Utc>, u64)>, Error> {
+        self.inner
+            .adapter
+            .time_bucket_count(T::TYPE, owner, granularity)
+            .await
     }
 
     /// Fetch all objects owned by a specific owner
@@ -397,6 +1565,181 @@ impl Engine {
         }
     }
 
+    // ==================== Import / Export ====================
+    /// Create one `T` per JSON line of `reader`, all owned by `owner`.
+    /// Each object gets a fresh ID. Lines are read `batch_size` at a time.
+    /// A line that doesn't parse or hits a unique constraint is recorded in
+    /// [`ImportResult::errors`], and the import carries on. Blank lines are ignored.
+    pub async fn import_objects<T: Object>(
+        &self,
+        reader: impl AsyncRead + Unpin,
+        owner: Uuid,
+        batch_size: usize,
+    ) -> Result<ImportResult, Error> {
+        let batch_size = batch_size.max(1);
+        let mut lines = BufReader::new(reader).lines();
+        let mut result = ImportResult::default();
+        let mut line_no = 0u64;
+        let mut done = false;
+
+        while !done {
+            let mut batch: Vec<(u64, T)> = Vec::with_capacity(batch_size);
+            while batch.len() < batch_size {
+                let Some(line) = lines
+                    .next_line()
+                    .await
+                    .map_err(|e| Error::Storage(e.to_string()))?
+                else {
+                    done = true;
+                    break;
+                };
+                line_no += 1;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<T>(&line) {
+                    Ok(mut obj) => {
+                        obj.meta_mut().owner = owner;
+                        batch.push((line_no, obj));
+                    }
+                    Err(e) => {
+                        result.skipped += 1;
+                        result.errors.push((line_no, e.to_string()));
+                    }
+                }
+            }
+
+            for (line_no, obj) in batch {
+                match self.create_object(&obj).await {
+                    Ok(()) => result.imported += 1,
+                    Err(e) => {
+                        result.skipped += 1;
+                        result.errors.push((line_no, e.to_string()));
+                    }
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Write every object matching `query` to `writer` as JSON Lines, in the
+    /// persisted form [`Engine::import_objects`] reads back. Returns the number written.
+    pub async fn export_objects<T: Object, W: AsyncWrite + Unpin>(
+        &self,
+        query: Query,
+        mut writer: W,
+    ) -> Result<u64, Error> {
+        let objects: Vec<T> = self.query_objects(query).await?;
+        let mut written = 0u64;
+        for obj in &objects {
+            let mut line = serde_json::to_string(&obj.__serialize_internal())
+                .map_err(|e| Error::Serialize(e.to_string()))?;
+            line.push('\n');
+            writer
+                .write_all(line.as_bytes())
+                .await
+                .map_err(|e| Error::Storage(e.to_string()))?;
+            written += 1;
+        }
+        writer
+            .flush()
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+        Ok(written)
+    }
+
+    // ==================== Diff ====================
+    /// Field-level changes between two states of the same object
+    pub fn diff_object<T: Object>(&self, before: &T, after: &T) -> ObjectDiff {
+        ObjectDiff::between(&ObjectDiff::snapshot(before), &ObjectDiff::snapshot(after))
+    }
+
+    /// Diff a snapshot taken with [`ObjectDiff::snapshot`] against the stored object
+    pub async fn diff_objects_by_id<T: Object>(
+        &self,
+        id_before_snapshot: serde_json::Value,
+        id: Uuid,
+    ) -> Result<ObjectDiff, Error> {
+        let current = self.fetch_object::<T>(id).await?.ok_or(Error::NotFound)?;
+        Ok(ObjectDiff::between(
+            &id_before_snapshot,
+            &ObjectDiff::snapshot(&current),
+        ))
+    }
+
+    // ==================== History ====================
+
+    /// Every stored state of a `#[ousia(versioned)]` object, oldest first, ending
+    /// with the current one. Snapshots are kept after the object is deleted.
+    pub async fn object_timeline<T: Object>(
+        &self,
+        id: Uuid,
+    ) -> Result<Vec<ObjectSnapshot<T>>, Error> {
+        let (history, current) = tokio::try_join!(
+            self.inner.adapter.fetch_history(T::TYPE, id),
+            self.inner.adapter.fetch_object(T::TYPE, id),
+        )?;
+
+        let mut timeline = history
+            .into_iter()
+            .map(|(version, record)| {
+                Ok(ObjectSnapshot {
+                    version,
+                    updated_at: record.updated_at,
+                    data: record.to_object::<T>()?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        if let Some(current) = current {
+            timeline.push(ObjectSnapshot {
+                version: timeline.last().map_or(1, |s| s.version + 1),
+                updated_at: current.updated_at,
+                data: current.to_object::<T>()?,
+            });
+        }
+        Ok(timeline)
+    }
+
+    /// The object as it was at `version`, counted as in [`Engine::object_timeline`]
+    pub async fn fetch_object_at_version<T: Object>(
+        &self,
+        id: Uuid,
+        version: u64,
+    ) -> Result<Option<T>, Error> {
+        Ok(self
+            .object_timeline::<T>(id)
+            .await?
+            .into_iter()
+            .find(|snapshot| snapshot.version == version)
+            .map(|snapshot| snapshot.data))
+    }
+
+    /// The latest `limit` writes to the object, oldest first. Unlike
+    /// [`Engine::object_timeline`] this needs no opt-in: every create, update
+    /// and delete of any type is logged by a database trigger.
+    pub async fn object_change_log<T: Object>(
+        &self,
+        id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<ChangeLogEntry>, Error> {
+        self.inner
+            .adapter
+            .fetch_change_log(T::TYPE, id, limit)
+            .await
+    }
+
+    /// Every logged write to objects of `T` at or after `since`, oldest first
+    pub async fn type_change_log<T: Object>(
+        &self,
+        since: chrono::DateTime<Utc>,
+    ) -> Result<Vec<ChangeLogEntry>, Error> {
+        self.inner
+            .adapter
+            .fetch_type_change_log(T::TYPE, since)
+            .await
+    }
+
     // ==================== Union Operations ====================
     /// Fetch an union by ID
     pub async fn fetch_union_object<A: Object, B: Object>(
@@ -453,67 +1796,409 @@ impl Engine {
         records.into_iter().map(|r| Ok(r.into())).collect()
     }
 
+    /// Fetch a mix of `A` and `B` by ID, each tagged by its stored type.
+    /// IDs belonging to neither type are skipped.
+    pub async fn fetch_union_as_enum<A: Object, B: Object>(
+        &self,
+        ids: Vec<Uuid>,
+    ) -> Result<Vec<Either<A, B>>, Error> {
+        let records = self
+            .inner
+            .adapter
+            .fetch_union_objects(A::TYPE, B::TYPE, ids)
+            .await?;
+        records.into_iter().map(|r| r.to_either()).collect()
+    }
+
+    /// Run `query` against `A` and `B` together, scoped to `owner`
+    pub async fn query_union<A: Object, B: Object>(
+        &self,
+        owner: Uuid,
+        mut query: Query,
+    ) -> Result<Vec<Either<A, B>>, Error> {
+        query.owner = owner;
+        query.validate()?;
+        query.stale = query.stale.or(self.stale_reads);
+        let records = self
+            .inner
+            .adapter
+            .query_union_objects(A::TYPE, B::TYPE, query)
+            .await?;
+        records.into_iter().map(|r| r.to_either()).collect()
+    }
+
     // ==================== Edge Operations ====================
 
     /// Create a new edge
     pub async fn create_edge<E: Edge>(&self, edge: &E) -> Result<(), Error> {
+        if !E::HAS_UNIQUE_FIELDS {
+            return self
+                .inner
+                .adapter
+                .insert_edge(EdgeRecord::from_edge(edge))
+                .await;
+        }
+
+        let hashes = E::unique_hashes(edge.from(), edge.to());
+        let keys: Vec<String> = hashes.iter().map(|(hash, _)| hash.clone()).collect();
         self.inner
+            .adapter
+            .insert_unique_hashes(E::TYPE, edge.from(), hashes)
+            .await?;
+
+        if let Err(err) = self
+            .inner
             .adapter
             .insert_edge(EdgeRecord::from_edge(edge))
             .await
+        {
+            self.inner.adapter.delete_unique_hashes(keys).await?;
+            return Err(err);
+        }
+        Ok(())
     }
 
-    /// Update an edge
-    pub async fn update_edge<E: Edge>(&self, edge: &mut E, to: Option<Uuid>) -> Result<(), Error> {
-        let old_link_id = edge.to();
-        if let Some(to) = to {
-            edge.meta_mut().to = to;
+    /// Create the edge unless a `from → to` edge of this type already exists.
+    /// Returns `false` (and leaves the stored edge data untouched) if it did.
+    pub async fn create_edge_if_absent<E: Edge>(&self, edge: &E) -> Result<bool, Error> {
+        if !E::HAS_UNIQUE_FIELDS {
+            return self
+                .inner
+                .adapter
+                .insert_edge_or_ignore(EdgeRecord::from_edge(edge))
+                .await;
         }
 
-        let _ = self
+        // Unique hashes are claimed before the row exists, so check first
+        if self
             .inner
             .adapter
-            .update_edge(EdgeRecord::from_edge(edge), old_link_id, to)
-            .await?;
-
-        Ok(())
+            .fetch_edge(E::TYPE, edge.from(), edge.to())
+            .await?
+            .is_some()
+        {
+            return Ok(false);
+        }
+        self.create_edge(edge).await?;
+        Ok(true)
     }
 
-    /// Delete an edge
-    pub async fn delete_edge<E: Edge>(&self, from: Uuid, to: Uuid) -> Result<(), Error> {
-        self.inner.adapter.delete_edge(E::TYPE, from, to).await
+    /// Fetch the `from → to` edge, creating it from `factory` if it doesn't exist.
+    /// The factory's edge should have the same `from` and `to`.
+    pub async fn ensure_edge<E: Edge>(
+        &self,
+        from: Uuid,
+        to: Uuid,
+        factory: impl FnOnce() -> E,
+    ) -> Result<E, Error> {
+        if let Some(existing) = self.fetch_edge::<E>(from, to).await? {
+            return Ok(existing);
+        }
+        let edge = factory();
+        if self.create_edge_if_absent(&edge).await? {
+            return Ok(edge);
+        }
+        // Lost a race with a concurrent writer; return what it stored
+        self.fetch_edge::<E>(from, to).await?.ok_or(Error::NotFound)
     }
 
-    /// Delete all edge of an object
-    pub async fn delete_object_edge<E: Edge>(&self, from: Uuid) -> Result<(), Error> {
-        self.inner.adapter.delete_object_edge(E::TYPE, from).await
-    }
+    /// Insert the edge, or overwrite the data of the existing `from → to` edge.
+    /// Unique constraints still apply against other edges of the same type.
+    pub async fn upsert_edge<E: Edge>(&self, edge: &E) -> Result<UpsertOutcome, Error> {
+        if E::HAS_UNIQUE_FIELDS {
+            let hashes = E::unique_hashes(edge.from(), edge.to());
+            match self
+                .inner
+                .adapter
+                .insert_unique_hashes(E::TYPE, edge.from(), hashes)
+                .await
+            {
+                Ok(()) => {}
+                // The hashes are already held by this very edge
+                Err(err)
+                    if err.is_unique_constraint_violation()
+                        && self
+                            .inner
+                            .adapter
+                            .fetch_edge(E::TYPE, edge.from(), edge.to())
+                            .await?
+                            .is_some() => {}
+                Err(err) => return Err(err),
+            }
+        }
 
-    /// Fetch a known edge
-    pub async fn fetch_edge<E: Edge>(&self, from: Uuid, to: Uuid) -> Result<Option<E>, Error> {
-        let edge_record = self.inner.adapter.fetch_edge(E::TYPE, from, to).await?;
-        let Some(edge_record) = edge_record else {
-            return Ok(None);
-        };
-        edge_record.to_edge().map(|edge| Some(edge))
+        self.inner
+            .adapter
+            .upsert_edge(EdgeRecord::from_edge(edge))
+            .await
     }
 
-    /// Query edges
-    pub async fn query_edges<E: Edge>(
-        &self,
-        from: Uuid,
-        query: EdgeQuery,
-    ) -> Result<Vec<E>, Error> {
-        let start = Instant::now();
-        let records = self.inner.adapter.query_edges(E::TYPE, from, query).await?;
-        histogram!("ousia.query_edges.duration_ms",
-            "type" => E::TYPE
-        )
-        .record(start.elapsed().as_millis() as f64);
-        records.into_iter().map(|r| r.to_edge()).collect()
+    /// Upsert `edges` in one round trip; a repeated `(from, to)` keeps its last
+    /// occurrence. Returns the number of edges written.
+    pub async fn upsert_edges_batch<E: Edge>(&self, edges: &[E]) -> Result<u64, Error> {
+        if E::HAS_UNIQUE_FIELDS {
+            // Each edge needs its own uniqueness claim
+            for edge in edges {
+                self.upsert_edge(edge).await?;
+            }
+            return Ok(edges.len() as u64);
+        }
+
+        let mut seen = HashSet::new();
+        let mut records: Vec<EdgeRecord> = edges
+            .iter()
+            .rev()
+            .filter(|edge| seen.insert((edge.from(), edge.to())))
+            .map(EdgeRecord::from_edge)
+            .collect();
+        records.reverse();
+
+        self.inner.adapter.upsert_edges_batch(records).await
     }
 
-    /// Query reverse edges
+    /// Insert `edges` in one round trip, resolving `from → to` pairs that already
+    /// exist per `on_conflict`. With `OnConflict::Error` the first conflict fails
+    /// the batch and nothing is written. A pair repeated within `edges` conflicts
+    /// too: `Ignore` keeps its first occurrence, `Replace` its last.
+    ///
+    /// Types with unique fields are written edge by edge, since each needs its own
+    /// uniqueness claim; an error there leaves the earlier edges in place.
+    pub async fn batch_create_edges<E: Edge>(
+        &self,
+        edges: &[E],
+        on_conflict: OnConflict,
+    ) -> Result<BatchEdgeResult, Error> {
+        let mut result = BatchEdgeResult::default();
+
+        if E::HAS_UNIQUE_FIELDS {
+            for edge in edges {
+                match on_conflict {
+                    OnConflict::Ignore => {
+                        if self.create_edge_if_absent(edge).await? {
+                            result.created += 1;
+                        } else {
+                            result.skipped += 1;
+                        }
+                    }
+                    OnConflict::Replace => {
+                        if self.upsert_edge(edge).await?.created {
+                            result.created += 1;
+                        } else {
+                            result.replaced += 1;
+                        }
+                    }
+                    OnConflict::Error => {
+                        if !self.create_edge_if_absent(edge).await? {
+                            return Err(Error::unique_violation("from+to"));
+                        }
+                        result.created += 1;
+                    }
+                }
+            }
+            return Ok(result);
+        }
+
+        let mut seen = HashSet::new();
+        let mut records = Vec::with_capacity(edges.len());
+        match on_conflict {
+            OnConflict::Replace => {
+                for edge in edges.iter().rev() {
+                    if seen.insert((edge.from(), edge.to())) {
+                        records.push(EdgeRecord::from_edge(edge));
+                    } else {
+                        result.replaced += 1;
+                    }
+                }
+                records.reverse();
+            }
+            OnConflict::Ignore | OnConflict::Error => {
+                for edge in edges {
+                    if seen.insert((edge.from(), edge.to())) {
+                        records.push(EdgeRecord::from_edge(edge));
+                    } else if on_conflict == OnConflict::Error {
+                        return Err(Error::unique_violation("from+to"));
+                    } else {
+                        result.skipped += 1;
+                    }
+                }
+            }
+        }
+
+        let written = self
+            .observed(
+                "batch_create_edges",
+                E::TYPE,
+                self.inner.adapter.insert_edges_batch(records, on_conflict),
+            )
+            .await?;
+        result.created += written.created;
+        result.replaced += written.replaced;
+        result.skipped += written.skipped;
+        Ok(result)
+    }
+
+    /// Claim the unique hashes `from → new_to` needs that `from → old_to` doesn't
+    /// hold. Returns the hashes to release once the edge has moved.
+    async fn claim_moved_edge_hashes<E: Edge>(
+        &self,
+        from: Uuid,
+        old_to: Uuid,
+        new_to: Uuid,
+    ) -> Result<Vec<String>, Error> {
+        if !E::HAS_UNIQUE_FIELDS || old_to == new_to {
+            return Ok(Vec::new());
+        }
+
+        let old_hashes = E::unique_hashes(from, old_to);
+        let new_hashes = E::unique_hashes(from, new_to);
+        let release: Vec<String> = old_hashes
+            .iter()
+            .filter(|(hash, _)| !new_hashes.iter().any(|(new, _)| new == hash))
+            .map(|(hash, _)| hash.clone())
+            .collect();
+        let claim: Vec<(String, &str)> = new_hashes
+            .into_iter()
+            .filter(|(hash, _)| !old_hashes.iter().any(|(old, _)| old == hash))
+            .collect();
+
+        self.inner
+            .adapter
+            .insert_unique_hashes(E::TYPE, from, claim)
+            .await?;
+        Ok(release)
+    }
+
+    /// Update an edge
+    pub async fn update_edge<E: Edge>(&self, edge: &mut E, to: Option<Uuid>) -> Result<(), Error> {
+        let old_link_id = edge.to();
+        if let Some(to) = to {
+            edge.meta_mut().to = to;
+        }
+        let released = self
+            .claim_moved_edge_hashes::<E>(edge.from(), old_link_id, edge.to())
+            .await?;
+
+        let _ = self
+            .inner
+            .adapter
+            .update_edge(EdgeRecord::from_edge(edge), old_link_id, to)
+            .await?;
+
+        if !released.is_empty() {
+            self.inner.adapter.delete_unique_hashes(released).await?;
+        }
+        Ok(())
+    }
+
+    /// Point the `E` edge `from → old_to` at `new_to`, keeping its data
+    pub async fn replace_edge_target<E: Edge>(
+        &self,
+        from: Uuid,
+        old_to: Uuid,
+        new_to: Uuid,
+    ) -> Result<(), Error> {
+        if new_to == from {
+            return Err(Error::InvalidQuery(
+                "edge cannot target its own source".to_string(),
+            ));
+        }
+
+        let mut edge = self
+            .fetch_edge::<E>(from, old_to)
+            .await?
+            .ok_or(Error::NotFound)?;
+        edge.meta_mut().to = new_to;
+        let released = self
+            .claim_moved_edge_hashes::<E>(from, old_to, new_to)
+            .await?;
+
+        self.inner
+            .adapter
+            .update_edge(EdgeRecord::from_edge(&edge), old_to, Some(new_to))
+            .await?;
+
+        if !released.is_empty() {
+            self.inner.adapter.delete_unique_hashes(released).await?;
+        }
+        Ok(())
+    }
+
+    /// Delete an edge
+    pub async fn delete_edge<E: Edge>(&self, from: Uuid, to: Uuid) -> Result<(), Error> {
+        self.inner.adapter.delete_edge(E::TYPE, from, to).await?;
+
+        if E::HAS_UNIQUE_FIELDS {
+            let hashes = E::unique_hashes(from, to)
+                .into_iter()
+                .map(|(hash, _)| hash)
+                .collect();
+            self.inner.adapter.delete_unique_hashes(hashes).await?;
+        }
+        Ok(())
+    }
+
+    /// Delete all edge of an object
+    pub async fn delete_object_edge<E: Edge>(&self, from: Uuid) -> Result<(), Error> {
+        if !E::HAS_UNIQUE_FIELDS {
+            return self.inner.adapter.delete_object_edge(E::TYPE, from).await;
+        }
+
+        let edges = self
+            .inner
+            .adapter
+            .query_edges(E::TYPE, from, EdgeQuery::default())
+            .await?;
+        self.inner.adapter.delete_object_edge(E::TYPE, from).await?;
+
+        let hashes: Vec<String> = edges
+            .iter()
+            .flat_map(|edge| E::unique_hashes(edge.from, edge.to))
+            .map(|(hash, _)| hash)
+            .collect();
+        if !hashes.is_empty() {
+            self.inner.adapter.delete_unique_hashes(hashes).await?;
+        }
+        Ok(())
+    }
+
+    /// Delete every edge touching `node_id`, in either direction and of any type.
+    /// Unique-edge claims are not released here, since the edge types are unknown;
+    /// use [`delete_object_edge`](Self::delete_object_edge) for `#[ousia(unique)]` edges.
+    pub async fn delete_edge_cascade(&self, node_id: Uuid) -> Result<u64, Error> {
+        self.inner.adapter.delete_edges_for_node(node_id).await
+    }
+
+    /// Fetch a known edge
+    pub async fn fetch_edge<E: Edge>(&self, from: Uuid, to: Uuid) -> Result<Option<E>, Error> {
+        let edge_record = self.inner.adapter.fetch_edge(E::TYPE, from, to).await?;
+        let Some(edge_record) = edge_record else {
+            return Ok(None);
+        };
+        edge_record.to_edge().map(|edge| Some(edge))
+    }
+
+    /// Query edges
+    pub async fn query_edges<E: Edge>(
+        &self,
+        from: Uuid,
+        query: EdgeQuery,
+    ) -> Result<Vec<E>, Error> {
+        let start = Instant::now();
+        let records = self
+            .observed(
+                "query_edges",
+                E::TYPE,
+                self.inner.adapter.query_edges(E::TYPE, from, query),
+            )
+            .await?;
+        histogram!("ousia.query_edges.duration_ms",
+            "type" => E::TYPE
+        )
+        .record(start.elapsed().as_millis() as f64);
+        records.into_iter().map(|r| r.to_edge()).collect()
+    }
+
+    /// Query reverse edges
     pub async fn query_reverse_edges<E: Edge>(
         &self,
         to: Uuid,
@@ -521,9 +2206,11 @@ impl Engine {
     ) -> Result<Vec<E>, Error> {
         let start = Instant::now();
         let records = self
-            .inner
-            .adapter
-            .query_reverse_edges(E::TYPE, to, query)
+            .observed(
+                "query_reverse_edges",
+                E::TYPE,
+                self.inner.adapter.query_reverse_edges(E::TYPE, to, query),
+            )
             .await?;
         histogram!("ousia.query_edges.duration_ms",
             "type" => E::TYPE
@@ -532,13 +2219,150 @@ impl Engine {
         records.into_iter().map(|r| r.to_edge()).collect()
     }
 
+    /// Outgoing `E` edges of `from`, each paired with its `T` target, in one JOIN.
+    /// `obj_filters` apply to the targets.
+    pub async fn query_edges_with_targets<E: Edge, T: Object>(
+        &self,
+        from: Uuid,
+        obj_filters: &[QueryFilter],
+        plan: EdgeQuery,
+    ) -> Result<Vec<(E, T)>, Error> {
+        self.inner
+            .adapter
+            .query_edges_with_targets(E::TYPE, T::TYPE, from, obj_filters, plan)
+            .await?
+            .into_iter()
+            .map(|(er, or)| Ok((er.to_edge::<E>()?, or.to_object::<T>()?)))
+            .collect()
+    }
+
+    /// [`query_edges_with_targets`](Self::query_edges_with_targets) with the edge
+    /// plan first: `E` edges of `from` and their `T` targets, joined in the database
+    /// rather than loaded in two steps.
+    pub async fn query_edge_targets<E: Edge, T: Object>(
+        &self,
+        from: Uuid,
+        edge_plan: EdgeQuery,
+        obj_filters: &[QueryFilter],
+    ) -> Result<Vec<(E, T)>, Error> {
+        self.query_edges_with_targets(from, obj_filters, edge_plan)
+            .await
+    }
+
+    /// Incoming `E` edges of `to`, each paired with its `T` source, in one JOIN.
+    /// `obj_filters` apply to the sources.
+    pub async fn query_reverse_edge_sources<E: Edge, T: Object>(
+        &self,
+        to: Uuid,
+        plan: EdgeQuery,
+        obj_filters: &[QueryFilter],
+    ) -> Result<Vec<(E, T)>, Error> {
+        self.observed(
+            "query_reverse_edges_with_sources",
+            E::TYPE,
+            self.inner.adapter.query_reverse_edges_with_sources(
+                E::TYPE,
+                T::TYPE,
+                to,
+                obj_filters,
+                plan,
+            ),
+        )
+        .await?
+        .into_iter()
+        .map(|(er, or)| Ok((er.to_edge::<E>()?, or.to_object::<T>()?)))
+        .collect()
+    }
+
+    /// Outgoing `E` edges with their `T` targets for several sources in one query,
+    /// keyed by source id. Every id in `from_ids` gets an entry, empty if it has no edges.
+    /// A limit in `plan` caps the total, not the count per source.
+    pub async fn query_edges_with_targets_map<E: Edge, T: Object>(
+        &self,
+        from_ids: &[Uuid],
+        plan: EdgeQuery,
+    ) -> Result<HashMap<Uuid, Vec<(E, T)>>, Error> {
+        let pairs = self
+            .inner
+            .adapter
+            .query_edges_with_targets_batch(E::TYPE, T::TYPE, from_ids, &[], plan)
+            .await?;
+
+        let mut grouped: HashMap<Uuid, Vec<(E, T)>> =
+            from_ids.iter().map(|id| (*id, Vec::new())).collect();
+        for (er, or) in pairs {
+            let from = er.from;
+            grouped
+                .entry(from)
+                .or_default()
+                .push((er.to_edge::<E>()?, or.to_object::<T>()?));
+        }
+        Ok(grouped)
+    }
+
+    /// One page of outgoing edges, newest target id first.
+    /// Pass the returned `next_cursor` back in to continue.
+    pub async fn paginate_edges<E: Edge>(
+        &self,
+        from: Uuid,
+        page_size: u32,
+        cursor: Option<EdgeCursor>,
+    ) -> Result<Page<E>, Error> {
+        let edges = self
+            .query_edges::<E>(from, Self::edge_page_query(page_size, cursor))
+            .await?;
+        Ok(Self::edge_page(edges, page_size, |e| e.to()))
+    }
+
+    /// One page of incoming edges, keyed on the source id
+    pub async fn paginate_reverse_edges<E: Edge>(
+        &self,
+        to: Uuid,
+        page_size: u32,
+        cursor: Option<EdgeCursor>,
+    ) -> Result<Page<E>, Error> {
+        let edges = self
+            .query_reverse_edges::<E>(to, Self::edge_page_query(page_size, cursor))
+            .await?;
+        Ok(Self::edge_page(edges, page_size, |e| e.from()))
+    }
+
+    /// Fetches one extra row to tell whether another page follows
+    fn edge_page_query(page_size: u32, cursor: Option<EdgeCursor>) -> EdgeQuery {
+        let query = EdgeQuery::default().with_limit(page_size.saturating_add(1));
+        match cursor {
+            Some(cursor) => query.with_cursor(cursor.last()),
+            None => query,
+        }
+    }
+
+    fn edge_page<E: Edge>(mut edges: Vec<E>, page_size: u32, key: fn(&E) -> Uuid) -> Page<E> {
+        let has_next = edges.len() > page_size as usize;
+        edges.truncate(page_size as usize);
+        let next_cursor = if has_next {
+            edges.last().map(|e| EdgeCursor::new(key(e)))
+        } else {
+            None
+        };
+        Page {
+            items: edges,
+            next_cursor,
+            has_next,
+        }
+    }
+
     /// Count edges
     pub async fn count_edges<E: Edge>(
         &self,
         from: Uuid,
         query: Option<EdgeQuery>,
     ) -> Result<u64, Error> {
-        self.inner.adapter.count_edges(E::TYPE, from, query).await
+        self.observed(
+            "count_edges",
+            E::TYPE,
+            self.inner.adapter.count_edges(E::TYPE, from, query),
+        )
+        .await
     }
 
     /// Count reverse edges
@@ -547,12 +2371,190 @@ impl Engine {
         to: Uuid,
         query: Option<EdgeQuery>,
     ) -> Result<u64, Error> {
+        self.observed(
+            "count_reverse_edges",
+            E::TYPE,
+            self.inner.adapter.count_reverse_edges(E::TYPE, to, query),
+        )
+        .await
+    }
+
+    /// Count forward edges for many sources in one query.
+    /// Ids with no edges are absent from the map.
+    pub async fn count_edges_batch<E: Edge>(
+        &self,
+        from_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, u64>, Error> {
+        let counts = self
+            .inner
+            .adapter
+            .count_edges_batch(E::TYPE, from_ids, EdgeQuery::default())
+            .await?;
+        Ok(counts.into_iter().collect())
+    }
+
+    /// Count reverse edges for many targets in one query.
+    /// Ids with no edges are absent from the map.
+    pub async fn count_reverse_edges_batch<E: Edge>(
+        &self,
+        to_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, u64>, Error> {
+        let counts = self
+            .inner
+            .adapter
+            .count_reverse_edges_batch(E::TYPE, to_ids, EdgeQuery::default())
+            .await?;
+        Ok(counts.into_iter().collect())
+    }
+
+    /// Forward `E` edges of many objects in one query, grouped by `from`, so a
+    /// page of objects doesn't cost one edge query each.
+    /// Ids with no edges are absent from the map.
+    pub async fn prefetch_edges_for_objects<E: Edge>(
+        &self,
+        object_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<E>>, Error> {
+        if object_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let records = self
+            .inner
+            .adapter
+            .query_edges_batch(E::TYPE, object_ids, EdgeQuery::default())
+            .await?;
+        let mut grouped: HashMap<Uuid, Vec<E>> = HashMap::new();
+        for record in records {
+            grouped
+                .entry(record.from)
+                .or_default()
+                .push(record.to_edge()?);
+        }
+        Ok(grouped)
+    }
+
+    /// Reverse `E` edges of many objects in one query, grouped by `to`.
+    /// Ids with no edges are absent from the map.
+    pub async fn prefetch_reverse_edges_for_objects<E: Edge>(
+        &self,
+        object_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<E>>, Error> {
+        if object_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let records = self
+            .inner
+            .adapter
+            .query_reverse_edges_batch(E::TYPE, object_ids, EdgeQuery::default())
+            .await?;
+        let mut grouped: HashMap<Uuid, Vec<E>> = HashMap::new();
+        for record in records {
+            grouped
+                .entry(record.to)
+                .or_default()
+                .push(record.to_edge()?);
+        }
+        Ok(grouped)
+    }
+
+    /// SUM/AVG/MIN/MAX of a numeric index field over the `E` edges leaving
+    /// `from`, e.g. the total weight of a user's follows. `0.0` without edges.
+    pub async fn aggregate_edge_field<E: Edge>(
+        &self,
+        from: Uuid,
+        field: &'static IndexField,
+        agg: Aggregation,
+    ) -> Result<f64, Error> {
+        self.inner
+            .adapter
+            .aggregate_edge_field(E::TYPE, from, field.name, agg)
+            .await
+    }
+
+    /// [`Engine::aggregate_edge_field`] over the `E` edges arriving at `to`
+    pub async fn aggregate_reverse_edge_field<E: Edge>(
+        &self,
+        to: Uuid,
+        field: &'static IndexField,
+        agg: Aggregation,
+    ) -> Result<f64, Error> {
         self.inner
             .adapter
-            .count_reverse_edges(E::TYPE, to, query)
+            .aggregate_reverse_edge_field(E::TYPE, to, field.name, agg)
             .await
     }
 
+    /// [`Engine::aggregate_edge_field`] for many sources in one query.
+    /// Ids with no edges are absent from the map.
+    pub async fn aggregate_edge_field_batch<E: Edge>(
+        &self,
+        from_ids: &[Uuid],
+        field: &'static IndexField,
+        agg: Aggregation,
+    ) -> Result<HashMap<Uuid, f64>, Error> {
+        let values = self
+            .inner
+            .adapter
+            .aggregate_edge_field_batch(E::TYPE, from_ids, field.name, agg)
+            .await?;
+        Ok(values.into_iter().collect())
+    }
+
+    /// Objects both `node_a` and `node_b` point to over `E`.
+    /// Edge filters narrow both sides; the limit caps the result.
+    pub async fn query_common_neighbors<E: Edge, T: Object>(
+        &self,
+        node_a: Uuid,
+        node_b: Uuid,
+        plan: EdgeQuery,
+    ) -> Result<Vec<T>, Error> {
+        let mut ids = self
+            .inner
+            .adapter
+            .common_neighbor_ids(E::TYPE, node_a, node_b, &plan.filters)
+            .await?;
+        if let Some(limit) = plan.limit {
+            ids.truncate(limit as usize);
+        }
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.fetch_objects::<T>(ids).await
+    }
+
+    /// Count `T` objects both `node_a` and `node_b` point to over `E`
+    pub async fn count_common_neighbors<E: Edge, T: Object>(
+        &self,
+        node_a: Uuid,
+        node_b: Uuid,
+    ) -> Result<u64, Error> {
+        let ids = self
+            .inner
+            .adapter
+            .common_neighbor_ids(E::TYPE, node_a, node_b, &[])
+            .await?;
+        Ok(ids.len() as u64)
+    }
+
+    // ==================== Pipeline ====================
+    /// Queue independent reads and run them together with [`Pipeline::execute`]
+    pub fn pipeline(&self) -> Pipeline {
+        Pipeline::new(self.clone())
+    }
+
+    // ==================== Locks ====================
+    /// Take an exclusive lock on an object and read its current state.
+    /// Postgres uses an advisory lock, so other processes are excluded too;
+    /// SQLite only excludes tasks in this process.
+    pub async fn lock_object<T: Object>(&self, id: Uuid) -> Result<ObjectLock<T>, Error> {
+        let guard = self
+            .inner
+            .adapter
+            .lock_object(T::TYPE, id, self.lock_timeout)
+            .await?;
+        let object = self.fetch_object::<T>(id).await?.ok_or(Error::NotFound)?;
+        Ok(ObjectLock::new(self.clone(), object, guard))
+    }
+
     // ==================== Sequence ====================
     pub async fn counter_value(&self, key: String) -> u64 {
         self.inner.adapter.sequence_value(key).await
@@ -562,6 +2564,29 @@ impl Engine {
         self.inner.adapter.sequence_next_value(key).await
     }
 
+    /// Set a counter to `to`, e.g. in test teardown or when restoring a backup.
+    /// The next [`counter_next_value`](Self::counter_next_value) returns `to + 1`;
+    /// [`counter_value`](Self::counter_value) reads without incrementing.
+    /// `0` (and anything above `i64::MAX`) returns `Error::InvalidSequenceValue`.
+    ///
+    /// Per-type object sequences are named `__type:<TypeName>`, so user
+    /// counters never touch them unless named that way on purpose.
+    pub async fn sequence_reset(&self, name: &str, to: u64) -> Result<(), Error> {
+        if to == 0 || to > i64::MAX as u64 {
+            return Err(Error::InvalidSequenceValue);
+        }
+        self.inner
+            .adapter
+            .sequence_reset(name.to_string(), to)
+            .await
+    }
+
+    /// Every counter with its current value, ordered by name. Includes the
+    /// per-type object sequences, keyed `__type:<TypeName>`.
+    pub async fn sequence_list(&self) -> Result<Vec<(String, u64)>, Error> {
+        self.inner.adapter.sequence_list().await
+    }
+
     // ==================== Advanced Query API ====================
 
     /// Start a single-pivot query context for edge traversals.
@@ -575,6 +2600,435 @@ impl Engine {
         self.inner.adapter.preload_objects(query)
     }
 
+    // ==================== Maintenance ====================
+    /// Reclaim dead rows and refresh planner statistics
+    pub async fn vacuum(&self) -> Result<(), Error> {
+        self.inner.adapter.vacuum().await
+    }
+
+    /// Run the housekeeping tasks enabled in `options`, in order: vacuum,
+    /// reindex, expire burned ledger value objects, and drop unique hashes
+    /// left behind by deleted objects and edges. Expiring ledger objects
+    /// without a ledger returns `Error::NotSupported`.
+    pub async fn run_maintenance(
+        &self,
+        options: MaintenanceOptions,
+    ) -> Result<MaintenanceReport, Error> {
+        let start = Instant::now();
+        let mut report = MaintenanceReport::default();
+
+        if options.vacuum {
+            self.inner.adapter.vacuum().await?;
+            report.vacuumed = true;
+        }
+        if options.reindex {
+            self.inner.adapter.reindex().await?;
+            report.reindexed = true;
+        }
+        if options.expire_ledger_objects {
+            report.rows_expired = self.expire_ledger_objects().await?;
+        }
+        if options.cleanup_orphan_unique_constraints {
+            report.orphans_removed = self
+                .inner
+                .adapter
+                .delete_orphan_unique_constraints()
+                .await?;
+        }
+
+        report.duration = start.elapsed();
+        Ok(report)
+    }
+
+    #[cfg(feature = "ledger")]
+    async fn expire_ledger_objects(&self) -> Result<u64, Error> {
+        let ledger = self.inner.ledger.as_ref().ok_or(Error::NotSupported)?;
+        ledger.expire_value_objects().await.map_err(|me| match me {
+            ledger::MoneyError::Storage(e) => Error::Storage(e),
+            _ => Error::Storage(me.to_string()),
+        })
+    }
+
+    #[cfg(not(feature = "ledger"))]
+    async fn expire_ledger_objects(&self) -> Result<u64, Error> {
+        Err(Error::NotSupported)
+    }
+
+    /// Keep `T` objects for `ttl` after creation; older ones are deleted by
+    /// [`Engine::run_retention_policies`]. Registering again replaces the TTL.
+    /// The TTL has one-second resolution and must be at least a second.
+    pub async fn register_retention_policy<T: Object>(&self, ttl: Duration) -> Result<(), Error> {
+        let ttl_seconds = ttl.as_secs();
+        if ttl_seconds == 0 || ttl_seconds > i64::MAX as u64 {
+            return Err(Error::InvalidOperation(
+                "retention TTL must be between one second and i64::MAX seconds".to_string(),
+            ));
+        }
+        self.inner
+            .adapter
+            .set_retention_policy(T::TYPE, ttl_seconds)
+            .await
+    }
+
+    /// Delete every object older than its type's retention TTL, along with its
+    /// unique hashes and every edge to or from it. Run it periodically, e.g.
+    /// next to [`Engine::run_maintenance`]. Objects are deleted per type in one
+    /// transaction; their edges follow one object at a time.
+    pub async fn run_retention_policies(&self) -> Result<RetentionReport, Error> {
+        let mut report = RetentionReport::default();
+        for (type_name, ttl_seconds) in self.inner.adapter.retention_policies().await? {
+            let cutoff = Utc::now() - chrono::Duration::seconds(ttl_seconds as i64);
+            let ids = self
+                .inner
+                .adapter
+                .delete_objects_created_before(&type_name, cutoff)
+                .await?;
+            for id in &ids {
+                report.edges_deleted += self.inner.adapter.delete_edges_for_node(*id).await?;
+            }
+            report.objects_deleted += ids.len() as u64;
+            report.policies_applied += 1;
+        }
+        if report.edges_deleted > 0 {
+            // Unique hashes of the removed edges
+            self.inner
+                .adapter
+                .delete_orphan_unique_constraints()
+                .await?;
+        }
+        Ok(report)
+    }
+
+    /// Row counts and storage size of the core tables
+    pub async fn table_stats(&self) -> Result<TableStats, Error> {
+        self.inner.adapter.table_stats().await
+    }
+
+    /// The core tables' DDL and columns as the database reports them. See
+    /// [`SchemaExport::to_markdown`] for a readable summary.
+    pub async fn export_schema(&self) -> Result<SchemaExport, Error> {
+        self.inner.adapter.export_schema().await
+    }
+
+    /// Check the database against what `init_schema` creates. Right after
+    /// `init_schema` the result has nothing missing and nothing extra.
+    pub async fn verify_schema(&self) -> Result<SchemaVerification, Error> {
+        self.inner.adapter.verify_schema().await
+    }
+
+    /// Every object type in storage with its count and newest `created_at`,
+    /// ordered by type name
+    pub async fn type_registry(&self) -> Result<Vec<TypeStats>, Error> {
+        self.inner.adapter.type_stats().await
+    }
+
+    /// `TYPE` of every `#[derive(OusiaObject)]` type linked into the binary,
+    /// whether or not any are stored yet
+    #[cfg(feature = "type-registry")]
+    pub fn registered_types() -> Vec<&'static str> {
+        registry::registered_types()
+    }
+
+    /// Compare the stored `index_meta` of up to `sample_size` random objects of
+    /// `T` with what `T::index_meta()` produces now. Run after changing a
+    /// type's indexes to see whether [`Engine::reindex_objects`] is needed.
+    pub async fn audit_index_meta<T: Object>(
+        &self,
+        sample_size: u64,
+    ) -> Result<IndexAuditReport, Error> {
+        let limit = sample_size.min(u32::MAX as u64) as u32;
+        let records = self
+            .inner
+            .adapter
+            .sample_object_records(T::TYPE, limit)
+            .await?;
+
+        let mut report = IndexAuditReport {
+            sampled: records.len() as u64,
+            ..Default::default()
+        };
+        for mut record in records {
+            let stored = std::mem::take(&mut record.index_meta);
+            let obj: T = record.to_object()?;
+            let mut current = ObjectRecord::from_object(&obj);
+            current.index_meta = stored;
+            let diff = current.diff_index_meta(&obj.index_meta());
+            if !diff.is_empty() {
+                report.stale += 1;
+                report.diffs.push((current.id, diff));
+            }
+        }
+        Ok(report)
+    }
+
+    /// Recompute `index_meta` for every object of `T` matching `query` (all
+    /// owners when `None`) and store it. Data and `updated_at` are left as is.
+    /// Returns the number of objects rewritten.
+    pub async fn reindex_objects<T: Object>(&self, query: Option<Query>) -> Result<u64, Error> {
+        let query = query.unwrap_or_else(Query::wide);
+        query.validate()?;
+        let records = self.inner.adapter.query_objects(T::TYPE, query).await?;
+
+        let mut count = 0;
+        for record in records {
+            let obj: T = record.to_object()?;
+            self.inner
+                .adapter
+                .update_object(ObjectRecord::from_object(&obj))
+                .await?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Record unique hashes for objects of `T` stored before its unique fields
+    /// were declared, in batches of 100. Values already taken are skipped, so a
+    /// count below the number of objects means duplicates to resolve by hand.
+    /// Returns the number of hashes inserted.
+    pub async fn backfill_unique_constraints<T: Object>(&self) -> Result<u64, Error> {
+        if !T::HAS_UNIQUE_FIELDS {
+            return Ok(0);
+        }
+        let mut inserted = 0;
+        let mut cursor = None;
+        loop {
+            let mut query = Query::wide().with_limit(MAINTENANCE_BATCH_SIZE);
+            if let Some(last_id) = cursor {
+                query = query.with_cursor(last_id);
+            }
+            let batch: Vec<T> = self.query_objects(query).await?;
+            let Some(last) = batch.last() else {
+                return Ok(inserted);
+            };
+            cursor = Some(last.id());
+            let full = batch.len() == MAINTENANCE_BATCH_SIZE as usize;
+
+            let entries = batch
+                .iter()
+                .map(|obj| (obj.id(), obj.derive_unique_hashes()))
+                .collect();
+            inserted += self
+                .inner
+                .adapter
+                .backfill_unique_constraints(T::TYPE, entries)
+                .await?;
+            if !full {
+                return Ok(inserted);
+            }
+        }
+    }
+
+    /// Remove every unique hash of `T`, e.g. after its unique fields are dropped
+    pub async fn remove_unique_backfill<T: Object>(&self) -> Result<(), Error> {
+        self.inner.adapter.delete_unique_for_type(T::TYPE).await
+    }
+
+    /// Replace the `Old` object `id` with `transform(original)` as a `New`
+    /// object keeping its ID, owner, creation time and namespace, and return
+    /// it. The row is retyped in place, together with its unique hashes, in
+    /// one transaction, so no `Old` object with that ID remains. Unique fields
+    /// of `New` are checked as usual, so `transform` must keep them unique.
+    pub async fn copy_object_to_type<Old: Object, New: Object>(
+        &self,
+        id: Uuid,
+        transform: impl FnOnce(Old) -> New,
+    ) -> Result<New, Error> {
+        let original = self.fetch_object::<Old>(id).await?.ok_or(Error::NotFound)?;
+        self.store_as_type(original, transform).await
+    }
+
+    /// Move every object of `Old` to `New` with [`Engine::copy_object_to_type`].
+    /// Runs in batches of 100 and returns the number of objects migrated. Not
+    /// atomic as a whole: on error, the objects already migrated stay migrated
+    /// and a rerun picks up the rest.
+    pub async fn migrate_type<Old: Object, New: Object>(
+        &self,
+        transform: impl Fn(Old) -> New,
+    ) -> Result<u64, Error> {
+        let mut migrated = 0;
+        let mut cursor = None;
+        loop {
+            let mut query = Query::wide().with_limit(MAINTENANCE_BATCH_SIZE);
+            if let Some(last_id) = cursor {
+                query = query.with_cursor(last_id);
+            }
+            let batch: Vec<Old> = self.query_objects(query).await?;
+            let Some(last) = batch.last() else {
+                return Ok(migrated);
+            };
+            cursor = Some(last.id());
+            let full = batch.len() == MAINTENANCE_BATCH_SIZE as usize;
+
+            for original in batch {
+                self.store_as_type(original, &transform).await?;
+                migrated += 1;
+            }
+            if !full {
+                return Ok(migrated);
+            }
+        }
+    }
+
+    async fn store_as_type<Old: Object, New: Object>(
+        &self,
+        original: Old,
+        transform: impl FnOnce(Old) -> New,
+    ) -> Result<New, Error> {
+        let meta = original.meta().clone();
+        let mut copy = transform(original);
+        let copy_meta = copy.meta_mut();
+        copy_meta.id = meta.id;
+        copy_meta.owner = meta.owner;
+        copy_meta.created_at = meta.created_at;
+        copy_meta.updated_at = Utc::now();
+        copy_meta.namespace = meta.namespace;
+        self.inner
+            .adapter
+            .retype_object(
+                Old::TYPE,
+                ObjectRecord::from_object(&copy),
+                copy.derive_unique_hashes(),
+            )
+            .await?;
+        Ok(copy)
+    }
+
+    /// Rename stored edges from `old_type` to `new_type` after `E::TYPE` changed,
+    /// in one statement. Where a pair already has a `new_type` edge, the
+    /// `old_type` edge is left in place; check with
+    /// [`Engine::count_edges_by_type`]. Returns the number of edges renamed.
+    /// Unique-constraint claims of the old type are not carried over.
+    pub async fn migrate_edge_type(&self, old_type: &str, new_type: &str) -> Result<u64, Error> {
+        self.inner
+            .adapter
+            .rename_edge_type(old_type, new_type)
+            .await
+    }
+
+    /// Copy every `old_type` edge to `new_type`, keeping the originals, for
+    /// dual-write migrations. Pairs that already have a `new_type` edge are
+    /// skipped. Returns the number of edges inserted.
+    pub async fn copy_edge_type(&self, old_type: &str, new_type: &str) -> Result<u64, Error> {
+        self.inner.adapter.copy_edge_type(old_type, new_type).await
+    }
+
+    /// Number of stored edges with this type name, across all sources.
+    pub async fn count_edges_by_type(&self, type_name: &str) -> Result<u64, Error> {
+        self.inner.adapter.count_edges_by_type(type_name).await
+    }
+
+    /// `EXPLAIN ANALYZE` of the SQL `query_objects::<T>(query)` would run, for
+    /// checking which indexes a query hits. Postgres only; other adapters
+    /// return `Error::NotSupported`.
+    pub async fn explain_analyze_query<T: Object>(&self, query: Query) -> Result<String, Error> {
+        self.inner.adapter.explain_query(T::TYPE, query).await
+    }
+
+    /// Planner estimate for `query_objects::<T>(query)` without running it:
+    /// expected rows, whether an index or a full table scan is used, and the
+    /// planner's cost. SQLite reports no row or cost estimates, so those stay
+    /// zero there. Adapters without a planner return `Error::NotSupported`.
+    pub async fn estimate_query_cost<T: Object>(&self, query: Query) -> Result<QueryCost, Error> {
+        self.inner.adapter.estimate_query_cost(T::TYPE, query).await
+    }
+
+    /// [`Engine::estimate_query_cost`] for `query_edges::<E>(from, plan)`
+    pub async fn estimate_edge_query_cost<E: Edge>(
+        &self,
+        from: Uuid,
+        plan: EdgeQuery,
+    ) -> Result<QueryCost, Error> {
+        self.inner
+            .adapter
+            .estimate_edge_query_cost(E::TYPE, from, plan)
+            .await
+    }
+
+    // ==================== Raw SQL ====================
+    /// Required by [`Engine::execute_raw`]. Take one only where hand-written SQL
+    /// is intended, so raw queries stay easy to find.
+    #[cfg(feature = "raw-queries")]
+    pub fn raw_query_guard(&self) -> RawQueryGuard {
+        RawQueryGuard { _private: () }
+    }
+
+    /// Run adapter-specific SQL with positional `params` and return the rows
+    /// as JSON objects keyed by column name.
+    ///
+    /// Unsafe in spirit: the SQL bypasses owner scoping, type names, unique
+    /// constraints and index metadata, and is not portable across adapters.
+    /// Adapters without SQL return `Error::NotSupported`.
+    #[cfg(feature = "raw-queries")]
+    pub async fn execute_raw(
+        &self,
+        _guard: &RawQueryGuard,
+        sql: &str,
+        params: Vec<serde_json::Value>,
+    ) -> Result<Vec<serde_json::Value>, Error> {
+        self.inner.adapter.execute_raw(sql, params).await
+    }
+
+    // ==================== Watch ====================
+    /// Stream the object every time it changes.
+    /// Postgres pushes via LISTEN/NOTIFY; other adapters poll `updated_at`.
+    /// The stream ends after yielding an error (e.g. the connection dropped).
+    #[cfg(feature = "watch")]
+    pub async fn watch_object<T: Object>(
+        &self,
+        id: Uuid,
+    ) -> Result<futures::stream::BoxStream<'static, Result<T, Error>>, Error> {
+        use futures::TryStreamExt;
+
+        if let Some(changes) = self.inner.adapter.change_stream().await? {
+            let stream =
+                watch::from_change_stream::<T>(self.clone(), changes, move |ev| ev.id == id);
+            return Ok(Box::pin(stream.map_ok(|(_, obj)| obj)));
+        }
+
+        let last_seen = self.fetch_object::<T>(id).await?.map(|o| o.updated_at());
+        Ok(watch::poll_object(self.clone(), id, last_seen))
+    }
+
+    /// Stream every insert/update of objects of type `T`, across all owners.
+    /// Deletes are not reported — the object can no longer be fetched.
+    #[cfg(feature = "watch")]
+    pub async fn watch_type<T: Object>(
+        &self,
+    ) -> Result<futures::stream::BoxStream<'static, Result<(watch::ChangeKind, T), Error>>, Error>
+    {
+        if let Some(changes) = self.inner.adapter.change_stream().await? {
+            return Ok(watch::from_change_stream::<T>(
+                self.clone(),
+                changes,
+                |_| true,
+            ));
+        }
+
+        Ok(watch::poll_type(self.clone(), Utc::now()))
+    }
+
+    /// Stream what changes in the result set of `query`: objects that start
+    /// or stop matching, or match and were updated. The query re-runs every
+    /// `poll_interval`; on Postgres a write to `T` also triggers a re-run.
+    /// Only the first page of a limited query is watched.
+    #[cfg(feature = "watch")]
+    pub async fn watch_query<T: Object + Clone>(
+        &self,
+        query: Query,
+        poll_interval: Duration,
+    ) -> Result<futures::stream::BoxStream<'static, Result<watch::QueryChange<T>, Error>>, Error>
+    {
+        let baseline = self.query_objects::<T>(query.clone()).await?;
+        let changes = self.inner.adapter.change_stream().await?;
+        Ok(watch::watch_query(
+            self.clone(),
+            query,
+            poll_interval,
+            baseline,
+            changes,
+        ))
+    }
+
     #[cfg(feature = "ledger")]
     pub fn ledger(&self) -> &Arc<dyn ledger::LedgerAdapter> {
         let ledger = self
@@ -586,6 +3040,12 @@ impl Engine {
         ledger
     }
 
+    /// Every asset registered with the ledger
+    #[cfg(feature = "ledger")]
+    pub async fn list_assets(&self) -> Result<Vec<ledger::Asset>, ledger::MoneyError> {
+        self.ledger().list_assets().await
+    }
+
     #[cfg(feature = "ledger")]
     pub fn ledger_ctx(&self) -> ledger::LedgerContext {
         let arc = self
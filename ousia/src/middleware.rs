@@ -0,0 +1,179 @@
+//! Hooks run by the [`Engine`](crate::Engine) after object writes, and an
+//! in-process change feed built on them.
+//!
+//! Unlike the `watch` feature, the change feed needs nothing from the
+//! database, so it works with every adapter. It only sees writes made through
+//! engines the publisher is attached to, in this process.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::{Object, ObjectDiff, ObjectRecord};
+
+pub use tokio::sync::broadcast::error::RecvError;
+
+/// Events a [`ChangeSubscriber`] can fall behind by before it starts losing them
+pub const CHANGE_CHANNEL_CAPACITY: usize = 1024;
+
+/// Called after single-object writes succeed, with the record as stored (or,
+/// for deletes, as it was). Register with
+/// [`Engine::with_middleware`](crate::Engine::with_middleware), which lists
+/// the writes covered.
+///
+/// Hooks run inline on the writing task, so keep them cheap and non-blocking.
+pub trait EngineMiddleware: Send + Sync + 'static {
+    fn on_create(&self, _record: &ObjectRecord) {}
+    fn on_update(&self, _record: &ObjectRecord) {}
+    fn on_delete(&self, _record: &ObjectRecord) {}
+
+    /// Called instead of `on_update` when the stored state the update
+    /// replaced is known. Defaults to `on_update`.
+    fn on_update_from(&self, _replaced: &ObjectRecord, record: &ObjectRecord) {
+        self.on_update(record);
+    }
+}
+
+/// One update seen by [`AuditMiddleware`]
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub type_name: String,
+    pub id: Uuid,
+    pub owner: Uuid,
+    pub updated_at: DateTime<Utc>,
+    pub diff: ObjectDiff,
+}
+
+/// Hands an [`ObjectDiff`] of every `update_object` that changed something
+/// to `sink`, e.g. to write an audit log. Creates and deletes are not
+/// reported.
+pub struct AuditMiddleware {
+    sink: Box<dyn Fn(AuditEntry) + Send + Sync>,
+}
+
+impl AuditMiddleware {
+    pub fn new(sink: impl Fn(AuditEntry) + Send + Sync + 'static) -> Self {
+        Self {
+            sink: Box::new(sink),
+        }
+    }
+}
+
+impl EngineMiddleware for AuditMiddleware {
+    fn on_update_from(&self, replaced: &ObjectRecord, record: &ObjectRecord) {
+        let diff = ObjectDiff::between(&replaced.data, &record.data);
+        if diff.is_empty() {
+            return;
+        }
+        (self.sink)(AuditEntry {
+            type_name: record.type_name.to_string(),
+            id: record.id,
+            owner: record.owner,
+            updated_at: record.updated_at,
+            diff,
+        });
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// One write to a `T` object. `object` is the state after the write; for
+/// deletes, the state that was removed.
+#[derive(Debug)]
+pub struct ChangeEvent<T> {
+    pub kind: ChangeKind,
+    pub id: Uuid,
+    pub object: Arc<T>,
+}
+
+impl<T> Clone for ChangeEvent<T> {
+    fn clone(&self) -> Self {
+        Self {
+            kind: self.kind,
+            id: self.id,
+            object: Arc::clone(&self.object),
+        }
+    }
+}
+
+/// Middleware half of [`Engine::subscribe`](crate::Engine::subscribe):
+/// publishes every write to a `T` object. Writes to other types are ignored.
+pub struct ChangePublisher<T> {
+    sender: broadcast::Sender<ChangeEvent<T>>,
+}
+
+impl<T: Object> ChangePublisher<T> {
+    fn publish(&self, kind: ChangeKind, record: &ObjectRecord) {
+        if record.type_name != T::TYPE || self.sender.receiver_count() == 0 {
+            return;
+        }
+        // A record that no longer deserializes as `T` has nothing to publish
+        if let Ok(object) = record.clone().to_object::<T>() {
+            let _ = self.sender.send(ChangeEvent {
+                kind,
+                id: record.id,
+                object: Arc::new(object),
+            });
+        }
+    }
+}
+
+impl<T: Object> EngineMiddleware for ChangePublisher<T> {
+    fn on_create(&self, record: &ObjectRecord) {
+        self.publish(ChangeKind::Created, record);
+    }
+
+    fn on_update(&self, record: &ObjectRecord) {
+        self.publish(ChangeKind::Updated, record);
+    }
+
+    fn on_delete(&self, record: &ObjectRecord) {
+        self.publish(ChangeKind::Deleted, record);
+    }
+}
+
+/// Receiving half of [`Engine::subscribe`](crate::Engine::subscribe). Clones
+/// receive every event published after they were made.
+pub struct ChangeSubscriber<T> {
+    receiver: broadcast::Receiver<ChangeEvent<T>>,
+}
+
+impl<T: Object> ChangeSubscriber<T> {
+    /// The next event. `Err(RecvError::Lagged(n))` means `n` events were
+    /// dropped because this subscriber fell more than
+    /// [`CHANGE_CHANNEL_CAPACITY`] behind; `Closed` that the publisher is gone.
+    pub async fn recv(&mut self) -> Result<ChangeEvent<T>, RecvError> {
+        self.receiver.recv().await
+    }
+
+    /// [`recv`](Self::recv) as a stream, ending when the publisher is dropped
+    pub fn stream(self) -> impl Stream<Item = Result<ChangeEvent<T>, RecvError>> + Send {
+        stream::unfold(self, |mut subscriber| async move {
+            match subscriber.recv().await {
+                Err(RecvError::Closed) => None,
+                next => Some((next, subscriber)),
+            }
+        })
+    }
+}
+
+impl<T> Clone for ChangeSubscriber<T> {
+    fn clone(&self) -> Self {
+        Self {
+            receiver: self.receiver.resubscribe(),
+        }
+    }
+}
+
+pub(crate) fn channel<T: Object>() -> (ChangePublisher<T>, ChangeSubscriber<T>) {
+    let (sender, receiver) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
+    (ChangePublisher { sender }, ChangeSubscriber { receiver })
+}
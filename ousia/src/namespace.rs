@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{Engine, Object, ObjectMeta, Query, error::Error};
+
+/// An [`Engine`] confined to one namespace, from [`Engine::with_namespace`].
+///
+/// Objects created through it are stamped with the namespace, and reads,
+/// updates and deletes only see objects carrying the same one. Anything not
+/// exposed here (edges, unions, ledger, maintenance) goes through the
+/// unscoped [`Engine`], which sees every namespace.
+#[derive(Clone)]
+pub struct NamespacedEngine {
+    engine: Engine,
+}
+
+impl NamespacedEngine {
+    pub(crate) fn new(mut engine: Engine, namespace: &str) -> Self {
+        engine.namespace = Some(Arc::from(namespace));
+        Self { engine }
+    }
+
+    pub fn namespace(&self) -> &str {
+        self.engine.namespace.as_deref().unwrap_or_default()
+    }
+
+    fn contains<T: Object>(&self, obj: &T) -> bool {
+        obj.meta().namespace == self.namespace()
+    }
+
+    pub async fn create_object<T: Object>(&self, obj: &T) -> Result<(), Error> {
+        self.engine.create_object(obj).await
+    }
+
+    /// `None` for objects in other namespaces, as if they didn't exist.
+    pub async fn fetch_object<T: Object>(&self, id: Uuid) -> Result<Option<T>, Error> {
+        let obj = self.engine.fetch_object::<T>(id).await?;
+        Ok(obj.filter(|obj| self.contains(obj)))
+    }
+
+    pub async fn fetch_objects<T: Object>(&self, ids: Vec<Uuid>) -> Result<Vec<T>, Error> {
+        let objects = self.engine.fetch_objects::<T>(ids).await?;
+        Ok(objects
+            .into_iter()
+            .filter(|obj| self.contains(obj))
+            .collect())
+    }
+
+    /// `Error::NotFound` when the stored object belongs to another namespace.
+    pub async fn update_object<T: Object>(&self, obj: &mut T) -> Result<(), Error> {
+        if self.fetch_object::<T>(obj.id()).await?.is_none() {
+            return Err(Error::NotFound);
+        }
+        self.engine.update_object(obj).await
+    }
+
+    /// Leaves objects in other namespaces alone and returns `None`.
+    pub async fn delete_object<T: Object>(
+        &self,
+        id: Uuid,
+        owner: Uuid,
+    ) -> Result<Option<T>, Error> {
+        if self.fetch_object::<T>(id).await?.is_none() {
+            return Ok(None);
+        }
+        self.engine.delete_object(id, owner).await
+    }
+
+    pub async fn query_objects<T: Object>(&self, query: Query) -> Result<Vec<T>, Error> {
+        let query = query.where_namespace(self.namespace());
+        self.engine.query_objects(query).await
+    }
+
+    /// `query: None` counts every object of `T` in the namespace.
+    pub async fn count_objects<T: Object>(&self, query: Option<Query>) -> Result<u64, Error> {
+        let query = query
+            .unwrap_or_else(Query::wide)
+            .where_namespace(self.namespace());
+        self.engine.count_objects::<T>(Some(query)).await
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use super::Object;
+
+/// Field-level difference between two states of an object.
+/// Only persisted, non-meta fields are compared.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjectDiff {
+    /// field -> (old, new). A field missing on one side is reported as `Null`.
+    pub changed: BTreeMap<String, (Value, Value)>,
+    pub unchanged: Vec<String>,
+}
+
+impl ObjectDiff {
+    /// Persisted state of `obj`, suitable for a later [`ObjectDiff::between`].
+    pub fn snapshot<T: Object>(obj: &T) -> Value {
+        obj.__serialize_internal()
+    }
+
+    /// Compare two snapshots key by key
+    pub fn between(before: &Value, after: &Value) -> Self {
+        let empty = serde_json::Map::new();
+        let before = before.as_object().unwrap_or(&empty);
+        let after = after.as_object().unwrap_or(&empty);
+
+        let mut diff = Self::default();
+        for key in before.keys().chain(after.keys()) {
+            if diff.changed.contains_key(key) || diff.unchanged.contains(key) {
+                continue;
+            }
+            let old = before.get(key).cloned().unwrap_or(Value::Null);
+            let new = after.get(key).cloned().unwrap_or(Value::Null);
+            if before.contains_key(key) == after.contains_key(key) && old == new {
+                diff.unchanged.push(key.clone());
+            } else {
+                diff.changed.insert(key.clone(), (old, new));
+            }
+        }
+        diff.unchanged.sort();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty()
+    }
+
+    pub fn changed_fields(&self) -> Vec<&str> {
+        self.changed.keys().map(String::as_str).collect()
+    }
+}
@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::Object;
+use crate::error::Error;
+
+/// One state of a `#[ousia(versioned)]` object, from [`Engine::object_timeline`].
+///
+/// Versions start at 1. Each update stores the state it replaced, so the current
+/// state is always the highest version.
+///
+/// [`Engine::object_timeline`]: crate::Engine::object_timeline
+#[derive(Debug, Clone)]
+pub struct ObjectSnapshot<T: Object> {
+    pub version: u64,
+    pub data: T,
+    /// When this state was written
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Kind of write recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeOperation {
+    Create,
+    Update,
+    Delete,
+}
+
+impl ChangeOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeOperation::Create => "create",
+            ChangeOperation::Update => "update",
+            ChangeOperation::Delete => "delete",
+        }
+    }
+
+    pub(crate) fn parse(value: &str) -> Result<Self, Error> {
+        match value {
+            "create" => Ok(ChangeOperation::Create),
+            "update" => Ok(ChangeOperation::Update),
+            "delete" => Ok(ChangeOperation::Delete),
+            other => Err(Error::Deserialize(format!(
+                "unknown change operation: {other}"
+            ))),
+        }
+    }
+}
+
+/// One row of `object_audit_log`, from [`Engine::object_change_log`].
+///
+/// Rows are written by a database trigger on every insert, update and delete
+/// of `objects`, whatever the type, so no opt-in is needed.
+///
+/// [`Engine::object_change_log`]: crate::Engine::object_change_log
+#[derive(Debug, Clone)]
+pub struct ChangeLogEntry {
+    /// Increases with every logged write
+    pub id: i64,
+    pub object_id: Uuid,
+    pub type_name: String,
+    pub operation: ChangeOperation,
+    /// Data before the write; `None` for creates
+    pub old_data: Option<serde_json::Value>,
+    /// Data after the write; `None` for deletes
+    pub new_data: Option<serde_json::Value>,
+    pub changed_at: DateTime<Utc>,
+    /// Owner of the object after the write (before it, for deletes). The
+    /// trigger has no notion of the calling user.
+    pub changed_by: Uuid,
+}
@@ -0,0 +1,36 @@
+use crate::{Engine, adapters::LockGuard, error::Error};
+
+use super::{Object, ObjectMeta};
+
+/// Exclusive hold on an object, returned by [`Engine::lock_object`].
+/// Dropping it without calling [`ObjectLock::update`] releases the lock and writes nothing.
+pub struct ObjectLock<T: Object> {
+    engine: Engine,
+    object: T,
+    _guard: Box<dyn LockGuard>,
+}
+
+impl<T: Object> ObjectLock<T> {
+    pub(crate) fn new(engine: Engine, object: T, guard: Box<dyn LockGuard>) -> Self {
+        Self {
+            engine,
+            object,
+            _guard: guard,
+        }
+    }
+
+    /// State of the object as read after the lock was granted
+    pub fn object(&self) -> &T {
+        &self.object
+    }
+
+    /// Save `obj` while still holding the lock, then release it
+    pub async fn update(self, obj: &mut T) -> Result<(), Error> {
+        if obj.id() != self.object.id() {
+            return Err(Error::InvalidQuery(
+                "ObjectLock::update called with a different object".to_string(),
+            ));
+        }
+        self.engine.update_object(obj).await
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::object::SYSTEM_OWNER;
@@ -8,6 +10,18 @@ pub struct Meta {
     pub owner: uuid::Uuid,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// Creation order within the type, assigned by `Engine::create_object`
+    /// and numbered from 1. `0` until the object has been stored.
+    #[serde(default)]
+    pub sequence: u64,
+    /// Free-form key/value tags, stored as `data.__labels` and queryable
+    /// with `Query::where_label`
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Tenant partition, stored as `data.__namespace`. Empty outside a
+    /// namespace; set by `NamespacedEngine` on create.
+    #[serde(default)]
+    pub namespace: String,
 }
 
 impl Default for Meta {
@@ -17,6 +31,9 @@ impl Default for Meta {
             owner: SYSTEM_OWNER,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            sequence: 0,
+            labels: BTreeMap::new(),
+            namespace: String::new(),
         }
     }
 }
@@ -28,6 +45,17 @@ impl Meta {
             owner,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            sequence: 0,
+            labels: BTreeMap::new(),
+            namespace: String::new(),
+        }
+    }
+
+    /// System-owned meta with a caller-chosen ID, e.g. one from `Engine::reserve_id`
+    pub fn with_id(id: uuid::Uuid) -> Self {
+        Self {
+            id,
+            ..Default::default()
         }
     }
 }
@@ -48,4 +76,28 @@ impl Meta {
     pub fn updated_at(&self) -> chrono::DateTime<chrono::Utc> {
         self.updated_at
     }
+
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    pub fn labels(&self) -> &BTreeMap<String, String> {
+        &self.labels
+    }
+
+    pub fn label(&self, key: &str) -> Option<&str> {
+        self.labels.get(key).map(String::as_str)
+    }
+
+    pub fn set_label(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.labels.insert(key.into(), value.into());
+    }
+
+    pub fn remove_label(&mut self, key: &str) -> Option<String> {
+        self.labels.remove(key)
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
 }
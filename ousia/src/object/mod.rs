@@ -1,7 +1,15 @@
+pub mod diff;
+pub mod history;
+pub mod lock;
 pub mod meta;
+pub mod stats;
 pub mod traits;
 
+pub use diff::*;
+pub use history::*;
+pub use lock::*;
 pub use meta::*;
+pub use stats::*;
 pub use traits::*;
 
 use uuid::Uuid;
@@ -15,6 +23,23 @@ pub fn system_owner() -> Uuid {
     SYSTEM_OWNER
 }
 
+/// Compile-time check used by the derives: a type name is non-empty and
+/// has no ASCII whitespace or `/`
+pub const fn is_valid_type_name(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    if bytes.is_empty() {
+        return false;
+    }
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_whitespace() || bytes[i] == b'/' {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
 pub fn derive_unique_hash(type_name: &str, field_name: &str, value: &str) -> String {
     let mut hasher = blake3::Hasher::new();
     hasher.update(type_name.as_bytes());
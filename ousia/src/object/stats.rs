@@ -0,0 +1,82 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use super::Object;
+use crate::query::{IndexField, IndexValue};
+
+/// Summary of one indexed field over objects already in memory.
+/// Objects where the field is missing or `Null` are left out of every figure.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjectStats {
+    /// Objects with a value for the field
+    pub count: u64,
+    pub min: Option<IndexValue>,
+    pub max: Option<IndexValue>,
+    /// Only numeric values (`Int`, `Float`) contribute; `None` if there were none
+    pub sum: Option<f64>,
+    pub avg: Option<f64>,
+    pub distinct_count: u64,
+}
+
+impl ObjectStats {
+    /// Read `field` from each object's index meta. `min` and `max` compare
+    /// values of the same kind (ints and floats together); arrays and values
+    /// of another kind than the first one seen don't move them.
+    pub fn compute<T: Object>(objects: &[T], field: &'static IndexField) -> Self {
+        let mut stats = Self::default();
+        let mut distinct = HashSet::new();
+        let mut numeric = 0u64;
+
+        for obj in objects {
+            let meta = obj.index_meta();
+            let Some(value) = meta.0.get(field.name).filter(|value| !value.is_null()) else {
+                continue;
+            };
+            stats.count += 1;
+            distinct.insert(serde_json::to_string(value).unwrap_or_default());
+
+            if let Some(n) = as_number(value) {
+                stats.sum = Some(stats.sum.unwrap_or(0.0) + n);
+                numeric += 1;
+            }
+            match &stats.min {
+                None if compare(value, value).is_some() => stats.min = Some(value.clone()),
+                Some(min) if compare(value, min) == Some(Ordering::Less) => {
+                    stats.min = Some(value.clone())
+                }
+                _ => {}
+            }
+            match &stats.max {
+                None if compare(value, value).is_some() => stats.max = Some(value.clone()),
+                Some(max) if compare(value, max) == Some(Ordering::Greater) => {
+                    stats.max = Some(value.clone())
+                }
+                _ => {}
+            }
+        }
+
+        stats.avg = stats.sum.map(|sum| sum / numeric as f64);
+        stats.distinct_count = distinct.len() as u64;
+        stats
+    }
+}
+
+fn as_number(value: &IndexValue) -> Option<f64> {
+    match value {
+        IndexValue::Int(i) => Some(*i as f64),
+        IndexValue::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+/// `None` for values of different kinds and for arrays
+fn compare(a: &IndexValue, b: &IndexValue) -> Option<Ordering> {
+    match (a, b) {
+        (IndexValue::Int(a), IndexValue::Int(b)) => Some(a.cmp(b)),
+        (IndexValue::String(a), IndexValue::String(b)) => Some(a.cmp(b)),
+        (IndexValue::Bool(a), IndexValue::Bool(b)) => Some(a.cmp(b)),
+        (IndexValue::Uuid(a), IndexValue::Uuid(b)) => Some(a.cmp(b)),
+        (IndexValue::Timestamp(a), IndexValue::Timestamp(b)) => Some(a.cmp(b)),
+        _ => as_number(a)?.partial_cmp(&as_number(b)?),
+    }
+}
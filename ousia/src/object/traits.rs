@@ -7,9 +7,9 @@ use crate::{object::Meta, query::IndexMeta};
 /// by the Ousia engine for persistence operations.
 #[doc(hidden)]
 pub trait ObjectInternal {
-    /// Serialize ALL fields including private ones for database persistence.
-    /// This bypasses the view system and includes data that should not be
-    /// exposed through normal serialization.
+    /// Serialize all persisted fields for database storage.
+    /// This bypasses the view system. `#[ousia(private)]` fields are never
+    /// included and fall back to their default after deserialization.
     fn __serialize_internal(&self) -> serde_json::Value;
 }
 
@@ -29,6 +29,9 @@ pub trait Object:
     /// Object type name
     const TYPE: &'static str;
 
+    /// Keep each pre-update state in `object_history`; set by `#[ousia(versioned)]`
+    const VERSIONED: bool = false;
+
     /// Object type name helper
     fn type_name(&self) -> &'static str {
         Self::TYPE
@@ -44,11 +47,36 @@ pub trait Object:
     fn index_meta(&self) -> IndexMeta;
 }
 
+/// Partial update generated by `#[derive(OusiaPartial)]` as `{Name}Patch`.
+pub trait ObjectPatch: Send + Sync + 'static {
+    type Target: Object;
+
+    /// Names of the fields this patch sets
+    fn changed_fields(&self) -> Vec<&'static str>;
+
+    /// Overwrite every set field on `target`
+    fn apply_to(self, target: &mut Self::Target);
+}
+
+/// Objects that can be updated with [`Engine::patch_object`](crate::Engine::patch_object).
+pub trait Partial: Object {
+    type Patch: ObjectPatch<Target = Self>;
+}
+
 pub trait ObjectMeta {
     fn id(&self) -> uuid::Uuid;
     fn owner(&self) -> uuid::Uuid;
     fn created_at(&self) -> chrono::DateTime<chrono::Utc>;
     fn updated_at(&self) -> chrono::DateTime<chrono::Utc>;
+
+    /// Owned by [`SYSTEM_OWNER`](super::SYSTEM_OWNER)
+    fn is_system_owned(&self) -> bool;
+    fn is_owned_by(&self, owner: uuid::Uuid) -> bool;
+
+    /// Time since `created_at`
+    fn age(&self) -> chrono::Duration;
+    /// `updated_at` is no older than `within`
+    fn was_recently_updated(&self, within: chrono::Duration) -> bool;
 }
 
 impl<T> ObjectMeta for T
@@ -70,6 +98,22 @@ where
     fn updated_at(&self) -> chrono::DateTime<chrono::Utc> {
         self.meta().updated_at()
     }
+
+    fn is_system_owned(&self) -> bool {
+        self.meta().owner() == super::SYSTEM_OWNER
+    }
+
+    fn is_owned_by(&self, owner: uuid::Uuid) -> bool {
+        self.meta().owner() == owner
+    }
+
+    fn age(&self) -> chrono::Duration {
+        chrono::Utc::now() - self.meta().created_at()
+    }
+
+    fn was_recently_updated(&self, within: chrono::Duration) -> bool {
+        chrono::Utc::now() - self.meta().updated_at() <= within
+    }
 }
 
 pub trait ObjectType {
@@ -86,22 +130,10 @@ where
 }
 
 pub trait ObjectOwnership {
-    fn is_system_owned(&self) -> bool;
-
-    fn is_owned_by<O: Object>(&self, owner: &O) -> bool;
-
     fn set_owner(&mut self, owner: uuid::Uuid);
 }
 
 impl<T: Object> ObjectOwnership for T {
-    fn is_system_owned(&self) -> bool {
-        self.meta().owner() == super::SYSTEM_OWNER
-    }
-
-    fn is_owned_by<O: Object>(&self, object: &O) -> bool {
-        self.meta().owner() == object.meta().id()
-    }
-
     fn set_owner(&mut self, owner: uuid::Uuid) {
         self.meta_mut().owner = owner;
     }
@@ -141,3 +173,36 @@ impl<A: Object, B: Object> Union<A, B> {
         }
     }
 }
+
+/// One object out of a two-type set, tagged by its stored type name.
+/// Returned by [`crate::Engine::fetch_union_as_enum`] and
+/// [`crate::Engine::query_union`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
+
+impl<A, B> Either<A, B> {
+    pub fn is_left(&self) -> bool {
+        matches!(self, Self::Left(_))
+    }
+
+    pub fn is_right(&self) -> bool {
+        matches!(self, Self::Right(_))
+    }
+
+    pub fn left(self) -> Option<A> {
+        match self {
+            Self::Left(a) => Some(a),
+            Self::Right(_) => None,
+        }
+    }
+
+    pub fn right(self) -> Option<B> {
+        match self {
+            Self::Left(_) => None,
+            Self::Right(b) => Some(b),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::{any::Any, future::Future, marker::PhantomData, pin::Pin, sync::Mutex};
+
+use uuid::Uuid;
+
+use crate::{Edge, EdgeQuery, Engine, Object, Query, error::Error};
+
+type Output = Result<Box<dyn Any + Send>, Error>;
+type Op = Box<dyn FnOnce(Engine) -> Pin<Box<dyn Future<Output = Output> + Send>> + Send>;
+
+/// Independent reads queued up and sent together by [`Pipeline::execute`].
+///
+/// Operations run concurrently, each on its own pooled connection, so the
+/// total latency is close to the slowest read rather than the sum of all of
+/// them. With a single-connection pool (in-memory SQLite) they simply run
+/// one after another.
+pub struct Pipeline {
+    engine: Engine,
+    ops: Vec<Op>,
+}
+
+/// Handle to one queued operation; read it with [`Deferred::resolve`]
+/// after the pipeline has run.
+pub struct Deferred<R> {
+    index: usize,
+    _marker: PhantomData<fn() -> R>,
+}
+
+/// Results of an executed [`Pipeline`], one slot per queued operation.
+pub struct PipelineResult {
+    slots: Vec<Mutex<Option<Output>>>,
+}
+
+impl Pipeline {
+    pub(crate) fn new(engine: Engine) -> Self {
+        Self {
+            engine,
+            ops: Vec::new(),
+        }
+    }
+
+    fn push<R, F, Fut>(&mut self, op: F) -> Deferred<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(Engine) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<R, Error>> + Send + 'static,
+    {
+        let index = self.ops.len();
+        self.ops.push(Box::new(move |engine| {
+            Box::pin(async move {
+                op(engine)
+                    .await
+                    .map(|value| Box::new(value) as Box<dyn Any + Send>)
+            })
+        }));
+        Deferred {
+            index,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn fetch_object<T: Object>(&mut self, id: Uuid) -> Deferred<Option<T>> {
+        self.push(move |engine| async move { engine.fetch_object::<T>(id).await })
+    }
+
+    pub fn fetch_objects<T: Object>(&mut self, ids: Vec<Uuid>) -> Deferred<Vec<T>> {
+        self.push(move |engine| async move { engine.fetch_objects::<T>(ids).await })
+    }
+
+    pub fn query_objects<T: Object>(&mut self, query: Query) -> Deferred<Vec<T>> {
+        self.push(move |engine| async move { engine.query_objects::<T>(query).await })
+    }
+
+    pub fn count_objects<T: Object>(&mut self, query: Option<Query>) -> Deferred<u64> {
+        self.push(move |engine| async move { engine.count_objects::<T>(query).await })
+    }
+
+    pub fn fetch_edge<E: Edge>(&mut self, from: Uuid, to: Uuid) -> Deferred<Option<E>> {
+        self.push(move |engine| async move { engine.fetch_edge::<E>(from, to).await })
+    }
+
+    pub fn count_edges<E: Edge>(&mut self, from: Uuid, query: Option<EdgeQuery>) -> Deferred<u64> {
+        self.push(move |engine| async move { engine.count_edges::<E>(from, query).await })
+    }
+
+    pub fn count_reverse_edges<E: Edge>(
+        &mut self,
+        to: Uuid,
+        query: Option<EdgeQuery>,
+    ) -> Deferred<u64> {
+        self.push(move |engine| async move { engine.count_reverse_edges::<E>(to, query).await })
+    }
+
+    /// Number of queued operations
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Run every queued operation. A failing operation doesn't stop the
+    /// others; its error is returned by the matching [`Deferred::resolve`].
+    pub async fn execute(self) -> Result<PipelineResult, Error> {
+        let engine = self.engine;
+        let outputs =
+            futures::future::join_all(self.ops.into_iter().map(|op| op(engine.clone()))).await;
+        Ok(PipelineResult {
+            slots: outputs
+                .into_iter()
+                .map(|output| Mutex::new(Some(output)))
+                .collect(),
+        })
+    }
+}
+
+impl<R: 'static> Deferred<R> {
+    /// Take this operation's result out of `result`. Each handle resolves once.
+    pub fn resolve(&self, result: &PipelineResult) -> Result<R, Error> {
+        let output = result
+            .slots
+            .get(self.index)
+            .and_then(|slot| slot.lock().unwrap().take())
+            .ok_or_else(|| {
+                Error::InvalidQuery("pipeline result already taken or missing".to_string())
+            })?;
+        output?
+            .downcast::<R>()
+            .map(|value| *value)
+            .map_err(|_| Error::InvalidQuery("pipeline result has an unexpected type".to_string()))
+    }
+}
@@ -10,6 +10,65 @@ impl IndexMeta {
     pub fn meta(&self) -> &BTreeMap<String, IndexValue> {
         &self.0
     }
+
+    /// Keys of `new` that are absent from or differ in `old`, as a JSON object,
+    /// and the keys of `old` that `new` no longer has.
+    pub fn diff(old: &IndexMeta, new: &IndexMeta) -> (serde_json::Value, Vec<String>) {
+        let changed: serde_json::Map<String, serde_json::Value> = new
+            .0
+            .iter()
+            .filter(|(key, value)| old.0.get(*key) != Some(*value))
+            .map(|(key, value)| {
+                let value = serde_json::to_value(value).expect("Failed to serialize index value");
+                (key.clone(), value)
+            })
+            .collect();
+        let removed = old
+            .0
+            .keys()
+            .filter(|key| !new.0.contains_key(*key))
+            .cloned()
+            .collect();
+        (serde_json::Value::Object(changed), removed)
+    }
+
+    /// Apply a patch produced by [`IndexMeta::diff`]; `null` values are kept
+    /// as [`IndexValue::Null`]. Entries that aren't valid index values are ignored.
+    ///
+    /// UUIDs and timestamps serialize as JSON strings, so a string is read back
+    /// as the variant the key already holds, or, for a new key, as a UUID or
+    /// RFC 3339 timestamp when it parses as one.
+    pub fn merge(&mut self, patch: serde_json::Value) {
+        let serde_json::Value::Object(patch) = patch else {
+            return;
+        };
+        for (key, value) in patch {
+            let value = match value {
+                serde_json::Value::String(s) => Self::typed_string(self.0.get(&key), s),
+                value => match serde_json::from_value(value) {
+                    Ok(value) => value,
+                    Err(_) => continue,
+                },
+            };
+            self.0.insert(key, value);
+        }
+    }
+
+    fn typed_string(current: Option<&IndexValue>, s: String) -> IndexValue {
+        let as_uuid = |s: &str| Uuid::parse_str(s).ok().map(IndexValue::Uuid);
+        let as_timestamp = |s: &str| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(|ts| IndexValue::Timestamp(ts.with_timezone(&chrono::Utc)))
+        };
+        let typed = match current {
+            Some(IndexValue::String(_)) => None,
+            Some(IndexValue::Uuid(_)) => as_uuid(&s),
+            Some(IndexValue::Timestamp(_)) => as_timestamp(&s),
+            _ => as_uuid(&s).or_else(|| as_timestamp(&s)),
+        };
+        typed.unwrap_or(IndexValue::String(s))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -43,6 +102,30 @@ impl IndexValueInner {
     }
 }
 
+impl From<String> for IndexValueInner {
+    fn from(s: String) -> Self {
+        IndexValueInner::String(s)
+    }
+}
+
+impl From<&str> for IndexValueInner {
+    fn from(s: &str) -> Self {
+        IndexValueInner::String(s.to_string())
+    }
+}
+
+impl From<i64> for IndexValueInner {
+    fn from(i: i64) -> Self {
+        IndexValueInner::Int(i)
+    }
+}
+
+impl From<f64> for IndexValueInner {
+    fn from(f: f64) -> Self {
+        IndexValueInner::Float(f)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(untagged)]
 pub enum IndexValue {
@@ -53,6 +136,8 @@ pub enum IndexValue {
     Uuid(Uuid),
     Timestamp(chrono::DateTime<chrono::Utc>),
     Array(Vec<IndexValueInner>),
+    /// An `Option` field set to `None`; stored as JSON `null`
+    Null,
 }
 
 impl IndexValue {
@@ -97,11 +182,33 @@ impl IndexValue {
             _ => None,
         }
     }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, IndexValue::Null)
+    }
 }
 
 // Helper trait to convert types to IndexValue
 pub trait ToIndexValue {
     fn to_index_value(&self) -> IndexValue;
+
+    /// Form used as an array element. UUIDs and timestamps become strings,
+    /// bools become 0/1.
+    fn to_index_value_inner(&self) -> IndexValueInner {
+        match self.to_index_value() {
+            IndexValue::String(s) => IndexValueInner::String(s),
+            IndexValue::Int(i) => IndexValueInner::Int(i),
+            IndexValue::Float(f) => IndexValueInner::Float(f),
+            IndexValue::Bool(b) => IndexValueInner::Int(b as i64),
+            IndexValue::Uuid(u) => IndexValueInner::String(u.to_string()),
+            IndexValue::Timestamp(t) => IndexValueInner::String(t.to_rfc3339()),
+            // Arrays don't nest; keep the JSON text so it still compares by value
+            IndexValue::Array(a) => {
+                IndexValueInner::String(serde_json::to_string(&a).unwrap_or_default())
+            }
+            IndexValue::Null => IndexValueInner::String(String::new()),
+        }
+    }
 }
 
 impl ToIndexValue for String {
@@ -160,43 +267,17 @@ impl ToIndexValue for IndexValueInner {
             IndexValueInner::Float(f) => IndexValue::Float(*f),
         }
     }
-}
-
-impl ToIndexValue for Vec<IndexValueInner> {
-    fn to_index_value(&self) -> IndexValue {
-        IndexValue::Array(self.clone())
-    }
-}
-
-impl ToIndexValue for Vec<String> {
-    fn to_index_value(&self) -> IndexValue {
-        IndexValue::Array(
-            self.iter()
-                .map(|s| IndexValueInner::String(s.clone()))
-                .collect(),
-        )
-    }
-}
-
-impl ToIndexValue for Vec<&str> {
-    fn to_index_value(&self) -> IndexValue {
-        IndexValue::Array(
-            self.iter()
-                .map(|s| IndexValueInner::String(s.to_string()))
-                .collect(),
-        )
-    }
-}
 
-impl ToIndexValue for Vec<i64> {
-    fn to_index_value(&self) -> IndexValue {
-        IndexValue::Array(self.iter().map(|i| IndexValueInner::Int(*i)).collect())
+    fn to_index_value_inner(&self) -> IndexValueInner {
+        self.clone()
     }
 }
 
-impl ToIndexValue for Vec<f64> {
+/// Any `Vec` of indexable scalars is stored as an array, so `Vec<String>`
+/// fields work with `where_contains` / `where_contains_all` out of the box.
+impl<T: ToIndexValue> ToIndexValue for Vec<T> {
     fn to_index_value(&self) -> IndexValue {
-        IndexValue::Array(self.iter().map(|f| IndexValueInner::Float(*f)).collect())
+        IndexValue::Array(self.iter().map(|x| x.to_index_value_inner()).collect())
     }
 }
 
@@ -206,11 +287,12 @@ impl ToIndexValue for Uuid {
     }
 }
 
-impl<T: ToIndexValue + Default> ToIndexValue for Option<T> {
+/// `None` is indexed as an explicit `null`, matched by `where_null`.
+impl<T: ToIndexValue> ToIndexValue for Option<T> {
     fn to_index_value(&self) -> IndexValue {
         match self {
             Some(val) => val.to_index_value(),
-            None => T::default().to_index_value(),
+            None => IndexValue::Null,
         }
     }
 }
@@ -238,6 +320,64 @@ pub struct QueryFilter {
     pub mode: QueryMode,
 }
 
+/// Key under which `Meta::labels` is stored in `data`
+pub(crate) const LABELS_KEY: &str = "__labels";
+
+pub(crate) static LABELS: IndexField = IndexField {
+    name: LABELS_KEY,
+    kinds: &[IndexKind::Search],
+};
+
+/// Key under which `Meta::sequence` is stored in `data`
+pub(crate) const SEQUENCE_KEY: &str = "__sequence";
+
+/// Key under which `Meta::namespace` is stored in `data`
+pub(crate) const NAMESPACE_KEY: &str = "__namespace";
+
+pub(crate) static NAMESPACE: IndexField = IndexField {
+    name: NAMESPACE_KEY,
+    kinds: &[IndexKind::Search],
+};
+
+impl QueryFilter {
+    /// The namespace of a `where_namespace` filter, which reads `data.__namespace`
+    pub(crate) fn as_namespace(&self) -> Option<&str> {
+        if self.field.name != NAMESPACE_KEY {
+            return None;
+        }
+        self.value.as_string()
+    }
+
+    /// `(key, Some(value))` for `where_label`, `(key, None)` for `where_has_label`.
+    /// Label filters read `data.__labels` rather than `index_meta`.
+    pub(crate) fn as_label(&self) -> Option<(&str, Option<&str>)> {
+        if self.field.name != LABELS_KEY {
+            return None;
+        }
+        match &self.value {
+            IndexValue::String(key) => Some((key, None)),
+            IndexValue::Array(pair) => match pair.as_slice() {
+                [key, value] => Some((key.as_string()?, Some(value.as_string()?))),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// `Some(true)` for IS NULL, `Some(false)` for IS NOT NULL. Comparing
+    /// against a null value, as in `where_eq(field, None::<T>)`, reads the same way.
+    pub(crate) fn as_null_check(&self) -> Option<bool> {
+        let search = self.mode.as_search()?;
+        match (&search.comparison, &self.value) {
+            (Comparison::IsNull, _) => Some(true),
+            (Comparison::IsNotNull, _) => Some(false),
+            (Comparison::NotEqual, IndexValue::Null) => Some(false),
+            (_, IndexValue::Null) => Some(true),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum QueryMode {
     Search(QuerySearch),
@@ -263,6 +403,7 @@ impl QueryMode {
         QueryMode::Search(QuerySearch {
             comparison: comp,
             operator: op.unwrap_or_default(),
+            case_sensitive: true,
         })
     }
 
@@ -271,6 +412,7 @@ impl QueryMode {
         QueryMode::Search(QuerySearch {
             comparison: Comparison::Equal,
             operator: Operator::And,
+            case_sensitive: true,
         })
     }
 
@@ -288,6 +430,8 @@ impl QueryMode {
 pub struct QuerySearch {
     pub comparison: Comparison,
     pub operator: Operator,
+    /// Only read by `Comparison::Regex`; other comparisons keep their own casing rules
+    pub case_sensitive: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -306,6 +450,10 @@ pub enum Comparison {
     GreaterThanOrEqual,
     LessThanOrEqual,
     NotEqual,
+    IsNull,
+    IsNotNull,
+    /// POSIX regular expression match; Postgres and CockroachDB only
+    Regex,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
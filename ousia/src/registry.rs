@@ -0,0 +1,45 @@
+//! Compile-time list of `#[derive(OusiaObject)]` types (`type-registry` feature).
+//!
+//! The derive always invokes [`__register_type!`](crate::__register_type); it
+//! only submits to the registry when the feature is on.
+
+#[cfg(feature = "type-registry")]
+#[doc(hidden)]
+pub use inventory;
+
+/// One object type, submitted by the derive
+#[cfg(feature = "type-registry")]
+pub struct RegisteredType(pub &'static str);
+
+#[cfg(feature = "type-registry")]
+inventory::collect!(RegisteredType);
+
+/// Type names of every linked object type, sorted and deduplicated
+#[cfg(feature = "type-registry")]
+pub(crate) fn registered_types() -> Vec<&'static str> {
+    let mut types: Vec<&'static str> = inventory::iter::<RegisteredType>
+        .into_iter()
+        .map(|t| t.0)
+        .collect();
+    types.sort_unstable();
+    types.dedup();
+    types
+}
+
+#[cfg(feature = "type-registry")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_type {
+    ($type_name:expr) => {
+        $crate::registry::inventory::submit! {
+            $crate::registry::RegisteredType($type_name)
+        }
+    };
+}
+
+#[cfg(not(feature = "type-registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_type {
+    ($type_name:expr) => {};
+}
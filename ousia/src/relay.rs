@@ -0,0 +1,43 @@
+//! Relay-style connections over object queries (`relay` feature).
+//!
+//! Objects are ordered by ID, oldest first. Cursors are the node's ID as
+//! URL-safe base64, so they stay valid as long as the object exists.
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// One page of a connection from
+/// [`Engine::page_objects_relay`](crate::Engine::page_objects_relay)
+#[derive(Debug, Clone)]
+pub struct RelayPage<T> {
+    pub edges: Vec<RelayEdge<T>>,
+    pub page_info: PageInfo,
+}
+
+#[derive(Debug, Clone)]
+pub struct RelayEdge<T> {
+    pub cursor: String,
+    pub node: T,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+    pub start_cursor: Option<String>,
+    pub end_cursor: Option<String>,
+}
+
+pub fn encode_cursor(id: Uuid) -> String {
+    URL_SAFE_NO_PAD.encode(id.as_bytes())
+}
+
+pub fn decode_cursor(cursor: &str) -> Result<Uuid, Error> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(cursor)
+        .map_err(|err| Error::InvalidQuery(format!("invalid relay cursor: {}", err)))?;
+    Uuid::from_slice(&bytes)
+        .map_err(|err| Error::InvalidQuery(format!("invalid relay cursor: {}", err)))
+}
@@ -0,0 +1,19 @@
+use crate::{Object, query::IndexField};
+
+/// Order of [`Engine::object_search`](crate::Engine::object_search) results
+/// when relevance isn't wanted. Without one, the best match comes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchSort {
+    Asc(&'static IndexField),
+    Desc(&'static IndexField),
+}
+
+/// One hit of [`Engine::object_search`](crate::Engine::object_search)
+#[derive(Debug, Clone)]
+pub struct SearchResult<T: Object> {
+    pub object: T,
+    /// Relevance as reported by the backend; higher is better. Postgres gives
+    /// `ts_rank`, SQLite the negated FTS5 `bm25` rank, so scores from the two
+    /// are not comparable.
+    pub score: f64,
+}
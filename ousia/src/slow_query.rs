@@ -0,0 +1,71 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use tokio::time::Instant;
+
+/// Entries kept by [`crate::Engine::slow_query_log`]; older ones are dropped
+pub const SLOW_QUERY_LOG_CAPACITY: usize = 100;
+
+/// An adapter call that took longer than the engine's slow-query threshold
+#[derive(Debug, Clone)]
+pub struct SlowQueryLog {
+    /// Engine method that issued the call, e.g. `"query_objects"`
+    pub operation: String,
+    pub type_name: String,
+    pub duration: Duration,
+    /// When the call finished
+    pub timestamp: DateTime<Utc>,
+}
+
+pub(crate) struct SlowQueryTracker {
+    threshold: Duration,
+    log: Arc<Mutex<VecDeque<SlowQueryLog>>>,
+}
+
+impl SlowQueryTracker {
+    pub(crate) fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            log: Arc::new(Mutex::new(VecDeque::with_capacity(SLOW_QUERY_LOG_CAPACITY))),
+        }
+    }
+
+    pub(crate) fn log(&self) -> Arc<Mutex<VecDeque<SlowQueryLog>>> {
+        self.log.clone()
+    }
+
+    pub(crate) async fn observe<F: Future>(
+        &self,
+        operation: &'static str,
+        type_name: &str,
+        call: F,
+    ) -> F::Output {
+        let start = Instant::now();
+        let output = call.await;
+        let duration = start.elapsed();
+        if duration > self.threshold {
+            tracing::warn!(
+                operation,
+                type_name,
+                duration_ms = duration.as_millis() as u64,
+                "slow query"
+            );
+            let mut log = self.log.lock().unwrap_or_else(|err| err.into_inner());
+            if log.len() == SLOW_QUERY_LOG_CAPACITY {
+                log.pop_front();
+            }
+            log.push_back(SlowQueryLog {
+                operation: operation.to_string(),
+                type_name: type_name.to_string(),
+                duration,
+                timestamp: Utc::now(),
+            });
+        }
+        output
+    }
+}
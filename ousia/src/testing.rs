@@ -0,0 +1,46 @@
+//! Fixtures for testing code built on ousia (`test-utils` feature).
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+pub use ledger::adapters::MemoryAdapter;
+
+use crate::{Engine, adapters::sqlite::SqliteAdapter};
+
+/// An [`Engine`] over a fresh in-memory SQLite database, with the ledger
+/// backed by a [`MemoryAdapter`] so `ledger_ctx()` works without Postgres.
+///
+/// Derefs to [`Engine`]. Each instance starts empty; nothing is shared
+/// between tests.
+#[derive(Clone)]
+pub struct TestEngine {
+    engine: Engine,
+}
+
+impl TestEngine {
+    /// Panics if the schema can't be created: this is test setup.
+    pub async fn new() -> Self {
+        let adapter = SqliteAdapter::new_memory()
+            .await
+            .expect("in-memory SQLite database");
+        adapter.init_schema().await.expect("SQLite schema");
+
+        let mut engine = Engine::new(Box::new(adapter));
+        Arc::get_mut(&mut engine.inner)
+            .expect("engine was just created")
+            .ledger = Some(Arc::new(MemoryAdapter::new()));
+        Self { engine }
+    }
+
+    pub fn into_inner(self) -> Engine {
+        self.engine
+    }
+}
+
+impl Deref for TestEngine {
+    type Target = Engine;
+
+    fn deref(&self) -> &Engine {
+        &self.engine
+    }
+}
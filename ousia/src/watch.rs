@@ -0,0 +1,230 @@
+//! Real-time change notifications (`watch` feature).
+//!
+//! Adapters with native pub/sub (Postgres `LISTEN`/`NOTIFY`) expose a
+//! [`ChangeStream`] through [`Adapter::change_stream`](crate::Adapter::change_stream).
+//! Everything else falls back to polling every [`POLL_INTERVAL`].
+
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, BoxStream, Stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::time::{Interval, MissedTickBehavior};
+use uuid::Uuid;
+
+use crate::{Engine, Error, Object, ObjectMeta, Query};
+
+/// Postgres channel the object trigger notifies on.
+pub const WATCH_CHANNEL: &str = "ousia_changes";
+
+/// How often polling watchers re-check the database.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// Notification payload: `{ "type": "User", "id": "...", "op": "update" }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub id: Uuid,
+    pub op: ChangeKind,
+}
+
+/// Raw feed of every object write, as produced by the adapter.
+pub type ChangeStream = Pin<Box<dyn Stream<Item = Result<ChangeEvent, Error>> + Send>>;
+
+fn poll_interval() -> Interval {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    interval
+}
+
+/// Re-fetch `T` for every native notification that matches `accept`.
+/// Deletes are skipped — there is nothing left to fetch.
+pub(crate) fn from_change_stream<T: Object>(
+    engine: Engine,
+    changes: ChangeStream,
+    accept: impl Fn(&ChangeEvent) -> bool + Send + 'static,
+) -> BoxStream<'static, Result<(ChangeKind, T), Error>> {
+    changes
+        .try_filter(move |ev| {
+            std::future::ready(ev.type_name == T::TYPE && ev.op != ChangeKind::Delete && accept(ev))
+        })
+        .and_then(move |ev| {
+            let engine = engine.clone();
+            async move {
+                let obj = engine.fetch_object::<T>(ev.id).await?;
+                Ok(obj.map(|obj| (ev.op, obj)))
+            }
+        })
+        .try_filter_map(|item| std::future::ready(Ok(item)))
+        .boxed()
+}
+
+/// Poll a single object and yield it whenever its `updated_at` moves.
+pub(crate) fn poll_object<T: Object>(
+    engine: Engine,
+    id: Uuid,
+    last_seen: Option<DateTime<Utc>>,
+) -> BoxStream<'static, Result<T, Error>> {
+    stream::unfold(
+        Some((engine, last_seen, poll_interval())),
+        move |state| async move {
+            let (engine, mut last_seen, mut interval) = state?;
+            loop {
+                interval.tick().await;
+                match engine.fetch_object::<T>(id).await {
+                    Ok(Some(obj)) if Some(obj.updated_at()) != last_seen => {
+                        last_seen = Some(obj.updated_at());
+                        return Some((Ok(obj), Some((engine, last_seen, interval))));
+                    }
+                    Ok(_) => continue,
+                    // The stream ends after surfacing the error
+                    Err(e) => return Some((Err(e), None)),
+                }
+            }
+        },
+    )
+    .boxed()
+}
+
+/// Poll every object of type `T` (across all owners) updated after `since`.
+pub(crate) fn poll_type<T: Object>(
+    engine: Engine,
+    since: DateTime<Utc>,
+) -> BoxStream<'static, Result<(ChangeKind, T), Error>> {
+    stream::unfold(
+        Some((engine, since, VecDeque::new(), poll_interval())),
+        |state| async move {
+            let (engine, mut since, mut pending, mut interval) = state?;
+            loop {
+                if let Some(item) = pending.pop_front() {
+                    return Some((Ok(item), Some((engine, since, pending, interval))));
+                }
+
+                interval.tick().await;
+                let query = Query::wide().where_updated_after(since);
+
+                match engine.query_objects::<T>(query).await {
+                    Ok(objects) => {
+                        // Anything created after the previous watermark is new to us
+                        let watermark = since;
+                        for obj in objects {
+                            // Backends compare timestamps as text; re-check exactly
+                            if obj.updated_at() <= watermark {
+                                continue;
+                            }
+                            since = since.max(obj.updated_at());
+                            let kind = if obj.created_at() > watermark {
+                                ChangeKind::Insert
+                            } else {
+                                ChangeKind::Update
+                            };
+                            pending.push_back((kind, obj));
+                        }
+                    }
+                    Err(e) => return Some((Err(e), None)),
+                }
+            }
+        },
+    )
+    .boxed()
+}
+
+/// One tick's difference in a watched query's result set, by object ID.
+/// `updated` holds objects still matching whose `updated_at` moved.
+#[derive(Debug, Clone)]
+pub struct QueryChange<T> {
+    pub added: Vec<T>,
+    pub removed: Vec<T>,
+    pub updated: Vec<T>,
+}
+
+impl<T> QueryChange<T> {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
+/// Diff `current` against `previous` and keep `current` as the new baseline.
+fn diff_results<T: Object + Clone>(
+    previous: &mut HashMap<Uuid, T>,
+    current: Vec<T>,
+) -> QueryChange<T> {
+    let mut change = QueryChange {
+        added: Vec::new(),
+        removed: Vec::new(),
+        updated: Vec::new(),
+    };
+    let mut next = HashMap::with_capacity(current.len());
+    for obj in current {
+        match previous.remove(&obj.id()) {
+            None => change.added.push(obj.clone()),
+            Some(old) if old.updated_at() != obj.updated_at() => change.updated.push(obj.clone()),
+            Some(_) => {}
+        }
+        next.insert(obj.id(), obj);
+    }
+    change.removed = std::mem::replace(previous, next).into_values().collect();
+    change
+}
+
+/// Re-run `query` on every tick of `poll` (and, when the adapter has one, on
+/// every native notification for `T`), yielding non-empty deltas.
+pub(crate) fn watch_query<T: Object + Clone>(
+    engine: Engine,
+    query: Query,
+    poll: Duration,
+    baseline: Vec<T>,
+    changes: Option<ChangeStream>,
+) -> BoxStream<'static, Result<QueryChange<T>, Error>> {
+    let mut interval = tokio::time::interval(poll);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let ticks = stream::unfold(interval, |mut interval| async move {
+        interval.tick().await;
+        Some((Ok(()), interval))
+    });
+    let triggers: BoxStream<'static, Result<(), Error>> = match changes {
+        Some(changes) => stream::select(
+            ticks,
+            changes
+                .try_filter(|ev| std::future::ready(ev.type_name == T::TYPE))
+                .map_ok(|_| ()),
+        )
+        .boxed(),
+        None => ticks.boxed(),
+    };
+    let previous: HashMap<Uuid, T> = baseline.into_iter().map(|obj| (obj.id(), obj)).collect();
+
+    stream::unfold(
+        Some((engine, query, triggers, previous)),
+        |state| async move {
+            let (engine, query, mut triggers, mut previous) = state?;
+            loop {
+                match triggers.next().await? {
+                    Ok(()) => {}
+                    Err(e) => return Some((Err(e), None)),
+                }
+                match engine.query_objects::<T>(query.clone()).await {
+                    Ok(current) => {
+                        let change = diff_results(&mut previous, current);
+                        if !change.is_empty() {
+                            return Some((Ok(change), Some((engine, query, triggers, previous))));
+                        }
+                    }
+                    Err(e) => return Some((Err(e), None)),
+                }
+            }
+        },
+    )
+    .boxed()
+}
@@ -17,7 +17,7 @@
 
 use criterion::{Criterion, criterion_group, criterion_main};
 use ousia::{EdgeMeta, EdgeQuery, Engine, ObjectMeta, Query, adapters::postgres::PostgresAdapter};
-use ousia_bench::{BenchFollow, BenchUser, RawFollow, RawUser, orm};
+use ousia_bench::{BenchFollow, BenchPost, BenchUser, RawFollow, RawUser, orm};
 use sea_orm::{
     ColumnTrait, DbBackend, EntityTrait, FromQueryResult, PaginatorTrait, QueryFilter, Statement,
     sea_query::OnConflict,
@@ -324,6 +324,86 @@ fn bench_count_edges(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_count_edges_batch(c: &mut Criterion) {
+    let (_rt, ctx) = state();
+    let mut group = c.benchmark_group("count_edges_batch");
+
+    group.bench_function("ousia_n_plus_1", |b| {
+        b.iter(|| {
+            run!({
+                for &pivot in &ctx.ousia_n1_user_ids {
+                    let _: u64 = ctx
+                        .engine
+                        .count_edges::<BenchFollow>(pivot, None)
+                        .await
+                        .unwrap();
+                }
+            })
+        })
+    });
+
+    group.bench_function("ousia_batch", |b| {
+        b.iter(|| {
+            run!({
+                let _: std::collections::HashMap<Uuid, u64> = ctx
+                    .engine
+                    .count_edges_batch::<BenchFollow>(&ctx.ousia_n1_user_ids)
+                    .await
+                    .unwrap();
+            })
+        })
+    });
+
+    group.finish();
+}
+
+/// Owned children + outgoing edges for a page of 100 users.
+///
+/// ousia_n_plus_1 — 1 parent query, then 2 queries per user
+/// ousia_batch    — 1 parent query, then children and edges batched concurrently
+fn bench_preload_children_and_edges(c: &mut Criterion) {
+    let (_rt, ctx) = state();
+    let mut group = c.benchmark_group("preload_children_and_edges");
+
+    group.bench_function("ousia_n_plus_1", |b| {
+        b.iter(|| {
+            run!({
+                let users: Vec<BenchUser> = ctx
+                    .engine
+                    .query_objects(Query::default().with_limit(100))
+                    .await
+                    .unwrap();
+                for user in &users {
+                    let _: Vec<BenchPost> =
+                        ctx.engine.fetch_owned_objects(user.id()).await.unwrap();
+                    let _: Vec<BenchFollow> = ctx
+                        .engine
+                        .query_edges(user.id(), EdgeQuery::default())
+                        .await
+                        .unwrap();
+                }
+            })
+        })
+    });
+
+    group.bench_function("ousia_batch", |b| {
+        b.iter(|| {
+            run!({
+                let _: ousia::BatchResult<BenchUser, BenchPost, BenchFollow> = ctx
+                    .engine
+                    .preload_objects::<BenchUser>(Query::default().with_limit(100))
+                    .preload::<BenchPost>()
+                    .with_edges::<BenchFollow>()
+                    .execute()
+                    .await
+                    .unwrap();
+            })
+        })
+    });
+
+    group.finish();
+}
+
 fn bench_edge_filter(c: &mut Criterion) {
     let (rt, ctx) = state();
     let mut group = c.benchmark_group("query_edges_with_filter");
@@ -717,11 +797,13 @@ fn run_all(c: &mut Criterion) {
     bench_query_edges(c);
     bench_reverse_edges(c);
     bench_count_edges(c);
+    bench_count_edges_batch(c);
     bench_edge_filter(c);
     bench_preload_forward(c);
     bench_preload_reverse(c);
     bench_preload_multi_pivot_forward(c);
     bench_preload_multi_pivot_count(c);
+    bench_preload_children_and_edges(c);
     bench_create_edge(c);
 }
 
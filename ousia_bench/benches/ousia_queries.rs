@@ -24,10 +24,13 @@ use uuid::Uuid;
 struct Ctx {
     engine: Engine,
     ousia_cursor_mid: Uuid,
+    ousia_update_post: Uuid,
+    ousia_author: Uuid,
 
     raw_pool: PgPool,
     orm_db: sea_orm::DatabaseConnection,
     raw_cursor_mid: Uuid,
+    raw_author: Uuid,
 }
 
 unsafe impl Sync for Ctx {}
@@ -68,13 +71,27 @@ async fn setup() -> Ctx {
     let ousia_user_ids = ousia_bench::seed_ousia_users_bulk(&ousia_pool, 50_000).await;
     seed_ousia_posts(&engine, &ousia_user_ids[..100], 20).await;
     let ousia_cursor_mid = ousia_user_ids[25_000];
+    let mut update_post = BenchPost::default();
+    update_post.set_owner(ousia_user_ids[0]);
+    update_post.title = "update target".to_string();
+    engine.create_object(&update_post).await.unwrap();
+    let ousia_update_post = update_post.id();
 
     setup_raw_schema(&raw_pool).await;
     let raw_user_ids = ousia_bench::seed_raw_users_bulk(&raw_pool, 50_000).await;
     seed_raw_posts(&raw_pool, &raw_user_ids[..100], 20).await;
     let raw_cursor_mid = raw_user_ids[25_000];
 
-    Ctx { engine, ousia_cursor_mid, raw_pool, orm_db, raw_cursor_mid }
+    Ctx {
+        engine,
+        ousia_cursor_mid,
+        ousia_update_post,
+        ousia_author: ousia_user_ids[1],
+        raw_pool,
+        orm_db,
+        raw_cursor_mid,
+        raw_author: raw_user_ids[1],
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -175,6 +192,7 @@ async fn seed_ousia_posts(engine: &Engine, owner_ids: &[Uuid], per_owner: usize)
             };
             post.view_count = (p as i64) * 13 % 50_000;
             post.tags = vec![format!("tag_{}", p % 5), format!("cat_{}", oi % 3)];
+            post.author_id = owner_id;
             engine.create_object(&post).await.unwrap();
         }
     }
@@ -234,6 +252,41 @@ fn bench_and_filter(c: &mut Criterion) {
     group.finish();
 }
 
+// Foreign-key style lookup: posts by author id across all owners
+fn bench_uuid_filter(c: &mut Criterion) {
+    let (_, ctx) = state();
+    let mut group = c.benchmark_group("query_uuid_field");
+
+    group.bench_function("ousia", |b| {
+        b.iter(|| {
+            run!({
+                let _: Vec<BenchPost> = ctx
+                    .engine
+                    .query_objects(
+                        Query::wide().where_eq(&BenchPost::FIELDS.author_id, ctx.ousia_author),
+                    )
+                    .await
+                    .unwrap();
+            })
+        })
+    });
+
+    group.bench_function("raw_sqlx", |b| {
+        b.iter(|| {
+            run!({
+                let _: Vec<ousia_bench::RawPost> =
+                    sqlx::query_as("SELECT * FROM posts WHERE owner_id = $1")
+                        .bind(ctx.raw_author)
+                        .fetch_all(&ctx.raw_pool)
+                        .await
+                        .unwrap();
+            })
+        })
+    });
+
+    group.finish();
+}
+
 fn bench_or_filter(c: &mut Criterion) {
     let (rt, ctx) = state();
     let mut group = c.benchmark_group("query_or_condition");
@@ -559,13 +612,138 @@ fn bench_create_object(c: &mut Criterion) {
     group.finish();
 }
 
+/// 1 000 updates per iteration. `partial` touches one of BenchPost's four index
+/// keys (sent as a patch); `full` touches three (whole row replaced).
+fn bench_update_object(c: &mut Criterion) {
+    let (_, ctx) = state();
+    let mut group = c.benchmark_group("update_object_x1000");
+
+    group.bench_function("partial", |b| {
+        b.iter(|| {
+            run!({
+                let mut post: BenchPost =
+                    ctx.engine.fetch_object(ctx.ousia_update_post).await.unwrap().unwrap();
+                for i in 0..1_000 {
+                    post.view_count = i;
+                    ctx.engine.update_object(&mut post).await.unwrap();
+                }
+            })
+        })
+    });
+
+    group.bench_function("full", |b| {
+        b.iter(|| {
+            run!({
+                let mut post: BenchPost =
+                    ctx.engine.fetch_object(ctx.ousia_update_post).await.unwrap().unwrap();
+                for i in 0..1_000 {
+                    post.view_count = i;
+                    post.title = format!("update target {i}");
+                    post.status = match i % 2 { 0 => PostStatus::Published, _ => PostStatus::Draft };
+                    ctx.engine.update_object(&mut post).await.unwrap();
+                }
+            })
+        })
+    });
+
+    group.finish();
+}
+
+// Five independent reads: one after another vs one pipeline
+fn bench_pipeline(c: &mut Criterion) {
+    let (_, ctx) = state();
+    let mut group = c.benchmark_group("five_reads");
+
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            run!({
+                let post: Option<BenchPost> = ctx.engine.fetch_object(ctx.ousia_update_post).await.unwrap();
+                let user: Option<BenchUser> = ctx.engine.fetch_object(ctx.ousia_cursor_mid).await.unwrap();
+                let posts = ctx.engine.count_objects::<BenchPost>(Some(Query::default())).await.unwrap();
+                let users: Vec<BenchUser> = ctx.engine.fetch_objects(vec![ctx.ousia_cursor_mid]).await.unwrap();
+                let page: Vec<BenchPost> = ctx.engine.query_objects(Query::default().with_limit(10)).await.unwrap();
+                (post, user, posts, users, page)
+            })
+        })
+    });
+
+    group.bench_function("pipeline", |b| {
+        b.iter(|| {
+            run!({
+                let mut p = ctx.engine.pipeline();
+                let post = p.fetch_object::<BenchPost>(ctx.ousia_update_post);
+                let user = p.fetch_object::<BenchUser>(ctx.ousia_cursor_mid);
+                let posts = p.count_objects::<BenchPost>(Some(Query::default()));
+                let users = p.fetch_objects::<BenchUser>(vec![ctx.ousia_cursor_mid]);
+                let page = p.query_objects::<BenchPost>(Query::default().with_limit(10));
+                let result = p.execute().await.unwrap();
+                (
+                    post.resolve(&result).unwrap(),
+                    user.resolve(&result).unwrap(),
+                    posts.resolve(&result).unwrap(),
+                    users.resolve(&result).unwrap(),
+                    page.resolve(&result).unwrap(),
+                )
+            })
+        })
+    });
+
+    group.finish();
+}
+
+// `score > 5000` on 100k users: GIN index only vs a B-tree expression index.
+// Uses its own database so the extra index doesn't skew the other groups.
+fn bench_numeric_expression_index(c: &mut Criterion) {
+    let (engine, adapter) = run!({
+        let pool = ousia_bench::connect_db("ousia_bench_q_expr").await;
+        let adapter = PostgresAdapter::from_pool(pool.clone());
+        adapter.init_schema().await.expect("ousia schema");
+        sqlx::query("DROP INDEX IF EXISTS idx_objects_score_num")
+            .execute(&pool).await.unwrap();
+        sqlx::query("TRUNCATE public.edges, public.objects")
+            .execute(&pool).await.unwrap();
+        ousia_bench::seed_ousia_users_bulk(&pool, 100_000).await;
+        sqlx::query("ANALYZE public.objects").execute(&pool).await.unwrap();
+        (
+            Engine::new(Box::new(PostgresAdapter::from_pool(pool.clone()))),
+            adapter,
+        )
+    });
+    let query = || Query::default().where_gt(&BenchUser::FIELDS.score, 5000_i64);
+    let mut group = c.benchmark_group("numeric_range_100k");
+
+    group.bench_function("gin_only", |b| {
+        b.iter(|| {
+            run!({
+                let _: Vec<BenchUser> = engine.query_objects(query()).await.unwrap();
+            })
+        })
+    });
+
+    run!(adapter.create_numeric_expression_index("score").await.unwrap());
+
+    group.bench_function("expression_index", |b| {
+        b.iter(|| {
+            run!({
+                let _: Vec<BenchUser> = engine.query_objects(query()).await.unwrap();
+            })
+        })
+    });
+
+    group.finish();
+}
+
 fn run_all(c: &mut Criterion) {
     bench_and_filter(c);
+    bench_uuid_filter(c);
     bench_or_filter(c);
     bench_cursor_pagination(c);
     bench_multi_sort(c);
     bench_full_scan(c);
     bench_create_object(c);
+    bench_update_object(c);
+    bench_pipeline(c);
+    bench_numeric_expression_index(c);
 }
 
 criterion_group! {
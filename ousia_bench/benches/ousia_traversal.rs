@@ -0,0 +1,359 @@
+//! Benchmark: Edge traversal at growing pivot counts
+//!
+//! 1 000 users, each following the next 10 (10k edges). Every case decodes
+//! into typed structs, as in the other bench binaries.
+//!
+//! Databases:
+//!   ousia_bench_t_ousia  — Ousia schema, seeded with `seed_ousia_edges_bulk`.
+//!   ousia_bench_t_raw    — plain `users` + `follows`; raw_sqlx and sea_orm share it.
+//!
+//! Covers:
+//!   query_edges        ousia / raw_sqlx / sea_orm   (10 outgoing edges of one user)
+//!   traversal_join/N   ousia / raw_sqlx / sea_orm   (targets of N users' follows, N = 1, 100, 1000)
+//!
+//! For N = 1 ousia runs the single traversal JOIN behind
+//! `preload_object(..).edge::<E, O>()`. For larger N it fetches the pivots and
+//! then runs one batch JOIN; raw_sqlx and sea_orm do both in one statement.
+//!
+//! Criterion reports the mean. After the groups, a latency summary prints
+//! mean / p95 / p99 per case and ousia's overhead over raw_sqlx for the JOIN.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use ousia::{EdgeQuery, Engine, Query, adapters::postgres::PostgresAdapter, system_owner};
+use ousia_bench::{BenchFollow, BenchUser, RawFollow, RawUser, orm};
+use sea_orm::{ColumnTrait, DbBackend, EntityTrait, FromQueryResult, QueryFilter, Statement};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Pivot counts measured by `traversal_join`
+const PIVOT_COUNTS: [usize; 3] = [1, 100, 1_000];
+
+/// Timed runs per case in the latency summary
+const LATENCY_SAMPLES: usize = 200;
+
+/// Batch JOIN row: pivot id + the followed user's columns.
+#[derive(Debug, sqlx::FromRow, FromQueryResult)]
+struct RawEdgeUser {
+    pivot_id: Uuid,
+    #[sqlx(flatten)]
+    #[sea_orm(nested)]
+    user: RawUser,
+}
+
+/// Groups batch JOIN rows per pivot, the shape ousia's batch traversal returns.
+fn group_by_pivot(rows: Vec<RawEdgeUser>) -> HashMap<Uuid, Vec<RawUser>> {
+    let mut grouped: HashMap<Uuid, Vec<RawUser>> = HashMap::new();
+    for row in rows {
+        grouped.entry(row.pivot_id).or_default().push(row.user);
+    }
+    grouped
+}
+
+const JOIN_ONE_SQL: &str = r#"SELECT u.id, u.username, u.email, u.display_name, u.score, u.active,
+                                     u.created_at, u.updated_at
+                              FROM follows f
+                              INNER JOIN users u ON u.id = f."to"
+                              WHERE f."from" = $1"#;
+
+const JOIN_MANY_SQL: &str = r#"SELECT f."from" AS pivot_id, u.id, u.username, u.email,
+                                      u.display_name, u.score, u.active, u.created_at, u.updated_at
+                               FROM follows f
+                               INNER JOIN users u ON u.id = f."to"
+                               WHERE f."from" IN (SELECT id FROM users ORDER BY id DESC LIMIT $1)"#;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Shared state
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct Ctx {
+    engine: Engine,
+    ousia_pivot: Uuid,
+
+    raw_pool: PgPool,
+    orm_db: sea_orm::DatabaseConnection,
+    raw_pivot: Uuid,
+}
+
+unsafe impl Sync for Ctx {}
+
+static STATE: ousia_bench::BenchHandle<Ctx> = ousia_bench::BenchHandle::new();
+
+fn state() -> &'static (tokio::runtime::Runtime, Ctx) {
+    STATE.get_or_init(|| {
+        let rt = ousia_bench::mt_rt();
+        let ctx = rt.block_on(setup());
+        (rt, ctx)
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Setup
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn setup() -> Ctx {
+    let ousia_pool = ousia_bench::connect_db("ousia_bench_t_ousia").await;
+    let raw_pool = ousia_bench::connect_db("ousia_bench_t_raw").await;
+    let orm_db = ousia_bench::connect_orm("ousia_bench_t_raw").await;
+
+    let adapter = PostgresAdapter::from_pool(ousia_pool.clone());
+    adapter.init_schema().await.expect("ousia schema");
+    let engine = Engine::new(Box::new(adapter));
+
+    let ousia_user_ids = ousia_bench::seed_ousia_users_bulk(&ousia_pool, 1_000).await;
+    ousia_bench::seed_ousia_edges_bulk(&ousia_pool, &ousia_user_ids, 10).await;
+    // The bulk seed writes the nil owner, which no public query matches; hand the
+    // users to the system owner so `Query::default()` selects the pivots.
+    sqlx::query("UPDATE public.objects SET owner = $1")
+        .bind(system_owner())
+        .execute(&ousia_pool)
+        .await
+        .unwrap();
+
+    setup_raw_schema(&raw_pool).await;
+    let raw_user_ids = ousia_bench::seed_raw_users_bulk(&raw_pool, 1_000).await;
+    ousia_bench::seed_raw_follows_bulk(&raw_pool, &raw_user_ids, 10).await;
+
+    Ctx {
+        engine,
+        ousia_pivot: ousia_user_ids[500],
+        raw_pool,
+        orm_db,
+        raw_pivot: raw_user_ids[500],
+    }
+}
+
+async fn setup_raw_schema(pool: &PgPool) {
+    sqlx::query(
+        r#"CREATE TABLE users (
+            id           UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+            username     TEXT        NOT NULL DEFAULT '',
+            email        TEXT        NOT NULL DEFAULT '',
+            display_name TEXT        NOT NULL DEFAULT '',
+            score        BIGINT      NOT NULL DEFAULT 0,
+            active       BOOLEAN     NOT NULL DEFAULT true,
+            created_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+            updated_at   TIMESTAMPTZ NOT NULL DEFAULT now()
+        )"#,
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+
+    sqlx::query(
+        r#"CREATE TABLE follows (
+            "from"  UUID    NOT NULL,
+            "to"    UUID    NOT NULL,
+            weight  BIGINT  NOT NULL DEFAULT 1,
+            PRIMARY KEY ("from", "to")
+        )"#,
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+
+    sqlx::query(r#"CREATE INDEX idx_follows_to ON follows("to")"#)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Cases — shared by the criterion groups and the latency summary
+// ─────────────────────────────────────────────────────────────────────────────
+
+async fn ousia_query_edges(ctx: &Ctx) {
+    let _: Vec<BenchFollow> = ctx
+        .engine
+        .query_edges(ctx.ousia_pivot, EdgeQuery::default())
+        .await
+        .unwrap();
+}
+
+async fn raw_query_edges(ctx: &Ctx) {
+    let _: Vec<RawFollow> =
+        sqlx::query_as(r#"SELECT "from", "to", weight FROM follows WHERE "from" = $1"#)
+            .bind(ctx.raw_pivot)
+            .fetch_all(&ctx.raw_pool)
+            .await
+            .unwrap();
+}
+
+async fn orm_query_edges(ctx: &Ctx) {
+    let _: Vec<orm::follows::Model> = orm::follows::Entity::find()
+        .filter(orm::follows::Column::FromId.eq(ctx.raw_pivot))
+        .all(&ctx.orm_db)
+        .await
+        .unwrap();
+}
+
+async fn ousia_traverse(ctx: &Ctx, n: usize) {
+    if n == 1 {
+        let _: Vec<BenchUser> = ctx
+            .engine
+            .preload_object::<BenchUser>(ctx.ousia_pivot)
+            .edge::<BenchFollow, BenchUser>()
+            .collect()
+            .await
+            .unwrap();
+    } else {
+        let _: Vec<(BenchUser, Vec<BenchUser>)> = ctx
+            .engine
+            .preload_objects::<BenchUser>(Query::default().with_limit(n as u32))
+            .edge::<BenchFollow, BenchUser>()
+            .collect()
+            .await
+            .unwrap();
+    }
+}
+
+async fn raw_traverse(ctx: &Ctx, n: usize) {
+    if n == 1 {
+        let _: Vec<RawUser> = sqlx::query_as(JOIN_ONE_SQL)
+            .bind(ctx.raw_pivot)
+            .fetch_all(&ctx.raw_pool)
+            .await
+            .unwrap();
+    } else {
+        let rows: Vec<RawEdgeUser> = sqlx::query_as(JOIN_MANY_SQL)
+            .bind(n as i64)
+            .fetch_all(&ctx.raw_pool)
+            .await
+            .unwrap();
+        group_by_pivot(rows);
+    }
+}
+
+async fn orm_traverse(ctx: &Ctx, n: usize) {
+    if n == 1 {
+        let _: Vec<orm::users::Model> =
+            orm::users::Model::find_by_statement(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                JOIN_ONE_SQL,
+                [ctx.raw_pivot.into()],
+            ))
+            .all(&ctx.orm_db)
+            .await
+            .unwrap();
+    } else {
+        let rows: Vec<RawEdgeUser> = RawEdgeUser::find_by_statement(
+            Statement::from_sql_and_values(DbBackend::Postgres, JOIN_MANY_SQL, [(n as i64).into()]),
+        )
+        .all(&ctx.orm_db)
+        .await
+        .unwrap();
+        group_by_pivot(rows);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Benchmarks
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn bench_query_edges(c: &mut Criterion) {
+    let (rt, ctx) = state();
+    let mut group = c.benchmark_group("query_edges");
+
+    group.bench_function("ousia", |b| b.iter(|| rt.block_on(ousia_query_edges(ctx))));
+    group.bench_function("raw_sqlx", |b| b.iter(|| rt.block_on(raw_query_edges(ctx))));
+    group.bench_function("sea_orm", |b| b.iter(|| rt.block_on(orm_query_edges(ctx))));
+
+    group.finish();
+}
+
+fn bench_traversal_join(c: &mut Criterion) {
+    let (rt, ctx) = state();
+    let mut group = c.benchmark_group("traversal_join");
+
+    for n in PIVOT_COUNTS {
+        group.bench_with_input(BenchmarkId::new("ousia", n), &n, |b, &n| {
+            b.iter(|| rt.block_on(ousia_traverse(ctx, n)))
+        });
+        group.bench_with_input(BenchmarkId::new("raw_sqlx", n), &n, |b, &n| {
+            b.iter(|| rt.block_on(raw_traverse(ctx, n)))
+        });
+        group.bench_with_input(BenchmarkId::new("sea_orm", n), &n, |b, &n| {
+            b.iter(|| rt.block_on(orm_traverse(ctx, n)))
+        });
+    }
+
+    group.finish();
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Latency summary (criterion has no tail percentiles)
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct Latency {
+    mean: Duration,
+    p95: Duration,
+    p99: Duration,
+}
+
+fn measure<F: Future<Output = ()>>(mut case: impl FnMut() -> F) -> Latency {
+    let (rt, _) = state();
+    let mut samples: Vec<Duration> = (0..LATENCY_SAMPLES)
+        .map(|_| {
+            let start = Instant::now();
+            rt.block_on(case());
+            start.elapsed()
+        })
+        .collect();
+    samples.sort();
+
+    let percentile = |p: usize| samples[(samples.len() * p / 100).min(samples.len() - 1)];
+    Latency {
+        mean: samples.iter().sum::<Duration>() / samples.len() as u32,
+        p95: percentile(95),
+        p99: percentile(99),
+    }
+}
+
+fn print_latency(name: &str, latency: &Latency) {
+    println!(
+        "{name:<28} mean {:>10.3?}  p95 {:>10.3?}  p99 {:>10.3?}",
+        latency.mean, latency.p95, latency.p99
+    );
+}
+
+fn latency_summary(_: &mut Criterion) {
+    let (_, ctx) = state();
+    println!("\nLatency summary ({LATENCY_SAMPLES} runs per case)");
+
+    print_latency("query_edges/ousia", &measure(|| ousia_query_edges(ctx)));
+    print_latency("query_edges/raw_sqlx", &measure(|| raw_query_edges(ctx)));
+    print_latency("query_edges/sea_orm", &measure(|| orm_query_edges(ctx)));
+
+    for n in PIVOT_COUNTS {
+        let ousia = measure(|| ousia_traverse(ctx, n));
+        let raw = measure(|| raw_traverse(ctx, n));
+        let orm = measure(|| orm_traverse(ctx, n));
+        print_latency(&format!("traversal_join/{n}/ousia"), &ousia);
+        print_latency(&format!("traversal_join/{n}/raw_sqlx"), &raw);
+        print_latency(&format!("traversal_join/{n}/sea_orm"), &orm);
+
+        // Target: under 20% over raw_sqlx
+        let overhead = ousia.mean.as_secs_f64() / raw.mean.as_secs_f64() - 1.0;
+        println!(
+            "traversal_join/{n} ousia overhead vs raw_sqlx: {:+.1}%",
+            overhead * 100.0
+        );
+    }
+}
+
+fn run_all(c: &mut Criterion) {
+    bench_query_edges(c);
+    bench_traversal_join(c);
+    latency_summary(c);
+}
+
+criterion_group! {
+    name = ousia_traversal;
+    config = Criterion::default()
+        .sample_size(10)
+        .measurement_time(std::time::Duration::from_secs(5));
+    targets = run_all
+}
+criterion_main!(ousia_traversal);
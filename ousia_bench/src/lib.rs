@@ -40,7 +40,8 @@ pub struct BenchUser {
     index = "title:search+sort",
     index = "status:search",
     index = "view_count:search+sort",
-    index = "tags:search"
+    index = "tags:search",
+    index = "author_id:search"
 )]
 pub struct BenchPost {
     pub _meta: Meta,
@@ -49,6 +50,8 @@ pub struct BenchPost {
     pub status: PostStatus,
     pub view_count: i64,
     pub tags: Vec<String>,
+    /// Same as the owner in the fixtures; exercises `IndexValue::Uuid` filters
+    pub author_id: uuid::Uuid,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -199,7 +202,7 @@ unsafe impl<T: Send> Sync for BenchHandle<T> {}
 // Every raw_sqlx bench must decode into one of these so the comparison is
 // apples-to-apples: ousia returns typed Rust structs, so must raw_sqlx.
 
-#[derive(Debug, sqlx::FromRow)]
+#[derive(Debug, sqlx::FromRow, sea_orm::FromQueryResult)]
 pub struct RawUser {
     pub id: uuid::Uuid,
     pub username: String,
@@ -475,3 +478,17 @@ pub async fn seed_ousia_edges_bulk(pool: &PgPool, user_ids: &[uuid::Uuid], follo
     .await
     .unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ousia::query::IndexKind;
+
+    #[test]
+    fn fields_keep_declared_index_kinds() {
+        let kinds = BenchUser::FIELDS.score.kinds;
+        assert!(kinds.contains(&IndexKind::Search));
+        assert!(kinds.contains(&IndexKind::Sort));
+        assert_eq!(BenchUser::FIELDS.email.kinds, &[IndexKind::Search]);
+    }
+}
@@ -2,12 +2,11 @@ use std::collections::BTreeMap;
 
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{
-    Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Lit, Meta, Type, parse_macro_input,
-};
+use syn::{Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Lit, Meta, parse_macro_input};
 
 use crate::shared::{
-    get_field_default_value, get_ousia_attr, import_ousia, is_meta_field, parse_index_kinds,
+    get_field_default_value, get_ousia_attr, import_ousia, is_meta_field, is_option_type,
+    parse_default_expr, parse_index_kinds, should_use_default, type_name_checks, type_name_error,
 };
 
 const RESERVED_EDGE_FIELDS: &[&str] = &["from", "to", "type"];
@@ -15,9 +14,10 @@ const RESERVED_EDGE_FIELDS: &[&str] = &["from", "to", "type"];
 fn parse_edge_attr(
     attr: Option<&Attribute>,
     struct_name: &syn::Ident,
-) -> (String, Vec<(String, String)>) {
+) -> (String, Vec<(String, String)>, Vec<String>) {
     let mut type_name = None;
     let mut indexes = vec![];
+    let mut unique = vec![];
 
     if let Some(attr) = attr {
         let meta = &attr.meta;
@@ -58,6 +58,25 @@ fn parse_edge_attr(
                             panic!("index must be a string literal");
                         }
                     }
+                    Meta::NameValue(nv) if nv.path.is_ident("unique") => {
+                        if let Expr::Lit(ExprLit {
+                            lit: Lit::Str(s), ..
+                        }) = &nv.value
+                        {
+                            let value = s.value();
+                            if value != "from" && value != "from+to" {
+                                panic!(
+                                    "Edge unique must be \"from\" or \"from+to\", got: {}",
+                                    value
+                                );
+                            }
+                            if !unique.contains(&value) {
+                                unique.push(value);
+                            }
+                        } else {
+                            panic!("unique must be a string literal");
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -66,7 +85,7 @@ fn parse_edge_attr(
 
     let type_name = type_name.unwrap_or_else(|| struct_name.to_string());
 
-    (type_name, indexes)
+    (type_name, indexes, unique)
 }
 
 pub fn derive(input: TokenStream) -> TokenStream {
@@ -76,7 +95,15 @@ pub fn derive(input: TokenStream) -> TokenStream {
 
     // --- get ousia attribute ---
     let attr = get_ousia_attr(&input.attrs);
-    let (type_name, indexes) = parse_edge_attr(attr, ident);
+    let (type_name, indexes, unique) = parse_edge_attr(attr, ident);
+    if let Some(message) = type_name_error(&type_name) {
+        return match attr {
+            Some(attr) => syn::Error::new_spanned(attr, message),
+            None => syn::Error::new_spanned(ident, message),
+        }
+        .to_compile_error()
+        .into();
+    }
 
     // --- extract fields and identify meta field ---
     let fields = match &input.data {
@@ -162,6 +189,22 @@ pub fn derive(input: TokenStream) -> TokenStream {
         }
     });
 
+    // --- generate unique hashes (keyed on meta only) ---
+    let has_unique = !unique.is_empty();
+    let unique_hash_generations = unique.iter().map(|constraint| {
+        let value = if constraint == "from" {
+            quote! { ::std::format!("{}", from) }
+        } else {
+            quote! { ::std::format!("{}:{}", from, to) }
+        };
+        quote! {
+            hashes.push((
+                #ousia::derive_unique_hash(#type_name, #constraint, &#value),
+                #constraint,
+            ));
+        }
+    });
+
     // --- generate Indexes struct ---
     let indexes_struct_name = format_ident!("{}Indexes", ident);
 
@@ -302,49 +345,6 @@ pub fn derive(input: TokenStream) -> TokenStream {
         }
     } else {
         // Normal case: struct has data fields
-        fn is_option_type(ty: &Type) -> bool {
-            if let Type::Path(type_path) = ty {
-                if let Some(segment) = type_path.path.segments.last() {
-                    return segment.ident == "Option";
-                }
-            }
-            false
-        }
-
-        fn should_use_default(ty: &Type) -> bool {
-            // Check if type is Option (always uses default)
-            if is_option_type(ty) {
-                return true;
-            }
-
-            // Check for common types that implement Default
-            if let Type::Path(type_path) = ty {
-                if let Some(segment) = type_path.path.segments.last() {
-                    let type_name = segment.ident.to_string();
-
-                    // Common stdlib types that implement Default
-                    match type_name.as_str() {
-                           // Collections
-                           "Vec" | "HashMap" | "HashSet" | "BTreeMap" | "BTreeSet" |
-                           "VecDeque" | "LinkedList" | "BinaryHeap" |
-                           // Strings
-                           "String" |
-                           // Numbers (all primitive number types implement Default)
-                           "i8" | "i16" | "i32" | "i64" | "i128" | "isize" |
-                           "u8" | "u16" | "u32" | "u64" | "u128" | "usize" |
-                           "f32" | "f64" |
-                           // Other common types
-                           "bool" | "PathBuf" | "Duration" => return true,
-                           _ => {}
-                       }
-                }
-            }
-
-            // For other types (including custom enums), assume they implement Default
-            // The compiler will verify at compile time
-            true
-        }
-
         // Check which fields are Option types (special handling)
         let field_is_optional: Vec<bool> = non_meta_fields
             .iter()
@@ -414,9 +414,7 @@ pub fn derive(input: TokenStream) -> TokenStream {
                     }
                 } else if let Some(default_expr) = default_value {
                     // For fields with explicit default value: parse and use the expression
-                    let default_tokens: proc_macro2::TokenStream = default_expr
-                        .parse()
-                        .expect("Failed to parse default value expression");
+                    let default_tokens = parse_default_expr(default_expr);
                     quote! {
                         #ident: #ident.unwrap_or_else(|| #default_tokens)
                     }
@@ -482,10 +480,15 @@ pub fn derive(input: TokenStream) -> TokenStream {
         }
     };
 
+    let type_checks = type_name_checks(&ousia, ident, &type_name);
+
     // --- generate impl ---
     let expanded = quote! {
+        #type_checks
+
         impl #ousia::edge::Edge for #ident {
             const TYPE: &'static str = #type_name;
+            const HAS_UNIQUE_FIELDS: bool = #has_unique;
 
             fn meta(&self) -> &#ousia::edge::EdgeMeta {
                 &self.#meta_field_ident
@@ -503,6 +506,13 @@ pub fn derive(input: TokenStream) -> TokenStream {
                 #(#index_meta_insertions)*
                 #ousia::query::IndexMeta(values)
             }
+
+            #[allow(unused_variables, unused_mut)]
+            fn unique_hashes(from: uuid::Uuid, to: uuid::Uuid) -> ::std::vec::Vec<(::std::string::String, &'static str)> {
+                let mut hashes = ::std::vec::Vec::new();
+                #(#unique_hash_generations)*
+                hashes
+            }
         }
 
         impl #ousia::query::IndexQuery for #ident {
@@ -6,19 +6,24 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{Data, DeriveInput, Fields, parse_macro_input};
 
-use crate::shared::{import_ousia, is_meta_field};
+use crate::shared::{field_default_tokens, import_ousia, is_meta_field};
 
 #[proc_macro_derive(OusiaObject, attributes(ousia, ousia_meta))]
 pub fn derive_ousia_object(input: TokenStream) -> TokenStream {
     object::derive(input)
 }
 
+#[proc_macro_derive(OusiaPartial, attributes(ousia))]
+pub fn derive_ousia_partial(input: TokenStream) -> TokenStream {
+    object::partial::derive(input)
+}
+
 #[proc_macro_derive(OusiaEdge, attributes(ousia))]
 pub fn derive_ousia_edge(input: TokenStream) -> TokenStream {
     edge::derive(input)
 }
 
-#[proc_macro_derive(OusiaDefault)]
+#[proc_macro_derive(OusiaDefault, attributes(ousia))]
 pub fn derive_ousia_default(input: TokenStream) -> TokenStream {
     let ousia = import_ousia();
     let input = parse_macro_input!(input as DeriveInput);
@@ -68,7 +73,8 @@ pub fn derive_ousia_default(input: TokenStream) -> TokenStream {
                 quote! { #name: #ousia::object::meta::Meta::default() }
             }
         } else {
-            quote! { #name: Default::default() }
+            let default_tokens = field_default_tokens(f);
+            quote! { #name: #default_tokens }
         }
     });
 
@@ -2,14 +2,25 @@ use std::collections::{BTreeMap, HashSet};
 
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::{Data, DeriveInput, Expr, ExprLit, Field, Fields, Lit, Meta, Result, Type};
+use syn::{
+    Data, DeriveInput, Expr, ExprLit, Field, Fields, GenericParam, Generics, Lit, Meta, Result,
+    Type,
+};
 
 use crate::shared::{
-    get_field_default_value, get_ousia_attr, import_ousia, is_meta_field, is_private_field,
-    parse_index_kinds, parse_ousia_attr,
+    bound_type_params, field_default_tokens, get_field_default_value, get_ousia_attr,
+    has_ousia_flag, import_ousia, is_flatten_field, is_meta_field, is_option_type,
+    is_private_field, parse_default_expr, parse_index_kinds, parse_ousia_attr, should_use_default,
+    static_lifetimes, type_mentions,
 };
 
-const RESERVED_FIELDS: &[&str] = &["id", "owner", "type", "created_at", "updated_at"];
+const RESERVED_FIELDS: &[&str] = &[
+    "id",
+    "owner",
+    "type",
+    "created_at",
+    "updated_at",
+];
 
 /// Check if meta field has #[ousia_meta(private)] attribute
 fn is_meta_private(field: &Field) -> bool {
@@ -136,12 +147,43 @@ fn is_valid_rust_identifier(name: &str) -> bool {
     chars.all(|c| c.is_alphanumeric() || c == '_')
 }
 
+/// Meta fields that may appear in `index = "..."`. The engine maintains them,
+/// so `FIELDS` always exposes them and nothing is extracted.
+fn is_meta_index(name: &str) -> bool {
+    matches!(name, "created_at" | "updated_at" | "sequence")
+}
+
 /// Default meta fields when no explicit view(default="...") is specified and not private
 const DEFAULT_META_FIELDS: &[&str] = &["id", "created_at", "updated_at"];
 
+/// Keep only the generic parameters that `types` actually use.
+/// View structs carry a subset of the fields, and unused parameters don't compile.
+fn generics_used_by(generics: &Generics, types: &[&Type]) -> Generics {
+    let params = generics
+        .params
+        .iter()
+        .filter(|param| {
+            let ident = match param {
+                GenericParam::Type(t) => &t.ident,
+                GenericParam::Lifetime(l) => &l.lifetime.ident,
+                GenericParam::Const(c) => &c.ident,
+            };
+            types.iter().any(|ty| type_mentions(ty, ident))
+        })
+        .cloned()
+        .collect();
+    Generics {
+        lt_token: generics.lt_token,
+        params,
+        gt_token: generics.gt_token,
+        where_clause: None,
+    }
+}
+
 /// Generate view struct and conversion method
 fn generate_view_code(
     struct_name: &syn::Ident,
+    generics: &Generics,
     view_name: &str,
     meta_fields: &[String],
     data_fields: &[(syn::Ident, Type)],
@@ -169,6 +211,10 @@ fn generate_view_code(
     let view_struct_name = format_ident!("{}{}View", struct_name, view_pascal);
     let method_name = format_ident!("_{}", view_name);
 
+    let field_types: Vec<_> = data_fields.iter().map(|(_, ty)| ty).collect();
+    let view_generics = generics_used_by(generics, &field_types);
+    let (_, view_ty_generics, _) = view_generics.split_for_impl();
+
     // Generate struct fields
     let mut struct_fields = Vec::new();
     let mut field_assignments = Vec::new();
@@ -192,8 +238,12 @@ fn generate_view_code(
                 struct_fields.push(quote! { pub updated_at: chrono::DateTime<chrono::Utc> });
                 field_assignments.push(quote! { updated_at: self._meta.updated_at });
             }
+            "sequence" => {
+                struct_fields.push(quote! { pub sequence: u64 });
+                field_assignments.push(quote! { sequence: self._meta.sequence });
+            }
             _ => panic!(
-                "Invalid meta field: {}. Valid fields are: id, owner, created_at, updated_at",
+                "Invalid meta field: {}. Valid fields are: id, owner, created_at, updated_at, sequence",
                 meta_field
             ),
         }
@@ -207,13 +257,13 @@ fn generate_view_code(
 
     let view_struct = quote! {
         #[derive(serde::Serialize, Clone, Debug)]
-        pub struct #view_struct_name {
+        pub struct #view_struct_name #view_generics {
             #(#struct_fields),*
         }
     };
 
     let view_method = quote! {
-        pub fn #method_name(&self) -> #view_struct_name {
+        pub fn #method_name(&self) -> #view_struct_name #view_ty_generics {
             #view_struct_name {
                 #(#field_assignments),*
             }
@@ -223,29 +273,65 @@ fn generate_view_code(
     (view_struct, view_method)
 }
 
-/// Generate the internal serialization implementation
+/// Generate the internal serialization implementation.
+/// Private fields are never persisted, so they are left out here too.
+/// Flattened fields are merged into the top-level object.
 fn generate_internal_serialize(non_meta_fields: &[&Field]) -> proc_macro2::TokenStream {
-    let field_serializations = non_meta_fields.iter().map(|f| {
+    let persisted: Vec<_> = non_meta_fields
+        .iter()
+        .filter(|f| !is_private_field(f))
+        .collect();
+
+    let field_serializations = persisted.iter().filter(|f| !is_flatten_field(f)).map(|f| {
         let field_name = f.ident.as_ref().unwrap();
         let field_name_str = field_name.to_string();
         quote! { #field_name_str: self.#field_name }
     });
 
+    let flatten_extends: Vec<_> = persisted
+        .iter()
+        .filter(|f| is_flatten_field(f))
+        .map(|f| {
+            let field_name = f.ident.as_ref().unwrap();
+            quote! {
+                if let Ok(serde_json::Value::Object(inner)) = serde_json::to_value(&self.#field_name) {
+                    fields.extend(inner);
+                }
+            }
+        })
+        .collect();
+
+    if flatten_extends.is_empty() {
+        return quote! {
+            serde_json::json!({
+                #(#field_serializations),*
+            })
+        };
+    }
+
     quote! {
-        serde_json::json!({
+        let mut value = serde_json::json!({
             #(#field_serializations),*
-        })
+        });
+        if let Some(fields) = value.as_object_mut() {
+            #(#flatten_extends)*
+        }
+        value
     }
 }
 
 pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
     let ousia = import_ousia();
     let ident = &input.ident;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let type_params: Vec<_> = generics.type_params().map(|p| &p.ident).collect();
 
     // --- get ousia attribute ---
     let attr = get_ousia_attr(&input.attrs);
     let (type_name, indexes) = parse_ousia_attr(attr);
     let type_name = type_name.unwrap_or_else(|| ident.to_string());
+    let versioned = has_ousia_flag(attr, "versioned");
 
     // --- extract fields and identify meta field ---
     let fields = match &input.data {
@@ -316,6 +402,9 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
     for field in &non_meta_fields {
         let field_name = field.ident.as_ref().unwrap().to_string();
         let views = extract_field_views(field);
+        if is_flatten_field(field) && !views.is_empty() {
+            panic!("Flattened field `{}` cannot be part of a view", field_name);
+        }
 
         for view in views {
             if !is_valid_rust_identifier(&view) {
@@ -337,6 +426,7 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
     // --- Generate view structs and methods ---
     let mut view_structs = Vec::new();
     let mut view_methods = Vec::new();
+    let mut view_field_types = Vec::new();
 
     for view_name in &all_view_names {
         // Get meta fields for this view
@@ -356,26 +446,54 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
             .map(|f| (f.ident.as_ref().unwrap().clone(), f.ty.clone()))
             .collect();
 
+        view_field_types.extend(data_fields.iter().map(|(_, ty)| ty.clone()));
         let (view_struct, view_method) =
-            generate_view_code(ident, view_name, &meta_fields, &data_fields);
+            generate_view_code(ident, generics, view_name, &meta_fields, &data_fields);
         view_structs.push(view_struct);
         view_methods.push(view_method);
     }
 
     // --- generate IndexField list ---
     let index_fields = indexes.iter().map(|(name, kind)| {
-        if RESERVED_FIELDS.contains(&name.as_str()) {
+        if RESERVED_FIELDS.contains(&name.as_str()) && !is_meta_index(name) {
             panic!(
                 "Index field `{}` is reserved for meta and cannot be indexed",
                 name
             );
         }
-        if !non_meta_fields
-            .iter()
-            .any(|f| &f.ident.as_ref().unwrap().to_string() == name)
+        if !is_meta_index(name)
+            && !non_meta_fields
+                .iter()
+                .any(|f| &f.ident.as_ref().unwrap().to_string() == name)
         {
             panic!("Indexed field `{}` does not exist on {}", name, ident);
         }
+        // `sequence` is a legal field name, but its index is always the meta one
+        if is_meta_index(name)
+            && non_meta_fields
+                .iter()
+                .any(|f| &f.ident.as_ref().unwrap().to_string() == name)
+        {
+            panic!(
+                "Index `{}` refers to meta; the field of the same name cannot be indexed",
+                name
+            );
+        }
+        if non_meta_fields
+            .iter()
+            .any(|f| &f.ident.as_ref().unwrap().to_string() == name && is_private_field(f))
+        {
+            panic!("Private field `{}` cannot be indexed", name);
+        }
+        if non_meta_fields
+            .iter()
+            .any(|f| &f.ident.as_ref().unwrap().to_string() == name && is_flatten_field(f))
+        {
+            panic!(
+                "Flattened field `{}` cannot be indexed, nor can the fields inside it",
+                name
+            );
+        }
 
         let kinds = parse_index_kinds(kind);
 
@@ -389,6 +507,10 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
 
     // --- generate index_meta insertions ---
     let index_meta_insertions = indexes.iter().map(|(name, _kind)| {
+        // Meta indexes are inserted from meta below (sequence by the engine)
+        if is_meta_index(name) {
+            return quote! {};
+        }
         let field_name = format_ident!("{}", name);
         let name_str = name.as_str();
 
@@ -400,12 +522,26 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
         }
     });
 
+    // Generic indexed fields need their concrete type to be indexable
+    let index_bounds: Vec<_> = non_meta_fields
+        .iter()
+        .filter(|f| {
+            let name = f.ident.as_ref().unwrap().to_string();
+            indexes.iter().any(|(n, _)| *n == name)
+                && type_params.iter().any(|p| type_mentions(&f.ty, p))
+        })
+        .map(|f| {
+            let ty = &f.ty;
+            quote! { #ty: #ousia::query::ToIndexValue }
+        })
+        .collect();
+
     // --- generate Indexes struct ---
     let indexes_struct_name = format_ident!("{}Fields", ident);
 
     // Build a map of field names to their kinds (merge multiple declarations)
     let mut field_kinds_map: BTreeMap<String, Vec<String>> = BTreeMap::new();
-    for (name, kind) in &indexes {
+    for (name, kind) in indexes.iter().filter(|(name, _)| !is_meta_index(name)) {
         field_kinds_map
             .entry(name.clone())
             .or_insert_with(Vec::new)
@@ -460,10 +596,20 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
             .unwrap_or_else(|| DEFAULT_META_FIELDS.iter().map(|s| s.to_string()).collect())
     };
 
+    // Flattened fields have no fixed key count, so those structs serialize as a map
+    let has_flatten = non_meta_fields
+        .iter()
+        .any(|f| is_flatten_field(f) && !is_private_field(f));
+    let serialize_method = if has_flatten {
+        quote!(serialize_entry)
+    } else {
+        quote!(serialize_field)
+    };
+
     let serialize_meta_fields = default_meta_fields.iter().map(|field_name| {
         let meta_field = format_ident!("{}", field_name);
         quote! {
-            state.serialize_field(#field_name, &self._meta.#meta_field)?;
+            state.#serialize_method(#field_name, &self._meta.#meta_field)?;
         }
     });
 
@@ -476,8 +622,25 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
             return None;
         }
 
+        if is_flatten_field(f) {
+            let error = format!(
+                "flattened field `{}` must serialize to a map",
+                field_name_str
+            );
+            return Some(quote! {
+                match serde_json::to_value(&self.#field_name).map_err(serde::ser::Error::custom)? {
+                    serde_json::Value::Object(inner) => {
+                        for (key, value) in &inner {
+                            state.serialize_entry(key, value)?;
+                        }
+                    }
+                    _ => return Err(serde::ser::Error::custom(#error)),
+                }
+            });
+        }
+
         Some(quote! {
-            state.serialize_field(#field_name_str, &self.#field_name)?;
+            state.#serialize_method(#field_name_str, &self.#field_name)?;
         })
     });
 
@@ -486,17 +649,53 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
         .filter(|f| !is_private_field(f))
         .count();
     let field_count = non_private_count + default_meta_fields.len();
+    let serialize_start = if has_flatten {
+        quote! {
+            use serde::ser::SerializeMap;
+            let mut state = serializer.serialize_map(None)?;
+        }
+    } else {
+        quote! {
+            use serde::ser::SerializeStruct;
+            let mut state = serializer.serialize_struct(stringify!(#ident), #field_count)?;
+        }
+    };
 
     // --- generate internal serialization ---
     let internal_serialize_body = generate_internal_serialize(&non_meta_fields);
 
     // --- generate Deserialize implementation ---
-    let deserialize_field_names: Vec<_> = non_meta_fields
+    // Private fields are not in the stored data; they come back as their
+    // `#[ousia(default = "...")]` value or `Default::default()`.
+    // Flattened fields are read from the keys no other field claims.
+    let persisted_fields: Vec<_> = non_meta_fields
+        .iter()
+        .filter(|f| !is_private_field(f) && !is_flatten_field(f))
+        .copied()
+        .collect();
+
+    let flatten_field_idents: Vec<_> = non_meta_fields
+        .iter()
+        .filter(|f| !is_private_field(f) && is_flatten_field(f))
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect();
+
+    let private_field_inits: Vec<_> = non_meta_fields
+        .iter()
+        .filter(|f| is_private_field(f))
+        .map(|f| {
+            let field_ident = f.ident.as_ref().unwrap();
+            let default_tokens = field_default_tokens(f);
+            quote! { #field_ident: #default_tokens }
+        })
+        .collect();
+
+    let deserialize_field_names: Vec<_> = persisted_fields
         .iter()
         .map(|f| f.ident.as_ref().unwrap().to_string())
         .collect();
 
-    let deserialize_field_idents: Vec<_> = non_meta_fields
+    let deserialize_field_idents: Vec<_> = persisted_fields
         .iter()
         .map(|f| f.ident.as_ref().unwrap())
         .collect();
@@ -520,130 +719,124 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
         })
         .collect();
 
-    let deserialize_field_types: Vec<_> = non_meta_fields.iter().map(|f| &f.ty).collect();
+    let deserialize_field_types: Vec<_> = persisted_fields.iter().map(|f| &f.ty).collect();
 
     let visitor_name = format_ident!("{}Visitor", ident);
 
+    // The visitor is a nested item, so it redeclares the struct's generics and
+    // holds them in a PhantomData. Type parameters only need to be deserializable.
+    let de_generics = static_lifetimes(&bound_type_params(
+        generics,
+        quote!(serde::de::DeserializeOwned),
+        |_| true,
+    ));
+    let (visitor_generics, _, de_where_clause) = de_generics.split_for_impl();
+    let mut de_generics_with_de = de_generics.clone();
+    de_generics_with_de.params.insert(0, syn::parse_quote!('de));
+    let (de_impl_generics, _, _) = de_generics_with_de.split_for_impl();
+
     // Handle the case where there are no data fields (only meta)
-    let deserialize_impl = if non_meta_fields.is_empty() {
+    let deserialize_impl = if persisted_fields.is_empty() && flatten_field_idents.is_empty() {
         // Simple case: no data fields, just create with default meta
         quote! {
-            impl<'de> serde::Deserialize<'de> for #ident {
-                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            impl #de_impl_generics serde::Deserialize<'de> for #ident #ty_generics #de_where_clause {
+                fn deserialize<__D>(deserializer: __D) -> Result<Self, __D::Error>
                 where
-                    D: serde::Deserializer<'de>,
+                    __D: serde::Deserializer<'de>,
                 {
                     // For empty structs, we just need to consume the empty map
-                    struct #visitor_name;
+                    struct #visitor_name #visitor_generics (std::marker::PhantomData<fn() -> #ident #ty_generics>) #de_where_clause;
 
-                    impl<'de> serde::de::Visitor<'de> for #visitor_name {
-                        type Value = #ident;
+                    impl #de_impl_generics serde::de::Visitor<'de> for #visitor_name #ty_generics #de_where_clause {
+                        type Value = #ident #ty_generics;
 
                         fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
                             formatter.write_str(concat!("struct ", stringify!(#ident)))
                         }
 
-                        fn visit_map<V>(self, mut map: V) -> Result<#ident, V::Error>
+                        fn visit_map<__V>(self, mut map: __V) -> Result<Self::Value, __V::Error>
                         where
-                            V: serde::de::MapAccess<'de>,
+                            __V: serde::de::MapAccess<'de>,
                         {
                             // Consume any fields in the map (ignore them)
                             while map.next_entry::<String, serde_json::Value>()?.is_some() {}
 
                             Ok(#ident {
                                 #meta_field_ident: #ousia::object::meta::Meta::default(),
+                                #(#private_field_inits,)*
                             })
                         }
 
-                        fn visit_unit<E>(self) -> Result<#ident, E>
+                        fn visit_unit<__E>(self) -> Result<Self::Value, __E>
                         where
-                            E: serde::de::Error,
+                            __E: serde::de::Error,
                         {
                             Ok(#ident {
                                 #meta_field_ident: #ousia::object::meta::Meta::default(),
+                                #(#private_field_inits,)*
                             })
                         }
                     }
 
-                    deserializer.deserialize_struct(stringify!(#ident), &[], #visitor_name)
+                    deserializer.deserialize_struct(stringify!(#ident), &[], #visitor_name(std::marker::PhantomData))
                 }
             }
         }
     } else {
         // Normal case: struct has data fields
-        fn is_option_type(ty: &Type) -> bool {
-            if let Type::Path(type_path) = ty {
-                if let Some(segment) = type_path.path.segments.last() {
-                    return segment.ident == "Option";
-                }
-            }
-            false
-        }
-
-        fn should_use_default(ty: &Type) -> bool {
-            // Check if type is Option (always uses default)
-            if is_option_type(ty) {
-                return true;
-            }
-
-            // Check for common types that implement Default
-            if let Type::Path(type_path) = ty {
-                if let Some(segment) = type_path.path.segments.last() {
-                    let type_name = segment.ident.to_string();
-
-                    // Common stdlib types that implement Default
-                    match type_name.as_str() {
-                                // Collections
-                                "Vec" | "HashMap" | "HashSet" | "BTreeMap" | "BTreeSet" |
-                                "VecDeque" | "LinkedList" | "BinaryHeap" |
-                                // Strings
-                                "String" |
-                                // Numbers (all primitive number types implement Default)
-                                "i8" | "i16" | "i32" | "i64" | "i128" | "isize" |
-                                "u8" | "u16" | "u32" | "u64" | "u128" | "usize" |
-                                "f32" | "f64" |
-                                // Other common types
-                                "bool" | "PathBuf" | "Duration" => return true,
-                                _ => {}
-                            }
-                }
-            }
-
-            // For other types (including custom enums), assume they implement Default
-            // The compiler will verify at compile time
-            true
-        }
-
         // Check which fields are Option types (special handling)
-        let field_is_optional: Vec<bool> = non_meta_fields
+        let field_is_optional: Vec<bool> = persisted_fields
             .iter()
             .map(|f| is_option_type(&f.ty))
             .collect();
 
+        // A bare type parameter isn't known to implement Default, so it's required
+        let is_type_param = |ty: &Type| match ty {
+            Type::Path(type_path) if type_path.qself.is_none() => type_path
+                .path
+                .get_ident()
+                .is_some_and(|i| type_params.contains(&i)),
+            _ => false,
+        };
+
         // Check which fields should use Default::default()
-        let field_uses_default: Vec<bool> = non_meta_fields
+        let field_uses_default: Vec<bool> = persisted_fields
             .iter()
-            .map(|f| should_use_default(&f.ty))
+            .map(|f| should_use_default(&f.ty) && !is_type_param(&f.ty))
             .collect();
 
         // Extract explicit default values from #[ousia(default = "value")]
-        let field_default_values: Vec<Option<String>> = non_meta_fields
+        let field_default_values: Vec<Option<String>> = persisted_fields
             .iter()
             .map(|f| get_field_default_value(f))
             .collect();
 
+        // With flattened fields, keys are matched as strings so unclaimed ones can be kept
+        let has_flatten = !flatten_field_idents.is_empty();
+        let field_patterns: Vec<_> = deserialize_field_variants
+            .iter()
+            .zip(deserialize_field_names.iter())
+            .map(|(variant, name)| {
+                if has_flatten {
+                    quote!(#name)
+                } else {
+                    quote!(Field::#variant)
+                }
+            })
+            .collect();
+
         // Generate match arms - handle Option<T> fields differently
-        let match_arms = deserialize_field_variants
+        let match_arms = field_patterns
             .iter()
             .zip(deserialize_field_idents.iter())
             .zip(deserialize_field_names.iter())
             .zip(field_is_optional.iter())
-            .map(|(((variant, ident), name), is_opt)| {
+            .map(|(((pattern, ident), name), is_opt)| {
                 if *is_opt {
                     // For Option<T>: don't wrap in Some, just assign directly
                     // map.next_value()? returns Option<T>, store as Some(Option<T>)
                     quote! {
-                        Field::#variant => {
+                        #pattern => {
                             if #ident.is_some() {
                                 return Err(serde::de::Error::duplicate_field(#name));
                             }
@@ -653,7 +846,7 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
                 } else {
                     // For T: wrap in Some as before
                     quote! {
-                        Field::#variant => {
+                        #pattern => {
                             if #ident.is_some() {
                                 return Err(serde::de::Error::duplicate_field(#name));
                             }
@@ -683,9 +876,7 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
                     }
                 } else if let Some(default_expr) = default_value {
                     // For fields with explicit default value: parse and use the expression
-                    let default_tokens: proc_macro2::TokenStream = default_expr
-                        .parse()
-                        .expect("Failed to parse default value expression");
+                    let default_tokens = parse_default_expr(default_expr);
                     quote! {
                         #ident: #ident.unwrap_or_else(|| #default_tokens)
                     }
@@ -702,12 +893,31 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
                 }
             });
 
-        quote! {
-            impl<'de> serde::Deserialize<'de> for #ident {
-                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-                where
-                    D: serde::Deserializer<'de>,
-                {
+        let flatten_inits = flatten_field_idents.iter().map(|ident| {
+            quote! {
+                #ident: serde_json::from_value(serde_json::Value::Object(__rest.clone()))
+                    .map_err(serde::de::Error::custom)?
+            }
+        });
+
+        let (field_enum, key_loop) = if has_flatten {
+            (
+                quote! {},
+                quote! {
+                    let mut __rest = serde_json::Map::new();
+                    while let Some(key) = map.next_key::<String>()? {
+                        match key.as_str() {
+                            #(#match_arms)*
+                            _ => {
+                                __rest.insert(key, map.next_value()?);
+                            }
+                        }
+                    }
+                },
+            )
+        } else {
+            (
+                quote! {
                     #[derive(serde::Deserialize)]
                     #[serde(field_identifier, rename_all = "snake_case")]
                     enum Field {
@@ -715,51 +925,92 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
                         #[serde(other)]
                          Unknown,
                     }
+                },
+                quote! {
+                    while let Some(key) = map.next_key()? {
+                        match key {
+                            #(#match_arms)*
+                            Field::Unknown => {
+                                let _: serde::de::IgnoredAny = map.next_value()?;
+                            }
+                        }
+                    }
+                },
+            )
+        };
+
+        quote! {
+            impl #de_impl_generics serde::Deserialize<'de> for #ident #ty_generics #de_where_clause {
+                fn deserialize<__D>(deserializer: __D) -> Result<Self, __D::Error>
+                where
+                    __D: serde::Deserializer<'de>,
+                {
+                    #field_enum
 
-                    struct #visitor_name;
+                    struct #visitor_name #visitor_generics (std::marker::PhantomData<fn() -> #ident #ty_generics>) #de_where_clause;
 
-                    impl<'de> serde::de::Visitor<'de> for #visitor_name {
-                        type Value = #ident;
+                    impl #de_impl_generics serde::de::Visitor<'de> for #visitor_name #ty_generics #de_where_clause {
+                        type Value = #ident #ty_generics;
 
                         fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
                             formatter.write_str(concat!("struct ", stringify!(#ident)))
                         }
 
-                        fn visit_map<V>(self, mut map: V) -> Result<#ident, V::Error>
+                        fn visit_map<__V>(self, mut map: __V) -> Result<Self::Value, __V::Error>
                         where
-                            V: serde::de::MapAccess<'de>,
+                            __V: serde::de::MapAccess<'de>,
                         {
                             #(
                                 let mut #deserialize_field_idents: Option<#deserialize_field_types> = None;
                             )*
 
-                            while let Some(key) = map.next_key()? {
-                                match key {
-                                    #(#match_arms)*
-                                    Field::Unknown => {
-                                        let _: serde::de::IgnoredAny = map.next_value()?;
-                                    }
-                                }
-                            }
+                            #key_loop
 
                             Ok(#ident {
                                 #meta_field_ident: #ousia::object::meta::Meta::default(),
                                 #(#field_inits,)*
+                                #(#flatten_inits,)*
+                                #(#private_field_inits,)*
                             })
                         }
                     }
 
                     const FIELDS: &[&str] = &[#(#deserialize_field_names),*];
-                    deserializer.deserialize_struct(stringify!(#ident), FIELDS, #visitor_name)
+                    deserializer.deserialize_struct(stringify!(#ident), FIELDS, #visitor_name(std::marker::PhantomData))
                 }
             }
         }
     };
 
+    // --- generics for each impl ---
+    let object_generics = {
+        let mut g = static_lifetimes(&bound_type_params(
+            generics,
+            quote!(serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static),
+            |_| true,
+        ));
+        let where_clause = g.make_where_clause();
+        for bound in &index_bounds {
+            where_clause.predicates.push(syn::parse_quote!(#bound));
+        }
+        g
+    };
+    let (object_impl_generics, _, object_where_clause) = object_generics.split_for_impl();
+
+    let ser_generics = bound_type_params(generics, quote!(serde::Serialize), |_| true);
+    let (ser_impl_generics, _, ser_where_clause) = ser_generics.split_for_impl();
+
+    // View methods clone their fields
+    let view_generics = bound_type_params(generics, quote!(Clone), |p| {
+        view_field_types.iter().any(|ty| type_mentions(ty, p))
+    });
+    let (view_impl_generics, _, view_where_clause) = view_generics.split_for_impl();
+
     // --- generate impl ---
     let expanded = quote! {
-        impl #ousia::object::traits::Object for #ident {
+        impl #object_impl_generics #ousia::object::traits::Object for #ident #ty_generics #object_where_clause {
             const TYPE: &'static str = #type_name;
+            const VERSIONED: bool = #versioned;
 
             fn meta(&self) -> &#ousia::object::meta::Meta {
                 &self.#meta_field_ident
@@ -779,17 +1030,17 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
             }
         }
 
-        impl #ousia::object::ObjectInternal for #ident {
+        impl #ser_impl_generics #ousia::object::ObjectInternal for #ident #ty_generics #ser_where_clause {
             fn __serialize_internal(&self) -> serde_json::Value {
                 #internal_serialize_body
             }
         }
 
-        impl #ident {
+        impl #view_impl_generics #ident #ty_generics #view_where_clause {
             #(#view_methods)*
         }
 
-        impl #ousia::query::IndexQuery for #ident {
+        impl #impl_generics #ousia::query::IndexQuery for #ident #ty_generics #where_clause {
             fn indexed_fields() -> &'static [#ousia::query::IndexField] {
                 &[ #(#index_fields),* ]
             }
@@ -799,10 +1050,11 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
         pub struct #indexes_struct_name {
             pub created_at: #ousia::query::IndexField,
             pub updated_at: #ousia::query::IndexField,
+            pub sequence: #ousia::query::IndexField,
             #(#indexes_struct_fields),*
         }
 
-        impl #ident {
+        impl #impl_generics #ident #ty_generics #where_clause {
             pub const FIELDS: #indexes_struct_name = #indexes_struct_name {
                 created_at: #ousia::query::IndexField {
                     name: "created_at",
@@ -812,6 +1064,10 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
                     name: "updated_at",
                     kinds: &[#ousia::query::IndexKind::Search, #ousia::query::IndexKind::Sort],
                 },
+                sequence: #ousia::query::IndexField {
+                    name: "sequence",
+                    kinds: &[#ousia::query::IndexKind::Search, #ousia::query::IndexKind::Sort],
+                },
                 #(#indexes_const_fields),*
             };
         }
@@ -819,13 +1075,12 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
         #(#view_structs)*
 
         // Custom Serialize implementation (default view)
-        impl serde::Serialize for #ident {
-            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        impl #ser_impl_generics serde::Serialize for #ident #ty_generics #ser_where_clause {
+            fn serialize<__S>(&self, serializer: __S) -> Result<__S::Ok, __S::Error>
             where
-                S: serde::Serializer,
+                __S: serde::Serializer,
             {
-                use serde::ser::SerializeStruct;
-                let mut state = serializer.serialize_struct(stringify!(#ident), #field_count)?;
+                #serialize_start
                 #(#serialize_meta_fields)*
                 #(#serialize_fields)*
                 state.end()
@@ -833,6 +1088,8 @@ pub fn generate_object_impl(input: &DeriveInput) -> Result<TokenStream> {
         }
 
         #deserialize_impl
+
+        #ousia::__register_type!(#type_name);
     };
 
     Ok(TokenStream::from(expanded))
@@ -1,4 +1,5 @@
 pub mod generate;
+pub mod partial;
 pub mod unique;
 
 use proc_macro::TokenStream;
@@ -0,0 +1,89 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, parse_macro_input};
+
+use crate::shared::{import_ousia, is_meta_field, is_private_field};
+
+/// Generate `{Name}Patch` with every persisted, non-meta field wrapped in `Option`
+pub fn derive(input: TokenStream) -> TokenStream {
+    let ousia = import_ousia();
+    let input = parse_macro_input!(input as DeriveInput);
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let patch_ident = format_ident!("{}Patch", ident);
+
+    let fields = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(f) => &f.named,
+            _ => panic!("OusiaPartial only supports named structs"),
+        },
+        _ => panic!("OusiaPartial only supports structs"),
+    };
+
+    // Meta is managed by the engine and private fields are never persisted
+    let patch_fields: Vec<_> = fields
+        .iter()
+        .filter(|f| {
+            !is_meta_field(f) && f.ident.as_ref().unwrap() != "_meta" && !is_private_field(f)
+        })
+        .collect();
+
+    let field_decls = patch_fields.iter().map(|f| {
+        let name = f.ident.as_ref().unwrap();
+        let ty = &f.ty;
+        let field_vis = &f.vis;
+        quote! { #field_vis #name: Option<#ty> }
+    });
+
+    let changed = patch_fields.iter().map(|f| {
+        let name = f.ident.as_ref().unwrap();
+        let name_str = name.to_string();
+        quote! {
+            if self.#name.is_some() {
+                fields.push(#name_str);
+            }
+        }
+    });
+
+    let applies = patch_fields.iter().map(|f| {
+        let name = f.ident.as_ref().unwrap();
+        quote! {
+            if let Some(value) = self.#name {
+                target.#name = value;
+            }
+        }
+    });
+
+    let doc = format!(
+        "Partial update for [`{}`]. `None` fields are left untouched.",
+        ident
+    );
+
+    let expanded = quote! {
+        #[doc = #doc]
+        #[derive(Default)]
+        #vis struct #patch_ident {
+            #(#field_decls,)*
+        }
+
+        impl #ousia::ObjectPatch for #patch_ident {
+            type Target = #ident;
+
+            fn changed_fields(&self) -> Vec<&'static str> {
+                let mut fields = Vec::new();
+                #(#changed)*
+                fields
+            }
+
+            fn apply_to(self, target: &mut #ident) {
+                #(#applies)*
+            }
+        }
+
+        impl #ousia::Partial for #ident {
+            type Patch = #patch_ident;
+        }
+    };
+
+    TokenStream::from(expanded)
+}
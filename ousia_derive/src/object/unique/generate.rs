@@ -1,8 +1,9 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{DeriveInput, Result};
+use syn::{Data, DeriveInput, Fields, Result};
 
 use crate::import_ousia;
+use crate::shared::{bound_type_params, type_mentions};
 
 use super::parse::{UniqueConfig, UniqueConstraint};
 
@@ -17,12 +18,36 @@ pub fn generate_uniqueness_impl(input: &DeriveInput) -> Result<TokenStream> {
     let name = &input.ident;
     let type_name_str = name.to_string();
 
+    // Unique values are hashed from their `Display` output
+    let unique_types: Vec<_> = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(f) => f
+                .named
+                .iter()
+                .filter(|f| {
+                    let field = f.ident.as_ref().unwrap().to_string();
+                    config.constraints.iter().any(|c| match c {
+                        UniqueConstraint::Single(single) => *single == field,
+                        UniqueConstraint::Composite(fields) => fields.contains(&field),
+                    })
+                })
+                .map(|f| &f.ty)
+                .collect(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    let generics = bound_type_params(&input.generics, quote!(::std::fmt::Display), |p| {
+        unique_types.iter().any(|ty| type_mentions(ty, p))
+    });
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     let has_unique_fields = config.has_constraints();
 
     if !has_unique_fields {
         // No unique constraints
         return Ok(quote! {
-            impl #ousia::Unique for #name {
+            impl #impl_generics #ousia::Unique for #name #ty_generics #where_clause {
                 const HAS_UNIQUE_FIELDS: bool = false;
 
                 fn derive_unique_hashes(&self) -> ::std::vec::Vec<(::std::string::String, &'static str)> {
@@ -102,7 +127,7 @@ pub fn generate_uniqueness_impl(input: &DeriveInput) -> Result<TokenStream> {
         });
 
     Ok(quote! {
-        impl #ousia::Unique for #name {
+        impl #impl_generics #ousia::Unique for #name #ty_generics #where_clause {
             const HAS_UNIQUE_FIELDS: bool = true;
 
             fn derive_unique_hashes(&self) -> ::std::vec::Vec<(::std::string::String, &'static str)> {
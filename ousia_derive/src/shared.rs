@@ -1,9 +1,13 @@
 use proc_macro_crate::{FoundCrate, crate_name};
+use proc_macro2::{Ident, TokenTree};
 use quote::quote;
-use syn::{Attribute, Expr, ExprLit, Field, Lit, Meta};
+use syn::{Attribute, Expr, ExprLit, Field, Generics, Lit, Meta, Type, parse_quote};
 
 pub fn import_ousia() -> proc_macro2::TokenStream {
-    // This finds the ousia crate in the user's dependencies
+    // This finds the ousia crate in the user's dependencies, under whatever
+    // name it was imported as. Which adapter features are on doesn't matter:
+    // everything the derives emit lives outside `ousia::adapters`. Inside
+    // ousia itself, `::ousia` resolves through `extern crate self as ousia`.
     let found_crate = crate_name("ousia").unwrap_or(FoundCrate::Itself);
 
     match found_crate {
@@ -77,6 +81,68 @@ pub fn get_field_default_value(field: &Field) -> Option<String> {
     None
 }
 
+/// Whether the field type is `Option<_>`. A missing key deserializes to `None`.
+pub fn is_option_type(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident == "Option";
+        }
+    }
+    false
+}
+
+/// Whether a missing key falls back to `Default::default()` instead of a
+/// `missing_field` error. Custom types are assumed to implement `Default`;
+/// the compiler checks it where the fallback is generated.
+pub fn should_use_default(ty: &Type) -> bool {
+    // Check if type is Option (always uses default)
+    if is_option_type(ty) {
+        return true;
+    }
+
+    // Check for common types that implement Default
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            let type_name = segment.ident.to_string();
+
+            // Common stdlib types that implement Default
+            match type_name.as_str() {
+                // Collections
+                "Vec" | "HashMap" | "HashSet" | "BTreeMap" | "BTreeSet" |
+                "VecDeque" | "LinkedList" | "BinaryHeap" |
+                // Strings
+                "String" |
+                // Numbers (all primitive number types implement Default)
+                "i8" | "i16" | "i32" | "i64" | "i128" | "isize" |
+                "u8" | "u16" | "u32" | "u64" | "u128" | "usize" |
+                "f32" | "f64" |
+                // Other common types
+                "bool" | "PathBuf" | "Duration" => return true,
+                _ => {}
+            }
+        }
+    }
+
+    // For other types (including custom enums), assume they implement Default
+    // The compiler will verify at compile time
+    true
+}
+
+/// Parse the expression of a `#[ousia(default = "...")]` attribute
+pub fn parse_default_expr(expr: &str) -> proc_macro2::TokenStream {
+    expr.parse()
+        .expect("Failed to parse default value expression")
+}
+
+/// Default for a field: its `#[ousia(default = "...")]` expression, or
+/// `Default::default()` when it has none
+pub fn field_default_tokens(field: &Field) -> proc_macro2::TokenStream {
+    match get_field_default_value(field) {
+        Some(expr) => parse_default_expr(&expr),
+        None => quote! { Default::default() },
+    }
+}
+
 /// Parse type and index list from `#[ousia(...)]` using updated syn API
 pub fn parse_ousia_attr(attr: Option<&Attribute>) -> (Option<String>, Vec<(String, String)>) {
     let mut type_name = None;
@@ -130,8 +196,84 @@ pub fn parse_ousia_attr(attr: Option<&Attribute>) -> (Option<String>, Vec<(Strin
     (type_name, indexes)
 }
 
+/// Whether a bare flag such as `versioned` appears in `#[ousia(...)]`
+pub fn has_ousia_flag(attr: Option<&Attribute>, flag: &str) -> bool {
+    let Some(Meta::List(meta_list)) = attr.map(|attr| &attr.meta) else {
+        return false;
+    };
+    meta_list
+        .parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated)
+        .map(|nested| {
+            nested
+                .iter()
+                .any(|meta| matches!(meta, Meta::Path(path) if path.is_ident(flag)))
+        })
+        .unwrap_or(false)
+}
+
+/// Why `type_name` can't be used as a storage key, if it can't
+pub fn type_name_error(type_name: &str) -> Option<String> {
+    if type_name.is_empty() {
+        return Some("type_name must not be empty".to_string());
+    }
+    if type_name.chars().any(|c| c.is_whitespace() || c == '/') {
+        return Some(format!(
+            "type_name \"{}\" must not contain whitespace or '/'",
+            type_name
+        ));
+    }
+    None
+}
+
+/// Validity flag and const assertion for `TYPE`, plus a deprecation-style
+/// warning when `type_name` doesn't match the struct name.
+pub fn type_name_checks(
+    ousia: &proc_macro2::TokenStream,
+    ident: &Ident,
+    type_name: &str,
+) -> proc_macro2::TokenStream {
+    let mismatch_warning = if ident != type_name {
+        let note = format!(
+            "ousia type_name \"{}\" differs from the struct name `{}`; stored rows are keyed by type_name",
+            type_name, ident
+        );
+        quote! {
+            const _: () = {
+                #[deprecated(note = #note)]
+                #[allow(non_upper_case_globals)]
+                const ousia_type_name_mismatch: () = ();
+                ousia_type_name_mismatch
+            };
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        impl #ident {
+            pub const OUSIA_TYPE_NAME_VALID: bool = #ousia::is_valid_type_name(#type_name);
+        }
+
+        const _: () = assert!(
+            #ident::OUSIA_TYPE_NAME_VALID,
+            "TYPE must not be empty or contain whitespace or '/'"
+        );
+
+        #mismatch_warning
+    }
+}
+
 /// Check if a field has #[ousia(private)] attribute
 pub fn is_private_field(field: &Field) -> bool {
+    has_field_flag(field, "private")
+}
+
+/// Check if a field has #[ousia(flatten)] attribute
+pub fn is_flatten_field(field: &Field) -> bool {
+    has_field_flag(field, "flatten")
+}
+
+fn has_field_flag(field: &Field, flag: &str) -> bool {
     field.attrs.iter().any(|attr| {
         if !attr.path().is_ident("ousia") {
             return false;
@@ -145,7 +287,7 @@ pub fn is_private_field(field: &Field) -> bool {
             if let Ok(nested) = result {
                 return nested.iter().any(|meta| {
                     if let Meta::Path(path) = meta {
-                        path.is_ident("private")
+                        path.is_ident(flag)
                     } else {
                         false
                     }
@@ -169,3 +311,49 @@ pub fn parse_index_kinds(kind_str: &str) -> Vec<proc_macro2::TokenStream> {
         })
         .collect()
 }
+
+/// Whether `ty` refers to `ident` anywhere, e.g. `T` in `Option<Vec<T>>`.
+/// Matches lifetimes by name too (`a` for `'a`).
+pub fn type_mentions(ty: &Type, ident: &Ident) -> bool {
+    fn walk(tokens: proc_macro2::TokenStream, ident: &Ident) -> bool {
+        tokens.into_iter().any(|tt| match tt {
+            TokenTree::Ident(i) => &i == ident,
+            TokenTree::Group(g) => walk(g.stream(), ident),
+            _ => false,
+        })
+    }
+    walk(quote!(#ty), ident)
+}
+
+/// Clone `generics`, adding `bound` to every type parameter accepted by `select`
+pub fn bound_type_params(
+    generics: &Generics,
+    bound: proc_macro2::TokenStream,
+    select: impl Fn(&Ident) -> bool,
+) -> Generics {
+    let mut generics = generics.clone();
+    let params: Vec<Ident> = generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .filter(|i| select(i))
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause.predicates.push(parse_quote!(#param: #bound));
+    }
+    generics
+}
+
+/// Clone `generics`, requiring every lifetime parameter to be `'static`.
+/// Objects are owned and `'static`, so borrowed fields only work as `Cow<'static, _>` and the like.
+pub fn static_lifetimes(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    let lifetimes: Vec<syn::Lifetime> = generics.lifetimes().map(|l| l.lifetime.clone()).collect();
+    let where_clause = generics.make_where_clause();
+    for lifetime in lifetimes {
+        where_clause
+            .predicates
+            .push(parse_quote!(#lifetime: 'static));
+    }
+    generics
+}
@@ -6,7 +6,7 @@ use chrono::{Days, Utc};
 use ousia::{
     Engine,
     adapters::postgres::PostgresAdapter,
-    ledger::{Asset, Balance, LedgerAdapter, LedgerSystem, Money, MoneyError},
+    ledger::{Asset, Balance, IsolationLevel, LedgerAdapter, LedgerSystem, Money, MoneyError},
 };
 use sqlx::PgPool;
 use testcontainers::ContainerAsync;
@@ -58,7 +58,7 @@ async fn setup() -> (ContainerAsync<Postgres>, Engine, Uuid) {
 }
 
 async fn create_usd_asset(system: &Arc<dyn LedgerAdapter>) -> Asset {
-    let usd = Asset::new("USD", 10_00, 2);
+    let usd = Asset::new("USD", 10_00, 2).unwrap();
     system.create_asset(usd.clone()).await.unwrap();
     usd
 }
@@ -578,9 +578,127 @@ async fn test_concurrent_transfers_double_spend_protection() {
     );
 }
 
+#[tokio::test]
+async fn test_isolation_level_override() {
+    let (_resource, engine, user) = setup().await;
+    let merchant1 = Uuid::now_v7();
+    let merchant2 = Uuid::now_v7();
+    create_usd_asset(&engine.ledger()).await;
+
+    // SET TRANSACTION must come first in the transaction or Postgres rejects it
+    let ctx = engine
+        .ledger_ctx()
+        .with_isolation(IsolationLevel::RepeatableRead);
+    Money::atomic(&ctx, |tx| async move {
+        tx.mint("USD", user, 100_00, "deposit".to_string()).await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    let ctx = engine
+        .ledger_ctx()
+        .with_isolation(IsolationLevel::Serializable);
+    let ctx1 = ctx.clone();
+    let ctx2 = ctx.clone();
+
+    let handle1 = tokio::spawn(async move {
+        Money::atomic(&ctx1, |tx| async move {
+            let money = tx.money("USD", user, 100_00).await?;
+            let slice = money.slice(100_00)?;
+            slice.transfer_to(merchant1, "payment1".to_string()).await?;
+            Ok(())
+        })
+        .await
+    });
+
+    let handle2 = tokio::spawn(async move {
+        Money::atomic(&ctx2, |tx| async move {
+            let money = tx.money("USD", user, 100_00).await?;
+            let slice = money.slice(100_00)?;
+            slice.transfer_to(merchant2, "payment2".to_string()).await?;
+            Ok(())
+        })
+        .await
+    });
+
+    let (result1, result2) = tokio::join!(handle1, handle2);
+    let outcomes = [result1.unwrap(), result2.unwrap()];
+
+    // The loser either finds nothing to lock or is aborted with a serialization failure
+    assert_eq!(outcomes.iter().filter(|r| r.is_ok()).count(), 1);
+    assert!(outcomes.iter().all(|r| matches!(
+        r,
+        Ok(((), _)) | Err(MoneyError::InsufficientFunds) | Err(MoneyError::Storage(_))
+    )));
+
+    let total_received = Balance::get("USD", merchant1, &ctx)
+        .await
+        .unwrap()
+        .available
+        + Balance::get("USD", merchant2, &ctx)
+            .await
+            .unwrap()
+            .available;
+    assert_eq!(total_received, 100_00);
+    assert_eq!(Balance::get("USD", user, &ctx).await.unwrap().total, 0);
+}
+
+#[tokio::test]
+async fn test_isolation_statement_changes_visibility() {
+    let (_resource, pool) = setup_test_db().await;
+    sqlx::query("CREATE TABLE iso_probe (v INT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO iso_probe (v) VALUES (1)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // Read once, let another connection commit a change, then read again
+    async fn reads_under(pool: &PgPool, level: IsolationLevel) -> (String, i32, i32) {
+        let mut tx = pool.begin().await.unwrap();
+        sqlx::query(&level.set_transaction_sql())
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        let (applied,): (String,) = sqlx::query_as("SHOW transaction_isolation")
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        let (first,): (i32,) = sqlx::query_as("SELECT v FROM iso_probe")
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE iso_probe SET v = v + 1")
+            .execute(pool)
+            .await
+            .unwrap();
+        let (second,): (i32,) = sqlx::query_as("SELECT v FROM iso_probe")
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        (applied, first, second)
+    }
+
+    let (applied, first, second) = reads_under(&pool, IsolationLevel::ReadCommitted).await;
+    assert_eq!(applied, "read committed");
+    assert_ne!(first, second, "read committed sees the concurrent commit");
+
+    let (applied, first, second) = reads_under(&pool, IsolationLevel::RepeatableRead).await;
+    assert_eq!(applied, "repeatable read");
+    assert_eq!(first, second, "repeatable read keeps its snapshot");
+
+    let (applied, first, second) = reads_under(&pool, IsolationLevel::Serializable).await;
+    assert_eq!(applied, "serializable");
+    assert_eq!(first, second, "serializable keeps its snapshot");
+}
+
 #[tokio::test]
 async fn test_asset_decimals_conversion() {
-    let usd = Asset::new("USD", 10_00, 2);
+    let usd = Asset::new("USD", 10_00, 2).unwrap();
     assert_eq!(usd.decimals, 2);
     assert_eq!(usd.to_internal(100.50), 10050);
     assert_eq!(usd.to_display(10050), 100.50);
@@ -684,7 +802,7 @@ async fn test_rollback_on_error() {
             .await?;
 
         // Intentionally fail
-        return Err(MoneyError::Storage("simulated error".to_string()));
+        return Err::<(), _>(MoneyError::Storage("simulated error".to_string()));
     })
     .await;
 
@@ -704,7 +822,7 @@ async fn test_multiple_assets() {
     let (_resource, engine, user) = setup().await;
     create_usd_asset(&engine.ledger()).await;
 
-    let ngn = Asset::new("NGN", 500_000, 2); // ₦5,000 unit
+    let ngn = Asset::new("NGN", 500_000, 2).unwrap(); // ₦5,000 unit
     engine.ledger().create_asset(ngn).await.unwrap();
 
     let ctx = engine.ledger_ctx();
@@ -730,7 +848,7 @@ async fn test_fetch_transactions() {
     let (_resource, engine, user) = setup().await;
     create_usd_asset(&engine.ledger()).await;
 
-    let usd = Asset::new("USD", 500_000, 2); // ₦5,000 unit
+    let usd = Asset::new("USD", 500_000, 2).unwrap(); // ₦5,000 unit
     engine.ledger().create_asset(usd).await.unwrap();
 
     let ctx = engine.ledger_ctx();
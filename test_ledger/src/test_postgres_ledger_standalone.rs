@@ -73,7 +73,7 @@ async fn setup() -> (
 
 #[cfg(test)]
 async fn create_usd_asset(system: &LedgerSystem) -> Asset {
-    let usd = Asset::new("USD", 10_00, 2);
+    let usd = Asset::new("USD", 10_00, 2).unwrap();
     system.adapter().create_asset(usd.clone()).await.unwrap();
     usd
 }
@@ -455,7 +455,7 @@ async fn test_concurrent_transfers_double_spend_protection() {
 
 #[tokio::test]
 async fn test_asset_decimals_conversion() {
-    let usd = Asset::new("USD", 10_00, 2);
+    let usd = Asset::new("USD", 10_00, 2).unwrap();
     assert_eq!(usd.decimals, 2);
     assert_eq!(usd.to_internal(100.50), 10050);
     assert_eq!(usd.to_display(10050), 100.50);
@@ -554,7 +554,7 @@ async fn test_rollback_on_error() {
             .await?;
 
         // Intentionally fail
-        return Err(MoneyError::Storage("simulated error".to_string()));
+        return Err::<(), _>(MoneyError::Storage("simulated error".to_string()));
     })
     .await;
 
@@ -574,7 +574,7 @@ async fn test_multiple_assets() {
     let (_resource, system, ctx, user) = setup().await;
     create_usd_asset(&system).await;
 
-    let ngn = Asset::new("NGN", 500_000, 2); // ₦5,000 unit
+    let ngn = Asset::new("NGN", 500_000, 2).unwrap(); // ₦5,000 unit
     system.adapter().create_asset(ngn).await.unwrap();
 
     Money::atomic(&ctx, |tx| async move {
@@ -2,7 +2,9 @@ pub mod test_cockroach;
 pub mod test_postgres;
 pub mod test_sqlite;
 
-use ousia::{EdgeMeta, Meta, OusiaDefault, OusiaEdge, OusiaObject, query::ToIndexValue};
+use ousia::{
+    EdgeMeta, Meta, OusiaDefault, OusiaEdge, OusiaObject, OusiaPartial, query::ToIndexValue,
+};
 use serde::{Deserialize, Serialize};
 
 /// Example: Blog Post object
@@ -11,7 +13,10 @@ use serde::{Deserialize, Serialize};
     type_name = "Post",
     index = "title:search+sort",
     index = "status:search",
-    index = "tags:search"
+    index = "tags:search",
+    index = "published_at:search",
+    index = "created_at:sort",
+    index = "views:sort"
 )]
 pub struct Post {
     _meta: Meta,
@@ -21,6 +26,7 @@ pub struct Post {
     pub status: PostStatus,
     pub published_at: Option<chrono::DateTime<chrono::Utc>>,
     pub tags: Vec<String>,
+    pub views: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -66,7 +72,7 @@ impl ToIndexValue for Wallet {
     }
 }
 
-#[derive(OusiaObject, OusiaDefault, Debug)]
+#[derive(OusiaObject, OusiaDefault, OusiaPartial, Debug)]
 #[ousia(
     type_name = "User",
     unique = "username",
@@ -83,9 +89,60 @@ pub struct User {
     pub balance: Wallet,
 }
 
+/// Example: kinds declared separately for one field are merged in `FIELDS`
+#[derive(OusiaObject, OusiaDefault, Debug)]
+#[ousia(
+    type_name = "Player",
+    index = "score:search",
+    index = "score:sort",
+    index = "nickname:search+sort"
+)]
+pub struct Player {
+    _meta: Meta,
+
+    pub nickname: String,
+    pub score: i64,
+}
+
+/// Example: object whose previous states are kept in `object_history`
+#[derive(OusiaObject, OusiaDefault, Debug)]
+#[ousia(type_name = "Document", versioned, index = "title:search")]
+pub struct Document {
+    _meta: Meta,
+
+    pub title: String,
+    pub body: String,
+}
+
 #[derive(Debug, OusiaEdge)]
 #[ousia(type_name = "Follow", index = "notification:search")]
 struct Follow {
     _meta: EdgeMeta,
     notification: bool,
 }
+
+/// Edge with an optional field that may be stored as `None`
+#[derive(Debug, OusiaEdge)]
+#[ousia(type_name = "Bookmark", index = "description:search")]
+struct Bookmark {
+    _meta: EdgeMeta,
+    description: Option<String>,
+}
+
+/// At most one pinned post per user
+#[derive(Debug, OusiaEdge)]
+#[ousia(type_name = "Pin", unique = "from")]
+struct Pin {
+    _meta: EdgeMeta,
+    note: String,
+}
+
+/// Example: generic object. Every instantiation shares the `Envelope` type name.
+#[derive(OusiaObject, Debug)]
+#[ousia(type_name = "Envelope", index = "payload:search")]
+pub struct Envelope<T> {
+    _meta: Meta,
+
+    pub label: String,
+    pub payload: T,
+}
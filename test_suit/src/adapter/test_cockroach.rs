@@ -465,7 +465,7 @@ async fn test_engine_ownership() {
     engine.create_object(&post).await.unwrap();
 
     // Verify ownership
-    assert!(post.is_owned_by(&owner));
+    assert!(post.is_owned_by(owner.id()));
 
     // Fetch owned objects
     let posts: Vec<Post> = engine.fetch_owned_objects(owner.id()).await.unwrap();
@@ -946,10 +946,7 @@ async fn test_unique_object() {
     michael.email = "michael@example.com".into();
     michael.display_name = "Michael".into();
     let err = engine.create_object(&michael).await.unwrap_err();
-    assert_eq!(
-        err,
-        Error::UniqueConstraintViolation(String::from("username"))
-    );
+    assert_eq!(err, Error::unique_violation("username"));
 
     use ousia::{Meta, OusiaDefault, OusiaObject};
     #[derive(OusiaObject, OusiaDefault, Debug)]
@@ -984,10 +981,7 @@ async fn test_unique_object() {
     bob.display_name = "Bob".into();
     let err = engine.create_object(&bob).await.unwrap_err();
 
-    assert_eq!(
-        err,
-        Error::UniqueConstraintViolation(String::from("username+email"))
-    );
+    assert_eq!(err, Error::unique_violation("username+email"));
 }
 
 #[tokio::test]
@@ -2014,3 +2008,58 @@ async fn test_fetch_owned_object() {
     let none: Option<Post> = engine.fetch_owned_object(bob.id()).await.unwrap();
     assert!(none.is_none());
 }
+
+#[tokio::test]
+async fn test_follower_reads() {
+    let (_resource, pool) = setup_test_db().await;
+    let adapter = CockroachAdapter::from_pool(pool.clone());
+    adapter.init_schema().await.unwrap();
+    // The stale snapshot must postdate the schema
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    let engine = Engine::new(Box::new(adapter));
+    let mut post = Post::default();
+    post.title = "Fresh".into();
+    engine.create_object(&post).await.unwrap();
+
+    let query = || Query::default().where_eq(&Post::FIELDS.title, "Fresh");
+
+    // A read one second in the past can't see the new row yet
+    let stale: Vec<Post> = engine
+        .query_objects(query().allow_stale(Duration::from_secs(1)))
+        .await
+        .unwrap();
+    assert!(stale.is_empty());
+    let fresh: Vec<Post> = engine.query_objects(query()).await.unwrap();
+    assert_eq!(fresh.len(), 1);
+
+    // Adapter-wide follower reads cover fetches too
+    let follower = Engine::new(Box::new(
+        CockroachAdapter::from_pool(pool).with_follower_read(Duration::from_secs(1)),
+    ));
+    assert!(
+        follower
+            .fetch_object::<Post>(post.id())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let engine = engine.with_follower_reads(Duration::from_secs(1));
+    assert_eq!(
+        engine.count_objects::<Post>(Some(query())).await.unwrap(),
+        0
+    );
+
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    assert!(
+        follower
+            .fetch_object::<Post>(post.id())
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert_eq!(
+        engine.count_objects::<Post>(Some(query())).await.unwrap(),
+        1
+    );
+}
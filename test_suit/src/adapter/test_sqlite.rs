@@ -4,11 +4,11 @@ use std::time::Duration;
 #[cfg(test)]
 use super::*;
 #[cfg(test)]
-use ousia::adapters::Adapter;
+use ousia::adapters::{Adapter, UniqueAdapter};
 #[cfg(test)]
 use ousia::{
-    EdgeMeta, EdgeMetaTrait, EdgeQuery, Engine, Error, Meta, Object, ObjectMeta, ObjectOwnership,
-    Query, Union,
+    BatchEdgeResult, EdgeMeta, EdgeMetaTrait, EdgeQuery, Engine, Error, MaintenanceOptions, Meta,
+    Object, ObjectMeta, ObjectOwnership, OnConflict, Query, SearchSort, Union, ValidationCode,
     adapters::{ObjectRecord, sqlite::SqliteAdapter},
     filter, system_owner,
 };
@@ -224,6 +224,29 @@ fn test_object_ownership_not_system_owned() {
     assert!(!user.is_system_owned());
 }
 
+#[test]
+fn test_object_meta_accessors() {
+    let owner = uuid::Uuid::now_v7();
+    let mut post = Post {
+        _meta: Meta::new_with_owner(owner),
+        ..Default::default()
+    };
+
+    assert_eq!(post.id(), post.meta().id());
+    assert_eq!(post.owner(), owner);
+    assert!(post.is_owned_by(owner));
+    assert!(!post.is_owned_by(uuid::Uuid::now_v7()));
+    assert!(!post.is_system_owned());
+    assert!(post.updated_at() >= post.created_at());
+
+    post.meta_mut().created_at = chrono::Utc::now() - chrono::Duration::hours(2);
+    post.meta_mut().updated_at = chrono::Utc::now() - chrono::Duration::minutes(10);
+    assert!(post.age() >= chrono::Duration::hours(2));
+    assert!(post.age() < chrono::Duration::hours(3));
+    assert!(post.was_recently_updated(chrono::Duration::hours(1)));
+    assert!(!post.was_recently_updated(chrono::Duration::minutes(5)));
+}
+
 #[test]
 fn test_index_meta() {
     let mut user = User::default();
@@ -238,6 +261,49 @@ fn test_index_meta() {
     );
 }
 
+#[test]
+fn test_index_meta_merge_round_trip() {
+    use ousia::query::{IndexMeta, IndexValue, IndexValueInner};
+
+    let old = IndexMeta(
+        [
+            ("name", IndexValue::String("old".to_string())),
+            ("id_like", IndexValue::String(uuid::Uuid::nil().to_string())),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect(),
+    );
+    let new = IndexMeta(
+        [
+            ("name", IndexValue::String("new".to_string())),
+            ("id_like", IndexValue::String(uuid::Uuid::now_v7().to_string())),
+            ("count", IndexValue::Int(42)),
+            ("score", IndexValue::Float(1.5)),
+            ("active", IndexValue::Bool(true)),
+            ("owner", IndexValue::Uuid(uuid::Uuid::now_v7())),
+            ("seen_at", IndexValue::Timestamp(chrono::Utc::now())),
+            (
+                "tags",
+                IndexValue::Array(vec![
+                    IndexValueInner::String("a".to_string()),
+                    IndexValueInner::Int(1),
+                    IndexValueInner::Float(2.5),
+                ]),
+            ),
+            ("deleted_at", IndexValue::Null),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect(),
+    );
+
+    let (patch, _) = IndexMeta::diff(&old, &new);
+    let mut merged = old.clone();
+    merged.merge(patch);
+    assert_eq!(merged.meta(), new.meta());
+}
+
 #[test]
 fn test_query_fields() {
     assert_eq!(User::FIELDS.username.name, "username");
@@ -420,7 +486,7 @@ async fn test_engine_ownership() {
     engine.create_object(&post).await.unwrap();
 
     // Verify ownership
-    assert!(post.is_owned_by(&owner));
+    assert!(post.is_owned_by(owner.id()));
 
     // Fetch owned objects
     let posts: Vec<Post> = engine.fetch_owned_objects(owner.id()).await.unwrap();
@@ -888,10 +954,17 @@ async fn test_unique_object() {
     michael.email = "michael@example.com".into();
     michael.display_name = "Michael".into();
     let err = engine.create_object(&michael).await.unwrap_err();
+    assert_eq!(err, Error::unique_violation("username"));
+    assert!(err.is_validation());
+    let field_errors = err.validation_errors().unwrap();
+    assert_eq!(field_errors.len(), 1);
+    assert_eq!(field_errors[0].field, "username");
+    assert_eq!(field_errors[0].code, ValidationCode::UniqueViolation);
     assert_eq!(
-        err,
-        Error::UniqueConstraintViolation(String::from("username"))
+        err.to_string(),
+        "Validation failed: username: must be unique"
     );
+    assert!(Error::NotFound.validation_errors().is_none());
 
     use ousia::{Meta, OusiaDefault, OusiaObject};
     #[derive(OusiaObject, OusiaDefault, Debug)]
@@ -926,10 +999,7 @@ async fn test_unique_object() {
     bob.display_name = "Bob".into();
     let err = engine.create_object(&bob).await.unwrap_err();
 
-    assert_eq!(
-        err,
-        Error::UniqueConstraintViolation(String::from("username+email"))
-    );
+    assert_eq!(err, Error::unique_violation("username+email"));
 }
 
 #[tokio::test]
@@ -949,6 +1019,44 @@ async fn test_sequence() {
     assert_eq!(value, 2);
 }
 
+#[tokio::test]
+async fn test_create_object_returns_sequence_errors() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let adapter = SqliteAdapter::from_pool(pool.clone());
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(SqliteAdapter::from_pool(pool.clone())));
+
+    sqlx::query("DROP TABLE sequences")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let mut user = User::default();
+    user.username = "alice".to_string();
+    assert!(matches!(
+        engine.create_object(&user).await,
+        Err(Error::Storage(_))
+    ));
+
+    // The unique username claimed before the failure was released
+    adapter.init_schema().await.unwrap();
+    engine.create_object(&user).await.unwrap();
+    assert_eq!(
+        engine
+            .fetch_object::<User>(user.id())
+            .await
+            .unwrap()
+            .unwrap()
+            .meta()
+            .sequence,
+        1
+    );
+}
+
 // ============================================================
 // Preload API — Single Pivot (QueryContext / EdgeQueryContext)
 // ============================================================
@@ -1966,3 +2074,4360 @@ async fn test_fetch_owned_object() {
     let none: Option<Post> = engine.fetch_owned_object(bob.id()).await.unwrap();
     assert!(none.is_none());
 }
+
+#[tokio::test]
+async fn test_watch_object_yields_on_update() {
+    use futures::StreamExt;
+
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut user = User::default();
+    user.username = "watched".to_string();
+    engine.create_object(&user).await.unwrap();
+
+    let mut stream = engine.watch_object::<User>(user.id()).await.unwrap();
+
+    let writer = engine.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        user.username = "changed".to_string();
+        writer.update_object(&mut user).await.unwrap();
+    });
+
+    let updated = tokio::time::timeout(Duration::from_secs(2), stream.next())
+        .await
+        .expect("no change within 2 seconds")
+        .expect("stream ended")
+        .unwrap();
+    assert_eq!(updated.username, "changed");
+}
+
+#[tokio::test]
+async fn test_watch_type_yields_inserts_and_updates() {
+    use futures::StreamExt;
+    use ousia::watch::ChangeKind;
+
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut stream = engine.watch_type::<User>().await.unwrap();
+
+    let writer = engine.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let mut user = User::default();
+        user.username = "new_user".to_string();
+        writer.create_object(&user).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        user.username = "renamed".to_string();
+        writer.update_object(&mut user).await.unwrap();
+    });
+
+    let (kind, inserted) = tokio::time::timeout(Duration::from_secs(2), stream.next())
+        .await
+        .expect("no insert within 2 seconds")
+        .expect("stream ended")
+        .unwrap();
+    assert_eq!(kind, ChangeKind::Insert);
+    assert_eq!(inserted.username, "new_user");
+
+    let (kind, updated) = tokio::time::timeout(Duration::from_secs(2), stream.next())
+        .await
+        .expect("no update within 2 seconds")
+        .expect("stream ended")
+        .unwrap();
+    assert_eq!(kind, ChangeKind::Update);
+    assert_eq!(updated.username, "renamed");
+}
+
+#[tokio::test]
+async fn test_vacuum_and_table_stats() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut ids = Vec::new();
+    for i in 0..100 {
+        let mut user = User::default();
+        user.username = format!("vacuum{}", i);
+        user.email = format!("vacuum{}@example.com", i);
+        ids.push(user.id());
+        engine.create_object(&user).await.unwrap();
+    }
+
+    let deleted = engine
+        .delete_objects::<User>(ids[..50].to_vec(), system_owner())
+        .await
+        .unwrap();
+    assert_eq!(deleted, 50);
+
+    engine.vacuum().await.unwrap();
+
+    let stats = engine.table_stats().await.unwrap();
+    assert_eq!(stats.objects_count, 50);
+    assert_eq!(stats.edges_count, 0);
+    assert_eq!(stats.estimated_size_bytes, None);
+}
+
+#[tokio::test]
+async fn test_patch_object_single_field() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut user = User::default();
+    user.username = "patchy".to_string();
+    user.email = "old@example.com".to_string();
+    user.display_name = "Patchy".to_string();
+    engine.create_object(&user).await.unwrap();
+
+    let patch = UserPatch {
+        email: Some("new@example.com".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(ousia::ObjectPatch::changed_fields(&patch), vec!["email"]);
+
+    let patched: User = engine
+        .patch_object(user.id(), system_owner(), patch)
+        .await
+        .unwrap();
+    assert!(patched.updated_at() > user.updated_at());
+
+    let fetched: User = engine.fetch_object(user.id()).await.unwrap().unwrap();
+    assert_eq!(fetched.email, "new@example.com");
+    assert_eq!(fetched.username, "patchy");
+    assert_eq!(fetched.display_name, "Patchy");
+
+    // index_meta follows the patched field
+    let by_new: Vec<User> = engine
+        .query_objects(Query::default().where_eq(&User::FIELDS.email, "new@example.com"))
+        .await
+        .unwrap();
+    assert_eq!(by_new.len(), 1);
+
+    let by_old: Vec<User> = engine
+        .query_objects(Query::default().where_eq(&User::FIELDS.email, "old@example.com"))
+        .await
+        .unwrap();
+    assert!(by_old.is_empty());
+}
+
+#[tokio::test]
+async fn test_sample_objects() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    for i in 0..100 {
+        let mut post = Post::default();
+        post.title = format!("Post {}", i);
+        engine.create_object(&post).await.unwrap();
+    }
+
+    let mut samples = Vec::new();
+    for _ in 0..5 {
+        let sample: Vec<Post> = engine.sample_objects(10, None).await.unwrap();
+        assert!(sample.len() <= 10);
+        samples.push(sample.iter().map(|p| p.id()).collect::<Vec<_>>());
+    }
+
+    // Five identical draws of 10 from 100 are practically impossible
+    assert!(samples.windows(2).any(|w| w[0] != w[1]));
+
+    let err = engine
+        .query_objects::<Post>(
+            Query::default()
+                .random_sample(10)
+                .sort_asc(&Post::FIELDS.title),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidQuery(_)));
+}
+
+#[tokio::test]
+async fn test_diff_object() {
+    use ousia::ObjectDiff;
+
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut user = User::default();
+    user.username = "differ".to_string();
+    user.email = "before@example.com".to_string();
+    engine.create_object(&user).await.unwrap();
+
+    let before = ObjectDiff::snapshot(&user);
+    let mut after: User = engine.fetch_object(user.id()).await.unwrap().unwrap();
+    after.email = "after@example.com".to_string();
+
+    let diff = engine.diff_object(&user, &after);
+    assert!(!diff.is_empty());
+    assert_eq!(diff.changed_fields(), vec!["email"]);
+    assert_eq!(
+        diff.changed["email"],
+        (
+            serde_json::json!("before@example.com"),
+            serde_json::json!("after@example.com")
+        )
+    );
+    assert!(diff.unchanged.contains(&"username".to_string()));
+
+    engine.update_object(&mut after).await.unwrap();
+    let stored = engine
+        .diff_objects_by_id::<User>(before.clone(), user.id())
+        .await
+        .unwrap();
+    assert_eq!(stored.changed_fields(), vec!["email"]);
+
+    // Added and removed fields show up with a Null on the missing side
+    let mut old_schema = before.clone();
+    old_schema.as_object_mut().unwrap().remove("display_name");
+    old_schema["legacy"] = serde_json::json!(true);
+    let diff = ObjectDiff::between(&old_schema, &before);
+    assert_eq!(diff.changed_fields(), vec!["display_name", "legacy"]);
+    assert_eq!(diff.changed["legacy"].1, serde_json::Value::Null);
+
+    assert!(engine.diff_object(&user, &user).is_empty());
+}
+
+#[tokio::test]
+async fn test_query_created_at_sort_and_filter() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut posts = Vec::new();
+    for i in 0..3 {
+        let mut post = Post::default();
+        post.title = format!("Post {}", i);
+        engine.create_object(&post).await.unwrap();
+        posts.push(post);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let newest_first: Vec<Post> = engine
+        .query_objects(Query::default().sort_desc(&Post::FIELDS.created_at))
+        .await
+        .unwrap();
+    let titles: Vec<_> = newest_first.iter().map(|p| p.title.as_str()).collect();
+    assert_eq!(titles, vec!["Post 2", "Post 1", "Post 0"]);
+
+    let after: Vec<Post> = engine
+        .query_objects(Query::default().where_created_after(posts[1].created_at()))
+        .await
+        .unwrap();
+    assert_eq!(after.len(), 1);
+    assert_eq!(after[0].title, "Post 2");
+
+    let before: Vec<Post> = engine
+        .query_objects(
+            Query::default()
+                .where_created_before(posts[2].created_at())
+                .sort_asc(&Post::FIELDS.created_at),
+        )
+        .await
+        .unwrap();
+    let titles: Vec<_> = before.iter().map(|p| p.title.as_str()).collect();
+    assert_eq!(titles, vec!["Post 0", "Post 1"]);
+
+    let mut touched = posts.remove(0);
+    let since = posts[1].updated_at();
+    engine.update_object(&mut touched).await.unwrap();
+    let updated: Vec<Post> = engine
+        .query_objects(Query::default().where_updated_after(since))
+        .await
+        .unwrap();
+    assert_eq!(updated.len(), 1);
+    assert_eq!(updated[0].title, "Post 0");
+}
+
+#[tokio::test]
+async fn test_find_global_across_owners() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    for owner in [uuid::Uuid::now_v7(), uuid::Uuid::now_v7(), system_owner()] {
+        let mut post = Post::default();
+        post.title = "Global".to_string();
+        post.set_owner(owner);
+        engine.create_object(&post).await.unwrap();
+    }
+
+    let filters = [filter!(&Post::FIELDS.title, "Global")];
+
+    let err = engine.find_global::<Post>(&filters).await.unwrap_err();
+    assert_eq!(err, Error::PermissionDenied);
+    let err = engine.count_global::<Post>(&filters).await.unwrap_err();
+    assert_eq!(err, Error::PermissionDenied);
+
+    let engine = engine.with_global_queries(true);
+    let posts: Vec<Post> = engine.find_global(&filters).await.unwrap();
+    assert_eq!(posts.len(), 3);
+    assert_eq!(engine.count_global::<Post>(&filters).await.unwrap(), 3);
+    assert_eq!(engine.count_global::<Post>(&[]).await.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_create_object_if_absent() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut post = Post::default();
+    post.title = "Original".to_string();
+    assert!(engine.create_object_if_absent(&post).await.unwrap());
+
+    post.title = "Changed".to_string();
+    assert!(!engine.create_object_if_absent(&post).await.unwrap());
+
+    assert_eq!(engine.count_objects::<Post>(None).await.unwrap(), 1);
+    let stored: Post = engine.fetch_object(post.id()).await.unwrap().unwrap();
+    assert_eq!(stored.title, "Original");
+
+    // Types with unique fields take the same path
+    let mut user = User::default();
+    user.username = "once".to_string();
+    assert!(engine.create_object_if_absent(&user).await.unwrap());
+    assert!(!engine.create_object_if_absent(&user).await.unwrap());
+    assert_eq!(engine.count_objects::<User>(None).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_replace_edge_target() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let (a, b, c) = (
+        uuid::Uuid::now_v7(),
+        uuid::Uuid::now_v7(),
+        uuid::Uuid::now_v7(),
+    );
+    let follow = Follow {
+        _meta: EdgeMeta::new(a, b),
+        notification: true,
+    };
+    engine.create_edge(&follow).await.unwrap();
+
+    engine.replace_edge_target::<Follow>(a, b, c).await.unwrap();
+
+    assert!(engine.fetch_edge::<Follow>(a, b).await.unwrap().is_none());
+    let moved = engine.fetch_edge::<Follow>(a, c).await.unwrap().unwrap();
+    assert_eq!(moved.to(), c);
+    assert!(moved.notification);
+
+    let err = engine
+        .replace_edge_target::<Follow>(a, b, c)
+        .await
+        .unwrap_err();
+    assert_eq!(err, Error::NotFound);
+
+    let err = engine
+        .replace_edge_target::<Follow>(a, c, a)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidQuery(_)));
+}
+
+#[tokio::test]
+async fn test_query_common_neighbors() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut ids = Vec::new();
+    for name in ["a", "b", "c", "d", "e"] {
+        let user = User {
+            _meta: Meta::default(),
+            username: name.to_string(),
+            email: format!("{name}@example.com"),
+            display_name: name.to_uppercase(),
+            balance: Wallet::default(),
+        };
+        engine.create_object(&user).await.unwrap();
+        ids.push(user.id());
+    }
+    let (a, b, c, d, e) = (ids[0], ids[1], ids[2], ids[3], ids[4]);
+
+    for (from, to) in [(a, b), (a, c), (a, d), (b, c), (b, d), (b, e)] {
+        let follow = Follow {
+            _meta: EdgeMeta::new(from, to),
+            notification: true,
+        };
+        engine.create_edge(&follow).await.unwrap();
+    }
+
+    let common: Vec<User> = engine
+        .query_common_neighbors::<Follow, User>(a, b, EdgeQuery::default())
+        .await
+        .unwrap();
+    let mut common_ids: Vec<_> = common.iter().map(|u| u.id()).collect();
+    common_ids.sort();
+    let mut expected = vec![c, d];
+    expected.sort();
+    assert_eq!(common_ids, expected);
+
+    let count = engine
+        .count_common_neighbors::<Follow, User>(a, b)
+        .await
+        .unwrap();
+    assert_eq!(count, 2);
+
+    let none: Vec<User> = engine
+        .query_common_neighbors::<Follow, User>(c, e, EdgeQuery::default())
+        .await
+        .unwrap();
+    assert!(none.is_empty());
+}
+
+#[tokio::test]
+async fn test_generic_object_round_trip() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+    let owner = uuid::Uuid::now_v7();
+
+    let text = Envelope {
+        _meta: Meta::new_with_owner(owner),
+        label: "text".to_string(),
+        payload: "hello".to_string(),
+    };
+    engine.create_object(&text).await.unwrap();
+
+    let number = Envelope {
+        _meta: Meta::new_with_owner(owner),
+        label: "number".to_string(),
+        payload: 42i64,
+    };
+    engine.create_object(&number).await.unwrap();
+
+    let fetched = engine
+        .fetch_object::<Envelope<String>>(text.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fetched.label, "text");
+    assert_eq!(fetched.payload, "hello");
+
+    let fetched = engine
+        .fetch_object::<Envelope<i64>>(number.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fetched.label, "number");
+    assert_eq!(fetched.payload, 42);
+
+    let found = engine
+        .query_objects::<Envelope<i64>>(
+            Query::new(owner).where_eq(&Envelope::<i64>::FIELDS.payload, 42i64),
+        )
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id(), number.id());
+}
+
+#[tokio::test]
+async fn test_object_sequence_order() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter)).with_global_queries(true);
+
+    let mut ids = Vec::new();
+    for i in 0..5 {
+        let post = Post {
+            _meta: Meta::new_with_owner(uuid::Uuid::now_v7()),
+            title: format!("Post {i}"),
+            ..Default::default()
+        };
+        engine.create_object(&post).await.unwrap();
+        ids.push(post.id());
+    }
+
+    let posts = engine
+        .fetch_objects_in_sequence_order::<Post>(1, 5)
+        .await
+        .unwrap();
+    let sequences: Vec<u64> = posts.iter().map(|p| p.meta().sequence).collect();
+    assert_eq!(sequences, vec![1, 2, 3, 4, 5]);
+    let fetched_ids: Vec<_> = posts.iter().map(|p| p.id()).collect();
+    assert_eq!(fetched_ids, ids);
+
+    let middle = engine
+        .fetch_objects_in_sequence_order::<Post>(2, 3)
+        .await
+        .unwrap();
+    assert_eq!(middle.len(), 2);
+    assert_eq!(middle[0].meta().sequence, 2);
+
+    // Updating an in-memory copy keeps the stored sequence
+    let mut post = Post {
+        _meta: Meta::new_with_owner(uuid::Uuid::now_v7()),
+        title: "Post 5".to_string(),
+        ..Default::default()
+    };
+    engine.create_object(&post).await.unwrap();
+    post.title = "Renamed".to_string();
+    engine.update_object(&mut post).await.unwrap();
+    assert_eq!(post.meta().sequence, 6);
+    let stored = engine
+        .fetch_object::<Post>(post.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.meta().sequence, 6);
+
+    // The meta sequence is kept apart from a user field of the same name
+    use ousia::{OusiaDefault, OusiaObject};
+    #[derive(OusiaObject, OusiaDefault, Debug)]
+    #[ousia(type_name = "Step")]
+    pub struct Step {
+        _meta: Meta,
+
+        pub sequence: u32,
+    }
+
+    let step = Step {
+        _meta: Meta::new_with_owner(uuid::Uuid::now_v7()),
+        sequence: 42,
+    };
+    engine.create_object(&step).await.unwrap();
+    let stored = engine
+        .fetch_object::<Step>(step.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.sequence, 42);
+    assert_eq!(stored.meta().sequence, 1);
+}
+
+#[tokio::test]
+async fn test_lock_object_serializes_updates() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let post = Post {
+        _meta: Meta::new_with_owner(uuid::Uuid::now_v7()),
+        title: "Original".to_string(),
+        ..Default::default()
+    };
+    engine.create_object(&post).await.unwrap();
+    let id = post.id();
+
+    let first = engine.lock_object::<Post>(id).await.unwrap();
+
+    // The second task waits until the first lock is released
+    let waiter = {
+        let engine = engine.clone();
+        tokio::spawn(async move {
+            let lock = engine.lock_object::<Post>(id).await.unwrap();
+            let seen = lock.object().title.clone();
+            let mut post = engine.fetch_object::<Post>(id).await.unwrap().unwrap();
+            post.title = "Second".to_string();
+            lock.update(&mut post).await.unwrap();
+            seen
+        })
+    };
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!waiter.is_finished());
+
+    assert_eq!(first.object().title, "Original");
+    let mut locked = engine.fetch_object::<Post>(id).await.unwrap().unwrap();
+    locked.title = "First".to_string();
+    first.update(&mut locked).await.unwrap();
+
+    assert_eq!(waiter.await.unwrap(), "First");
+    let stored = engine.fetch_object::<Post>(id).await.unwrap().unwrap();
+    assert_eq!(stored.title, "Second");
+
+    // Dropping without update releases the lock and writes nothing
+    let lock = engine.lock_object::<Post>(id).await.unwrap();
+    drop(lock);
+    let _held = engine.lock_object::<Post>(id).await.unwrap();
+    let result = engine
+        .clone()
+        .with_lock_timeout(Duration::from_millis(20))
+        .lock_object::<Post>(id)
+        .await;
+    assert!(matches!(result, Err(Error::LockTimeout)));
+}
+
+#[tokio::test]
+async fn test_unique_edge_from() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let user = uuid::Uuid::now_v7();
+    let first_post = uuid::Uuid::now_v7();
+    let second_post = uuid::Uuid::now_v7();
+
+    let pin = Pin {
+        _meta: EdgeMeta::new(user, first_post),
+        note: "first".to_string(),
+    };
+    engine.create_edge(&pin).await.unwrap();
+
+    let second = Pin {
+        _meta: EdgeMeta::new(user, second_post),
+        note: "second".to_string(),
+    };
+    let result = engine.create_edge(&second).await;
+    assert_eq!(result, Err(Error::unique_violation("from")));
+    assert!(
+        engine
+            .fetch_edge::<Pin>(user, second_post)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // Upserting the held edge rewrites its data
+    let updated = Pin {
+        _meta: EdgeMeta::new(user, first_post),
+        note: "edited".to_string(),
+    };
+    assert!(!engine.upsert_edge(&updated).await.unwrap().created);
+    let stored = engine
+        .fetch_edge::<Pin>(user, first_post)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.note, "edited");
+    assert!(
+        engine
+            .upsert_edge(&second)
+            .await
+            .unwrap_err()
+            .is_unique_constraint_violation()
+    );
+
+    // Other sources are unaffected
+    let other = Pin {
+        _meta: EdgeMeta::new(uuid::Uuid::now_v7(), first_post),
+        note: "other".to_string(),
+    };
+    engine.create_edge(&other).await.unwrap();
+
+    // Deleting the edge frees the slot
+    engine.delete_edge::<Pin>(user, first_post).await.unwrap();
+    engine.create_edge(&second).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_update_object_partial_index_meta() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+    let owner = uuid::Uuid::now_v7();
+
+    let mut post = Post {
+        _meta: Meta::new_with_owner(owner),
+        title: "Draft title".to_string(),
+        content: "body".to_string(),
+        tags: vec!["rust".to_string()],
+        ..Default::default()
+    };
+    engine.create_object(&post).await.unwrap();
+
+    let old_meta = post.index_meta();
+
+    // One index key changed: written as a patch
+    post.title = "Final title".to_string();
+    post.content = "new body".to_string();
+    let (changed, removed) = ousia::query::IndexMeta::diff(&old_meta, &post.index_meta());
+    assert_eq!(changed.as_object().unwrap().len(), 1);
+    assert!(removed.is_empty());
+    engine.update_object(&mut post).await.unwrap();
+
+    let posts: Vec<Post> = engine
+        .query_objects(Query::new(owner).where_eq(&Post::FIELDS.title, "Final title"))
+        .await
+        .unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].content, "new body");
+    assert_eq!(posts[0].tags, vec!["rust".to_string()]);
+
+    // Untouched keys are still indexed
+    let posts: Vec<Post> = engine
+        .query_objects(Query::new(owner).where_eq(&Post::FIELDS.status, PostStatus::Draft))
+        .await
+        .unwrap();
+    assert_eq!(posts.len(), 1);
+
+    // Most keys changed: full replacement
+    post.title = "Archived title".to_string();
+    post.status = PostStatus::Archived;
+    post.tags = vec!["old".to_string()];
+    engine.update_object(&mut post).await.unwrap();
+
+    let posts: Vec<Post> = engine
+        .query_objects(Query::new(owner).where_eq(&Post::FIELDS.status, PostStatus::Archived))
+        .await
+        .unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].title, "Archived title");
+
+    // merge applies a diff
+    let mut merged = old_meta.clone();
+    let (patch, _) = ousia::query::IndexMeta::diff(&old_meta, &post.index_meta());
+    merged.merge(patch);
+    assert_eq!(merged.meta(), post.index_meta().meta());
+}
+
+#[tokio::test]
+async fn test_partial_update_drops_stale_index_keys() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let adapter = SqliteAdapter::from_pool(pool.clone());
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(SqliteAdapter::from_pool(pool)));
+
+    let mut post = Post {
+        _meta: Meta::new_with_owner(uuid::Uuid::now_v7()),
+        title: "Draft title".to_string(),
+        ..Default::default()
+    };
+    engine.create_object(&post).await.unwrap();
+
+    // A key left behind by an older version of the type
+    let mut stored = adapter
+        .fetch_object_record(Post::TYPE, post.id())
+        .await
+        .unwrap()
+        .unwrap();
+    stored.index_meta["legacy"] = serde_json::json!("stale");
+    adapter.update_object(stored).await.unwrap();
+
+    // One changed key plus one removed is still the partial path
+    post.title = "Final title".to_string();
+    engine.update_object(&mut post).await.unwrap();
+
+    let stored = adapter
+        .fetch_object_record(Post::TYPE, post.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(stored.index_meta.get("legacy").is_none());
+    assert_eq!(stored.index_meta["title"], "Final title");
+}
+
+#[tokio::test]
+async fn test_upsert_edge() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let alice = uuid::Uuid::now_v7();
+    let bob = uuid::Uuid::now_v7();
+
+    let outcome = engine
+        .upsert_edge(&Follow {
+            _meta: EdgeMeta::new(alice, bob),
+            notification: false,
+        })
+        .await
+        .unwrap();
+    assert!(outcome.created);
+
+    let outcome = engine
+        .upsert_edge(&Follow {
+            _meta: EdgeMeta::new(alice, bob),
+            notification: true,
+        })
+        .await
+        .unwrap();
+    assert!(!outcome.created);
+
+    let stored = engine
+        .fetch_edge::<Follow>(alice, bob)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(stored.notification);
+    assert_eq!(engine.count_edges::<Follow>(alice, None).await.unwrap(), 1);
+
+    // Batch: one existing edge, two new, one repeated key (last one wins)
+    let carol = uuid::Uuid::now_v7();
+    let dave = uuid::Uuid::now_v7();
+    let batch = vec![
+        Follow {
+            _meta: EdgeMeta::new(alice, bob),
+            notification: false,
+        },
+        Follow {
+            _meta: EdgeMeta::new(alice, carol),
+            notification: false,
+        },
+        Follow {
+            _meta: EdgeMeta::new(alice, dave),
+            notification: false,
+        },
+        Follow {
+            _meta: EdgeMeta::new(alice, dave),
+            notification: true,
+        },
+    ];
+    let written = engine.upsert_edges_batch(&batch).await.unwrap();
+    assert_eq!(written, 3);
+    assert_eq!(engine.count_edges::<Follow>(alice, None).await.unwrap(), 3);
+
+    let bob_edge = engine
+        .fetch_edge::<Follow>(alice, bob)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!bob_edge.notification);
+    let dave_edge = engine
+        .fetch_edge::<Follow>(alice, dave)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(dave_edge.notification);
+}
+
+#[tokio::test]
+async fn test_query_by_label() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let owner = uuid::Uuid::now_v7();
+    for (title, env) in [
+        ("a", Some("prod")),
+        ("b", Some("prod")),
+        ("c", Some("staging")),
+        ("d", None),
+    ] {
+        let mut post = Post {
+            _meta: Meta::new_with_owner(owner),
+            title: title.to_string(),
+            ..Default::default()
+        };
+        if let Some(env) = env {
+            post._meta.set_label("env", env);
+        }
+        post._meta.set_label("team", "core");
+        engine.create_object(&post).await.unwrap();
+    }
+
+    let prod: Vec<Post> = engine
+        .query_objects(Query::new(owner).where_label("env", "prod"))
+        .await
+        .unwrap();
+    assert_eq!(prod.len(), 2);
+    assert!(prod.iter().all(|p| p.meta().label("env") == Some("prod")));
+
+    let labelled = engine
+        .count_objects::<Post>(Some(Query::new(owner).where_has_label("env")))
+        .await
+        .unwrap();
+    assert_eq!(labelled, 3);
+
+    let staging_core = engine
+        .count_objects::<Post>(Some(
+            Query::new(owner)
+                .where_label("env", "staging")
+                .where_label("team", "core"),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(staging_core, 1);
+
+    let missing = engine
+        .count_objects::<Post>(Some(Query::new(owner).where_has_label("region")))
+        .await
+        .unwrap();
+    assert_eq!(missing, 0);
+}
+
+#[tokio::test]
+async fn test_fetch_objects_as_map_and_ordered() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let owner = uuid::Uuid::now_v7();
+    let mut stored = Vec::new();
+    for i in 0..3 {
+        let post = Post {
+            _meta: Meta::new_with_owner(owner),
+            title: format!("Post {i}"),
+            ..Default::default()
+        };
+        engine.create_object(&post).await.unwrap();
+        stored.push(post.id());
+    }
+    let missing_a = uuid::Uuid::now_v7();
+    let missing_b = uuid::Uuid::now_v7();
+    let ids = vec![stored[2], missing_a, stored[0], missing_b, stored[1]];
+
+    let map = engine
+        .fetch_objects_as_map::<Post>(ids.clone())
+        .await
+        .unwrap();
+    assert_eq!(map.len(), 3);
+    assert!(stored.iter().all(|id| map.contains_key(id)));
+    assert!(!map.contains_key(&missing_a));
+
+    let ordered = engine.fetch_objects_ordered::<Post>(ids).await.unwrap();
+    assert_eq!(ordered.len(), 5);
+    let titles: Vec<Option<&str>> = ordered
+        .iter()
+        .map(|p| p.as_ref().map(|p| p.title.as_str()))
+        .collect();
+    assert_eq!(
+        titles,
+        vec![Some("Post 2"), None, Some("Post 0"), None, Some("Post 1")]
+    );
+}
+
+#[tokio::test]
+async fn test_pipeline_reads() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let owner = uuid::Uuid::now_v7();
+    let post = Post {
+        _meta: Meta::new_with_owner(owner),
+        title: "Pipelined".to_string(),
+        ..Default::default()
+    };
+    engine.create_object(&post).await.unwrap();
+    let follower = uuid::Uuid::now_v7();
+    engine
+        .create_edge(&Follow {
+            _meta: EdgeMeta::new(follower, owner),
+            notification: false,
+        })
+        .await
+        .unwrap();
+
+    let mut p = engine.pipeline();
+    let post_fut = p.fetch_object::<Post>(post.id());
+    let missing_fut = p.fetch_object::<Post>(uuid::Uuid::now_v7());
+    let count_fut = p.count_objects::<Post>(Some(Query::new(owner)));
+    let followers_fut = p.count_reverse_edges::<Follow>(owner, None);
+    assert_eq!(p.len(), 4);
+    let result = p.execute().await.unwrap();
+
+    assert_eq!(
+        post_fut.resolve(&result).unwrap().unwrap().title,
+        "Pipelined"
+    );
+    assert!(missing_fut.resolve(&result).unwrap().is_none());
+    assert_eq!(count_fut.resolve(&result).unwrap(), 1);
+    assert_eq!(followers_fut.resolve(&result).unwrap(), 1);
+    // A handle can only be resolved once
+    assert!(count_fut.resolve(&result).is_err());
+}
+
+#[tokio::test]
+async fn test_where_array_contains() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let owner = uuid::Uuid::now_v7();
+    for (title, tags) in [
+        ("rust", vec!["rust", "db"]),
+        ("go", vec!["go"]),
+        ("both", vec!["go", "rust"]),
+        ("none", vec![]),
+    ] {
+        let post = Post {
+            _meta: Meta::new_with_owner(owner),
+            title: title.to_string(),
+            tags: tags.into_iter().map(String::from).collect(),
+            ..Default::default()
+        };
+        engine.create_object(&post).await.unwrap();
+    }
+
+    let rust: Vec<Post> = engine
+        .query_objects(Query::new(owner).where_array_contains(&Post::FIELDS.tags, "rust"))
+        .await
+        .unwrap();
+    let mut titles: Vec<&str> = rust.iter().map(|p| p.title.as_str()).collect();
+    titles.sort();
+    assert_eq!(titles, vec!["both", "rust"]);
+
+    let none = engine
+        .count_objects::<Post>(Some(
+            Query::new(owner).where_array_contains(&Post::FIELDS.tags, "python"),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(none, 0);
+}
+
+#[tokio::test]
+async fn test_explain_analyze_query_not_supported() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let result = engine
+        .explain_analyze_query::<Post>(Query::new(uuid::Uuid::now_v7()))
+        .await;
+    assert_eq!(result, Err(Error::NotSupported));
+}
+
+#[tokio::test]
+async fn test_estimate_query_cost_uses_index() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let owner = uuid::Uuid::now_v7();
+    for i in 0..20 {
+        let post = Post {
+            _meta: Meta::new_with_owner(owner),
+            title: format!("Post {i}"),
+            ..Default::default()
+        };
+        engine.create_object(&post).await.unwrap();
+    }
+
+    // (type, owner) leads idx_objects_type_owner
+    let cost = engine
+        .estimate_query_cost::<Post>(Query::new(owner).with_limit(10))
+        .await
+        .unwrap();
+    assert!(cost.uses_index, "{cost:?}");
+    assert!(!cost.sequential_scan, "{cost:?}");
+
+    let cost = engine
+        .estimate_edge_query_cost::<Follow>(owner, EdgeQuery::default())
+        .await
+        .unwrap();
+    assert!(cost.uses_index, "{cost:?}");
+}
+
+#[tokio::test]
+async fn test_import_and_export_objects() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let owner = uuid::Uuid::now_v7();
+    let mut input = String::new();
+    for i in 0..1_000 {
+        input.push_str(&format!(
+            "{{\"username\":\"user{i}\",\"email\":\"user{i}@example.com\",\"display_name\":\"User {i}\",\"balance\":{{\"inner\":0}}}}\n"
+        ));
+        if i == 499 {
+            input.push_str("not json\n");
+        }
+    }
+
+    let result = engine
+        .import_objects::<User>(input.as_bytes(), owner, 100)
+        .await
+        .unwrap();
+    assert_eq!(result.imported, 1_000);
+    assert_eq!(result.skipped, 1);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].0, 501);
+    assert_eq!(
+        engine
+            .count_objects::<User>(Some(Query::new(owner)))
+            .await
+            .unwrap(),
+        1_000
+    );
+
+    // Same usernames again: every line hits the unique constraint
+    let again = engine
+        .import_objects::<User>(input.as_bytes(), owner, 100)
+        .await
+        .unwrap();
+    assert_eq!(again.imported, 0);
+    assert_eq!(again.skipped, 1_001);
+
+    let mut out: Vec<u8> = Vec::new();
+    let written = engine
+        .export_objects::<User, _>(Query::new(owner), &mut out)
+        .await
+        .unwrap();
+    assert_eq!(written, 1_000);
+    let exported = String::from_utf8(out).unwrap();
+    assert_eq!(exported.lines().count(), 1_000);
+    assert!(exported.contains("\"username\":\"user42\""));
+}
+
+#[tokio::test]
+async fn test_clone_object() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut alice = User {
+        username: "alice".to_string(),
+        email: "alice@example.com".to_string(),
+        display_name: "Alice".to_string(),
+        ..Default::default()
+    };
+    alice._meta.set_label("tier", "gold");
+    engine.create_object(&alice).await.unwrap();
+
+    // A straight copy collides on the unique username
+    let err = engine
+        .clone_object::<User>(alice.id(), None)
+        .await
+        .unwrap_err();
+    assert!(err.is_unique_constraint_violation());
+
+    let new_owner = uuid::Uuid::now_v7();
+    let copy: User = engine
+        .clone_object_with_overrides(
+            alice.id(),
+            serde_json::json!({ "username": "alice-copy" }),
+            Some(new_owner),
+        )
+        .await
+        .unwrap();
+    assert_ne!(copy.id(), alice.id());
+    assert_eq!(copy.username, "alice-copy");
+    assert_eq!(copy.email, "alice@example.com");
+    assert_eq!(copy.owner(), new_owner);
+    assert!(copy.meta().labels().is_empty());
+
+    let stored = engine
+        .fetch_object::<User>(copy.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.username, "alice-copy");
+    let original = engine
+        .fetch_object::<User>(alice.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(original.username, "alice");
+    assert_eq!(original.meta().label("tier"), Some("gold"));
+}
+
+#[tokio::test]
+async fn test_find_object_globally() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let system_user = User {
+        username: "root".to_string(),
+        email: "root@example.com".to_string(),
+        ..Default::default()
+    };
+    engine.create_object(&system_user).await.unwrap();
+    let owned_post = Post {
+        _meta: Meta::new_with_owner(uuid::Uuid::now_v7()),
+        title: "Owned".to_string(),
+        ..Default::default()
+    };
+    engine.create_object(&owned_post).await.unwrap();
+
+    // Off by default
+    assert_eq!(
+        engine
+            .find_object_globally::<Post>(&[filter!(&Post::FIELDS.title, "Owned")])
+            .await
+            .unwrap_err(),
+        Error::PermissionDenied
+    );
+
+    let engine = engine.with_global_queries(true);
+    let post = engine
+        .find_object_globally::<Post>(&[filter!(&Post::FIELDS.title, "Owned")])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(post.id(), owned_post.id());
+    let user = engine
+        .find_object_globally::<User>(&[filter!(&User::FIELDS.username, "root")])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(user.id(), system_user.id());
+
+    // fetch_object never needed the owner
+    assert!(
+        engine
+            .fetch_object::<Post>(owned_post.id())
+            .await
+            .unwrap()
+            .is_some()
+    );
+
+    let raw = engine
+        .fetch_object_of_any_type(owned_post.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(raw["__type"], "Post");
+    assert_eq!(raw["title"], "Owned");
+    let raw = engine
+        .fetch_object_of_any_type(system_user.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(raw["__type"], "User");
+    assert!(
+        engine
+            .fetch_object_of_any_type(uuid::Uuid::now_v7())
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_count_edges_batch() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut users = Vec::new();
+    for name in ["alice", "michael", "bob", "carol"] {
+        let mut user = User::default();
+        user.username = name.into();
+        user.email = format!("{name}@example.com");
+        engine.create_object(&user).await.unwrap();
+        users.push(user);
+    }
+    let (alice, michael, bob, carol) = (&users[0], &users[1], &users[2], &users[3]);
+
+    for (from, to) in [(alice, bob), (michael, bob), (alice, michael)] {
+        engine
+            .create_edge(&Follow {
+                _meta: EdgeMeta::new(from.id(), to.id()),
+                notification: false,
+            })
+            .await
+            .unwrap();
+    }
+
+    let ids: Vec<_> = users.iter().map(|u| u.id()).collect();
+
+    let following = engine.count_edges_batch::<Follow>(&ids).await.unwrap();
+    assert_eq!(following.len(), 2);
+    assert_eq!(following[&alice.id()], 2);
+    assert_eq!(following[&michael.id()], 1);
+    assert!(!following.contains_key(&bob.id()));
+    assert!(!following.contains_key(&carol.id()));
+
+    let followers = engine
+        .count_reverse_edges_batch::<Follow>(&ids)
+        .await
+        .unwrap();
+    assert_eq!(followers.len(), 2);
+    assert_eq!(followers[&bob.id()], 2);
+    assert_eq!(followers[&michael.id()], 1);
+    assert_eq!(followers.get(&alice.id()).copied().unwrap_or(0), 0);
+    assert!(!followers.contains_key(&carol.id()));
+
+    assert!(
+        engine
+            .count_edges_batch::<Follow>(&[])
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_query_null_fields() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+    let owner = uuid::Uuid::now_v7();
+
+    for i in 0..5 {
+        let post = Post {
+            _meta: Meta::new_with_owner(owner),
+            title: format!("Post {i}"),
+            published_at: (i % 2 == 0).then(chrono::Utc::now),
+            ..Default::default()
+        };
+        engine.create_object(&post).await.unwrap();
+    }
+
+    let drafts: Vec<Post> = engine
+        .query_objects(Query::new(owner).where_null(&Post::FIELDS.published_at))
+        .await
+        .unwrap();
+    assert_eq!(drafts.len(), 2);
+    assert!(drafts.iter().all(|p| p.published_at.is_none()));
+
+    let published: Vec<Post> = engine
+        .query_objects(Query::new(owner).where_not_null(&Post::FIELDS.published_at))
+        .await
+        .unwrap();
+    assert_eq!(published.len(), 3);
+    assert!(published.iter().all(|p| p.published_at.is_some()));
+
+    let count = engine
+        .count_objects::<Post>(Some(
+            Query::new(owner)
+                .where_null(&Post::FIELDS.published_at)
+                .where_eq(&Post::FIELDS.title, "Post 1"),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+
+    // Publishing a draft moves it across
+    let mut post = drafts.into_iter().next().unwrap();
+    post.published_at = Some(chrono::Utc::now());
+    engine.update_object(&mut post).await.unwrap();
+    let count = engine
+        .count_objects::<Post>(Some(
+            Query::new(owner).where_not_null(&Post::FIELDS.published_at),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(count, 4);
+
+    // None is indexed as an explicit null
+    let meta = Post::default().index_meta();
+    assert_eq!(
+        meta.meta().get("published_at"),
+        Some(&ousia::query::IndexValue::Null)
+    );
+}
+
+#[tokio::test]
+async fn test_object_age_distribution() {
+    use chrono::TimeZone;
+    use ousia::Granularity;
+
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+    let owner = uuid::Uuid::now_v7();
+
+    // Monday 4 March 2024 through Wednesday, ten posts a day
+    let day = |d: u32, h: u32| chrono::Utc.with_ymd_and_hms(2024, 3, d, h, 0, 0).unwrap();
+    for i in 0..30 {
+        let mut meta = Meta::new_with_owner(owner);
+        meta.created_at = day(4 + i / 10, i % 10 + 8);
+        let post = Post {
+            _meta: meta,
+            title: format!("Post {i}"),
+            ..Default::default()
+        };
+        engine.create_object(&post).await.unwrap();
+    }
+    // Another owner, another month
+    let mut meta = Meta::new_with_owner(uuid::Uuid::now_v7());
+    meta.created_at = chrono::Utc.with_ymd_and_hms(2024, 4, 2, 9, 0, 0).unwrap();
+    engine
+        .create_object(&Post {
+            _meta: meta,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let daily = engine
+        .object_age_distribution::<Post>(Granularity::Day, Some(owner))
+        .await
+        .unwrap();
+    assert_eq!(
+        daily,
+        vec![(day(4, 0), 10), (day(5, 0), 10), (day(6, 0), 10)]
+    );
+
+    let weekly = engine
+        .object_age_distribution::<Post>(Granularity::Week, Some(owner))
+        .await
+        .unwrap();
+    assert_eq!(weekly, vec![(day(4, 0), 30)]);
+
+    let monthly = engine
+        .object_age_distribution::<Post>(Granularity::Month, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        monthly,
+        vec![
+            (day(1, 0), 30),
+            (
+                chrono::Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap(),
+                1
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_swap_owners() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+
+    let engine = Engine::new(Box::new(adapter));
+
+    let alice = uuid::Uuid::now_v7();
+    let bob = uuid::Uuid::now_v7();
+
+    let mut post_a = Post::default();
+    post_a.set_owner(alice);
+    post_a.title = "A".to_string();
+    engine.create_object(&post_a).await.unwrap();
+
+    let mut post_b = Post::default();
+    post_b.set_owner(bob);
+    post_b.title = "B".to_string();
+    engine.create_object(&post_b).await.unwrap();
+
+    let (a, b): (Post, Post) = engine
+        .swap_owners(post_a.id(), alice, post_b.id(), bob)
+        .await
+        .unwrap();
+    assert_eq!(a.owner(), bob);
+    assert_eq!(b.owner(), alice);
+
+    // Stale owners: a now belongs to bob, so the whole swap is rejected
+    let result: Result<(Post, Post), Error> = engine
+        .swap_owners(post_b.id(), alice, post_a.id(), alice)
+        .await;
+    assert!(matches!(result, Err(Error::NotFound)));
+
+    let a: Post = engine.fetch_object(post_a.id()).await.unwrap().unwrap();
+    let b: Post = engine.fetch_object(post_b.id()).await.unwrap().unwrap();
+    assert_eq!(a.owner(), bob);
+    assert_eq!(b.owner(), alice);
+
+    let result: Result<(Post, Post), Error> =
+        engine.swap_owners(post_a.id(), bob, post_a.id(), bob).await;
+    assert!(matches!(result, Err(Error::InvalidOperation(_))));
+}
+
+#[tokio::test]
+async fn test_find_or_create_object() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+
+    let engine = Engine::new(Box::new(adapter));
+
+    let filters = [filter!(&User::FIELDS.username, "dana")];
+    let factory = || {
+        let mut user = User::default();
+        user.username = "dana".to_string();
+        user
+    };
+
+    let (a, b) = tokio::join!(
+        engine.find_or_create_object::<User>(&filters, factory),
+        engine.find_or_create_object::<User>(&filters, factory),
+    );
+    let (a, a_created) = a.unwrap();
+    let (b, b_created) = b.unwrap();
+
+    assert_eq!(a.id(), b.id());
+    assert!(a_created ^ b_created);
+    assert_eq!(
+        engine
+            .count_objects::<User>(Some(
+                Query::default().where_eq(&User::FIELDS.username, "dana")
+            ))
+            .await
+            .unwrap(),
+        1
+    );
+
+    let (found, created) = engine
+        .find_or_create_object::<User>(&filters, factory)
+        .await
+        .unwrap();
+    assert_eq!(found.id(), a.id());
+    assert!(!created);
+}
+
+#[tokio::test]
+async fn test_preload_children_and_edges() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut alice = User::default();
+    alice.username = "alice".into();
+    engine.create_object(&alice).await.unwrap();
+
+    let mut bob = User::default();
+    bob.username = "bob".into();
+    engine.create_object(&bob).await.unwrap();
+
+    for title in ["First", "Second"] {
+        let mut post = Post::default();
+        post.set_owner(alice.id());
+        post.title = title.into();
+        engine.create_object(&post).await.unwrap();
+    }
+
+    engine
+        .create_edge(&Follow {
+            _meta: EdgeMeta::new(alice.id(), bob.id()),
+            notification: true,
+        })
+        .await
+        .unwrap();
+
+    let batch: ousia::BatchResult<User, Post, Follow> = engine
+        .preload_objects::<User>(Query::default())
+        .preload::<Post>()
+        .with_edges::<Follow>()
+        .execute()
+        .await
+        .unwrap();
+
+    assert_eq!(batch.parents.len(), 2);
+    assert_eq!(batch.children[&alice.id()].len(), 2);
+    assert!(!batch.children.contains_key(&bob.id()));
+    assert_eq!(batch.edges[&alice.id()].len(), 1);
+    assert_eq!(batch.edges[&alice.id()][0].to(), bob.id());
+    assert!(!batch.edges.contains_key(&bob.id()));
+}
+
+#[tokio::test]
+async fn test_fts_search() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter)).with_global_queries(true);
+
+    let owner = uuid::Uuid::now_v7();
+    let mut posts = Vec::new();
+    for (title, content) in [
+        (
+            "Rust deep dive",
+            "rust ownership, rust lifetimes and rust traits",
+        ),
+        (
+            "Weekly notes",
+            "mostly gardening, with a short aside on rust",
+        ),
+        ("Garden", "tomatoes and basil"),
+    ] {
+        let mut post = Post::default();
+        post.set_owner(owner);
+        post.title = title.into();
+        post.content = content.into();
+        engine.create_object(&post).await.unwrap();
+        posts.push(post);
+    }
+
+    let found: Vec<Post> = engine.fts_search("rust", 10).await.unwrap();
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].id(), posts[0].id());
+    assert_eq!(found[1].id(), posts[1].id());
+
+    // Triggers keep the index in step with updates and deletes
+    let mut garden = posts.pop().unwrap();
+    garden.content = "basil grows well next to rust-coloured tomatoes".into();
+    engine.update_object(&mut garden).await.unwrap();
+    engine
+        .delete_object::<Post>(posts[0].id(), owner)
+        .await
+        .unwrap();
+
+    let found: Vec<Post> = engine.fts_search("rust", 10).await.unwrap();
+    let ids: Vec<_> = found.iter().map(|p| p.id()).collect();
+    assert_eq!(ids.len(), 2);
+    assert!(ids.contains(&posts[1].id()));
+    assert!(ids.contains(&garden.id()));
+
+    let found: Vec<Post> = engine.fts_search("rust", 1).await.unwrap();
+    assert_eq!(found.len(), 1);
+}
+
+#[tokio::test]
+async fn test_execute_raw() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+    let guard = engine.raw_query_guard();
+
+    let plan = engine
+        .execute_raw(&guard, "EXPLAIN SELECT 1", vec![])
+        .await
+        .unwrap();
+    assert!(!plan.is_empty());
+
+    let rows = engine
+        .execute_raw(
+            &guard,
+            "SELECT ? AS n, ? AS label, ? AS missing",
+            vec![
+                serde_json::json!(42),
+                serde_json::json!("hi"),
+                serde_json::Value::Null,
+            ],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        rows,
+        vec![serde_json::json!({ "n": 42, "label": "hi", "missing": null })]
+    );
+}
+
+#[tokio::test]
+async fn test_type_registry() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+    let owner = uuid::Uuid::now_v7();
+
+    for i in 0..3 {
+        let mut post = Post::default();
+        post.set_owner(owner);
+        post.title = format!("Post {i}");
+        engine.create_object(&post).await.unwrap();
+    }
+    for name in ["ana", "ben"] {
+        let mut user = User::default();
+        user.username = name.to_string();
+        engine.create_object(&user).await.unwrap();
+    }
+    let envelope = Envelope {
+        _meta: Meta::new_with_owner(owner),
+        label: "only".to_string(),
+        payload: 1i64,
+    };
+    engine.create_object(&envelope).await.unwrap();
+
+    let stats = engine.type_registry().await.unwrap();
+    let counts: Vec<(&str, u64)> = stats
+        .iter()
+        .map(|s| (s.type_name.as_str(), s.count))
+        .collect();
+    assert_eq!(counts, vec![("Envelope", 1), ("Post", 3), ("User", 2)]);
+    let envelope_stats = &stats[0];
+    assert_eq!(
+        envelope_stats.latest_created_at.timestamp_millis(),
+        envelope.created_at().timestamp_millis()
+    );
+
+    let registered = Engine::registered_types();
+    for type_name in ["Envelope", "Post", "User"] {
+        assert!(registered.contains(&type_name));
+    }
+}
+
+#[tokio::test]
+async fn test_paginate_edges() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let from = uuid::Uuid::now_v7();
+    let mut targets = Vec::new();
+    for _ in 0..15 {
+        let to = uuid::Uuid::now_v7();
+        engine
+            .create_edge(&Follow {
+                _meta: EdgeMeta::new(from, to),
+                notification: false,
+            })
+            .await
+            .unwrap();
+        targets.push(to);
+    }
+
+    let mut seen = Vec::new();
+    let mut cursor = None;
+    for page_no in 0..3 {
+        let page = engine
+            .paginate_edges::<Follow>(from, 5, cursor)
+            .await
+            .unwrap();
+        assert_eq!(page.items.len(), 5);
+        assert_eq!(page.has_next, page_no < 2);
+        seen.extend(page.items.iter().map(|e| e.to()));
+
+        // Cursor survives a trip through its string form
+        cursor = page
+            .next_cursor
+            .map(|c| ousia::EdgeCursor::from_base64(&c.to_base64()).unwrap());
+    }
+    assert!(cursor.is_none());
+
+    targets.reverse();
+    assert_eq!(seen, targets);
+
+    // Reverse: 15 followers of one target
+    let to = uuid::Uuid::now_v7();
+    for _ in 0..15 {
+        engine
+            .create_edge(&Follow {
+                _meta: EdgeMeta::new(uuid::Uuid::now_v7(), to),
+                notification: false,
+            })
+            .await
+            .unwrap();
+    }
+    let mut count = 0;
+    let mut cursor = None;
+    loop {
+        let page = engine
+            .paginate_reverse_edges::<Follow>(to, 5, cursor)
+            .await
+            .unwrap();
+        count += page.items.len();
+        if !page.has_next {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    assert_eq!(count, 15);
+
+    assert!(matches!(
+        ousia::EdgeCursor::from_base64("not a cursor"),
+        Err(Error::InvalidQuery(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_delete_edge_cascade() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut user = User::default();
+    user.username = "cascade".to_string();
+    engine.create_object(&user).await.unwrap();
+    let node = user.id();
+
+    // 5 outgoing: 4 follows + 1 pin
+    for _ in 0..4 {
+        engine
+            .create_edge(&Follow {
+                _meta: EdgeMeta::new(node, uuid::Uuid::now_v7()),
+                notification: true,
+            })
+            .await
+            .unwrap();
+    }
+    engine
+        .create_edge(&Pin {
+            _meta: EdgeMeta::new(node, uuid::Uuid::now_v7()),
+            note: "mine".to_string(),
+        })
+        .await
+        .unwrap();
+
+    // 3 incoming: 2 follows + 1 pin
+    for _ in 0..2 {
+        engine
+            .create_edge(&Follow {
+                _meta: EdgeMeta::new(uuid::Uuid::now_v7(), node),
+                notification: false,
+            })
+            .await
+            .unwrap();
+    }
+    engine
+        .create_edge(&Pin {
+            _meta: EdgeMeta::new(uuid::Uuid::now_v7(), node),
+            note: "theirs".to_string(),
+        })
+        .await
+        .unwrap();
+
+    // Unrelated edge survives
+    let (a, b) = (uuid::Uuid::now_v7(), uuid::Uuid::now_v7());
+    engine
+        .create_edge(&Follow {
+            _meta: EdgeMeta::new(a, b),
+            notification: false,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(engine.delete_edge_cascade(node).await.unwrap(), 8);
+
+    let follows: Vec<Follow> = engine
+        .query_edges(node, EdgeQuery::default())
+        .await
+        .unwrap();
+    let followers: Vec<Follow> = engine
+        .query_reverse_edges(node, EdgeQuery::default())
+        .await
+        .unwrap();
+    let pins: Vec<Pin> = engine
+        .query_edges(node, EdgeQuery::default())
+        .await
+        .unwrap();
+    let pinned_by: Vec<Pin> = engine
+        .query_reverse_edges(node, EdgeQuery::default())
+        .await
+        .unwrap();
+    assert!(follows.is_empty() && followers.is_empty());
+    assert!(pins.is_empty() && pinned_by.is_empty());
+    assert!(engine.fetch_edge::<Follow>(a, b).await.unwrap().is_some());
+
+    // Object + edges in one call
+    engine
+        .create_edge(&Follow {
+            _meta: EdgeMeta::new(b, node),
+            notification: false,
+        })
+        .await
+        .unwrap();
+    let deleted = engine
+        .delete_object_cascade::<User>(node, user.owner())
+        .await
+        .unwrap();
+    assert!(deleted.is_some());
+    assert!(
+        engine
+            .fetch_edge::<Follow>(b, node)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_object_with_edges() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut user = User::default();
+    user.username = "hub".to_string();
+    user.email = "hub@example.com".to_string();
+    engine.create_object(&user).await.unwrap();
+
+    for _ in 0..3 {
+        engine
+            .create_edge(&Follow {
+                _meta: EdgeMeta::new(user.id(), uuid::Uuid::now_v7()),
+                notification: true,
+            })
+            .await
+            .unwrap();
+    }
+    let follower = uuid::Uuid::now_v7();
+    engine
+        .create_edge(&Follow {
+            _meta: EdgeMeta::new(follower, user.id()),
+            notification: false,
+        })
+        .await
+        .unwrap();
+
+    let (fetched, follows) = engine
+        .fetch_object_with_edges::<User, Follow>(user.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fetched.username, "hub");
+    assert_eq!(fetched.email, "hub@example.com");
+    assert_eq!(follows.len(), 3);
+
+    let (_, followers) = engine
+        .fetch_object_with_reverse_edges::<User, Follow>(user.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(followers.len(), 1);
+    assert_eq!(followers[0].from(), follower);
+
+    let map = engine
+        .fetch_object_with_edge_map::<User>(user.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(map.object().username, "hub");
+    assert_eq!(map.edges::<Follow>().await.unwrap().len(), 3);
+    assert!(map.edges::<Pin>().await.unwrap().is_empty());
+    assert_eq!(map.reverse_edges::<Follow>().await.unwrap().len(), 1);
+
+    assert!(
+        engine
+            .fetch_object_with_edges::<User, Follow>(uuid::Uuid::now_v7())
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_group_by_field() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+    let owner = uuid::Uuid::now_v7();
+
+    // 4 draft, 4 published, 2 archived
+    for i in 0..10 {
+        let mut post = Post::default();
+        post.set_owner(owner);
+        post.status = match i {
+            0..4 => PostStatus::Draft,
+            4..8 => PostStatus::Published,
+            _ => PostStatus::Archived,
+        };
+        engine.create_object(&post).await.unwrap();
+    }
+    let mut other = Post::default();
+    other.set_owner(uuid::Uuid::now_v7());
+    engine.create_object(&other).await.unwrap();
+
+    let groups = engine
+        .group_by_field::<Post>(&Post::FIELDS.status, Some(Query::new(owner)))
+        .await
+        .unwrap();
+    let counts: Vec<(&str, u64)> = groups
+        .iter()
+        .map(|g| (g.value.as_string().unwrap(), g.count))
+        .collect();
+    assert_eq!(
+        counts,
+        vec![("draft", 4), ("published", 4), ("archived", 2)]
+    );
+
+    // Every owner
+    let groups = engine
+        .group_by_field::<Post>(&Post::FIELDS.status, None)
+        .await
+        .unwrap();
+    assert_eq!(groups[0].value.as_string(), Some("draft"));
+    assert_eq!(groups[0].count, 5);
+
+    // Sort-only fields can't be grouped
+    assert!(matches!(
+        engine
+            .group_by_field::<Post>(&Post::FIELDS.views, None)
+            .await,
+        Err(Error::InvalidQuery(_))
+    ));
+
+    let per_owner = engine.group_count_by_owner::<Post>(None).await.unwrap();
+    assert_eq!(per_owner.len(), 2);
+    assert_eq!(per_owner[&owner], 10);
+    assert_eq!(per_owner[&other.owner()], 1);
+}
+
+#[tokio::test]
+async fn test_audit_and_reindex_index_meta() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let owner = uuid::Uuid::now_v7();
+
+    // Rows written before `status` was indexed, one with a since-changed `title` type
+    for i in 0..4 {
+        let mut post = Post::default();
+        post.set_owner(owner);
+        post.title = format!("Post {i}");
+        post.status = PostStatus::Published;
+        let mut record = ObjectRecord::from_object(&post);
+        let index_meta = record.index_meta.as_object_mut().unwrap();
+        index_meta.remove("status");
+        if i == 0 {
+            index_meta.insert("title".to_string(), serde_json::json!(0));
+            index_meta.insert("legacy".to_string(), serde_json::json!(true));
+        }
+        adapter.insert_object(record).await.unwrap();
+    }
+    let engine = Engine::new(Box::new(adapter));
+
+    let report = engine.audit_index_meta::<Post>(10).await.unwrap();
+    assert_eq!(report.sampled, 4);
+    assert_eq!(report.stale, 4);
+    assert_eq!(report.corruption_rate(), 1.0);
+    let worst = report
+        .diffs
+        .iter()
+        .map(|(_, diff)| diff)
+        .find(|diff| !diff.extra.is_empty())
+        .unwrap();
+    assert_eq!(worst.missing, vec!["status".to_string()]);
+    assert_eq!(worst.extra, vec!["legacy".to_string()]);
+    assert_eq!(worst.type_mismatch.len(), 1);
+    assert_eq!(worst.type_mismatch[0].0, "title");
+
+    let published = Query::new(owner).where_eq(&Post::FIELDS.status, PostStatus::Published);
+    let found: Vec<Post> = engine.query_objects(published.clone()).await.unwrap();
+    assert!(found.is_empty());
+
+    assert_eq!(engine.reindex_objects::<Post>(None).await.unwrap(), 4);
+
+    let found: Vec<Post> = engine.query_objects(published).await.unwrap();
+    assert_eq!(found.len(), 4);
+    let report = engine.audit_index_meta::<Post>(10).await.unwrap();
+    assert_eq!(report.stale, 0);
+    assert_eq!(report.corruption_rate(), 0.0);
+}
+
+#[tokio::test]
+async fn test_query_edges_with_targets_map() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut users = Vec::new();
+    for i in 0..7 {
+        let user = User {
+            _meta: Meta::default(),
+            username: format!("user{i}"),
+            email: format!("user{i}@example.com"),
+            display_name: format!("User {i}"),
+            balance: Wallet::default(),
+        };
+        engine.create_object(&user).await.unwrap();
+        users.push(user);
+    }
+
+    // Users 0..3 each follow the four users after them
+    let followers: Vec<uuid::Uuid> = users[..3].iter().map(|u| u.id()).collect();
+    for (i, from) in followers.iter().enumerate() {
+        for target in &users[i + 1..i + 5] {
+            engine
+                .create_edge(&Follow {
+                    _meta: EdgeMeta::new(*from, target.id()),
+                    notification: false,
+                })
+                .await
+                .unwrap();
+        }
+    }
+
+    let single: Vec<(Follow, User)> = engine
+        .query_edges_with_targets(followers[0], &[], EdgeQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(single.len(), 4);
+    for (edge, user) in &single {
+        assert_eq!(edge.to(), user.id());
+    }
+
+    let map = engine
+        .query_edges_with_targets_map::<Follow, User>(&followers, EdgeQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(map.len(), 3);
+    for (i, from) in followers.iter().enumerate() {
+        let entries = &map[from];
+        assert_eq!(entries.len(), 4);
+        for (edge, user) in entries {
+            assert_eq!(edge.from(), *from);
+            assert_eq!(edge.to(), user.id());
+            let expected = users.iter().find(|u| u.id() == user.id()).unwrap();
+            assert_eq!(user.username, expected.username);
+            assert!(users[i + 1..i + 5].iter().any(|u| u.id() == user.id()));
+        }
+    }
+
+    // Sources without edges still get an (empty) entry
+    let lonely = users[6].id();
+    let map = engine
+        .query_edges_with_targets_map::<Follow, User>(&[lonely], EdgeQuery::default())
+        .await
+        .unwrap();
+    assert!(map[&lonely].is_empty());
+}
+
+#[tokio::test]
+async fn test_create_edge_if_absent() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let a = uuid::Uuid::now_v7();
+    let b = uuid::Uuid::now_v7();
+
+    let created = engine
+        .create_edge_if_absent(&Follow {
+            _meta: EdgeMeta::new(a, b),
+            notification: true,
+        })
+        .await
+        .unwrap();
+    assert!(created);
+
+    let created = engine
+        .create_edge_if_absent(&Follow {
+            _meta: EdgeMeta::new(a, b),
+            notification: false,
+        })
+        .await
+        .unwrap();
+    assert!(!created);
+
+    // The first write's data is preserved
+    let stored = engine.fetch_edge::<Follow>(a, b).await.unwrap().unwrap();
+    assert!(stored.notification);
+
+    // ensure_edge returns the existing edge without calling the factory
+    let ensured = engine
+        .ensure_edge::<Follow>(a, b, || panic!("edge already exists"))
+        .await
+        .unwrap();
+    assert!(ensured.notification);
+
+    let c = uuid::Uuid::now_v7();
+    let ensured = engine
+        .ensure_edge(a, c, || Follow {
+            _meta: EdgeMeta::new(a, c),
+            notification: false,
+        })
+        .await
+        .unwrap();
+    assert!(!ensured.notification);
+    assert!(engine.fetch_edge::<Follow>(a, c).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_object_timeline() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut doc = Document::default();
+    doc.title = "draft 0".to_string();
+    engine.create_object(&doc).await.unwrap();
+
+    for i in 1..=5 {
+        doc.title = format!("draft {i}");
+        engine.update_object(&mut doc).await.unwrap();
+    }
+
+    let timeline = engine.object_timeline::<Document>(doc.id()).await.unwrap();
+    assert_eq!(timeline.len(), 6);
+    for (i, snapshot) in timeline.iter().enumerate() {
+        assert_eq!(snapshot.version, i as u64 + 1);
+        assert_eq!(snapshot.data.title, format!("draft {i}"));
+        assert_eq!(snapshot.data.id(), doc.id());
+    }
+    assert!(
+        timeline
+            .windows(2)
+            .all(|w| w[0].updated_at <= w[1].updated_at)
+    );
+
+    let v3: Document = engine
+        .fetch_object_at_version(doc.id(), 3)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(v3.title, "draft 2");
+    let latest: Document = engine
+        .fetch_object_at_version(doc.id(), 6)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest.title, "draft 5");
+    assert!(
+        engine
+            .fetch_object_at_version::<Document>(doc.id(), 7)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // Types without `versioned` keep no history
+    let mut post = Post::default();
+    engine.create_object(&post).await.unwrap();
+    post.title = "edited".to_string();
+    engine.update_object(&mut post).await.unwrap();
+    let timeline = engine.object_timeline::<Post>(post.id()).await.unwrap();
+    assert_eq!(timeline.len(), 1);
+    assert_eq!(timeline[0].data.title, "edited");
+}
+
+#[tokio::test]
+async fn test_object_history_written_with_update() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let adapter = SqliteAdapter::from_pool(pool.clone());
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(SqliteAdapter::from_pool(pool.clone())));
+
+    let mut doc = Document::default();
+    doc.title = "draft 0".to_string();
+    engine.create_object(&doc).await.unwrap();
+    doc.title = "draft 1".to_string();
+    engine.update_object(&mut doc).await.unwrap();
+
+    // A version can only be recorded once
+    let stored = adapter
+        .fetch_object(Document::TYPE, doc.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(adapter.insert_history_snapshot(stored, 1).await.is_err());
+
+    // Without a place for the snapshot, the update doesn't happen either
+    sqlx::query("DROP TABLE object_history")
+        .execute(&pool)
+        .await
+        .unwrap();
+    doc.title = "draft 2".to_string();
+    assert!(matches!(
+        engine.update_object(&mut doc).await,
+        Err(Error::Storage(_))
+    ));
+    let stored: Document = engine.fetch_object(doc.id()).await.unwrap().unwrap();
+    assert_eq!(stored.title, "draft 1");
+}
+
+#[tokio::test]
+async fn test_query_by_uuid_field() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    use ousia::{OusiaDefault, OusiaObject};
+    #[derive(OusiaObject, OusiaDefault, Debug)]
+    #[ousia(type_name = "AuthoredPost", index = "author_id:search")]
+    pub struct AuthoredPost {
+        _meta: Meta,
+
+        pub title: String,
+        pub author_id: uuid::Uuid,
+    }
+
+    let alice = uuid::Uuid::now_v7();
+    let bob = uuid::Uuid::now_v7();
+    for (i, author) in [alice, bob, alice, alice, bob].into_iter().enumerate() {
+        let mut post = AuthoredPost::default();
+        post.title = format!("post {i}");
+        post.author_id = author;
+        engine.create_object(&post).await.unwrap();
+    }
+
+    let by_alice: Vec<AuthoredPost> = engine
+        .query_objects(Query::default().where_eq(&AuthoredPost::FIELDS.author_id, alice))
+        .await
+        .unwrap();
+    assert_eq!(by_alice.len(), 3);
+    assert!(by_alice.iter().all(|p| p.author_id == alice));
+
+    let not_alice: Vec<AuthoredPost> = engine
+        .query_objects(Query::default().where_ne(&AuthoredPost::FIELDS.author_id, alice))
+        .await
+        .unwrap();
+    assert_eq!(not_alice.len(), 2);
+    assert!(not_alice.iter().all(|p| p.author_id == bob));
+
+    let nobody: Vec<AuthoredPost> = engine
+        .query_objects(
+            Query::default().where_eq(&AuthoredPost::FIELDS.author_id, uuid::Uuid::now_v7()),
+        )
+        .await
+        .unwrap();
+    assert!(nobody.is_empty());
+}
+
+#[tokio::test]
+async fn test_edge_context_comparisons() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let root = uuid::Uuid::now_v7();
+    for (i, name) in ["alice", "albert", "bob", "carol", "dave"]
+        .iter()
+        .enumerate()
+    {
+        let user = User {
+            _meta: Meta::default(),
+            username: name.to_string(),
+            email: format!("{name}@example.com"),
+            display_name: name.to_uppercase(),
+            balance: Wallet {
+                inner: (i as i64 + 1) * 10,
+            },
+        };
+        engine.create_object(&user).await.unwrap();
+        engine
+            .create_edge(&Follow {
+                _meta: EdgeMeta::new(root, user.id()),
+                notification: i % 2 == 0,
+            })
+            .await
+            .unwrap();
+    }
+
+    let ctx = || {
+        engine
+            .preload_object::<User>(root)
+            .then_edges::<Follow, User>()
+    };
+
+    assert_eq!(ctx().collect().await.unwrap().len(), 5);
+    let cases = [
+        (ctx().where_eq(&User::FIELDS.balance, 30_i64), 1),
+        (ctx().where_ne(&User::FIELDS.balance, 30_i64), 4),
+        (ctx().where_gt(&User::FIELDS.balance, 30_i64), 2),
+        (ctx().where_gte(&User::FIELDS.balance, 30_i64), 3),
+        (ctx().where_lt(&User::FIELDS.balance, 30_i64), 2),
+        (ctx().where_lte(&User::FIELDS.balance, 30_i64), 3),
+        (ctx().where_begins_with(&User::FIELDS.username, "al"), 2),
+        (ctx().where_contains(&User::FIELDS.username, "o"), 2),
+        (ctx().edge_eq(&Follow::FIELDS.notification, true), 3),
+    ];
+    for (i, (query, expected)) in cases.into_iter().enumerate() {
+        assert_eq!(query.collect().await.unwrap().len(), expected, "case {i}");
+    }
+
+    let pairs: Vec<(Follow, User)> = ctx()
+        .where_gt(&User::FIELDS.balance, 30_i64)
+        .collect_with_edges()
+        .await
+        .unwrap();
+    assert_eq!(pairs.len(), 2);
+    for (edge, user) in &pairs {
+        assert_eq!(edge.from(), root);
+        assert_eq!(edge.to(), user.id());
+        assert!(user.balance.inner > 30);
+    }
+
+    let edges: Vec<Follow> = ctx()
+        .edge_eq(&Follow::FIELDS.notification, false)
+        .collect_edges()
+        .await
+        .unwrap();
+    assert_eq!(edges.len(), 2);
+
+    // Array fields: any-of vs all-of
+    for tags in [vec!["rust", "db"], vec!["rust"], vec!["db", "go"]] {
+        let post = Post {
+            _meta: Meta::default(),
+            title: tags.join("+"),
+            content: String::new(),
+            status: PostStatus::Published,
+            published_at: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            views: 0,
+        };
+        engine.create_object(&post).await.unwrap();
+        engine
+            .create_edge(&Follow {
+                _meta: EdgeMeta::new(root, post.id()),
+                notification: false,
+            })
+            .await
+            .unwrap();
+    }
+    let posts = || {
+        engine
+            .preload_object::<Post>(root)
+            .then_edges::<Follow, Post>()
+    };
+
+    let any_rust: Vec<Post> = posts()
+        .where_contains(&Post::FIELDS.tags, vec!["rust"])
+        .collect()
+        .await
+        .unwrap();
+    assert_eq!(any_rust.len(), 2);
+
+    let rust_and_db: Vec<Post> = posts()
+        .where_contains_all(&Post::FIELDS.tags, vec!["rust", "db"])
+        .collect()
+        .await
+        .unwrap();
+    assert_eq!(rust_and_db.len(), 1);
+    assert_eq!(rust_and_db[0].title, "rust+db");
+}
+
+#[tokio::test]
+async fn test_namespaced_engine() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let acme = engine.with_namespace("acme");
+    let globex = engine.with_namespace("globex");
+    assert_eq!(acme.namespace(), "acme");
+
+    let mut acme_post = Post {
+        _meta: Meta::default(),
+        title: "acme".to_string(),
+        content: String::new(),
+        status: PostStatus::Published,
+        published_at: None,
+        tags: vec![],
+        views: 0,
+    };
+    acme.create_object(&acme_post).await.unwrap();
+    let globex_post = Post {
+        _meta: Meta::default(),
+        title: "globex".to_string(),
+        content: String::new(),
+        status: PostStatus::Draft,
+        published_at: None,
+        tags: vec![],
+        views: 0,
+    };
+    globex.create_object(&globex_post).await.unwrap();
+    engine
+        .create_object(&Post {
+            _meta: Meta::default(),
+            title: "shared".to_string(),
+            content: String::new(),
+            status: PostStatus::Draft,
+            published_at: None,
+            tags: vec![],
+            views: 0,
+        })
+        .await
+        .unwrap();
+
+    // Each namespace only sees its own objects
+    let fetched: Post = acme.fetch_object(acme_post.id()).await.unwrap().unwrap();
+    assert_eq!(fetched.meta().namespace(), "acme");
+    assert!(
+        globex
+            .fetch_object::<Post>(acme_post.id())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let posts: Vec<Post> = globex.query_objects(Query::wide()).await.unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].title, "globex");
+    assert_eq!(acme.count_objects::<Post>(None).await.unwrap(), 1);
+    let fetched: Vec<Post> = acme
+        .fetch_objects(vec![acme_post.id(), globex_post.id()])
+        .await
+        .unwrap();
+    assert_eq!(fetched.len(), 1);
+
+    // Writes can't cross namespaces
+    acme_post.title = "acme v2".to_string();
+    assert!(matches!(
+        globex.update_object(&mut acme_post).await,
+        Err(Error::NotFound)
+    ));
+    acme.update_object(&mut acme_post).await.unwrap();
+    let stored: Post = engine.fetch_object(acme_post.id()).await.unwrap().unwrap();
+    assert_eq!(stored.title, "acme v2");
+    assert_eq!(stored.meta().namespace(), "acme");
+    assert!(
+        globex
+            .delete_object::<Post>(acme_post.id(), acme_post.owner())
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // The unscoped engine sees everything
+    assert_eq!(engine.count_objects::<Post>(None).await.unwrap(), 3);
+    assert_eq!(
+        engine.list_namespaces::<Post>().await.unwrap(),
+        vec!["acme".to_string(), "globex".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_transaction_rolls_back_on_drop() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+
+    let user = User::default();
+    {
+        let tx = adapter.begin().await.unwrap();
+        tx.insert_object(ObjectRecord::from_object(&user))
+            .await
+            .unwrap();
+        assert!(
+            tx.fetch_object(User::TYPE, user.id())
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    assert!(
+        adapter
+            .fetch_object(User::TYPE, user.id())
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_type() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    use ousia::{OusiaDefault, OusiaObject};
+    #[derive(OusiaObject, OusiaDefault, Debug)]
+    #[ousia(type_name = "Article", index = "headline:search")]
+    pub struct Article {
+        _meta: Meta,
+
+        pub headline: String,
+    }
+    let to_article = |post: Post| Article {
+        _meta: Meta::default(),
+        headline: post.title,
+    };
+
+    let owner = uuid::Uuid::now_v7();
+    let mut ids = Vec::new();
+    for i in 0..150 {
+        let mut post = Post {
+            _meta: Meta::default(),
+            title: format!("post {i}"),
+            content: String::new(),
+            status: PostStatus::Draft,
+            published_at: None,
+            tags: vec![],
+            views: 0,
+        };
+        post.set_owner(owner);
+        engine.create_object(&post).await.unwrap();
+        ids.push(post.id());
+    }
+
+    // A single object is retyped in place
+    let article: Article = engine
+        .copy_object_to_type(ids[0], to_article)
+        .await
+        .unwrap();
+    assert_eq!(article.id(), ids[0]);
+    assert_eq!(article.owner(), owner);
+    assert_eq!(article.headline, "post 0");
+    assert!(engine.fetch_object::<Post>(ids[0]).await.unwrap().is_none());
+    let stored: Article = engine.fetch_object(ids[0]).await.unwrap().unwrap();
+    assert_eq!(stored.headline, "post 0");
+    let found: Vec<Article> = engine
+        .query_objects(Query::new(owner).where_eq(&Article::FIELDS.headline, "post 0"))
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert!(matches!(
+        engine
+            .copy_object_to_type::<Post, Article>(ids[0], to_article)
+            .await,
+        Err(Error::NotFound)
+    ));
+
+    // Migrating moves every remaining object across batches
+    let migrated = engine.migrate_type(to_article).await.unwrap();
+    assert_eq!(migrated, 149);
+    assert_eq!(engine.count_objects::<Post>(None).await.unwrap(), 0);
+    let mut articles: Vec<Article> = engine.fetch_objects(ids.clone()).await.unwrap();
+    assert_eq!(articles.len(), 150);
+    articles.sort_by_key(|a| a.id());
+    ids.sort();
+    assert_eq!(articles.iter().map(|a| a.id()).collect::<Vec<_>>(), ids);
+    assert!(articles.iter().all(|a| a.owner() == owner));
+
+    // A unique clash rolls the retype back
+    #[derive(OusiaObject, OusiaDefault, Debug)]
+    #[ousia(type_name = "Slug", unique = "slug")]
+    pub struct Slug {
+        _meta: Meta,
+
+        pub slug: String,
+    }
+    let to_slug = |_: Article| Slug {
+        _meta: Meta::default(),
+        slug: "same".to_string(),
+    };
+    engine
+        .copy_object_to_type::<Article, Slug>(ids[0], to_slug)
+        .await
+        .unwrap();
+    let err = engine
+        .copy_object_to_type::<Article, Slug>(ids[1], to_slug)
+        .await
+        .unwrap_err();
+    assert!(err.is_unique_constraint_violation());
+    assert!(engine.fetch_object::<Article>(ids[1]).await.unwrap().is_some());
+    assert!(engine.fetch_object::<Slug>(ids[1]).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_aggregate_edge_field() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    use ousia::{Aggregation, OusiaEdge};
+    #[derive(Debug, OusiaEdge)]
+    #[ousia(type_name = "WeightedFollow", index = "weight:search+sort")]
+    struct WeightedFollow {
+        _meta: EdgeMeta,
+        weight: i64,
+    }
+
+    let a = uuid::Uuid::now_v7();
+    let b = uuid::Uuid::now_v7();
+    let c = uuid::Uuid::now_v7();
+    let target = uuid::Uuid::now_v7();
+    for weight in 1..=5 {
+        engine
+            .create_edge(&WeightedFollow {
+                _meta: EdgeMeta::new(a, uuid::Uuid::now_v7()),
+                weight,
+            })
+            .await
+            .unwrap();
+    }
+    engine
+        .create_edge(&WeightedFollow {
+            _meta: EdgeMeta::new(b, target),
+            weight: 10,
+        })
+        .await
+        .unwrap();
+    engine
+        .create_edge(&WeightedFollow {
+            _meta: EdgeMeta::new(c, target),
+            weight: 6,
+        })
+        .await
+        .unwrap();
+
+    let weight = &WeightedFollow::FIELDS.weight;
+    let aggregate = |from, agg| engine.aggregate_edge_field::<WeightedFollow>(from, weight, agg);
+    assert_eq!(aggregate(a, Aggregation::Sum).await.unwrap(), 15.0);
+    assert_eq!(aggregate(a, Aggregation::Avg).await.unwrap(), 3.0);
+    assert_eq!(aggregate(a, Aggregation::Min).await.unwrap(), 1.0);
+    assert_eq!(aggregate(a, Aggregation::Max).await.unwrap(), 5.0);
+    assert_eq!(aggregate(target, Aggregation::Sum).await.unwrap(), 0.0);
+
+    let reverse = engine
+        .aggregate_reverse_edge_field::<WeightedFollow>(target, weight, Aggregation::Sum)
+        .await
+        .unwrap();
+    assert_eq!(reverse, 16.0);
+
+    let batch = engine
+        .aggregate_edge_field_batch::<WeightedFollow>(&[a, b, target], weight, Aggregation::Avg)
+        .await
+        .unwrap();
+    assert_eq!(batch.len(), 2);
+    assert_eq!(batch[&a], 3.0);
+    assert_eq!(batch[&b], 10.0);
+}
+
+#[tokio::test]
+async fn test_backfill_unique_constraints() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    // The same stored type before and after `handle` became unique
+    mod before {
+        use ousia::{Meta, OusiaDefault, OusiaObject};
+        #[derive(OusiaObject, OusiaDefault, Debug)]
+        pub struct Handle {
+            pub _meta: Meta,
+            pub handle: String,
+        }
+    }
+    mod after {
+        use ousia::{Meta, OusiaDefault, OusiaObject};
+        #[derive(OusiaObject, OusiaDefault, Debug)]
+        #[ousia(unique = "handle")]
+        pub struct Handle {
+            pub _meta: Meta,
+            pub handle: String,
+        }
+    }
+
+    for i in 0..10 {
+        let mut handle = before::Handle::default();
+        handle.handle = format!("handle-{i}");
+        engine.create_object(&handle).await.unwrap();
+    }
+    assert_eq!(
+        engine.table_stats().await.unwrap().unique_constraints_count,
+        0
+    );
+
+    assert_eq!(
+        engine
+            .backfill_unique_constraints::<after::Handle>()
+            .await
+            .unwrap(),
+        10
+    );
+    assert_eq!(
+        engine.table_stats().await.unwrap().unique_constraints_count,
+        10
+    );
+
+    // Running it again inserts nothing, and the constraint now holds
+    assert_eq!(
+        engine
+            .backfill_unique_constraints::<after::Handle>()
+            .await
+            .unwrap(),
+        0
+    );
+    let mut duplicate = after::Handle::default();
+    duplicate.handle = "handle-3".to_string();
+    assert!(
+        engine
+            .create_object(&duplicate)
+            .await
+            .unwrap_err()
+            .is_unique_constraint_violation()
+    );
+
+    engine
+        .remove_unique_backfill::<after::Handle>()
+        .await
+        .unwrap();
+    assert_eq!(
+        engine.table_stats().await.unwrap().unique_constraints_count,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_union_as_enum() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    use ousia::{Either, OusiaDefault, OusiaObject};
+    #[derive(OusiaObject, OusiaDefault, Debug)]
+    struct Repost {
+        _meta: Meta,
+        original: uuid::Uuid,
+    }
+
+    let owner = uuid::Uuid::now_v7();
+    let mut ids = Vec::new();
+    for i in 0..3 {
+        let post = Post {
+            _meta: Meta::new_with_owner(owner),
+            title: format!("Post {i}"),
+            ..Default::default()
+        };
+        engine.create_object(&post).await.unwrap();
+        ids.push(post.id());
+        if i < 2 {
+            let repost = Repost {
+                _meta: Meta::new_with_owner(owner),
+                original: post.id(),
+            };
+            engine.create_object(&repost).await.unwrap();
+            ids.push(repost.id());
+        }
+    }
+    // An ID of neither type is skipped
+    let mut stranger = User::default();
+    stranger.set_owner(owner);
+    engine.create_object(&stranger).await.unwrap();
+    ids.push(stranger.id());
+
+    let items = engine
+        .fetch_union_as_enum::<Post, Repost>(ids.clone())
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 5);
+    assert_eq!(items.iter().filter(|item| item.is_left()).count(), 3);
+    for item in &items {
+        match item {
+            Either::Left(post) => assert!(post.title.starts_with("Post ")),
+            Either::Right(repost) => assert!(ids.contains(&repost.original)),
+        }
+    }
+
+    let queried = engine
+        .query_union::<Post, Repost>(owner, Query::default())
+        .await
+        .unwrap();
+    assert_eq!(queried.len(), 5);
+    assert_eq!(queried.into_iter().filter_map(Either::right).count(), 2);
+
+    let elsewhere = engine
+        .query_union::<Post, Repost>(uuid::Uuid::now_v7(), Query::default())
+        .await
+        .unwrap();
+    assert!(elsewhere.is_empty());
+}
+
+#[tokio::test]
+async fn test_slow_query_log() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+    let owner = uuid::Uuid::now_v7();
+
+    let post = Post {
+        _meta: Meta::new_with_owner(owner),
+        ..Default::default()
+    };
+    engine.create_object(&post).await.unwrap();
+    assert!(engine.slow_query_log().lock().unwrap().is_empty());
+
+    // Every call is slower than a zero threshold
+    let engine = engine.with_slow_query_threshold(Duration::ZERO);
+    engine.fetch_object::<Post>(post.id()).await.unwrap();
+    engine
+        .query_objects::<Post>(Query::new(owner))
+        .await
+        .unwrap();
+
+    {
+        let log = engine.slow_query_log();
+        let log = log.lock().unwrap();
+        let operations: Vec<_> = log.iter().map(|entry| entry.operation.as_str()).collect();
+        assert_eq!(operations, ["fetch_object", "query_objects"]);
+        assert!(log.iter().all(|entry| entry.type_name == "Post"));
+    }
+
+    let relaxed = engine.with_slow_query_threshold(Duration::from_secs(60));
+    relaxed.fetch_object::<Post>(post.id()).await.unwrap();
+    assert!(relaxed.slow_query_log().lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_query_edges_by_endpoint_fields() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    assert_eq!(Follow::FIELDS.from.name, "from");
+    assert_eq!(Follow::FIELDS.to.name, "to");
+
+    let alice = uuid::Uuid::now_v7();
+    let bob = uuid::Uuid::now_v7();
+    let carol = uuid::Uuid::now_v7();
+    for (from, to) in [(alice, bob), (alice, carol), (bob, carol)] {
+        engine
+            .create_edge(&Follow {
+                _meta: EdgeMeta::new(from, to),
+                notification: false,
+            })
+            .await
+            .unwrap();
+    }
+
+    let to_carol = engine
+        .query_edges::<Follow>(
+            alice,
+            EdgeQuery::default().where_eq(&Follow::FIELDS.to, carol),
+        )
+        .await
+        .unwrap();
+    assert_eq!(to_carol.len(), 1);
+    assert_eq!(to_carol[0].to(), carol);
+
+    let from_bob = engine
+        .query_reverse_edges::<Follow>(
+            carol,
+            EdgeQuery::default().where_eq(&Follow::FIELDS.from, bob),
+        )
+        .await
+        .unwrap();
+    assert_eq!(from_bob.len(), 1);
+    assert_eq!(from_bob[0].from(), bob);
+}
+
+#[tokio::test]
+async fn test_transfer_to_user_and_back() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let template = Post {
+        _meta: Meta::new_with_owner(system_owner()),
+        title: "Template".to_string(),
+        ..Default::default()
+    };
+    engine.create_object(&template).await.unwrap();
+    assert!(engine.is_system_owned::<Post>(template.id()).await.unwrap());
+
+    let user = uuid::Uuid::now_v7();
+    let promoted: Post = engine.transfer_to_user(template.id(), user).await.unwrap();
+    assert_eq!(promoted.owner(), user);
+    assert!(!engine.is_system_owned::<Post>(template.id()).await.unwrap());
+
+    // Already promoted: no longer held by the system
+    assert!(matches!(
+        engine
+            .transfer_to_user::<Post>(template.id(), uuid::Uuid::now_v7())
+            .await,
+        Err(Error::NotFound)
+    ));
+
+    let demoted: Post = engine
+        .transfer_to_system(template.id(), user)
+        .await
+        .unwrap();
+    assert_eq!(demoted.owner(), system_owner());
+    assert_eq!(demoted.title, "Template");
+    assert!(engine.is_system_owned::<Post>(template.id()).await.unwrap());
+
+    assert!(matches!(
+        engine.is_system_owned::<Post>(uuid::Uuid::now_v7()).await,
+        Err(Error::NotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_prefetch_edges_for_objects() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let owner = uuid::Uuid::now_v7();
+    let mut post_ids = Vec::new();
+    let mut followers = Vec::new();
+    for _ in 0..5 {
+        let post = Post {
+            _meta: Meta::new_with_owner(owner),
+            ..Default::default()
+        };
+        engine.create_object(&post).await.unwrap();
+        post_ids.push(post.id());
+        for _ in 0..3 {
+            let follower = uuid::Uuid::now_v7();
+            followers.push(follower);
+            engine
+                .create_edge(&Follow {
+                    _meta: EdgeMeta::new(post.id(), follower),
+                    notification: false,
+                })
+                .await
+                .unwrap();
+        }
+    }
+
+    let edges = engine
+        .prefetch_edges_for_objects::<Follow>(&post_ids)
+        .await
+        .unwrap();
+    assert_eq!(edges.len(), 5);
+    for id in &post_ids {
+        assert_eq!(edges[id].len(), 3);
+        assert!(edges[id].iter().all(|edge| edge.from() == *id));
+    }
+
+    let reverse = engine
+        .prefetch_reverse_edges_for_objects::<Follow>(&followers[..4])
+        .await
+        .unwrap();
+    assert_eq!(reverse.len(), 4);
+    assert!(reverse.values().all(|edges| edges.len() == 1));
+
+    assert!(
+        engine
+            .prefetch_edges_for_objects::<Follow>(&[])
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_apply_patch_from_json() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let owner = uuid::Uuid::now_v7();
+    let post = Post {
+        _meta: Meta::new_with_owner(owner),
+        title: "Draft".to_string(),
+        content: "Body".to_string(),
+        tags: vec!["rust".to_string()],
+        ..Default::default()
+    };
+    engine.create_object(&post).await.unwrap();
+
+    let patched: Post = engine
+        .apply_patch_from_json(
+            post.id(),
+            owner,
+            serde_json::json!({ "title": "Final", "owner": uuid::Uuid::now_v7() }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(patched.title, "Final");
+    assert_eq!(patched.content, "Body");
+    assert_eq!(patched.tags, vec!["rust".to_string()]);
+    assert_eq!(patched.owner(), owner);
+
+    let stored = engine
+        .fetch_object::<Post>(post.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.title, "Final");
+    assert_eq!(stored.content, "Body");
+
+    let rejected = engine
+        .apply_patch_from_json_strict::<Post>(
+            post.id(),
+            owner,
+            serde_json::json!({ "title": "Other", "status": "Archived" }),
+            &["title", "content"],
+        )
+        .await;
+    assert!(matches!(rejected, Err(Error::InvalidPatch(_))));
+    let stored = engine
+        .fetch_object::<Post>(post.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.title, "Final");
+
+    let allowed: Post = engine
+        .apply_patch_from_json_strict(
+            post.id(),
+            owner,
+            serde_json::json!({ "content": "New body" }),
+            &["title", "content"],
+        )
+        .await
+        .unwrap();
+    assert_eq!(allowed.content, "New body");
+
+    let wrong_owner = engine
+        .apply_patch_from_json::<Post>(
+            post.id(),
+            uuid::Uuid::now_v7(),
+            serde_json::json!({ "title": "Hijacked" }),
+        )
+        .await;
+    assert!(matches!(wrong_owner, Err(Error::NotFound)));
+}
+
+#[tokio::test]
+async fn test_where_regex_not_supported() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let result = engine
+        .query_objects::<User>(Query::default().where_regex(&User::FIELDS.username, "^alice", true))
+        .await;
+    assert!(matches!(result, Err(Error::NotSupported)));
+}
+
+#[tokio::test]
+async fn test_watch_query_yields_added() {
+    use futures::StreamExt;
+    use ousia::{OusiaDefault, OusiaObject};
+
+    #[derive(OusiaObject, OusiaDefault, Debug, Clone)]
+    #[ousia(index = "published:search")]
+    struct Story {
+        _meta: Meta,
+        title: String,
+        published: bool,
+    }
+
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+    let owner = uuid::Uuid::now_v7();
+
+    let mut existing = Story {
+        _meta: Meta::new_with_owner(owner),
+        title: "Existing".to_string(),
+        published: true,
+    };
+    engine.create_object(&existing).await.unwrap();
+
+    let poll = Duration::from_millis(250);
+    let query = Query::new(owner).where_eq(&Story::FIELDS.published, true);
+    let mut stream = engine.watch_query::<Story>(query, poll).await.unwrap();
+
+    // A draft doesn't enter the result set
+    let draft = Story {
+        _meta: Meta::new_with_owner(owner),
+        title: "Draft".to_string(),
+        published: false,
+    };
+    engine.create_object(&draft).await.unwrap();
+    let fresh = Story {
+        _meta: Meta::new_with_owner(owner),
+        title: "Fresh".to_string(),
+        published: true,
+    };
+    engine.create_object(&fresh).await.unwrap();
+
+    let change = tokio::time::timeout(poll * 2, stream.next())
+        .await
+        .expect("no change within two poll intervals")
+        .expect("stream ended")
+        .unwrap();
+    assert_eq!(change.added.len(), 1);
+    assert_eq!(change.added[0].title, "Fresh");
+    assert!(change.removed.is_empty() && change.updated.is_empty());
+
+    existing.published = false;
+    engine.update_object(&mut existing).await.unwrap();
+    let change = tokio::time::timeout(poll * 2, stream.next())
+        .await
+        .expect("no change within two poll intervals")
+        .expect("stream ended")
+        .unwrap();
+    assert!(change.added.is_empty());
+    assert_eq!(change.removed.len(), 1);
+    assert_eq!(change.removed[0].id(), existing.id());
+}
+
+#[tokio::test]
+async fn test_delete_owned_objects_releases_unique_keys() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let owner = uuid::Uuid::now_v7();
+    for i in 0..5 {
+        let mut user = User::default();
+        user.set_owner(owner);
+        user.username = format!("owned-{i}");
+        engine.create_object(&user).await.unwrap();
+    }
+    let mut other = User::default();
+    other.username = "someone-else".to_string();
+    engine.create_object(&other).await.unwrap();
+
+    assert_eq!(
+        engine.table_stats().await.unwrap().unique_constraints_count,
+        6
+    );
+
+    assert_eq!(engine.delete_owned_objects::<User>(owner).await.unwrap(), 5);
+    // Only the other owner's key is left
+    assert_eq!(
+        engine.table_stats().await.unwrap().unique_constraints_count,
+        1
+    );
+
+    // The released usernames can be taken again
+    let mut reused = User::default();
+    reused.username = "owned-0".to_string();
+    engine.create_object(&reused).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_create_object_and_link_is_atomic() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let pinner = uuid::Uuid::now_v7();
+    let mut first = User::default();
+    first.username = "first-pinned".to_string();
+    let pin = Pin {
+        _meta: EdgeMeta::new(uuid::Uuid::nil(), uuid::Uuid::nil()),
+        note: "first".to_string(),
+    };
+    let (stored, pin) = engine
+        .create_object_and_link(&first, pinner, pin)
+        .await
+        .unwrap();
+    assert_eq!(stored.id(), first.id());
+    assert_eq!(stored.username, "first-pinned");
+    assert_eq!(pin.from(), pinner);
+    assert_eq!(pin.to(), first.id());
+    assert!(
+        engine
+            .fetch_edge::<Pin>(pinner, first.id())
+            .await
+            .unwrap()
+            .is_some()
+    );
+
+    // `pinner` already holds a pin, so the edge fails and takes the object with it
+    let mut second = User::default();
+    second.username = "second-pinned".to_string();
+    let pin = Pin {
+        _meta: EdgeMeta::new(uuid::Uuid::nil(), uuid::Uuid::nil()),
+        note: "second".to_string(),
+    };
+    let result = engine.create_object_and_link(&second, pinner, pin).await;
+    assert_eq!(result.err(), Some(Error::unique_violation("from")));
+    assert!(
+        engine
+            .fetch_object::<User>(second.id())
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // The object's unique keys were released too
+    let id = engine.create_object_returning_id(&second).await.unwrap();
+    assert_eq!(id, second.id());
+}
+
+#[tokio::test]
+async fn test_query_objects_distinct() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let owner = uuid::Uuid::now_v7();
+    let mut both = Post::default();
+    both.set_owner(owner);
+    both.title = "Hello".to_string();
+    both.status = PostStatus::Published;
+    engine.create_object(&both).await.unwrap();
+    let mut draft = Post::default();
+    draft.set_owner(owner);
+    draft.title = "Hello again".to_string();
+    draft.status = PostStatus::Draft;
+    engine.create_object(&draft).await.unwrap();
+
+    // `both` matches each query, so the merged list holds it twice
+    let mut merged: Vec<Post> = engine
+        .query_objects(Query::new(owner).where_eq(&Post::FIELDS.status, PostStatus::Published))
+        .await
+        .unwrap();
+    merged.extend(
+        engine
+            .query_objects::<Post>(
+                Query::new(owner).where_begins_with(&Post::FIELDS.title, "Hello"),
+            )
+            .await
+            .unwrap(),
+    );
+    assert_eq!(merged.len(), 3);
+
+    let deduped = Engine::deduplicate(merged);
+    assert_eq!(deduped.len(), 2);
+    assert_eq!(deduped[0].id(), both.id());
+    assert_eq!(deduped.iter().filter(|p| p.id() == both.id()).count(), 1);
+
+    let distinct: Vec<Post> = engine
+        .query_objects_distinct(
+            Query::new(owner)
+                .where_begins_with(&Post::FIELDS.title, "Hello")
+                .sort_asc(&Post::FIELDS.title),
+        )
+        .await
+        .unwrap();
+    let ids: Vec<_> = distinct.iter().map(|p| p.id()).collect();
+    assert_eq!(ids, vec![both.id(), draft.id()]);
+}
+
+#[tokio::test]
+async fn test_object_change_log() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let since = chrono::Utc::now() - chrono::Duration::seconds(1);
+    let owner = uuid::Uuid::now_v7();
+    let mut post = Post::default();
+    post.set_owner(owner);
+    post.title = "Draft".to_string();
+    engine.create_object(&post).await.unwrap();
+    post.title = "Final".to_string();
+    engine.update_object(&mut post).await.unwrap();
+    engine
+        .delete_object::<Post>(post.id(), owner)
+        .await
+        .unwrap();
+
+    let log = engine
+        .object_change_log::<Post>(post.id(), 10)
+        .await
+        .unwrap();
+    let operations: Vec<_> = log.iter().map(|entry| entry.operation).collect();
+    assert_eq!(
+        operations,
+        [
+            ousia::ChangeOperation::Create,
+            ousia::ChangeOperation::Update,
+            ousia::ChangeOperation::Delete,
+        ]
+    );
+    assert!(log[0].old_data.is_none());
+    assert_eq!(log[1].old_data.as_ref().unwrap()["title"], "Draft");
+    assert_eq!(log[1].new_data.as_ref().unwrap()["title"], "Final");
+    assert!(log[2].new_data.is_none());
+    assert!(log.iter().all(|entry| entry.changed_by == owner));
+
+    // The limit keeps the latest entries
+    let latest = engine
+        .object_change_log::<Post>(post.id(), 1)
+        .await
+        .unwrap();
+    assert_eq!(latest.len(), 1);
+    assert_eq!(latest[0].operation, ousia::ChangeOperation::Delete);
+
+    let by_type = engine.type_change_log::<Post>(since).await.unwrap();
+    assert_eq!(by_type.len(), 3);
+    assert!(
+        engine
+            .type_change_log::<User>(since)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_object_search() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter)).with_global_queries(true);
+
+    let owner = uuid::Uuid::now_v7();
+    let mut posts = Vec::new();
+    for (title, content, status) in [
+        ("A", "rust rust rust", PostStatus::Published),
+        (
+            "B",
+            "a long ramble about many things, rust among them",
+            PostStatus::Published,
+        ),
+        ("C", "rust ownership", PostStatus::Draft),
+        ("D", "gardening notes", PostStatus::Published),
+        ("E", "tomatoes and basil", PostStatus::Published),
+    ] {
+        let mut post = Post::default();
+        post.set_owner(owner);
+        post.title = title.into();
+        post.content = content.into();
+        post.status = status;
+        engine.create_object(&post).await.unwrap();
+        posts.push(post);
+    }
+
+    let hits = engine
+        .object_search::<Post>("rust", &[], None, 10)
+        .await
+        .unwrap();
+    let titles: Vec<_> = hits.iter().map(|h| h.object.title.as_str()).collect();
+    assert_eq!(titles.len(), 3);
+    assert_eq!(titles[0], "A");
+    assert!(hits.iter().all(|h| h.score > 0.0));
+    assert!(hits.windows(2).all(|w| w[0].score >= w[1].score));
+
+    // Field filters narrow the matches
+    let hits = engine
+        .object_search::<Post>(
+            "rust",
+            &[filter!(&Post::FIELDS.status, PostStatus::Draft)],
+            None,
+            10,
+        )
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].object.id(), posts[2].id());
+
+    // A field sort replaces the relevance order
+    let hits = engine
+        .object_search::<Post>("rust", &[], Some(SearchSort::Desc(&Post::FIELDS.title)), 2)
+        .await
+        .unwrap();
+    let titles: Vec<_> = hits.iter().map(|h| h.object.title.as_str()).collect();
+    assert_eq!(titles, ["C", "B"]);
+
+    let denied = engine
+        .clone()
+        .with_global_queries(false)
+        .object_search::<Post>("rust", &[], None, 10)
+        .await;
+    assert!(matches!(denied, Err(Error::PermissionDenied)));
+}
+
+#[tokio::test]
+async fn test_fetch_objects_by_sequence_range() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter)).with_global_queries(true);
+
+    assert_eq!(engine.fetch_latest_sequence::<Post>().await.unwrap(), 0);
+
+    let mut ids = Vec::new();
+    for i in 0..10 {
+        let post = Post {
+            _meta: Meta::new_with_owner(uuid::Uuid::now_v7()),
+            title: format!("Post {i}"),
+            ..Default::default()
+        };
+        engine.create_object(&post).await.unwrap();
+        ids.push(post.id());
+    }
+    let latest = engine.fetch_latest_sequence::<Post>().await.unwrap();
+    assert_eq!(latest, 10);
+
+    // Consume the stream in windows of 3, resuming after the last sequence seen
+    let mut seen = Vec::new();
+    let mut next = 1;
+    while next <= latest {
+        let window = engine
+            .fetch_objects_by_sequence_range::<Post>(next, next + 3)
+            .await
+            .unwrap();
+        assert!(window.len() <= 3);
+        next = window.last().unwrap().meta().sequence + 1;
+        seen.extend(window);
+    }
+    let sequences: Vec<u64> = seen.iter().map(|p| p.meta().sequence).collect();
+    assert_eq!(sequences, (1..=10).collect::<Vec<_>>());
+    let seen_ids: Vec<_> = seen.iter().map(|p| p.id()).collect();
+    assert_eq!(seen_ids, ids);
+
+    assert!(
+        engine
+            .fetch_objects_by_sequence_range::<Post>(11, 14)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_index_field_kinds_sort_query() {
+    use ousia::query::{IndexKind, QueryMode, QuerySort};
+
+    for field in [&Player::FIELDS.score, &Player::FIELDS.nickname] {
+        assert!(field.kinds.contains(&IndexKind::Search));
+        assert!(field.kinds.contains(&IndexKind::Sort));
+        assert_eq!(field.kinds.len(), 2);
+    }
+
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let owner = uuid::Uuid::now_v7();
+    for (nickname, score) in [("low", 3), ("high", 30), ("mid", 12)] {
+        let mut player = Player::default();
+        player.set_owner(owner);
+        player.nickname = nickname.to_string();
+        player.score = score;
+        engine.create_object(&player).await.unwrap();
+    }
+
+    let players: Vec<Player> = engine
+        .query_objects(Query::new(owner).filter(
+            &Player::FIELDS.score,
+            "score",
+            QueryMode::Sort(QuerySort { ascending: false }),
+        ))
+        .await
+        .unwrap();
+    let scores: Vec<i64> = players.iter().map(|p| p.score).collect();
+    assert_eq!(scores, vec![30, 12, 3]);
+}
+
+#[tokio::test]
+async fn test_touch_object() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let owner = uuid::Uuid::now_v7();
+    let mut post = Post::default();
+    post.set_owner(owner);
+    post.title = "Unchanged".to_string();
+    post.tags = vec!["a".to_string()];
+    engine.create_object(&post).await.unwrap();
+    let before: Post = engine.fetch_object(post.id()).await.unwrap().unwrap();
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    engine.touch_object::<Post>(post.id()).await.unwrap();
+
+    let after: Post = engine.fetch_object(post.id()).await.unwrap().unwrap();
+    assert!(after.updated_at() > before.updated_at());
+    assert_eq!(after.created_at(), before.created_at());
+    assert_eq!(after.owner(), before.owner());
+    assert_eq!(after.meta().sequence, before.meta().sequence);
+    assert_eq!(after.title, before.title);
+    assert_eq!(after.tags, before.tags);
+
+    let other = Post::default();
+    engine.create_object(&other).await.unwrap();
+    let touched = engine
+        .touch_objects_batch::<Post>(&[post.id(), other.id(), uuid::Uuid::now_v7()])
+        .await
+        .unwrap();
+    assert_eq!(touched, 2);
+
+    let missing = engine.touch_object::<Post>(uuid::Uuid::now_v7()).await;
+    assert_eq!(missing, Err(Error::NotFound));
+    // The type is part of the match
+    let wrong_type = engine.touch_object::<User>(post.id()).await;
+    assert_eq!(wrong_type, Err(Error::NotFound));
+}
+
+#[tokio::test]
+async fn test_run_maintenance_removes_orphan_unique_constraints() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    // Hashes pointing at objects that were never stored
+    adapter
+        .insert_unique_hashes(
+            "User",
+            uuid::Uuid::now_v7(),
+            vec![("orphan-hash-1".to_string(), "username")],
+        )
+        .await
+        .unwrap();
+    adapter
+        .insert_unique_hashes(
+            "User",
+            uuid::Uuid::now_v7(),
+            vec![("orphan-hash-2".to_string(), "username")],
+        )
+        .await
+        .unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut user = User::default();
+    user.username = "still_here".to_string();
+    engine.create_object(&user).await.unwrap();
+    assert_eq!(
+        engine.table_stats().await.unwrap().unique_constraints_count,
+        3
+    );
+
+    let report = engine
+        .run_maintenance(MaintenanceOptions {
+            vacuum: true,
+            reindex: true,
+            cleanup_orphan_unique_constraints: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(report.vacuumed);
+    assert!(report.reindexed);
+    assert_eq!(report.orphans_removed, 2);
+    assert_eq!(report.rows_expired, 0);
+    assert_eq!(
+        engine.table_stats().await.unwrap().unique_constraints_count,
+        1
+    );
+
+    // The live user's hash still blocks duplicates
+    let mut dup = User::default();
+    dup.username = "still_here".to_string();
+    assert!(engine.create_object(&dup).await.is_err());
+
+    // No ledger configured
+    let expired = engine
+        .run_maintenance(MaintenanceOptions {
+            expire_ledger_objects: true,
+            ..Default::default()
+        })
+        .await;
+    assert_eq!(expired, Err(Error::NotSupported));
+}
+
+#[tokio::test]
+async fn test_top_owners_and_objects_paged_by_owner() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let owners: Vec<uuid::Uuid> = (0..3).map(|_| uuid::Uuid::now_v7()).collect();
+    for (owner, count) in owners.iter().zip([1, 5, 3]) {
+        for i in 0..count {
+            let post = Post {
+                _meta: Meta::new_with_owner(*owner),
+                title: format!("Post {i}"),
+                ..Default::default()
+            };
+            engine.create_object(&post).await.unwrap();
+        }
+    }
+
+    let top = engine.top_owners::<Post>(10).await.unwrap();
+    assert_eq!(top, vec![(owners[1], 5), (owners[2], 3), (owners[0], 1)]);
+    let top = engine.top_owners::<Post>(2).await.unwrap();
+    assert_eq!(top, vec![(owners[1], 5), (owners[2], 3)]);
+    assert!(engine.top_owners::<User>(10).await.unwrap().is_empty());
+
+    let groups = engine
+        .query_objects_paged_by_owner::<Post>(Query::wide().sort_asc(&Post::FIELDS.title))
+        .await
+        .unwrap();
+    let mut expected_owners = owners.clone();
+    expected_owners.sort();
+    assert_eq!(
+        groups.iter().map(|(owner, _)| *owner).collect::<Vec<_>>(),
+        expected_owners
+    );
+    for (owner, posts) in &groups {
+        assert!(posts.iter().all(|p| p.owner() == *owner));
+        assert!(
+            posts
+                .windows(2)
+                .all(|w| w[0].created_at() >= w[1].created_at())
+        );
+    }
+    let five = &groups
+        .iter()
+        .find(|(owner, _)| *owner == owners[1])
+        .unwrap()
+        .1;
+    assert_eq!(five.len(), 5);
+    assert_eq!(five[0].title, "Post 4");
+}
+
+#[tokio::test]
+async fn test_edge_option_field_round_trip() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let (alice, bob, carol) = (
+        uuid::Uuid::now_v7(),
+        uuid::Uuid::now_v7(),
+        uuid::Uuid::now_v7(),
+    );
+    engine
+        .create_edge(&Bookmark {
+            _meta: EdgeMeta::new(alice, bob),
+            description: None,
+        })
+        .await
+        .unwrap();
+    engine
+        .create_edge(&Bookmark {
+            _meta: EdgeMeta::new(alice, carol),
+            description: Some("colleague".to_string()),
+        })
+        .await
+        .unwrap();
+
+    let stored = engine
+        .fetch_edge::<Bookmark>(alice, bob)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.description, None);
+
+    let unset = engine
+        .query_edges::<Bookmark>(
+            alice,
+            EdgeQuery::default().where_null(&Bookmark::FIELDS.description),
+        )
+        .await
+        .unwrap();
+    assert_eq!(unset.len(), 1);
+    assert_eq!(unset[0].to(), bob);
+
+    let set = engine
+        .query_edges::<Bookmark>(
+            alice,
+            EdgeQuery::default().where_not_null(&Bookmark::FIELDS.description),
+        )
+        .await
+        .unwrap();
+    assert_eq!(set.len(), 1);
+    assert_eq!(set[0].description.as_deref(), Some("colleague"));
+
+    // Data written before the field existed has no key at all
+    let missing: Bookmark = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert_eq!(missing.description, None);
+}
+
+#[tokio::test]
+async fn test_create_object_with_id() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+
+    let engine = Engine::new(Box::new(adapter));
+
+    let id = engine.reserve_id();
+    let mut user = User::default();
+    user.username = "preassigned".to_string();
+    user.email = "preassigned@example.com".to_string();
+
+    engine.create_object_with_id(&user, id).await.unwrap();
+
+    let fetched: User = engine.fetch_object(id).await.unwrap().unwrap();
+    assert_eq!(fetched.id(), id);
+    assert_eq!(fetched.username, "preassigned");
+    assert!(
+        engine
+            .fetch_object::<User>(user.id())
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // Unique keys are claimed under the specified ID
+    let mut dup = User::default();
+    dup.username = "preassigned".to_string();
+    let err = engine
+        .create_object_with_id(&dup, engine.reserve_id())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ValidationFailed(_)));
+
+    let meta = Meta::with_id(id);
+    assert_eq!(meta.id, id);
+
+    let err = engine
+        .create_object_with_id(&User::default(), uuid::Uuid::nil())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidId));
+}
+
+#[tokio::test]
+async fn test_test_engine_fixture() {
+    let engine = ousia::testing::TestEngine::new().await;
+
+    let mut post = Post::default();
+    post.title = "fixture".to_string();
+    engine.create_object(&post).await.unwrap();
+    let fetched: Post = engine.fetch_object(post.id()).await.unwrap().unwrap();
+    assert_eq!(fetched.title, "fixture");
+
+    // Ledger runs on the in-memory adapter
+    let usd = ousia::ledger::Asset::new("USD", 10_000, 2).unwrap();
+    engine.ledger().create_asset(usd).await.unwrap();
+    let assets = engine.list_assets().await.unwrap();
+    assert_eq!(assets.len(), 1);
+    assert_eq!(assets[0].code, "USD");
+}
+
+#[tokio::test]
+async fn test_query_edge_targets_filters_objects() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut posts = Vec::new();
+    for (title, status) in [
+        ("one", PostStatus::Published),
+        ("two", PostStatus::Published),
+        ("draft", PostStatus::Draft),
+    ] {
+        let mut post = Post::default();
+        post.title = title.to_string();
+        post.status = status;
+        engine.create_object(&post).await.unwrap();
+        posts.push(post);
+    }
+
+    // Three users follow both published posts and the draft
+    let mut users = Vec::new();
+    for i in 0..3 {
+        let mut user = User::default();
+        user.username = format!("reader{i}");
+        engine.create_object(&user).await.unwrap();
+        for post in &posts {
+            engine
+                .create_edge(&Follow {
+                    _meta: EdgeMeta::new(user.id(), post.id()),
+                    notification: i == 0,
+                })
+                .await
+                .unwrap();
+        }
+        users.push(user);
+    }
+
+    let published = [filter!(&Post::FIELDS.status, PostStatus::Published)];
+    for user in &users {
+        let pairs: Vec<(Follow, Post)> = engine
+            .query_edge_targets(user.id(), EdgeQuery::default(), &published)
+            .await
+            .unwrap();
+        assert_eq!(pairs.len(), 2);
+        for (edge, post) in &pairs {
+            assert_eq!(edge.from(), user.id());
+            assert_eq!(edge.to(), post.id());
+            assert_eq!(post.status, PostStatus::Published);
+        }
+    }
+
+    // Reverse: every reader of a published post, filtered on the reader
+    let readers: Vec<(Follow, User)> = engine
+        .query_reverse_edge_sources(
+            posts[0].id(),
+            EdgeQuery::default(),
+            &[filter!(&User::FIELDS.username, "reader1")],
+        )
+        .await
+        .unwrap();
+    assert_eq!(readers.len(), 1);
+    assert_eq!(readers[0].0.from(), users[1].id());
+    assert_eq!(readers[0].1.username, "reader1");
+}
+
+#[tokio::test]
+async fn test_sequence_reset_and_list() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut value = 0;
+    while value < 50 {
+        value = engine.counter_next_value("orders".to_string()).await;
+    }
+    assert_eq!(engine.counter_value("orders".to_string()).await, 50);
+
+    engine.sequence_reset("orders", 1).await.unwrap();
+    assert_eq!(engine.counter_value("orders".to_string()).await, 1);
+    assert_eq!(engine.counter_next_value("orders".to_string()).await, 2);
+
+    assert!(matches!(
+        engine.sequence_reset("orders", 0).await,
+        Err(Error::InvalidSequenceValue)
+    ));
+
+    // Resetting a counter that was never used creates it
+    engine.sequence_reset("invoices", 10).await.unwrap();
+    engine.counter_next_value("tickets".to_string()).await;
+
+    let list = engine.sequence_list().await.unwrap();
+    assert_eq!(
+        list,
+        vec![
+            ("invoices".to_string(), 10),
+            ("orders".to_string(), 2),
+            ("tickets".to_string(), 2),
+        ]
+    );
+
+    // A user counter named after a type leaves its object sequence alone
+    engine.sequence_reset("Post", 100).await.unwrap();
+    let post = Post {
+        _meta: Meta::new_with_owner(uuid::Uuid::now_v7()),
+        ..Default::default()
+    };
+    engine.create_object(&post).await.unwrap();
+    let stored = engine
+        .fetch_object::<Post>(post.id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.meta().sequence, 1);
+    assert_eq!(engine.counter_value("Post".to_string()).await, 100);
+
+    let list = engine.sequence_list().await.unwrap();
+    assert!(list.contains(&("__type:Post".to_string(), 2)));
+}
+
+#[tokio::test]
+async fn test_batch_create_edges_on_conflict() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let from = uuid::Uuid::now_v7();
+    let targets: Vec<uuid::Uuid> = (0..10).map(|_| uuid::Uuid::now_v7()).collect();
+    let follows = |notification: bool| -> Vec<Follow> {
+        targets
+            .iter()
+            .map(|to| Follow {
+                _meta: EdgeMeta::new(from, *to),
+                notification,
+            })
+            .collect()
+    };
+
+    let first = engine
+        .batch_create_edges(&follows(false), OnConflict::Error)
+        .await
+        .unwrap();
+    assert_eq!(
+        first,
+        BatchEdgeResult {
+            created: 10,
+            replaced: 0,
+            skipped: 0
+        }
+    );
+
+    let ignored = engine
+        .batch_create_edges(&follows(true), OnConflict::Ignore)
+        .await
+        .unwrap();
+    assert_eq!(ignored.skipped, 10);
+    assert_eq!(ignored.created, 0);
+    let stored: Follow = engine.fetch_edge(from, targets[0]).await.unwrap().unwrap();
+    assert!(!stored.notification);
+
+    let replaced = engine
+        .batch_create_edges(&follows(true), OnConflict::Replace)
+        .await
+        .unwrap();
+    assert_eq!(replaced.replaced, 10);
+    assert_eq!(replaced.created, 0);
+    let edges: Vec<Follow> = engine
+        .query_edges(from, EdgeQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(edges.len(), 10);
+    assert!(edges.iter().all(|edge| edge.notification));
+
+    // One conflict fails the batch; the new edge is not written either
+    let mut batch = follows(false);
+    let fresh = uuid::Uuid::now_v7();
+    batch.push(Follow {
+        _meta: EdgeMeta::new(from, fresh),
+        notification: false,
+    });
+    let err = engine
+        .batch_create_edges(&batch, OnConflict::Error)
+        .await
+        .unwrap_err();
+    assert!(err.is_unique_constraint_violation());
+    assert!(
+        engine
+            .fetch_edge::<Follow>(from, fresh)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_object_stats() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut players = Vec::new();
+    for score in 1..=10 {
+        let mut player = Player::default();
+        player.nickname = if score % 2 == 0 { "even" } else { "odd" }.to_string();
+        player.score = score;
+        engine.create_object(&player).await.unwrap();
+        players.push(player);
+    }
+
+    let stats = ousia::ObjectStats::compute(&players, &Player::FIELDS.score);
+    assert_eq!(stats.count, 10);
+    assert_eq!(stats.min, Some(ousia::query::IndexValue::Int(1)));
+    assert_eq!(stats.max, Some(ousia::query::IndexValue::Int(10)));
+    assert_eq!(stats.sum, Some(55.0));
+    assert_eq!(stats.avg, Some(5.5));
+    assert_eq!(stats.distinct_count, 10);
+
+    let names = ousia::ObjectStats::compute(&players, &Player::FIELDS.nickname);
+    assert_eq!(names.distinct_count, 2);
+    assert_eq!(
+        names.min,
+        Some(ousia::query::IndexValue::String("even".to_string()))
+    );
+    assert_eq!(names.sum, None);
+
+    let stored = engine
+        .compute_stats::<Player>(Query::default(), &Player::FIELDS.score)
+        .await
+        .unwrap();
+    assert_eq!(stored, stats);
+
+    let empty = ousia::ObjectStats::compute::<Player>(&[], &Player::FIELDS.score);
+    assert_eq!(empty.count, 0);
+    assert_eq!(empty.avg, None);
+}
+
+#[tokio::test]
+async fn test_migrate_edge_type() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let from = uuid::Uuid::now_v7();
+    let targets: Vec<uuid::Uuid> = (0..5).map(|_| uuid::Uuid::now_v7()).collect();
+    for to in &targets {
+        engine
+            .create_edge(&Follow {
+                _meta: EdgeMeta::new(from, *to),
+                notification: true,
+            })
+            .await
+            .unwrap();
+    }
+    assert_eq!(engine.count_edges_by_type("Follow").await.unwrap(), 5);
+
+    // Dual write first: both types present
+    assert_eq!(engine.copy_edge_type("Follow", "Mirror").await.unwrap(), 5);
+    assert_eq!(engine.copy_edge_type("Follow", "Mirror").await.unwrap(), 0);
+    assert_eq!(engine.count_edges_by_type("Follow").await.unwrap(), 5);
+    assert_eq!(engine.count_edges_by_type("Mirror").await.unwrap(), 5);
+
+    assert_eq!(
+        engine
+            .migrate_edge_type("Follow", "Subscribe")
+            .await
+            .unwrap(),
+        5
+    );
+    assert_eq!(engine.count_edges_by_type("Follow").await.unwrap(), 0);
+    assert_eq!(engine.count_edges_by_type("Subscribe").await.unwrap(), 5);
+
+    // Pairs already present under the new type stay behind
+    let moved = engine
+        .migrate_edge_type("Mirror", "Subscribe")
+        .await
+        .unwrap();
+    assert_eq!(moved, 0);
+    assert_eq!(engine.count_edges_by_type("Mirror").await.unwrap(), 5);
+}
+
+#[tokio::test]
+async fn test_retention_policies() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let kept = Post::default();
+    engine.create_object(&kept).await.unwrap();
+
+    let mut ids = Vec::new();
+    for i in 0..5 {
+        let mut user = User::default();
+        user.username = format!("session{i}");
+        engine.create_object(&user).await.unwrap();
+        engine
+            .create_edge(&Follow {
+                _meta: EdgeMeta::new(user.id(), kept.id()),
+                notification: false,
+            })
+            .await
+            .unwrap();
+        ids.push(user.id());
+    }
+    assert_eq!(
+        engine.table_stats().await.unwrap().unique_constraints_count,
+        5
+    );
+
+    assert!(
+        engine
+            .register_retention_policy::<User>(Duration::from_millis(500))
+            .await
+            .is_err()
+    );
+    engine
+        .register_retention_policy::<User>(Duration::from_secs(1))
+        .await
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    let report = engine.run_retention_policies().await.unwrap();
+    assert_eq!(report.policies_applied, 1);
+    assert_eq!(report.objects_deleted, 5);
+    assert_eq!(report.edges_deleted, 5);
+
+    for id in ids {
+        assert!(engine.fetch_object::<User>(id).await.unwrap().is_none());
+    }
+    let stats = engine.table_stats().await.unwrap();
+    assert_eq!(stats.unique_constraints_count, 0);
+    assert_eq!(stats.edges_count, 0);
+    assert!(
+        engine
+            .fetch_object::<Post>(kept.id())
+            .await
+            .unwrap()
+            .is_some()
+    );
+
+    // Usernames are free again
+    let mut user = User::default();
+    user.username = "session0".to_string();
+    engine.create_object(&user).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_subscribe_change_feed() {
+    use ousia::middleware::ChangeKind;
+
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let (publisher, mut subscriber) = engine.subscribe::<Post>();
+    let engine = engine.with_middleware(publisher);
+
+    // Writes to other types are not published
+    engine.create_object(&User::default()).await.unwrap();
+
+    let mut posts = Vec::new();
+    for title in ["first", "second", "third"] {
+        let mut post = Post::default();
+        post.title = title.to_string();
+        engine.create_object(&post).await.unwrap();
+        posts.push(post);
+    }
+
+    for post in &posts {
+        let event = subscriber.recv().await.unwrap();
+        assert_eq!(event.kind, ChangeKind::Created);
+        assert_eq!(event.id, post.id());
+        assert_eq!(event.object.title, post.title);
+    }
+
+    posts[0].title = "edited".to_string();
+    engine.update_object(&mut posts[0]).await.unwrap();
+    let event = subscriber.recv().await.unwrap();
+    assert_eq!(event.kind, ChangeKind::Updated);
+    assert_eq!(event.object.title, "edited");
+
+    engine
+        .delete_object::<Post>(posts[1].id(), posts[1].owner())
+        .await
+        .unwrap();
+    let event = subscriber.recv().await.unwrap();
+    assert_eq!(event.kind, ChangeKind::Deleted);
+    assert_eq!(event.id, posts[1].id());
+
+    // The other single-object writes are published too
+    let id = engine.reserve_id();
+    engine
+        .create_object_with_id(&Post::default(), id)
+        .await
+        .unwrap();
+    assert_eq!(subscriber.recv().await.unwrap().id, id);
+
+    let post = Post::default();
+    assert!(engine.create_object_if_absent(&post).await.unwrap());
+    assert!(!engine.create_object_if_absent(&post).await.unwrap());
+    let event = subscriber.recv().await.unwrap();
+    assert_eq!((event.kind, event.id), (ChangeKind::Created, post.id()));
+
+    let new_owner = uuid::Uuid::now_v7();
+    engine
+        .transfer_object::<Post>(posts[0].id(), posts[0].owner(), new_owner)
+        .await
+        .unwrap();
+    let event = subscriber.recv().await.unwrap();
+    assert_eq!(event.kind, ChangeKind::Updated);
+    assert_eq!(event.object.owner(), new_owner);
+
+    engine
+        .swap_owners::<Post>(posts[0].id(), new_owner, posts[2].id(), posts[2].owner())
+        .await
+        .unwrap();
+    let swapped: Vec<_> = [
+        subscriber.recv().await.unwrap(),
+        subscriber.recv().await.unwrap(),
+    ]
+    .iter()
+    .map(|event| (event.kind, event.id))
+    .collect();
+    assert_eq!(
+        swapped,
+        vec![
+            (ChangeKind::Updated, posts[0].id()),
+            (ChangeKind::Updated, posts[2].id())
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_audit_middleware_captures_update_diffs() {
+    use ousia::middleware::{AuditEntry, AuditMiddleware};
+    use std::sync::{Arc, Mutex};
+
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let entries: Arc<Mutex<Vec<AuditEntry>>> = Arc::default();
+    let sink = Arc::clone(&entries);
+    let engine = Engine::new(Box::new(adapter))
+        .with_middleware(AuditMiddleware::new(move |entry| sink.lock().unwrap().push(entry)));
+
+    let mut post = Post::default();
+    post.title = "draft".to_string();
+    engine.create_object(&post).await.unwrap();
+    assert!(entries.lock().unwrap().is_empty());
+
+    post.title = "final".to_string();
+    post.tags = vec!["rust".to_string()];
+    engine.update_object(&mut post).await.unwrap();
+    // Nothing changed, nothing logged
+    engine.update_object(&mut post).await.unwrap();
+
+    let entries = entries.lock().unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.type_name, "Post");
+    assert_eq!(entry.id, post.id());
+    assert_eq!(entry.diff.changed_fields(), vec!["tags", "title"]);
+    assert_eq!(
+        entry.diff.changed["title"],
+        (serde_json::json!("draft"), serde_json::json!("final"))
+    );
+}
+
+#[tokio::test]
+async fn test_page_objects_relay() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let mut ids = Vec::new();
+    for i in 0..5 {
+        let mut post = Post::default();
+        post.title = format!("post{}", i);
+        engine.create_object(&post).await.unwrap();
+        ids.push(post.id());
+    }
+    ids.sort();
+    let node_ids = |page: &ousia::RelayPage<Post>| -> Vec<uuid::Uuid> {
+        page.edges.iter().map(|e| e.node.id()).collect()
+    };
+
+    // first
+    let page = engine
+        .page_objects_relay::<Post>(Query::default(), Some(2), None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(node_ids(&page), ids[..2]);
+    assert!(page.page_info.has_next_page);
+    assert!(!page.page_info.has_previous_page);
+    assert_eq!(
+        page.page_info.start_cursor.as_ref(),
+        Some(&page.edges[0].cursor)
+    );
+
+    // first + after
+    let page = engine
+        .page_objects_relay::<Post>(
+            Query::default(),
+            Some(2),
+            page.page_info.end_cursor,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(node_ids(&page), ids[2..4]);
+    assert!(page.page_info.has_next_page);
+    assert!(page.page_info.has_previous_page);
+    let page = engine
+        .page_objects_relay::<Post>(
+            Query::default(),
+            Some(2),
+            page.page_info.end_cursor,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(node_ids(&page), ids[4..]);
+    assert!(!page.page_info.has_next_page);
+
+    // last
+    let page = engine
+        .page_objects_relay::<Post>(Query::default(), None, None, Some(2), None)
+        .await
+        .unwrap();
+    assert_eq!(node_ids(&page), ids[3..]);
+    assert!(page.page_info.has_previous_page);
+    assert!(!page.page_info.has_next_page);
+
+    // last + before
+    let page = engine
+        .page_objects_relay::<Post>(
+            Query::default(),
+            None,
+            None,
+            Some(2),
+            page.page_info.start_cursor,
+        )
+        .await
+        .unwrap();
+    assert_eq!(node_ids(&page), ids[1..3]);
+    assert!(page.page_info.has_previous_page);
+    assert!(page.page_info.has_next_page);
+    let page = engine
+        .page_objects_relay::<Post>(
+            Query::default(),
+            None,
+            None,
+            Some(2),
+            page.page_info.start_cursor,
+        )
+        .await
+        .unwrap();
+    assert_eq!(node_ids(&page), ids[..1]);
+    assert!(!page.page_info.has_previous_page);
+
+    // Directions can't be mixed, and cursors must decode
+    assert!(
+        engine
+            .page_objects_relay::<Post>(Query::default(), Some(2), None, Some(2), None)
+            .await
+            .is_err()
+    );
+    assert!(
+        engine
+            .page_objects_relay::<Post>(
+                Query::default(),
+                Some(2),
+                Some("not a cursor".to_string()),
+                None,
+                None
+            )
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_export_and_verify_schema() {
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+    let engine = Engine::new(Box::new(adapter));
+
+    let verification = engine.verify_schema().await.unwrap();
+    assert!(
+        verification.missing.is_empty(),
+        "{:?}",
+        verification.missing
+    );
+    assert!(verification.extra.is_empty(), "{:?}", verification.extra);
+
+    let schema = engine.export_schema().await.unwrap();
+    assert!(schema.objects_ddl.contains("CREATE TABLE"));
+    assert!(schema.edges_ddl.contains("PRIMARY KEY"));
+    assert!(schema.sequences_ddl.contains("sequences"));
+    assert!(
+        schema
+            .indexes_ddl
+            .iter()
+            .any(|ddl| ddl.contains("idx_objects_type_owner"))
+    );
+    assert!(
+        schema
+            .columns
+            .iter()
+            .any(|c| c.table == "edges" && c.name == "from" && !c.nullable)
+    );
+
+    let markdown = schema.to_markdown();
+    assert!(markdown.contains("### `objects`"));
+    assert!(markdown.contains("| `index_meta` | `TEXT` | no |"));
+
+    // Before init_schema everything is missing
+    let empty = Engine::new(Box::new(SqliteAdapter::new_memory().await.unwrap()));
+    let verification = empty.verify_schema().await.unwrap();
+    assert!(verification.missing.contains(&"objects".to_string()));
+    assert!(verification.missing.contains(&"idx_edges_from".to_string()));
+    assert!(empty.export_schema().await.unwrap().objects_ddl.is_empty());
+}
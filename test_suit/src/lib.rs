@@ -49,3 +49,110 @@ async fn test_view() {
     assert_eq!(&dashboard_view.display_name, &user.display_name);
     assert_eq!(&dashboard_view.created_at, &user.created_at());
 }
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_private_field_not_persisted() {
+    use ousia::{
+        Adapter, Meta, Object, ObjectMeta, ObjectRecord, OusiaDefault, OusiaObject,
+        adapters::sqlite::SqliteAdapter,
+    };
+    #[derive(OusiaObject, OusiaDefault, Debug, Clone)]
+    #[ousia(index = "username:search")]
+    pub struct Account {
+        _meta: Meta,
+        pub username: String,
+
+        #[ousia(private)]
+        password: String,
+    }
+
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+
+    let mut account = Account::default();
+    account.username = "alice".to_string();
+    account.password = "encrypted_password".to_string();
+
+    let public = serde_json::to_value(&account).unwrap();
+    assert!(public.get("password").is_none());
+
+    adapter
+        .insert_object(ObjectRecord::from_object(&account))
+        .await
+        .unwrap();
+
+    let record = adapter
+        .fetch_object(Account::TYPE, account.id())
+        .await
+        .unwrap()
+        .expect("account should exist");
+    assert!(record.data.get("password").is_none());
+    assert!(record.index_meta.get("password").is_none());
+    assert_eq!(record.data["username"], "alice");
+
+    // Private fields come back as their default and must be re-hydrated by the app
+    let fetched: Account = record.to_object().unwrap();
+    assert_eq!(fetched.username, "alice");
+    assert_eq!(fetched.password, "");
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_flatten_field() {
+    use ousia::{
+        Adapter, Meta, Object, ObjectMeta, ObjectRecord, OusiaDefault, OusiaObject,
+        adapters::sqlite::SqliteAdapter,
+    };
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+    pub struct ContentMeta {
+        pub category: String,
+        pub tags: Vec<String>,
+    }
+
+    #[derive(OusiaObject, OusiaDefault, Debug, Clone)]
+    #[ousia(index = "body:search")]
+    pub struct Note {
+        _meta: Meta,
+        pub body: String,
+
+        #[ousia(flatten)]
+        pub common: ContentMeta,
+    }
+
+    let adapter = SqliteAdapter::new_memory().await.unwrap();
+    adapter.init_schema().await.unwrap();
+
+    let mut note = Note::default();
+    assert_eq!(note.common, ContentMeta::default());
+    note.body = "hello".to_string();
+    note.common = ContentMeta {
+        category: "news".to_string(),
+        tags: vec!["rust".to_string()],
+    };
+
+    // No nesting in either serialization
+    let public = serde_json::to_value(&note).unwrap();
+    assert_eq!(public["category"], "news");
+    assert_eq!(public["body"], "hello");
+    assert!(public.get("common").is_none());
+
+    adapter
+        .insert_object(ObjectRecord::from_object(&note))
+        .await
+        .unwrap();
+    let record = adapter
+        .fetch_object(Note::TYPE, note.id())
+        .await
+        .unwrap()
+        .expect("note should exist");
+    assert_eq!(record.data["category"], "news");
+    assert_eq!(record.data["tags"], serde_json::json!(["rust"]));
+    assert!(record.data.get("common").is_none());
+
+    let fetched: Note = record.to_object().unwrap();
+    assert_eq!(fetched.body, "hello");
+    assert_eq!(fetched.common, note.common);
+}
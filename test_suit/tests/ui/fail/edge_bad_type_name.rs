@@ -0,0 +1,9 @@
+use ousia::{EdgeMeta, OusiaEdge};
+
+#[derive(Debug, OusiaEdge)]
+#[ousia(type_name = "Bad Name")]
+struct BadName {
+    _meta: EdgeMeta,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ousia::{EdgeMeta, OusiaEdge};
+
+#[derive(Debug, OusiaEdge)]
+#[ousia(type_name = "")]
+struct Empty {
+    _meta: EdgeMeta,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use ousia::{Meta, OusiaDefault, OusiaObject};
+
+#[derive(OusiaObject, OusiaDefault, Debug)]
+#[ousia(type_name = "Account", index = "password_hash:search")]
+struct Account {
+    _meta: Meta,
+
+    pub username: String,
+    #[ousia(private)]
+    pub password_hash: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use ousia::{Meta, OusiaDefault, OusiaObject};
+
+#[derive(OusiaObject, OusiaDefault, Debug)]
+#[ousia(type_name = "Step", index = "sequence:sort")]
+struct Step {
+    _meta: Meta,
+
+    pub sequence: u32,
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use ousia::{Meta, OusiaDefault, OusiaObject};
+use serde::{Deserialize, Serialize};
+
+// No `Default` impl: the field default below stands in for it
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum PostStatus {
+    Draft,
+    Published,
+}
+
+#[derive(Debug, OusiaObject, OusiaDefault)]
+#[ousia(type_name = "ScoredPost")]
+struct ScoredPost {
+    _meta: Meta,
+    title: String,
+    #[ousia(default = "100_i64")]
+    score: i64,
+    #[ousia(default = "PostStatus::Published")]
+    status: PostStatus,
+}
+
+fn main() {
+    let post = ScoredPost::default();
+    assert!(post.title.is_empty());
+    assert_eq!(post.score, 100);
+    assert_eq!(post.status, PostStatus::Published);
+    assert_ne!(post.status, PostStatus::Draft);
+}